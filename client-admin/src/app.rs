@@ -9,6 +9,10 @@
 //!       2. Place construction-site station
 //!       3. Connect two sectors with a bidirectional jumpgate
 //!
+//!     plus module fitting, server messages, export/import of the whole
+//!     galaxy as a layout file (see `layout_file.rs`), staff roles and
+//!     moderation (sanctions and the chat filter).
+//!
 //! Deliberately utilitarian — this ships only to admins/moderators. The right
 //! side panel lists current galaxy state so the designer can see the effect of
 //! each reducer as the subscription updates stream back in.
@@ -16,6 +20,7 @@
 use macroquad::prelude::*;
 use spacetimedb_sdk::{DbContext, Identity, Table};
 
+use crate::layout_file;
 use crate::server::bindings::*;
 use crate::stdb::connector;
 
@@ -38,13 +43,16 @@ const STATION_SIZES: [StationSize; 6] = [
 ];
 
 /// Module keys understood by `admin_place_station`, paired with UI labels.
-const STATION_MODULES: [(&str, &str); 6] = [
+const STATION_MODULES: [(&str, &str); 9] = [
     ("trading", "Trading port"),
     ("iron_refinery", "Iron refinery"),
     ("ice_refinery", "Ice refinery"),
     ("silicon_refinery", "Silicon refinery"),
     ("solar_array", "Solar array"),
     ("advanced_manufacturing", "Advanced manufacturing"),
+    ("storage_depot", "Storage depot"),
+    ("shipyard", "Shipyard"),
+    ("metal_plate_factory", "Metal plate factory"),
 ];
 
 struct SectorForm {
@@ -123,6 +131,9 @@ struct ConnectForm {
 struct AddModuleForm {
     station_id: Option<u64>,
     module_key: String,
+    /// Module on the selected station whose logistics priority is being set.
+    priority_module_id: Option<u64>,
+    logistics_priority: u8,
 }
 
 impl Default for AddModuleForm {
@@ -130,6 +141,9 @@ impl Default for AddModuleForm {
         Self {
             station_id: None,
             module_key: STATION_MODULES[0].0.to_string(),
+            priority_module_id: None,
+            // Mirrors `DEFAULT_LOGISTICS_PRIORITY` server-side.
+            logistics_priority: 100,
         }
     }
 }
@@ -157,6 +171,75 @@ impl Default for MessageForm {
     }
 }
 
+/// State for the layout export/import panel.
+struct LayoutFileForm {
+    /// File written by "Export" and read by "Import", relative to the working
+    /// directory.
+    path: String,
+}
+
+impl Default for LayoutFileForm {
+    fn default() -> Self {
+        Self {
+            path: "galaxy.ron".to_string(),
+        }
+    }
+}
+
+/// State for the staff roles panel. Granting and revoking is Owner-only.
+struct RolesForm {
+    /// Hex identity to grant a role to; a player can also be picked from the
+    /// dropdown, which fills this in.
+    identity_hex: String,
+    role: AdminRoleKind,
+}
+
+impl Default for RolesForm {
+    fn default() -> Self {
+        Self {
+            identity_hex: String::new(),
+            role: AdminRoleKind::Moderator,
+        }
+    }
+}
+
+/// State for the moderation panel: issuing sanctions and editing the chat
+/// filter. The filter fields are loaded from `chat_filter_config` on first
+/// show and whenever "Reload" is clicked.
+struct ModerationForm {
+    /// Hex identity to sanction; picking a player fills this in.
+    identity_hex: String,
+    kind: SanctionKind,
+    /// Minutes; blank or 0 means until lifted.
+    duration_minutes: String,
+    reason: String,
+
+    filter_loaded: bool,
+    /// Comma-separated.
+    banned_words: String,
+    max_messages_per_window: u32,
+    rate_limit_window_secs: u32,
+    max_message_length: u32,
+    reject_repeats: bool,
+}
+
+impl Default for ModerationForm {
+    fn default() -> Self {
+        Self {
+            identity_hex: String::new(),
+            kind: SanctionKind::Mute,
+            duration_minutes: "60".to_string(),
+            reason: String::new(),
+            filter_loaded: false,
+            banned_words: String::new(),
+            max_messages_per_window: 5,
+            rate_limit_window_secs: 10,
+            max_message_length: 500,
+            reject_repeats: true,
+        }
+    }
+}
+
 /// Owned snapshot of the galaxy used to populate dropdowns and listings for a
 /// single frame, so the egui closure never holds a borrow on the connection's
 /// table cache.
@@ -174,8 +257,18 @@ struct GalaxyData {
     /// Read-only live-state snapshot (#145): players and ships (grouped by sector).
     player_lines: Vec<String>,
     ship_lines: Vec<String>,
+    /// One line per asteroid field: population, ore left and the ore mix.
+    asteroid_field_lines: Vec<String>,
     /// Players as `(identity, label)` for the message-recipient picker.
     players: Vec<(Identity, String)>,
+    /// Staff as `(identity, role, label)` for the roles panel.
+    roles: Vec<(Identity, AdminRoleKind, String)>,
+    /// Newest first. Owners and Admins get every row; other staff only their
+    /// own calls (the `my_admin_audit_log` view decides).
+    audit_lines: Vec<String>,
+    /// Every sanction as `(sanction id, label)`, for roles that may moderate.
+    sanctions: Vec<(u64, String)>,
+    chat_filter: Option<ChatFilterConfig>,
 }
 
 pub struct AdminApp {
@@ -196,6 +289,9 @@ pub struct AdminApp {
     connect_form: ConnectForm,
     add_module_form: AddModuleForm,
     message_form: MessageForm,
+    layout_file_form: LayoutFileForm,
+    roles_form: RolesForm,
+    moderation_form: ModerationForm,
 }
 
 impl AdminApp {
@@ -213,6 +309,9 @@ impl AdminApp {
             connect_form: ConnectForm::default(),
             add_module_form: AddModuleForm::default(),
             message_form: MessageForm::default(),
+            layout_file_form: LayoutFileForm::default(),
+            roles_form: RolesForm::default(),
+            moderation_form: ModerationForm::default(),
         }
     }
 
@@ -272,6 +371,9 @@ impl AdminApp {
             connect_form,
             add_module_form,
             message_form,
+            layout_file_form,
+            roles_form,
+            moderation_form,
         } = self;
 
         let mut requested_connect = false;
@@ -289,6 +391,9 @@ impl AdminApp {
                     connect_form,
                     add_module_form,
                     message_form,
+                    layout_file_form,
+                    roles_form,
+                    moderation_form,
                 );
             } else {
                 requested_connect = connection_dialog(
@@ -400,11 +505,18 @@ fn gather_galaxy(conn: &DbConnection) -> GalaxyData {
         .iter()
         .map(|st| {
             // Append a build-progress suffix while a matching construction site
-            // is still in progress (id is shared with the station).
+            // is still in progress or growing (id is shared with the station).
             let suffix = match db.station_under_construction().id().find(&st.id) {
                 Some(uc) if !uc.is_operational => {
                     format!("  [building {:.0}%]", uc.construction_progress_percentage)
                 }
+                Some(uc) => match &uc.growing_to {
+                    Some(next) => format!(
+                        "  [growing to {:?} {:.0}%]",
+                        next, uc.construction_progress_percentage
+                    ),
+                    None => String::new(),
+                },
                 _ => String::new(),
             };
             format!(
@@ -482,6 +594,49 @@ fn gather_galaxy(conn: &DbConnection) -> GalaxyData {
     ships.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
     let ship_lines: Vec<String> = ships.into_iter().map(|(_, _, line)| line).collect();
 
+    let sector_name = |id: u64| {
+        db.sector()
+            .id()
+            .find(&id)
+            .map(|s| s.name)
+            .unwrap_or_else(|| format!("sector {id}"))
+    };
+    let item_name = |id: u32| {
+        items
+            .iter()
+            .find(|(iid, _)| *iid == id)
+            .map(|(_, n)| n.clone())
+            .unwrap_or_else(|| format!("item {id}"))
+    };
+    let mut asteroid_field_lines: Vec<String> = db
+        .asteroid_field_report()
+        .iter()
+        .map(|r| {
+            let health = if r.initial_resources > 0 {
+                100.0 * r.current_resources as f64 / r.initial_resources as f64
+            } else {
+                0.0
+            };
+            let mix = r
+                .ore_mix
+                .iter()
+                .map(|o| format!("{} {}×/{}", item_name(o.item_id), o.asteroids, o.resources))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                "#{} \"{}\"  {}/{} rocks (+{} respawning)  ore {:.0}%  [{}]",
+                r.id,
+                sector_name(r.id),
+                r.asteroid_count,
+                r.target_population,
+                r.pending_respawns,
+                health,
+                mix,
+            )
+        })
+        .collect();
+    asteroid_field_lines.sort();
+
     let mut gate_lines: Vec<String> = db
         .jump_gate()
         .iter()
@@ -489,6 +644,69 @@ fn gather_galaxy(conn: &DbConnection) -> GalaxyData {
         .collect();
     gate_lines.sort();
 
+    let player_name = |id: &Identity| {
+        db.player()
+            .id()
+            .find(id)
+            .map(|p| p.username)
+            .unwrap_or_else(|| "(not a player)".to_string())
+    };
+    let mut roles: Vec<(Identity, AdminRoleKind, String)> = db
+        .admin_role()
+        .iter()
+        .map(|r| {
+            let label = format!(
+                "{:?}  {} [{}]",
+                r.role,
+                player_name(&r.id),
+                r.id.to_abbreviated_hex()
+            );
+            (r.id, r.role, label)
+        })
+        .collect();
+    roles.sort_by(|a, b| a.2.cmp(&b.2));
+
+    let mut audit: Vec<_> = db.my_admin_audit_log().iter().collect();
+    audit.sort_by_key(|a| std::cmp::Reverse(a.id));
+    let audit_lines: Vec<String> = audit
+        .into_iter()
+        .map(|a| {
+            format!(
+                "#{}  {}  {}  {}  {}",
+                a.id,
+                a.caller.to_abbreviated_hex(),
+                a.role
+                    .map(|role| format!("{role:?}"))
+                    .unwrap_or_else(|| "server".to_string()),
+                a.reducer,
+                a.details,
+            )
+        })
+        .collect();
+
+    let mut sanctions: Vec<(u64, String)> = db
+        .moderation_player_sanctions()
+        .iter()
+        .map(|s| {
+            let until = s
+                .until
+                .map(|until| format!("until {until}"))
+                .unwrap_or_else(|| "indefinitely".to_string());
+            let label = format!(
+                "#{}  {:?}  {} [{}]  {}  ({})",
+                s.id,
+                s.kind,
+                player_name(&s.target),
+                s.target.to_abbreviated_hex(),
+                until,
+                s.reason,
+            );
+            (s.id, label)
+        })
+        .collect();
+    sanctions.sort_by_key(|s| std::cmp::Reverse(s.0));
+    let chat_filter = db.chat_filter_config().iter().next();
+
     GalaxyData {
         systems,
         factions,
@@ -500,7 +718,12 @@ fn gather_galaxy(conn: &DbConnection) -> GalaxyData {
         gate_lines,
         player_lines,
         ship_lines,
+        asteroid_field_lines,
         players,
+        roles,
+        audit_lines,
+        sanctions,
+        chat_filter,
     }
 }
 
@@ -614,6 +837,9 @@ fn connected_ui(
     connect_form: &mut ConnectForm,
     add_module_form: &mut AddModuleForm,
     message_form: &mut MessageForm,
+    layout_file_form: &mut LayoutFileForm,
+    roles_form: &mut RolesForm,
+    moderation_form: &mut ModerationForm,
 ) -> bool {
     let mut disconnect = false;
 
@@ -653,8 +879,10 @@ fn connected_ui(
                 section_list(ui, "Players", &galaxy.player_lines);
                 section_list(ui, "Ships", &galaxy.ship_lines);
                 section_list(ui, "Sectors", &galaxy.sector_lines);
+                section_list(ui, "Asteroid fields", &galaxy.asteroid_field_lines);
                 section_list(ui, "Stations", &galaxy.station_lines);
                 section_list(ui, "Jumpgates", &galaxy.gate_lines);
+                section_list(ui, "Admin audit log", &galaxy.audit_lines);
                 ui.add_space(8.0);
                 ui.separator();
                 ui.collapsing("Activity log", |ui| {
@@ -683,6 +911,15 @@ fn connected_ui(
 
             egui::CollapsingHeader::new("5: Send server message")
                 .show(ui, |ui| message_panel(ui, conn, message_form, galaxy));
+
+            egui::CollapsingHeader::new("6: Export / import galaxy layout")
+                .show(ui, |ui| layout_file_panel(ui, conn, layout_file_form));
+
+            egui::CollapsingHeader::new("7: Staff roles")
+                .show(ui, |ui| roles_panel(ui, conn, roles_form, galaxy));
+
+            egui::CollapsingHeader::new("8: Moderation")
+                .show(ui, |ui| moderation_panel(ui, conn, moderation_form, galaxy));
         });
     });
 
@@ -1073,6 +1310,108 @@ fn add_module_panel(
             log_send_error(res);
         }
     });
+
+    let Some(station_id) = form.station_id else {
+        return;
+    };
+    ui.separator();
+    ui.weak("Logistics priority: higher-priority modules get inputs first when supplies run short.");
+    let modules: Vec<(u64, String)> = conn
+        .db()
+        .station_module()
+        .iter()
+        .filter(|m| m.station_id == station_id)
+        .map(|m| (m.id, format!("#{} {}", m.id, m.station_slot_identifier)))
+        .collect();
+    egui::Grid::new("logistics_priority_grid")
+        .num_columns(2)
+        .spacing([12.0, 6.0])
+        .show(ui, |ui| {
+            u64_combo(
+                ui,
+                "logistics_priority_module",
+                "Module",
+                &mut form.priority_module_id,
+                &modules,
+            );
+            ui.label("Priority");
+            ui.add(egui::DragValue::new(&mut form.logistics_priority));
+            ui.end_row();
+        });
+    ui.add_enabled_ui(form.priority_module_id.is_some(), |ui| {
+        if ui.button("Set priority").clicked() {
+            let module_id = form.priority_module_id.unwrap();
+            let priority = form.logistics_priority;
+            let label = format!("set_module_logistics_priority {module_id} → {priority}");
+            let res = conn.reducers.admin_set_module_logistics_priority_then(
+                module_id,
+                priority,
+                move |_ctx, result| log_reducer_result(label, result),
+            );
+            log_send_error(res);
+        }
+    });
+}
+
+fn layout_file_panel(ui: &mut egui::Ui, conn: &DbConnection, form: &mut LayoutFileForm) {
+    ui.weak("Save the galaxy as a layout file to check in as server/content/galaxy.ron, or apply one to this database.");
+    ui.horizontal(|ui| {
+        ui.label("File");
+        ui.text_edit_singleline(&mut form.path);
+    });
+
+    let path = form.path.trim().to_string();
+    ui.add_space(4.0);
+    ui.add_enabled_ui(!path.is_empty(), |ui| {
+        ui.horizontal(|ui| {
+            if ui.button("Export").clicked() {
+                export_layout_file(conn, &path);
+            }
+            if ui.button("Import").clicked() {
+                import_layout_file(conn, &path);
+            }
+        });
+    });
+}
+
+fn export_layout_file(conn: &DbConnection, path: &str) {
+    let (layout, warnings) = layout_file::export_layout(conn);
+    for warning in &warnings {
+        connector::log_activity(format!("! export: {warning}"));
+    }
+    // Validate before writing so a broken export never lands on disk.
+    let written = layout
+        .validate()
+        .and_then(|_| layout.to_ron())
+        .and_then(|text| std::fs::write(path, text).map_err(|e| e.to_string()));
+    match written {
+        Ok(()) => connector::log_activity(format!(
+            "✓ exported {} sectors to {path}",
+            layout.sectors().count()
+        )),
+        Err(e) => connector::log_activity(format!("✗ export to {path}: {e}")),
+    }
+}
+
+fn import_layout_file(conn: &DbConnection, path: &str) {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) => {
+            connector::log_activity(format!("✗ import from {path}: {e}"));
+            return;
+        }
+    };
+    // The server validates too, but checking here gives the full problem list
+    // without a round trip.
+    if let Err(e) = solarance_shared::GalaxyLayout::from_ron(&text) {
+        connector::log_activity(format!("✗ import from {path}: {e}"));
+        return;
+    }
+    let label = format!("import_galaxy {path}");
+    let res = conn
+        .reducers
+        .admin_import_galaxy_then(text, move |_ctx, result| log_reducer_result(label, result));
+    log_send_error(res);
 }
 
 fn message_panel(
@@ -1176,11 +1515,216 @@ fn message_panel(
     });
 }
 
+fn roles_panel(ui: &mut egui::Ui, conn: &DbConnection, form: &mut RolesForm, galaxy: &GalaxyData) {
+    ui.weak("Owners grant and revoke roles. Admins can do everything else; Moderators send server messages and moderate players; Designers edit the galaxy and definitions.");
+
+    if galaxy.roles.is_empty() {
+        ui.weak("(no staff)");
+    }
+    for (id, role, label) in &galaxy.roles {
+        ui.horizontal(|ui| {
+            ui.monospace(label);
+            if ui.small_button("Revoke").clicked() {
+                let label = format!("revoke {role:?} from {}", id.to_abbreviated_hex());
+                let res = conn
+                    .reducers
+                    .admin_revoke_role_then(*id, move |_ctx, result| {
+                        log_reducer_result(label, result)
+                    });
+                log_send_error(res);
+            }
+        });
+    }
+
+    ui.separator();
+    ui.horizontal(|ui| {
+        ui.label("Identity (hex):");
+        ui.text_edit_singleline(&mut form.identity_hex);
+        egui::ComboBox::from_id_salt("role_player")
+            .selected_text("or pick a player")
+            .show_ui(ui, |ui| {
+                for (id, label) in &galaxy.players {
+                    if ui.selectable_label(false, label).clicked() {
+                        form.identity_hex = id.to_hex().to_string();
+                    }
+                }
+            });
+    });
+    ui.horizontal(|ui| {
+        ui.label("Role:");
+        for role in [
+            AdminRoleKind::Owner,
+            AdminRoleKind::Admin,
+            AdminRoleKind::Moderator,
+            AdminRoleKind::Designer,
+        ] {
+            ui.selectable_value(&mut form.role, role, format!("{role:?}"));
+        }
+    });
+
+    let identity = Identity::from_hex(form.identity_hex.trim()).ok();
+    ui.add_space(4.0);
+    ui.add_enabled_ui(identity.is_some(), |ui| {
+        if ui.button("Grant role").clicked() {
+            if let Some(identity) = identity {
+                let role = form.role;
+                let label = format!("grant {role:?} to {}", identity.to_abbreviated_hex());
+                let res = conn.reducers.admin_grant_role_then(
+                    identity,
+                    role,
+                    move |_ctx, result| log_reducer_result(label, result),
+                );
+                log_send_error(res);
+            }
+        }
+    });
+}
+
+fn moderation_panel(
+    ui: &mut egui::Ui,
+    conn: &DbConnection,
+    form: &mut ModerationForm,
+    galaxy: &GalaxyData,
+) {
+    ui.weak("Mutes stop a player chatting; Bans refuse their connection. A kick is a 5 minute Ban.");
+
+    if galaxy.sanctions.is_empty() {
+        ui.weak("(no sanctions)");
+    }
+    for (id, label) in &galaxy.sanctions {
+        ui.horizontal(|ui| {
+            ui.monospace(label);
+            if ui.small_button("Lift").clicked() {
+                let label = format!("lift sanction #{id}");
+                let res = conn
+                    .reducers
+                    .admin_lift_sanction_then(*id, move |_ctx, result| {
+                        log_reducer_result(label, result)
+                    });
+                log_send_error(res);
+            }
+        });
+    }
+
+    ui.separator();
+    ui.horizontal(|ui| {
+        ui.label("Identity (hex):");
+        ui.text_edit_singleline(&mut form.identity_hex);
+        egui::ComboBox::from_id_salt("moderation_player")
+            .selected_text("or pick a player")
+            .show_ui(ui, |ui| {
+                for (id, label) in &galaxy.players {
+                    if ui.selectable_label(false, label).clicked() {
+                        form.identity_hex = id.to_hex().to_string();
+                    }
+                }
+            });
+    });
+    ui.horizontal(|ui| {
+        ui.label("Sanction:");
+        ui.selectable_value(&mut form.kind, SanctionKind::Mute, "Mute");
+        ui.selectable_value(&mut form.kind, SanctionKind::Ban, "Ban");
+        ui.label("Minutes (blank = until lifted):");
+        ui.add(egui::TextEdit::singleline(&mut form.duration_minutes).desired_width(60.0));
+    });
+    ui.horizontal(|ui| {
+        ui.label("Reason:");
+        ui.text_edit_singleline(&mut form.reason);
+    });
+
+    let identity = Identity::from_hex(form.identity_hex.trim()).ok();
+    let duration_secs = form
+        .duration_minutes
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|minutes| *minutes > 0)
+        .map(|minutes| minutes * 60);
+    let reason = form.reason.trim().to_string();
+    ui.add_space(4.0);
+    ui.add_enabled_ui(identity.is_some() && !reason.is_empty(), |ui| {
+        ui.horizontal(|ui| {
+            if ui.button("Sanction").clicked() {
+                if let Some(identity) = identity {
+                    let kind = form.kind;
+                    let label = format!("{kind:?} {}", identity.to_abbreviated_hex());
+                    let res = conn.reducers.admin_sanction_player_then(
+                        identity,
+                        kind,
+                        duration_secs,
+                        reason.clone(),
+                        move |_ctx, result| log_reducer_result(label, result),
+                    );
+                    log_send_error(res);
+                }
+            }
+            if ui.button("Kick").clicked() {
+                if let Some(identity) = identity {
+                    let label = format!("kick {}", identity.to_abbreviated_hex());
+                    let res = conn.reducers.admin_kick_player_then(
+                        identity,
+                        reason.clone(),
+                        move |_ctx, result| log_reducer_result(label, result),
+                    );
+                    log_send_error(res);
+                }
+            }
+        });
+    });
+
+    ui.separator();
+    ui.strong("Chat filter");
+    if let Some(config) = &galaxy.chat_filter {
+        if !form.filter_loaded || ui.small_button("Reload").clicked() {
+            form.banned_words = config.banned_words.join(", ");
+            form.max_messages_per_window = config.max_messages_per_window;
+            form.rate_limit_window_secs = config.rate_limit_window_secs;
+            form.max_message_length = config.max_message_length;
+            form.reject_repeats = config.reject_repeats;
+            form.filter_loaded = true;
+        }
+    }
+    ui.horizontal(|ui| {
+        ui.label("Banned words:");
+        ui.text_edit_singleline(&mut form.banned_words);
+    });
+    ui.horizontal(|ui| {
+        ui.label("At most");
+        ui.add(egui::DragValue::new(&mut form.max_messages_per_window).range(1..=100));
+        ui.label("messages every");
+        ui.add(egui::DragValue::new(&mut form.rate_limit_window_secs).range(1..=3600));
+        ui.label("seconds");
+    });
+    ui.horizontal(|ui| {
+        ui.label("Max length:");
+        ui.add(egui::DragValue::new(&mut form.max_message_length).range(1..=5000));
+        ui.checkbox(&mut form.reject_repeats, "Reject repeated messages");
+    });
+    if ui.button("Save chat filter").clicked() {
+        let banned_words: Vec<String> = form
+            .banned_words
+            .split(',')
+            .map(|word| word.trim().to_string())
+            .filter(|word| !word.is_empty())
+            .collect();
+        let label = format!("set chat filter ({} banned words)", banned_words.len());
+        let res = conn.reducers.admin_set_chat_filter_then(
+            banned_words,
+            form.max_messages_per_window,
+            form.rate_limit_window_secs,
+            form.max_message_length,
+            form.reject_repeats,
+            move |_ctx, result| log_reducer_result(label, result),
+        );
+        log_send_error(res);
+    }
+}
+
 /// Record a reducer result into the activity log. Used from the one-shot
 /// `_then` callbacks. `Ok(Ok(()))` = committed; `Ok(Err(msg))` = the server
-/// reducer returned an error (e.g. `try_server_only` rejection); `Err(_)` = the
-/// SDK failed to deliver/await the call. Generic over the SDK's internal error
-/// type so we never have to name it.
+/// reducer returned an error (e.g. a `require_admin_permission` rejection);
+/// `Err(_)` = the SDK failed to deliver/await the call. Generic over the SDK's
+/// internal error type so we never have to name it.
 fn log_reducer_result<E: std::fmt::Display>(
     label: String,
     result: Result<Result<(), String>, E>,
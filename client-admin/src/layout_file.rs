@@ -0,0 +1,328 @@
+//! Galaxy layout export for the Galaxy Creator.
+//!
+//! Rebuilds a [`GalaxyLayout`] from the subscribed tables so a designer can
+//! save what they built interactively and check it in as
+//! `server/content/galaxy.ron`. Importing goes the other way through the
+//! `admin_import_galaxy` reducer; see `app.rs`.
+//!
+//! Stations are exported by module *key* (the vocabulary of
+//! `admin_place_station`), so only modules that a key can recreate survive the
+//! round trip. Anything else is left out and reported back as a warning.
+
+use std::collections::HashSet;
+
+use solarance_shared::galaxy::{self as layout, GalaxyLayout, GALAXY_LAYOUT_VERSION};
+use spacetimedb_sdk::Table;
+
+use crate::server::bindings::*;
+
+/// Blueprint ids behind each module key, from `definitions::station_module_types`
+/// and the module type files on the server.
+const BLUEPRINT_TRADING_BAZAAR: u32 = 1_000;
+const BLUEPRINT_MINOR_DEPOT: u32 = 2_000;
+const BLUEPRINT_REFINERY_MINOR: u32 = 3_110;
+const BLUEPRINT_MANUFACTURING_FACTORY: u32 = 6_000;
+const BLUEPRINT_MANUFACTURING_FACTORY_ADVANCED: u32 = 6_001;
+const BLUEPRINT_MANUFACTURING_SHIPYARD: u32 = 6_003;
+const BLUEPRINT_SOLAR_ARRAY_SMALL: u32 = 7_000;
+
+/// Refinery keys by input ore item id (see `server/content/items.ron`).
+const REFINERY_KEYS: [(u32, &str); 3] = [
+    (2_002, "iron_refinery"),
+    (2_000, "ice_refinery"),
+    (2_003, "silicon_refinery"),
+];
+
+/// Builds a layout from the client cache. The second value lists whatever
+/// couldn't be expressed in the layout format.
+pub fn export_layout(conn: &DbConnection) -> (GalaxyLayout, Vec<String>) {
+    let db = conn.db();
+    let mut warnings = Vec::new();
+
+    let capitals: HashSet<u64> = db
+        .faction()
+        .iter()
+        .filter_map(|f| f.capital_station_id)
+        .collect();
+
+    let mut systems: Vec<StarSystem> = db.star_system().iter().collect();
+    systems.sort_by_key(|s| s.id);
+
+    let mut star_systems = Vec::with_capacity(systems.len());
+    for system in systems {
+        let mut objects: Vec<StarSystemObject> = db
+            .star_system_object()
+            .iter()
+            .filter(|o| o.system_id == system.id)
+            .collect();
+        objects.sort_by_key(|o| o.id);
+
+        let mut sectors: Vec<Sector> = db
+            .sector()
+            .iter()
+            .filter(|s| s.system_id == system.id)
+            .collect();
+        sectors.sort_by_key(|s| s.id);
+
+        star_systems.push(layout::StarSystemLayout {
+            name: system.name.clone(),
+            map_coordinates: vec2(&system.map_coordinates),
+            spectral: spectral(&system.spectral),
+            luminosity: system.luminosity,
+            controlling_faction_id: system.controlling_faction_id,
+            objects: objects
+                .iter()
+                .map(|o| layout::CelestialLayout {
+                    kind: celestial(&o.kind),
+                    orbit_au: o.orbit_au,
+                    rotation_or_width_km: o.rotation_or_width_km,
+                    gfx_key: o.gfx_key.clone(),
+                })
+                .collect(),
+            sectors: sectors
+                .iter()
+                .map(|sector| export_sector(conn, sector, &capitals, &mut warnings))
+                .collect(),
+        });
+    }
+
+    let gates: Vec<JumpGate> = db.jump_gate().iter().collect();
+    let mut links: Vec<(u64, u64)> = gates
+        .iter()
+        .map(|g| layout::GateLink::new(g.current_sector_id, g.target_sector_id).normalized())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    links.sort();
+
+    // Each end keeps its placement and on/off switch, so an import puts the
+    // gates back exactly where they were.
+    let gate_end = |from: u64, to: u64| {
+        gates
+            .iter()
+            .find(|g| g.current_sector_id == from && g.target_sector_id == to)
+            .map(|g| layout::GateEndLayout {
+                position: vec2(&g.position),
+                is_active: g.is_active,
+            })
+    };
+
+    let layout = GalaxyLayout {
+        version: GALAXY_LAYOUT_VERSION,
+        star_systems,
+        jumpgates: links
+            .into_iter()
+            .map(|(a, b)| layout::GateLink {
+                a,
+                b,
+                a_gate: gate_end(a, b),
+                b_gate: gate_end(b, a),
+            })
+            .collect(),
+    };
+    (layout, warnings)
+}
+
+fn export_sector(
+    conn: &DbConnection,
+    sector: &Sector,
+    capitals: &HashSet<u64>,
+    warnings: &mut Vec<String>,
+) -> layout::SectorLayout {
+    let db = conn.db();
+
+    let asteroid_field = db
+        .asteroid_sector()
+        .id()
+        .find(&sector.id)
+        .map(|field| layout::AsteroidFieldLayout {
+            sparseness: field.sparseness,
+            rarity: field.rarity,
+            cluster_extent: field.cluster_extent,
+            cluster_inner: field.cluster_inner,
+            ore_weights: field
+                .ore_weights
+                .iter()
+                .map(|w| layout::OreWeightLayout {
+                    item_id: w.item_id,
+                    weight: w.weight,
+                })
+                .collect(),
+        });
+
+    let mut nebulae: Vec<SectorNebula> = db
+        .sector_nebula()
+        .iter()
+        .filter(|n| n.sector_id == sector.id)
+        .collect();
+    nebulae.sort_by_key(|n| n.id);
+
+    let mut stations: Vec<Station> = db
+        .station()
+        .iter()
+        .filter(|s| s.sector_id == sector.id)
+        .collect();
+    stations.sort_by_key(|s| s.id);
+
+    layout::SectorLayout {
+        id: sector.id,
+        name: sector.name.clone(),
+        description: sector.description.clone(),
+        controlling_faction_id: sector.controlling_faction_id,
+        security_level: sector.security_level,
+        sunlight: sector.sunlight,
+        anomalous: sector.anomalous,
+        nebula: sector.nebula,
+        rare_ore: sector.rare_ore,
+        position: solarance_shared::Vec2::new(sector.x, sector.y),
+        background_gfx_key: sector.background_gfx_key.clone(),
+        asteroid_field,
+        nebulae: nebulae
+            .iter()
+            .map(|n| layout::NebulaLayout {
+                position: vec2(&n.position),
+                gfx_key: n.gfx_key.clone(),
+                scale: n.scale,
+                rotation_radians: n.rotation_radians,
+                tint: n.tint,
+            })
+            .collect(),
+        stations: stations
+            .iter()
+            .map(|station| export_station(conn, station, capitals, warnings))
+            .collect(),
+    }
+}
+
+fn export_station(
+    conn: &DbConnection,
+    station: &Station,
+    capitals: &HashSet<u64>,
+    warnings: &mut Vec<String>,
+) -> layout::StationLayout {
+    let db = conn.db();
+
+    // A site that hasn't finished yet is exported as a site; once it's
+    // operational its requirements are history.
+    let under_construction = db
+        .station_under_construction()
+        .id()
+        .find(&station.id)
+        .is_some_and(|site| !site.is_operational);
+    let construction_requirements = if under_construction {
+        let mut requirements: Vec<ConstructionRequirement> = db
+            .construction_requirement()
+            .iter()
+            .filter(|r| r.station_id == station.id)
+            .collect();
+        requirements.sort_by_key(|r| r.id);
+        requirements
+            .iter()
+            .map(|r| layout::RequirementLayout {
+                item_id: r.resource_item_id,
+                quantity: r.quantity_required,
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut modules: Vec<StationModule> = db
+        .station_module()
+        .iter()
+        .filter(|m| m.station_id == station.id)
+        .collect();
+    modules.sort_by_key(|m| m.id);
+    let mut module_keys = Vec::with_capacity(modules.len());
+    for module in &modules {
+        match module_key(conn, module) {
+            Some(key) => module_keys.push(key.to_string()),
+            None => warnings.push(format!(
+                "station {:?}: module {} (blueprint {}) has no module key and was left out",
+                station.name, module.id, module.blueprint
+            )),
+        }
+    }
+    if under_construction && !module_keys.is_empty() {
+        warnings.push(format!(
+            "station {:?}: construction site modules were left out",
+            station.name
+        ));
+        module_keys.clear();
+    }
+
+    layout::StationLayout {
+        name: station.name.clone(),
+        size: station_size(&station.size),
+        owner_faction_id: station.owner_faction_id,
+        position: vec2(&station.position),
+        rotation: station.rotation,
+        capital: capitals.contains(&station.id),
+        modules: module_keys,
+        construction_requirements,
+    }
+}
+
+/// The `admin_place_station` key that recreates `module`, if any.
+fn module_key(conn: &DbConnection, module: &StationModule) -> Option<&'static str> {
+    match module.blueprint {
+        BLUEPRINT_TRADING_BAZAAR => Some("trading"),
+        BLUEPRINT_SOLAR_ARRAY_SMALL => Some("solar_array"),
+        BLUEPRINT_MINOR_DEPOT => Some("storage_depot"),
+        // Only the metal plate factory is built with its recipe already set.
+        BLUEPRINT_MANUFACTURING_FACTORY => conn
+            .db()
+            .manufacturing_module()
+            .id()
+            .find(&module.id)?
+            .current_recipe_id
+            .map(|_| "metal_plate_factory"),
+        BLUEPRINT_MANUFACTURING_FACTORY_ADVANCED => Some("advanced_manufacturing"),
+        BLUEPRINT_MANUFACTURING_SHIPYARD => Some("shipyard"),
+        BLUEPRINT_REFINERY_MINOR => {
+            let refinery = conn.db().refinery_module().id().find(&module.id)?;
+            REFINERY_KEYS
+                .iter()
+                .find(|(ore, _)| *ore == refinery.input_ore_resource_id)
+                .map(|(_, key)| *key)
+        }
+        _ => None,
+    }
+}
+
+fn vec2(v: &Vec2) -> solarance_shared::Vec2 {
+    solarance_shared::Vec2::new(v.x, v.y)
+}
+
+fn spectral(kind: &SpectralKind) -> layout::SpectralKind {
+    match kind {
+        SpectralKind::O => layout::SpectralKind::O,
+        SpectralKind::B => layout::SpectralKind::B,
+        SpectralKind::A => layout::SpectralKind::A,
+        SpectralKind::F => layout::SpectralKind::F,
+        SpectralKind::G => layout::SpectralKind::G,
+        SpectralKind::K => layout::SpectralKind::K,
+        SpectralKind::M => layout::SpectralKind::M,
+    }
+}
+
+fn celestial(kind: &StarSystemObjectKind) -> layout::CelestialKind {
+    match kind {
+        StarSystemObjectKind::Star => layout::CelestialKind::Star,
+        StarSystemObjectKind::Planet => layout::CelestialKind::Planet,
+        StarSystemObjectKind::Moon => layout::CelestialKind::Moon,
+        StarSystemObjectKind::AsteroidBelt => layout::CelestialKind::AsteroidBelt,
+        StarSystemObjectKind::NebulaBelt => layout::CelestialKind::NebulaBelt,
+    }
+}
+
+fn station_size(size: &StationSize) -> layout::StationSize {
+    match size {
+        StationSize::Capital => layout::StationSize::Capital,
+        StationSize::Large => layout::StationSize::Large,
+        StationSize::Medium => layout::StationSize::Medium,
+        StationSize::Small => layout::StationSize::Small,
+        StationSize::Outpost => layout::StationSize::Outpost,
+        StationSize::Satellite => layout::StationSize::Satellite,
+    }
+}
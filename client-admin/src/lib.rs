@@ -5,5 +5,6 @@
 //! `spacetime generate` (see `Taskfile.yml`).
 
 pub mod app;
+pub mod layout_file;
 pub mod server;
 pub mod stdb;
@@ -124,8 +124,24 @@ fn subscribe_to_tables(ctx: &DbConnection) {
             "SELECT * FROM station_under_construction",
             "SELECT * FROM jump_gate",
             "SELECT * FROM item_definition",
+            // Everything else a galaxy layout export needs.
+            "SELECT * FROM star_system_object",
+            "SELECT * FROM asteroid_sector",
+            "SELECT * FROM sector_nebula",
+            "SELECT * FROM station_module",
+            "SELECT * FROM refinery_module",
+            "SELECT * FROM manufacturing_module",
+            "SELECT * FROM construction_requirement",
             // Live-state snapshot tables for the read-only galaxy overview (#145).
             "SELECT * FROM player",
             "SELECT * FROM ship",
+            // Hourly asteroid field health, for balancing.
+            "SELECT * FROM asteroid_field_report",
+            // Staff roles, and the audit log rows the caller's role may see.
+            "SELECT * FROM admin_role",
+            "SELECT * FROM my_admin_audit_log",
+            // Sanctions (empty unless the role may moderate) and the chat filter.
+            "SELECT * FROM moderation_player_sanctions",
+            "SELECT * FROM chat_filter_config",
         ]);
 }
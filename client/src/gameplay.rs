@@ -7,6 +7,7 @@ use crate::{shader::*, stdb::utils::*};
 
 mod gui;
 mod player;
+pub mod direct_messages;
 pub mod direct_server_messages;
 pub mod render;
 pub mod resources;
@@ -206,6 +207,18 @@ pub async fn gameplay(connection: Option<DbConnection>) {
             }
         }
 
+        // A Ban (a kick is a short one) shows up in `my_player_sanctions`;
+        // the server refuses our next connection until it expires.
+        if ctx
+            .db()
+            .my_player_sanctions()
+            .iter()
+            .any(|sanction| sanction.kind == SanctionKind::Ban)
+        {
+            warn!("Disconnecting: this identity has been banned.");
+            game_state.done = true;
+        }
+
         if game_state.done {
             let _ = ctx.disconnect();
             break;
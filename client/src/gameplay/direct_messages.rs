@@ -0,0 +1,64 @@
+//! Client-side helpers for player→player **Direct Messages**.
+//!
+//! The server exposes them through the `my_direct_messages` View (sent and
+//! received, minus muted senders) and the block/mute list through
+//! `my_chat_blocks`. Read state is login-relative, the same as the Direct
+//! Server Message inbox, so the cutoff logic is shared with
+//! `DirectServerMessageUtils`.
+
+use crate::{gameplay::direct_server_messages::DirectServerMessageUtils, server::bindings::*};
+use spacetimedb_sdk::{DbContext, Identity, Table};
+
+/// Client-side utilities for handling player Direct Messages.
+pub struct DirectMessageUtils;
+
+impl DirectMessageUtils {
+    /// Every Direct Message the current player sent or received, newest first.
+    pub fn get_messages(ctx: &DbConnection) -> Vec<DirectMessage> {
+        let mut messages: Vec<DirectMessage> = ctx.db().my_direct_messages().iter().collect();
+        messages.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        messages
+    }
+
+    /// Received messages that arrived after the effective unread cutoff.
+    /// Messages the player sent are never unread.
+    pub fn get_unread(
+        ctx: &DbConnection,
+        last_login: Option<spacetimedb_sdk::Timestamp>,
+        dismissed_at: Option<spacetimedb_sdk::Timestamp>,
+    ) -> Vec<DirectMessage> {
+        let me = ctx.identity();
+        let cutoff = DirectServerMessageUtils::effective_cutoff(last_login, dismissed_at);
+        Self::get_messages(ctx)
+            .into_iter()
+            .filter(|m| m.to == me)
+            .filter(|m| cutoff.map(|c| m.created_at > c).unwrap_or(true))
+            .collect()
+    }
+
+    /// Count of unread Direct Messages (post effective cutoff).
+    pub fn get_unread_count(
+        ctx: &DbConnection,
+        last_login: Option<spacetimedb_sdk::Timestamp>,
+        dismissed_at: Option<spacetimedb_sdk::Timestamp>,
+    ) -> usize {
+        Self::get_unread(ctx, last_login, dismissed_at).len()
+    }
+
+    /// The other side of a conversation row, from the current player's view.
+    pub fn counterpart(ctx: &DbConnection, message: &DirectMessage) -> Identity {
+        if message.from == ctx.identity() {
+            message.to
+        } else {
+            message.from
+        }
+    }
+
+    /// Whether the current player has a `kind` entry against `target`.
+    pub fn has_block(ctx: &DbConnection, target: &Identity, kind: &ChatBlockKind) -> bool {
+        ctx.db()
+            .my_chat_blocks()
+            .iter()
+            .any(|b| b.target == *target && b.kind == *kind)
+    }
+}
@@ -24,8 +24,9 @@ impl DirectServerMessageUtils {
 
     /// Effective unread cutoff: the later of `last_login` (server-side
     /// login-relative read state) and `dismissed_at` (session-local "Mark all
-    /// read" click). Either or both may be `None`.
-    fn effective_cutoff(
+    /// read" click). Either or both may be `None`. Shared with the player
+    /// Direct Message tab so both inboxes agree on what "unread" means.
+    pub fn effective_cutoff(
         last_login: Option<spacetimedb_sdk::Timestamp>,
         dismissed_at: Option<spacetimedb_sdk::Timestamp>,
     ) -> Option<spacetimedb_sdk::Timestamp> {
@@ -1,4 +1,4 @@
-use crate::server::bindings::*;
+use crate::{server::bindings::*, stdb::utils::*};
 use egui::{Align, Layout, Ui};
 
 /// Trait for handling ship selection and actions in the asset tree
@@ -73,7 +73,14 @@ pub fn display_ship_on_tree<T: ShipTreeHandler>(
         // Buttons on the right
         ui.with_layout(Layout::right_to_left(Align::TOP), |ui| {
             // Add buttons in reverse order of appearance (rightmost first)
-            if ui.button("Undock").clicked() {
+            // ADR-0002: at most one Piloted Ship, so the current one has to
+            // dock before another hull can leave.
+            let can_undock = get_player_ship(ctx).is_none();
+            if ui
+                .add_enabled(can_undock, egui::Button::new("Undock"))
+                .on_disabled_hover_text("Dock your Piloted Ship first.")
+                .clicked()
+            {
                 println!("Undock clicked for ship ID: {}", ship.id);
                 handler.deselect_ship();
                 let _ = ctx.reducers.undock_ship(ship.clone());
@@ -89,3 +96,19 @@ pub fn display_ship_on_tree<T: ShipTreeHandler>(
         });
     });
 }
+
+/// Where a ship is: the sector it's flying in, or the station it's docked at.
+pub fn ship_location_label(ctx: &DbConnection, ship: &Ship) -> String {
+    match ship.location {
+        ShipLocation::System => "In transit".to_string(),
+        ShipLocation::Sector => format!("Piloted in {}", get_sector_name(ctx, &ship.sector_id)),
+        ShipLocation::Station => match ctx.db.station().id().find(&ship.station_id) {
+            Some(station) => format!(
+                "Docked at {} ({})",
+                station_display_name(ctx, &station),
+                get_sector_name(ctx, &station.sector_id)
+            ),
+            None => format!("Docked at Station #{}", ship.station_id),
+        },
+    }
+}
@@ -1,10 +1,8 @@
-use egui::Context;
+use egui::{Color32, Context, RichText, Ui};
+use spacetimedb_sdk::{DbContext, Table};
 
 use crate::{
-    gameplay::gui::{
-        asset_utils::{display_sectors_with_ships, ShipTreeHandler},
-        out_of_play_screen::utils::prepare_ships_for_system_tree,
-    },
+    gameplay::gui::asset_utils::{ship_location_label, ShipTreeHandler},
     server::bindings::*,
     stdb::utils::*,
 };
@@ -63,35 +61,89 @@ pub fn draw(
             ));
 
             ui.separator();
-            ui.heading("Docked Ships");
-
-            // Prepare the system tree data
-            let system_to_docked_ships_map = prepare_ships_for_system_tree(ctx);
-            let mut sorted_system_to_docked_ships: Vec<_> =
-                system_to_docked_ships_map.values().collect();
-            sorted_system_to_docked_ships.sort_by_key(|(system, _)| system.name.clone());
+            ui.heading("Fleet");
 
             egui::ScrollArea::vertical().show(ui, |ui| {
-                if sorted_system_to_docked_ships.is_empty() {
-                    ui.label("No docked ships found.");
-                } else {
-                    for (star_system, sectors_with_ships) in sorted_system_to_docked_ships {
-                        egui::collapsing_header::CollapsingState::load_with_default_open(
-                            ui.ctx(),
-                            ui.make_persistent_id(format!("system_{}", star_system.id)),
-                            true, // Default open state
-                        )
-                        .show_header(ui, |ui| {
-                            ui.label(format!(
-                                "System: {} (ID: {})",
-                                star_system.name, star_system.id
-                            ));
-                        })
-                        .body(|ui| {
-                            display_sectors_with_ships(ctx, sectors_with_ships, ui, state);
-                        });
-                    }
+                let fleet = get_my_fleet(ctx);
+                if fleet.is_empty() {
+                    ui.label("You don't own any ships.");
+                }
+                for ship in fleet {
+                    show_fleet_entry(ctx, state, ui, &ship);
                 }
             });
         })
 }
+
+/// One owned ship: type, location, cargo and hull status.
+fn show_fleet_entry(ctx: &DbConnection, state: &mut State, ui: &mut Ui, ship: &Ship) {
+    let type_name = ctx
+        .db()
+        .ship_type_definition()
+        .id()
+        .find(&ship.shiptype_id)
+        .map_or_else(|| "Unknown Ship Type".to_string(), |t| t.name);
+
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            let selected = state.is_ship_selected(ship);
+            if ui
+                .selectable_label(selected, format!("{} #{}", type_name, ship.id))
+                .clicked()
+            {
+                if selected {
+                    state.deselect_ship();
+                } else {
+                    state.select_ship(ship);
+                }
+            }
+            if ship.location == ShipLocation::Sector {
+                ui.label(RichText::new("PILOTED").strong().color(Color32::LIGHT_GREEN));
+            }
+        });
+        ui.label(ship_location_label(ctx, ship));
+
+        match ctx.db().ship_status().id().find(&ship.id) {
+            Some(status) => {
+                ui.label(format!(
+                    "Hull {:.0} · Shields {:.0} · Energy {:.0}",
+                    status.health,
+                    status.current_shields(now_unix_micros()),
+                    status.current_energy(now_unix_micros())
+                ));
+                ui.label(format!(
+                    "Cargo: {} / {}v",
+                    status.used_cargo_capacity, status.max_cargo_capacity
+                ));
+            }
+            None => {
+                ui.label("Status unknown.");
+            }
+        }
+
+        if state.is_ship_selected(ship) {
+            let mut cargo: Vec<(String, u16)> = ctx
+                .db()
+                .ship_cargo_item()
+                .iter()
+                .filter(|c| c.ship_id == ship.id)
+                .map(|c| {
+                    let name = ctx
+                        .db()
+                        .item_definition()
+                        .id()
+                        .find(&c.item_id)
+                        .map_or_else(|| format!("Item #{}", c.item_id), |def| def.name);
+                    (name, c.quantity)
+                })
+                .collect();
+            cargo.sort();
+            if cargo.is_empty() {
+                ui.label("    Cargo hold is empty.");
+            }
+            for (name, quantity) in cargo {
+                ui.label(format!("    {}x {}", quantity, name));
+            }
+        }
+    });
+}
@@ -1,4 +1,4 @@
-//! Chat widget — tabbed access to all six messaging channels (#101).
+//! Chat widget — tabbed access to all seven messaging channels (#101).
 //!
 //! Each tab reads directly from its STDB View / public table — there is no
 //! mirrored state in `State`. The views auto-update on insert, so each frame
@@ -12,27 +12,46 @@
 //! - **System** — `my_star_system_chat` view; player's current star system.
 //! - **Sector** — `my_sector_chat` view; player's current sector.
 //! - **Faction** — `my_faction_chat` view; player's faction.
-//! - **DM** — `my_direct_server_messages` view; the async inbox.
+//! - **Inbox** — `my_direct_server_messages` view; the async server inbox.
+//! - **DM** — `my_direct_messages` view; player↔player messages. The send box
+//!   gains a "To:" field, and the header carries Block / Mute toggles for the
+//!   current recipient (`my_chat_blocks`). Muted senders are already filtered
+//!   out of every channel view server-side.
+//!
+//! ## Look back
+//! Channel tabs show the newest `PAGE_SIZE` messages. "Load older" first
+//! reveals more of what the view already holds, then asks the server for the
+//! next page of archived messages (`request_chat_history`), which arrive
+//! through `my_chat_history`. A divider marks where messages posted since the
+//! player's `last_login` begin.
 
-use std::cmp::Ordering;
+use std::{cmp::Ordering, collections::HashMap};
 
 use egui::{Align2, Color32, Context, RichText, ScrollArea, TextStyle, Ui};
 use macroquad::prelude::*;
 use spacetimedb_sdk::{DbContext, Table, Timestamp};
 
 use crate::{
-    gameplay::direct_server_messages::{render_sender, DirectServerMessageUtils},
+    gameplay::{
+        direct_messages::DirectMessageUtils,
+        direct_server_messages::{render_sender, DirectServerMessageUtils},
+    },
     server::bindings::*,
     stdb::utils::*,
 };
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd)]
+/// Messages a channel tab shows at first, and how many more each "Load older"
+/// click reveals.
+const PAGE_SIZE: usize = 50;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Hash)]
 pub enum ChatTab {
     Server,
     Galaxy,
     System,
     Sector,
     Faction,
+    Inbox,
     DirectMessages,
 }
 
@@ -48,11 +67,40 @@ pub struct State {
     pub selected_tab: ChatTab,
     pub has_focus: bool,
     pub hidden: bool,
-    /// Session-local "Mark all read" timestamp. The DM unread count is taken
-    /// against `max(player.last_login, dms_dismissed_at)`. None until the
-    /// player clicks the Read button; cleared on next session (login-relative
-    /// is still the source of truth across sessions).
+    /// Session-local "Mark all read" timestamp. The Inbox unread count is
+    /// taken against `max(player.last_login, inbox_dismissed_at)`. None until
+    /// the player clicks the Read button; cleared on next session
+    /// (login-relative is still the source of truth across sessions).
+    pub inbox_dismissed_at: Option<Timestamp>,
+    /// Same as `inbox_dismissed_at`, for the player DM tab.
     pub dms_dismissed_at: Option<Timestamp>,
+    /// Username the DM tab sends to. Filled by typing or by clicking a name in
+    /// the conversation list.
+    pub dm_recipient: String,
+    /// How many messages each channel tab shows, counted from the newest.
+    /// Missing means `PAGE_SIZE`.
+    pub shown_rows: HashMap<ChatTab, usize>,
+}
+
+/// One line of a channel tab, from the live view or a loaded history page.
+struct ChannelRow {
+    /// Id in the live channel table; orders rows and is the paging cursor.
+    id: u64,
+    created_at: Timestamp,
+    text: String,
+}
+
+impl ChatTab {
+    /// The archived channel behind this tab, if it can page back.
+    fn archived_channel(self) -> Option<ChatChannel> {
+        match self {
+            ChatTab::Galaxy => Some(ChatChannel::Galaxy),
+            ChatTab::System => Some(ChatChannel::StarSystem),
+            ChatTab::Sector => Some(ChatChannel::Sector),
+            ChatTab::Faction => Some(ChatChannel::Faction),
+            ChatTab::Server | ChatTab::Inbox | ChatTab::DirectMessages => None,
+        }
+    }
 }
 
 fn contents_hidden(ui: &mut Ui, ctx: &DbConnection, chat_window: &mut State) {
@@ -61,8 +109,12 @@ fn contents_hidden(ui: &mut Ui, ctx: &DbConnection, chat_window: &mut State) {
             chat_window.hidden = false;
         }
 
-        let unread_count = unread_dm_count(ctx, chat_window);
-        let dm_tab_text = if unread_count > 0 {
+        let inbox_tab_text = if unread_inbox_count(ctx, chat_window) > 0 {
+            format!(" Inbox* ")
+        } else {
+            " Inbox ".to_string()
+        };
+        let dm_tab_text = if unread_dm_count(ctx, chat_window) > 0 {
             format!(" DM* ")
         } else {
             " DM ".to_string()
@@ -74,6 +126,7 @@ fn contents_hidden(ui: &mut Ui, ctx: &DbConnection, chat_window: &mut State) {
             (" System ".to_string(), ChatTab::System),
             (" Sector ".to_string(), ChatTab::Sector),
             (" Faction ".to_string(), ChatTab::Faction),
+            (inbox_tab_text, ChatTab::Inbox),
             (dm_tab_text, ChatTab::DirectMessages),
         ] {
             ui.label(RichText::new(label).color(if chat_window.selected_tab == tab {
@@ -152,6 +205,14 @@ pub fn draw_panel(ui: &mut Ui, ctx: &DbConnection, chat_window: &mut State) {
                 }
                 ui.selectable_value(&mut chat_window.selected_tab, ChatTab::Faction, "Faction");
 
+                let unread_count = unread_inbox_count(ctx, chat_window);
+                let inbox_text = if unread_count > 0 {
+                    format!("*Inbox ({})", unread_count)
+                } else {
+                    "Inbox".to_string()
+                };
+                ui.selectable_value(&mut chat_window.selected_tab, ChatTab::Inbox, inbox_text);
+
                 let unread_count = unread_dm_count(ctx, chat_window);
                 let dm_text = if unread_count > 0 {
                     format!("*DM ({})", unread_count)
@@ -167,14 +228,19 @@ pub fn draw_panel(ui: &mut Ui, ctx: &DbConnection, chat_window: &mut State) {
         .show_inside(ui, |ui| {
             ui.horizontal(|ui| {
                 chat_window.has_focus = false;
-                // The DM tab + Server tab are read-only (server-composed).
-                let read_only = matches!(
-                    chat_window.selected_tab,
-                    ChatTab::Server | ChatTab::DirectMessages
-                );
+                // The Inbox tab + Server tab are read-only (server-composed).
+                let read_only = matches!(chat_window.selected_tab, ChatTab::Server | ChatTab::Inbox);
                 if read_only {
                     ui.label(RichText::new("(read-only)").color(Color32::DARK_GRAY));
                 } else {
+                    if chat_window.selected_tab == ChatTab::DirectMessages {
+                        ui.label("To:");
+                        let recipient = egui::TextEdit::singleline(&mut chat_window.dm_recipient)
+                            .desired_width(80.0);
+                        if ui.add(recipient).has_focus() {
+                            chat_window.has_focus = true;
+                        }
+                    }
                     if ui.text_edit_singleline(&mut chat_window.text).has_focus() {
                         chat_window.has_focus = true;
                     }
@@ -189,11 +255,12 @@ pub fn draw_panel(ui: &mut Ui, ctx: &DbConnection, chat_window: &mut State) {
         });
 
     egui::CentralPanel::default().show_inside(ui, |ui| match chat_window.selected_tab {
-        ChatTab::Server => draw_server_channel(ctx, ui),
-        ChatTab::Galaxy => draw_galaxy_channel(ctx, ui),
-        ChatTab::System => draw_system_channel(ctx, ui),
-        ChatTab::Sector => draw_sector_channel(ctx, ui),
-        ChatTab::Faction => draw_faction_channel(ctx, ui),
+        ChatTab::Server => draw_server_channel(ctx, chat_window, ui),
+        ChatTab::Galaxy => draw_galaxy_channel(ctx, chat_window, ui),
+        ChatTab::System => draw_system_channel(ctx, chat_window, ui),
+        ChatTab::Sector => draw_sector_channel(ctx, chat_window, ui),
+        ChatTab::Faction => draw_faction_channel(ctx, chat_window, ui),
+        ChatTab::Inbox => draw_inbox(ctx, chat_window, ui),
         ChatTab::DirectMessages => draw_direct_messages(ctx, chat_window, ui),
     });
 }
@@ -205,8 +272,15 @@ fn send_message(ctx: &DbConnection, chat_window: &mut State) {
         ChatTab::System => ctx.reducers.send_star_system_chat(body),
         ChatTab::Sector => ctx.reducers.send_sector_chat(body),
         ChatTab::Faction => ctx.reducers.send_faction_chat(body),
+        ChatTab::DirectMessages => {
+            let recipient = chat_window.dm_recipient.trim().to_string();
+            if recipient.is_empty() {
+                return;
+            }
+            ctx.reducers.send_direct_message(recipient, body)
+        }
         // Read-only tabs are filtered out before send_message is called.
-        ChatTab::Server | ChatTab::DirectMessages => return,
+        ChatTab::Server | ChatTab::Inbox => return,
     };
     if let Err(error) = result {
         info!("Failed to send message: {}", error);
@@ -217,63 +291,108 @@ fn send_message(ctx: &DbConnection, chat_window: &mut State) {
     }
 }
 
+fn unread_inbox_count(ctx: &DbConnection, chat_window: &State) -> usize {
+    let last_login = get_current_player(ctx).and_then(|p| p.last_login);
+    DirectServerMessageUtils::get_unread_count(ctx, last_login, chat_window.inbox_dismissed_at)
+}
+
 fn unread_dm_count(ctx: &DbConnection, chat_window: &State) -> usize {
     let last_login = get_current_player(ctx).and_then(|p| p.last_login);
-    DirectServerMessageUtils::get_unread_count(ctx, last_login, chat_window.dms_dismissed_at)
+    DirectMessageUtils::get_unread_count(ctx, last_login, chat_window.dms_dismissed_at)
 }
 
-fn draw_galaxy_channel(ctx: &DbConnection, ui: &mut Ui) {
-    let mut messages: Vec<GalaxyChannelMessage> = ctx.db().my_galaxy_chat().iter().collect();
-    messages.sort_by_key(|m| m.created_at);
-    draw_scrolling_list(ui, messages.len(), |ui, idx| {
-        let message = &messages[idx];
-        let timestamp = DirectServerMessageUtils::format_timestamp_short(&message.created_at);
-        (timestamp, format!("[{}]: {}", render_sender(ctx, &message.sender), message.body))
-    });
+fn draw_galaxy_channel(ctx: &DbConnection, chat_window: &mut State, ui: &mut Ui) {
+    let format = |sender: &str, body: &str| format!("[{}]: {}", sender, body);
+    let mut rows: Vec<ChannelRow> = ctx
+        .db()
+        .my_galaxy_chat()
+        .iter()
+        .map(|m| channel_row(ctx, m.id, m.created_at, &m.sender, &m.body, format))
+        .collect();
+    rows.extend(history_rows(ctx, ChatChannel::Galaxy, format));
+    draw_scrolling_list(ui, ctx, chat_window, rows);
 }
 
-fn draw_system_channel(ctx: &DbConnection, ui: &mut Ui) {
-    let mut messages: Vec<StarSystemChannelMessage> =
-        ctx.db().my_star_system_chat().iter().collect();
-    messages.sort_by_key(|m| m.created_at);
-    draw_scrolling_list(ui, messages.len(), |ui, idx| {
-        let message = &messages[idx];
-        let timestamp = DirectServerMessageUtils::format_timestamp_short(&message.created_at);
-        (timestamp, format!("({}): {}", render_sender(ctx, &message.sender), message.body))
-    });
+fn draw_system_channel(ctx: &DbConnection, chat_window: &mut State, ui: &mut Ui) {
+    let format = |sender: &str, body: &str| format!("({}): {}", sender, body);
+    let mut rows: Vec<ChannelRow> = ctx
+        .db()
+        .my_star_system_chat()
+        .iter()
+        .map(|m| channel_row(ctx, m.id, m.created_at, &m.sender, &m.body, format))
+        .collect();
+    rows.extend(history_rows(ctx, ChatChannel::StarSystem, format));
+    draw_scrolling_list(ui, ctx, chat_window, rows);
 }
 
-fn draw_sector_channel(ctx: &DbConnection, ui: &mut Ui) {
-    let mut messages: Vec<SectorChannelMessage> = ctx.db().my_sector_chat().iter().collect();
-    messages.sort_by_key(|m| m.created_at);
-    draw_scrolling_list(ui, messages.len(), |ui, idx| {
-        let message = &messages[idx];
-        let timestamp = DirectServerMessageUtils::format_timestamp_short(&message.created_at);
-        (timestamp, format!("({}): {}", render_sender(ctx, &message.sender), message.body))
-    });
+fn draw_sector_channel(ctx: &DbConnection, chat_window: &mut State, ui: &mut Ui) {
+    let format = |sender: &str, body: &str| format!("({}): {}", sender, body);
+    let mut rows: Vec<ChannelRow> = ctx
+        .db()
+        .my_sector_chat()
+        .iter()
+        .map(|m| channel_row(ctx, m.id, m.created_at, &m.sender, &m.body, format))
+        .collect();
+    rows.extend(history_rows(ctx, ChatChannel::Sector, format));
+    draw_scrolling_list(ui, ctx, chat_window, rows);
 }
 
-fn draw_faction_channel(ctx: &DbConnection, ui: &mut Ui) {
-    let mut messages: Vec<FactionChannelMessage> = ctx.db().my_faction_chat().iter().collect();
-    messages.sort_by_key(|m| m.created_at);
-    draw_scrolling_list(ui, messages.len(), |ui, idx| {
-        let message = &messages[idx];
-        let timestamp = DirectServerMessageUtils::format_timestamp_short(&message.created_at);
-        (timestamp, format!("{}: {}", render_sender(ctx, &message.sender), message.body))
-    });
+fn draw_faction_channel(ctx: &DbConnection, chat_window: &mut State, ui: &mut Ui) {
+    let format = |sender: &str, body: &str| format!("{}: {}", sender, body);
+    let mut rows: Vec<ChannelRow> = ctx
+        .db()
+        .my_faction_chat()
+        .iter()
+        .map(|m| channel_row(ctx, m.id, m.created_at, &m.sender, &m.body, format))
+        .collect();
+    rows.extend(history_rows(ctx, ChatChannel::Faction, format));
+    draw_scrolling_list(ui, ctx, chat_window, rows);
 }
 
-fn draw_server_channel(ctx: &DbConnection, ui: &mut Ui) {
-    let mut messages: Vec<ServerChannelMessage> = ctx.db().server_channel_message().iter().collect();
-    messages.sort_by_key(|m| m.created_at);
-    draw_scrolling_list(ui, messages.len(), |ui, idx| {
-        let message = &messages[idx];
-        let timestamp = DirectServerMessageUtils::format_timestamp_short(&message.created_at);
-        (timestamp, format!("[MOTD] {}", message.body))
-    });
+fn draw_server_channel(ctx: &DbConnection, chat_window: &mut State, ui: &mut Ui) {
+    let rows: Vec<ChannelRow> = ctx
+        .db()
+        .server_channel_message()
+        .iter()
+        .map(|m| ChannelRow {
+            id: m.id,
+            created_at: m.created_at,
+            text: format!("[MOTD] {}", m.body),
+        })
+        .collect();
+    draw_scrolling_list(ui, ctx, chat_window, rows);
 }
 
-fn draw_direct_messages(ctx: &DbConnection, chat_window: &mut State, ui: &mut Ui) {
+fn channel_row(
+    ctx: &DbConnection,
+    id: u64,
+    created_at: Timestamp,
+    sender: &MessageSender,
+    body: &str,
+    format: impl Fn(&str, &str) -> String,
+) -> ChannelRow {
+    ChannelRow {
+        id,
+        created_at,
+        text: format(&render_sender(ctx, sender), body),
+    }
+}
+
+/// Archived messages of `channel` the player has paged back to.
+fn history_rows(
+    ctx: &DbConnection,
+    channel: ChatChannel,
+    format: impl Fn(&str, &str) -> String,
+) -> Vec<ChannelRow> {
+    ctx.db()
+        .my_chat_history()
+        .iter()
+        .filter(|m| m.channel == channel)
+        .map(|m| channel_row(ctx, m.original_id, m.sent_at, &m.sender, &m.body, &format))
+        .collect()
+}
+
+fn draw_inbox(ctx: &DbConnection, chat_window: &mut State, ui: &mut Ui) {
     let last_login = get_current_player(ctx).and_then(|p| p.last_login);
 
     // Newest-at-bottom to match every other chat tab. `get_messages` returns
@@ -287,7 +406,7 @@ fn draw_direct_messages(ctx: &DbConnection, chat_window: &mut State, ui: &mut Ui
     let unread_count = DirectServerMessageUtils::get_unread_count(
         ctx,
         last_login,
-        chat_window.dms_dismissed_at,
+        chat_window.inbox_dismissed_at,
     );
     ui.horizontal(|ui| {
         if unread_count > 0 {
@@ -296,7 +415,7 @@ fn draw_direct_messages(ctx: &DbConnection, chat_window: &mut State, ui: &mut Ui
                     .color(Color32::from_rgb(255, 215, 0)),
             );
             if ui.button("Read").clicked() {
-                chat_window.dms_dismissed_at = Some(Timestamp::now());
+                chat_window.inbox_dismissed_at = Some(Timestamp::now());
             }
         } else {
             ui.label(RichText::new("No unread").color(Color32::DARK_GRAY));
@@ -307,7 +426,7 @@ fn draw_direct_messages(ctx: &DbConnection, chat_window: &mut State, ui: &mut Ui
     let text_style = TextStyle::Body;
     let row_height = ui.text_style_height(&text_style) * 1.5;
 
-    let dismissed_at = chat_window.dms_dismissed_at;
+    let dismissed_at = chat_window.inbox_dismissed_at;
     ScrollArea::vertical()
         .auto_shrink([false, true])
         .stick_to_bottom(true)
@@ -324,11 +443,7 @@ fn draw_direct_messages(ctx: &DbConnection, chat_window: &mut State, ui: &mut Ui
                     );
                     last_timestamp = timestamp;
                 }
-                let cutoff = match (last_login, dismissed_at) {
-                    (Some(a), Some(b)) => Some(if a > b { a } else { b }),
-                    (Some(t), None) | (None, Some(t)) => Some(t),
-                    (None, None) => None,
-                };
+                let cutoff = DirectServerMessageUtils::effective_cutoff(last_login, dismissed_at);
                 let is_unread = cutoff.map(|c| message.created_at > c).unwrap_or(true);
                 let severity_color =
                     DirectServerMessageUtils::color_for_severity(&message.severity);
@@ -359,20 +474,163 @@ fn draw_direct_messages(ctx: &DbConnection, chat_window: &mut State, ui: &mut Ui
         });
 }
 
-/// Shared scroll-list scaffolding with grouped timestamp gutters.
-fn draw_scrolling_list<F>(ui: &mut Ui, total: usize, mut row: F)
-where
-    F: FnMut(&mut Ui, usize) -> (String, String),
-{
+fn draw_direct_messages(ctx: &DbConnection, chat_window: &mut State, ui: &mut Ui) {
+    let last_login = get_current_player(ctx).and_then(|p| p.last_login);
+    let me = ctx.identity();
+
+    // Newest-at-bottom, same as the Inbox tab.
+    let mut messages = DirectMessageUtils::get_messages(ctx);
+    messages.reverse();
+
+    let unread_count =
+        DirectMessageUtils::get_unread_count(ctx, last_login, chat_window.dms_dismissed_at);
+    ui.horizontal(|ui| {
+        if unread_count > 0 {
+            ui.label(
+                RichText::new(format!("{} unread", unread_count))
+                    .color(Color32::from_rgb(255, 215, 0)),
+            );
+            if ui.button("Read").clicked() {
+                chat_window.dms_dismissed_at = Some(Timestamp::now());
+            }
+        } else {
+            ui.label(RichText::new("No unread").color(Color32::DARK_GRAY));
+        }
+
+        // Block / Mute toggles for whoever is in the "To:" field.
+        let recipient = chat_window.dm_recipient.trim().to_string();
+        if let Some(target) = ctx
+            .db()
+            .player()
+            .username()
+            .find(&recipient)
+            .filter(|p| p.id != me)
+        {
+            ui.separator();
+            toggle_chat_block(ui, ctx, &target, ChatBlockKind::Block, "Block", "Unblock");
+            toggle_chat_block(ui, ctx, &target, ChatBlockKind::Mute, "Mute", "Unmute");
+        }
+    });
+    ui.separator();
+
     let text_style = TextStyle::Body;
-    let row_height = ui.text_style_height(&text_style);
+    let row_height = ui.text_style_height(&text_style) * 1.5;
+
+    let cutoff = DirectServerMessageUtils::effective_cutoff(last_login, chat_window.dms_dismissed_at);
     ScrollArea::vertical()
         .auto_shrink([false, true])
         .stick_to_bottom(true)
-        .show_rows(ui, row_height, total, |ui, row_range| {
+        .show_rows(ui, row_height, messages.len(), |ui, row_range| {
             let mut last_timestamp = String::new();
             for idx in row_range {
-                let (timestamp, text) = row(ui, idx);
+                let message = &messages[idx];
+                let timestamp = DirectServerMessageUtils::format_timestamp_short(&message.created_at);
+                if timestamp.cmp(&last_timestamp) != Ordering::Equal {
+                    ui.label(
+                        RichText::new(format!("[{}]", timestamp))
+                            .color(Color32::GRAY)
+                            .size(10.0),
+                    );
+                    last_timestamp = timestamp;
+                }
+                let received = message.to == me;
+                let is_unread = received && cutoff.map(|c| message.created_at > c).unwrap_or(true);
+                let counterpart = DirectMessageUtils::counterpart(ctx, message);
+                let name = get_username(ctx, &counterpart);
+                ui.horizontal(|ui| {
+                    if is_unread {
+                        ui.label(RichText::new("●").color(Color32::from_rgb(255, 215, 0)));
+                    }
+                    let arrow = if received { "from" } else { "to" };
+                    ui.label(RichText::new(arrow).color(Color32::GRAY));
+                    // Clicking a name makes them the reply target.
+                    if ui.link(format!("[{}]", name)).clicked() {
+                        chat_window.dm_recipient = name.clone();
+                    }
+                    let mut body_text = RichText::new(&message.body);
+                    if is_unread {
+                        body_text = body_text.strong();
+                    }
+                    ui.label(body_text);
+                });
+                ui.add_space(2.0);
+            }
+        });
+}
+
+fn toggle_chat_block(
+    ui: &mut Ui,
+    ctx: &DbConnection,
+    target: &Player,
+    kind: ChatBlockKind,
+    on_label: &str,
+    off_label: &str,
+) {
+    let active = DirectMessageUtils::has_block(ctx, &target.id, &kind);
+    let label = if active { off_label } else { on_label };
+    if !ui.button(label).clicked() {
+        return;
+    }
+    let username = target.username.clone();
+    let result = match (kind, active) {
+        (ChatBlockKind::Block, false) => ctx.reducers.block_player(username),
+        (ChatBlockKind::Block, true) => ctx.reducers.unblock_player(username),
+        (ChatBlockKind::Mute, false) => ctx.reducers.mute_player(username),
+        (ChatBlockKind::Mute, true) => ctx.reducers.unmute_player(username),
+    };
+    if let Err(error) = result {
+        info!("Failed to {} {}: {}", label, target.username, error);
+    }
+}
+
+/// Shared scroll-list scaffolding with grouped timestamp gutters, the
+/// "Load older" pager and the unread divider.
+fn draw_scrolling_list(
+    ui: &mut Ui,
+    ctx: &DbConnection,
+    chat_window: &mut State,
+    mut rows: Vec<ChannelRow>,
+) {
+    rows.sort_by_key(|row| (row.created_at, row.id));
+    rows.dedup_by_key(|row| row.id);
+
+    let tab = chat_window.selected_tab;
+    let shown = chat_window.shown_rows.entry(tab).or_insert(PAGE_SIZE);
+    let first_shown = rows.len().saturating_sub(*shown);
+    // The first message posted since the player's last login, if any older
+    // ones are on screen to divide it from.
+    let last_login = get_current_player(ctx).and_then(|p| p.last_login);
+    let first_unread = last_login
+        .and_then(|login| rows.iter().position(|row| row.created_at > login))
+        .filter(|idx| *idx > first_shown);
+
+    ScrollArea::vertical()
+        .auto_shrink([false, true])
+        .stick_to_bottom(true)
+        .show(ui, |ui| {
+            let channel = tab.archived_channel();
+            if (first_shown > 0 || channel.is_some()) && ui.small_button("Load older").clicked() {
+                *shown += PAGE_SIZE;
+                // Everything the views hold is already on screen, so fetch
+                // the next archived page from before the oldest row.
+                if let (0, Some(channel)) = (first_shown, channel) {
+                    let before_id = rows.first().map(|row| row.id);
+                    if let Err(error) = ctx.reducers.request_chat_history(channel, before_id) {
+                        info!("Failed to load older messages: {}", error);
+                    }
+                }
+            }
+
+            let mut last_timestamp = String::new();
+            for (idx, row) in rows.iter().enumerate().skip(first_shown) {
+                if Some(idx) == first_unread {
+                    ui.label(
+                        RichText::new("── new since your last login ──")
+                            .color(Color32::from_rgb(255, 215, 0))
+                            .size(10.0),
+                    );
+                }
+                let timestamp = DirectServerMessageUtils::format_timestamp_short(&row.created_at);
                 if timestamp.cmp(&last_timestamp) != Ordering::Equal {
                     ui.label(
                         RichText::new(format!("[{}]", timestamp))
@@ -381,7 +639,7 @@ where
                     );
                     last_timestamp = timestamp;
                 }
-                ui.label(text);
+                ui.label(&row.text);
             }
         });
 }
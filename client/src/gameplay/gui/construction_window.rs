@@ -1,25 +1,42 @@
 use egui::{Color32, Context, ProgressBar, RichText};
 use spacetimedb_sdk::*;
 
-use crate::{server::bindings::*, stdb::utils::*};
+use crate::{
+    gameplay::direct_server_messages::DirectServerMessageUtils, server::bindings::*,
+    stdb::utils::*,
+};
 
 /// Must match `server::logic::stations::contribution::CONTRIBUTE_RANGE_PX`.
 /// The server rejects deposits past this distance; we mirror it here so the
 /// UI can grey the deposit buttons before the player tries.
 const CONTRIBUTE_RANGE_PX: f32 = 300.0;
 
-pub struct State {}
+/// How many contributors the site tab lists next to the requirement bars.
+const SITE_TOP_CONTRIBUTORS: usize = 5;
+
+#[derive(PartialEq)]
+enum CurrentTab {
+    Site,
+    Leaderboards,
+    MyContributions,
+}
+
+pub struct State {
+    current_tab: CurrentTab,
+}
 
 impl State {
     pub fn new() -> Self {
-        State {}
+        State {
+            current_tab: CurrentTab::Site,
+        }
     }
 }
 
 pub fn draw(
     egui_ctx: &Context,
     ctx: &DbConnection,
-    _state: &mut State,
+    state: &mut State,
     open: &mut bool,
 ) -> Option<egui::InnerResponse<Option<()>>> {
     egui::Window::new("Construction")
@@ -31,12 +48,33 @@ pub fn draw(
         .vscroll(true)
         .default_width(360.0)
         .default_height(420.0)
-        .show(egui_ctx, |ui| match nearest_construction_site(ctx) {
-            None => {
-                ui.label("No construction site in this sector.");
-            }
-            Some((station, under_construction)) => {
-                draw_site(ui, ctx, &station, &under_construction);
+        .show(egui_ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut state.current_tab, CurrentTab::Site, "Site");
+                ui.selectable_value(
+                    &mut state.current_tab,
+                    CurrentTab::Leaderboards,
+                    "Leaderboards",
+                );
+                ui.selectable_value(
+                    &mut state.current_tab,
+                    CurrentTab::MyContributions,
+                    "My Contributions",
+                );
+            });
+            ui.separator();
+
+            match state.current_tab {
+                CurrentTab::Site => match nearest_construction_site(ctx) {
+                    None => {
+                        ui.label("No construction site in this sector.");
+                    }
+                    Some((station, under_construction)) => {
+                        draw_site(ui, ctx, &station, &under_construction);
+                    }
+                },
+                CurrentTab::Leaderboards => draw_leaderboards_tab(ui, ctx),
+                CurrentTab::MyContributions => draw_my_contributions_tab(ui, ctx),
             }
         })
 }
@@ -103,15 +141,33 @@ fn draw_site(
     }
     ui.separator();
 
-    if under_construction.is_operational {
-        ui.add_space(8.0);
-        ui.label(
-            RichText::new("Construction Complete!")
-                .heading()
-                .color(Color32::from_rgb(120, 220, 120)),
-        );
-        ui.label("This station is now operational.");
-        return;
+    // Past the initial build the pool grows the station a size at a time;
+    // once nothing is left to grow into, there is no pool to show.
+    match (&under_construction.growing_to, under_construction.is_operational) {
+        (None, true) => {
+            ui.add_space(8.0);
+            ui.label(
+                RichText::new("Fully Grown")
+                    .heading()
+                    .color(Color32::from_rgb(120, 220, 120)),
+            );
+            ui.label(format!(
+                "This {:?} station is operational with room for {} modules.",
+                station.size,
+                station.size.modules()
+            ));
+            return;
+        }
+        (Some(next), _) => {
+            ui.label(format!(
+                "Growing from {:?} to {:?} ({} → {} module slots).",
+                station.size,
+                next,
+                station.size.modules(),
+                next.modules()
+            ));
+        }
+        (None, false) => {}
     }
 
     let pct = under_construction.construction_progress_percentage.clamp(0.0, 100.0);
@@ -129,7 +185,7 @@ fn draw_site(
         .db()
         .construction_requirement()
         .iter()
-        .filter(|r| r.station_id == station.id)
+        .filter(|r| r.station_id == station.id && r.stage == under_construction.stage)
         .collect();
     requirements.sort_by_key(|r| r.resource_item_id);
 
@@ -141,7 +197,12 @@ fn draw_site(
                 .db()
                 .construction_contribution_log()
                 .iter()
-                .filter(|c| c.station_id == station.id && c.item_id == req.resource_item_id)
+                .filter(|c| {
+                    c.station_id == station.id
+                        && c.stage == under_construction.stage
+                        && c.module_proposal_id.is_none()
+                        && c.item_id == req.resource_item_id
+                })
                 .map(|c| c.quantity)
                 .sum();
             let name = ctx
@@ -161,6 +222,18 @@ fn draw_site(
         }
     }
 
+    ui.add_space(8.0);
+    ui.heading("Top Contributors");
+    ui.separator();
+
+    let top: Vec<ContributionLeaderboardEntry> = ctx
+        .db()
+        .construction_site_leaderboards()
+        .iter()
+        .filter(|e| e.scope_id == station.id)
+        .collect();
+    draw_leaderboard(ui, ctx, top, SITE_TOP_CONTRIBUTORS);
+
     ui.add_space(8.0);
     ui.heading("Deposit From Cargo");
     ui.separator();
@@ -251,3 +324,108 @@ fn deposit_buttons(
         deposit(cargo_qty as u32);
     }
 }
+
+fn draw_leaderboards_tab(ui: &mut egui::Ui, ctx: &DbConnection) {
+    ui.heading("This Week");
+    ui.separator();
+    let weekly: Vec<ContributionLeaderboardEntry> =
+        ctx.db().weekly_contribution_leaderboard().iter().collect();
+    draw_leaderboard(ui, ctx, weekly, usize::MAX);
+
+    ui.add_space(8.0);
+    ui.heading("All Time");
+    ui.separator();
+    let all_time: Vec<ContributionLeaderboardEntry> =
+        ctx.db().all_time_contribution_leaderboard().iter().collect();
+    draw_leaderboard(ui, ctx, all_time, usize::MAX);
+
+    // One board per faction, the player's own first.
+    let own_faction_id = get_current_player(ctx).map(|p| p.faction_id.value as u64);
+    let mut factions: Vec<(u64, String)> = ctx
+        .db()
+        .faction_contribution_leaderboards()
+        .iter()
+        .map(|e| (e.scope_id, e.scope_name))
+        .collect();
+    factions.sort_by_key(|(id, _)| (Some(*id) != own_faction_id, *id));
+    factions.dedup();
+    for (faction_id, faction_name) in factions {
+        ui.add_space(8.0);
+        ui.heading(
+            RichText::new(format!("{} Stations", faction_name))
+                .color(crate::gameplay::gui::faction_color(faction_id as u32)),
+        );
+        ui.separator();
+        let board: Vec<ContributionLeaderboardEntry> = ctx
+            .db()
+            .faction_contribution_leaderboards()
+            .iter()
+            .filter(|e| e.scope_id == faction_id)
+            .collect();
+        draw_leaderboard(ui, ctx, board, usize::MAX);
+    }
+}
+
+fn draw_my_contributions_tab(ui: &mut egui::Ui, ctx: &DbConnection) {
+    let mut history: Vec<ContributionHistoryEntry> =
+        ctx.db().my_contribution_history().iter().collect();
+    if history.is_empty() {
+        ui.label("You haven't contributed to any construction yet.");
+        return;
+    }
+    history.sort_by_key(|h| std::cmp::Reverse(h.log_id));
+
+    let total_value: u64 = history.iter().map(|h| h.value).sum();
+    ui.label(format!(
+        "{} contributions worth {} credits.",
+        history.len(),
+        total_value
+    ));
+    ui.separator();
+
+    egui::Grid::new("my_contributions_grid")
+        .striped(true)
+        .show(ui, |ui| {
+            for entry in &history {
+                ui.small(DirectServerMessageUtils::format_timestamp_short(
+                    &entry.contributed_at,
+                ));
+                ui.label(&entry.station_name);
+                ui.label(format!("{}x {}", entry.quantity, entry.item_name));
+                ui.label(format!("{}c", entry.value));
+                ui.end_row();
+            }
+        });
+}
+
+/// Ranked rows, the local player's highlighted.
+fn draw_leaderboard(
+    ui: &mut egui::Ui,
+    ctx: &DbConnection,
+    mut entries: Vec<ContributionLeaderboardEntry>,
+    limit: usize,
+) {
+    if entries.is_empty() {
+        ui.label("No contributions yet.");
+        return;
+    }
+    entries.sort_by_key(|e| e.rank);
+
+    egui::Grid::new(ui.next_auto_id())
+        .striped(true)
+        .show(ui, |ui| {
+            for entry in entries.iter().take(limit) {
+                let is_you = entry.player_id == ctx.identity();
+                let name = if is_you {
+                    RichText::new(&entry.username).strong()
+                } else {
+                    RichText::new(&entry.username)
+                };
+                ui.label(format!("#{}", entry.rank));
+                ui.label(name);
+                ui.label(format!("{} units", entry.quantity));
+                ui.label(format!("{}c", entry.value));
+                ui.end_row();
+            }
+        });
+}
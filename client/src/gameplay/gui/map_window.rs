@@ -2,6 +2,7 @@ use std::collections::{BTreeSet, HashMap, HashSet};
 
 use egui::*;
 use macroquad::prelude::*;
+use solarance_shared::{plan_route, GateEdge, Route};
 use spacetimedb_sdk::Table;
 use spacetimedb_sdk::*;
 
@@ -12,6 +13,8 @@ use crate::{server::bindings::*, stdb::utils::*};
 const MAP_FIT_FACTOR: f32 = 0.82;
 /// Half-size (px) of a sector marker on the galaxy map.
 const MAP_SECTOR_RADIUS: f32 = 8.0;
+/// Stroke for the jumpgate edges on the planned route to the selected sector.
+const MAP_ROUTE_COLOR: Color32 = Color32::from_rgb(255, 215, 0);
 
 #[derive(PartialEq)]
 enum MapTab {
@@ -117,9 +120,25 @@ impl State {
         // subscribed tables, so the indicators and panel update live (#121).
         let construction_sectors = sectors_with_active_construction(ctx);
 
+        // Route from the Piloted Ship to the clicked sector, if there is one.
+        // Planned fresh each frame so toggling a gate re-routes live.
+        let route = self
+            .selected_sector_id
+            .and_then(|target| plan_route_to_sector(ctx, target));
+        let route_hops: HashSet<(u64, u64)> = route
+            .as_ref()
+            .map(|(route, _)| {
+                route
+                    .sectors
+                    .windows(2)
+                    .map(|hop| if hop[0] <= hop[1] { (hop[0], hop[1]) } else { (hop[1], hop[0]) })
+                    .collect()
+            })
+            .unwrap_or_default();
+
         // Details side panel for the clicked sector — drawn before the canvas
         // so the canvas consumes the remaining width.
-        self.draw_sector_details(ui, ctx, &construction_sectors);
+        self.draw_sector_details(ui, ctx, &construction_sectors, route.as_ref());
 
         Frame::canvas(ui.style()).show(ui, |ui| {
             let (response, painter) = ui.allocate_painter(
@@ -232,6 +251,8 @@ impl State {
             let positions: HashMap<u64, (f32, f32)> =
                 sectors.iter().map(|s| (s.id, (s.x, s.y))).collect();
             let edge_stroke = Stroke::new(1.5, Color32::from_rgb(90, 160, 150));
+            let route_stroke = Stroke::new(3.0, MAP_ROUTE_COLOR);
+            let mut route_edges = Vec::new();
             let mut seen: HashSet<(u64, u64)> = HashSet::new();
             for gate in ctx.db().jump_gate().iter() {
                 let (a, b) = (gate.current_sector_id, gate.target_sector_id);
@@ -242,12 +263,16 @@ impl State {
                 if let (Some(&(ax, ay)), Some(&(bx, by))) =
                     (positions.get(&a), positions.get(&b))
                 {
-                    edges.push(Shape::line_segment(
-                        [to_screen(ax, ay), to_screen(bx, by)],
-                        edge_stroke,
-                    ));
+                    let segment = [to_screen(ax, ay), to_screen(bx, by)];
+                    if route_hops.contains(&key) {
+                        route_edges.push(Shape::line_segment(segment, route_stroke));
+                    } else {
+                        edges.push(Shape::line_segment(segment, edge_stroke));
+                    }
                 }
             }
+            // Route edges sit on top of the plain network.
+            edges.extend(route_edges);
 
             // --- Sector markers ---------------------------------------------
             // Build the dot shapes now; labels are drawn last so they sit on
@@ -363,6 +388,7 @@ impl State {
         ui: &mut egui::Ui,
         ctx: &DbConnection,
         construction_sectors: &HashSet<u64>,
+        route: Option<&(Route, f32)>,
     ) {
         let Some(selected_id) = self.selected_sector_id else {
             return;
@@ -408,6 +434,33 @@ impl State {
                     ui.label(description);
                 }
 
+                ui.separator();
+                ui.strong("Route");
+                match route {
+                    Some((route, _)) if route.jump_count() == 0 => {
+                        ui.weak("You are here.");
+                    }
+                    Some((route, speed)) => {
+                        ui.colored_label(
+                            MAP_ROUTE_COLOR,
+                            format!(
+                                "{} jump{} · {}",
+                                route.jump_count(),
+                                if route.jump_count() == 1 { "" } else { "s" },
+                                format_travel_time(route.estimated_travel_secs(*speed))
+                            ),
+                        );
+                        // Hops that leave this system don't show on the
+                        // canvas, so list the whole chain here.
+                        for sector_id in route.sectors.iter().skip(1) {
+                            ui.weak(format!("→ {}", sector_label(ctx, *sector_id, sector.system_id)));
+                        }
+                    }
+                    None => {
+                        ui.weak("No route over active jumpgates.");
+                    }
+                }
+
                 ui.separator();
                 ui.strong("Connected sectors");
                 // Gates are bidirectional (two rows per pair); collecting the
@@ -420,22 +473,7 @@ impl State {
                     .map(|gate| gate.target_sector_id)
                     .collect();
                 for adjacent_id in adjacent {
-                    // Cross-system gates carry the destination system in
-                    // front of the sector name, e.g. "[Kingdom's End] Spacefalls".
-                    let label = match ctx.db().sector().id().find(&adjacent_id) {
-                        Some(adj) if adj.system_id != sector.system_id => {
-                            let system_name = ctx
-                                .db()
-                                .star_system()
-                                .id()
-                                .find(&adj.system_id)
-                                .map(|s| s.name)
-                                .unwrap_or_else(|| format!("#{}", adj.system_id));
-                            format!("[{}] {}", system_name, adj.name)
-                        }
-                        Some(adj) => adj.name,
-                        None => format!("Sector #{}", adjacent_id),
-                    };
+                    let label = sector_label(ctx, adjacent_id, sector.system_id);
                     if ui.link(label).clicked() {
                         self.selected_sector_id = Some(adjacent_id);
                     }
@@ -444,6 +482,52 @@ impl State {
     }
 }
 
+/// Sector name for the details panel. Sectors outside `home_system_id` carry
+/// their system in front, e.g. "[Kingdom's End] Spacefalls".
+fn sector_label(ctx: &DbConnection, sector_id: u64, home_system_id: u32) -> String {
+    match ctx.db().sector().id().find(&sector_id) {
+        Some(sector) if sector.system_id != home_system_id => {
+            let system_name = ctx
+                .db()
+                .star_system()
+                .id()
+                .find(&sector.system_id)
+                .map(|s| s.name)
+                .unwrap_or_else(|| format!("#{}", sector.system_id));
+            format!("[{}] {}", system_name, sector.name)
+        }
+        Some(sector) => sector.name,
+        None => format!("Sector #{}", sector_id),
+    }
+}
+
+/// Plans the route from the Piloted Ship to `target_sector` with the shared
+/// planner (the same one the server autopilot uses), paired with the ship
+/// type's `base_speed` for the travel-time estimate. `None` if there is no
+/// Piloted Ship or no active route.
+fn plan_route_to_sector(ctx: &DbConnection, target_sector: u64) -> Option<(Route, f32)> {
+    let (ship, snapshot) = predicted_player_snapshot(ctx)?;
+    let speed = ctx
+        .db()
+        .ship_type_definition()
+        .id()
+        .find(&ship.shiptype_id)
+        .map(|t| t.base_speed)
+        .unwrap_or(0.0);
+    let gates: Vec<GateEdge> = ctx.db().jump_gate().iter().map(|g| (&g).into()).collect();
+    let route = plan_route(&gates, ship.sector_id, snapshot.pos, target_sector, None)?;
+    Some((route, speed))
+}
+
+/// "~1m 05s"-style estimate; "—" for a ship that can't move.
+fn format_travel_time(secs: f32) -> String {
+    if !secs.is_finite() {
+        return "—".to_string();
+    }
+    let secs = secs.round() as u64;
+    format!("~{}m {:02}s", secs / 60, secs % 60)
+}
+
 pub fn draw(
     egui_ctx: &Context,
     ctx: &DbConnection,
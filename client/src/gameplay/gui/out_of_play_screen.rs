@@ -2,8 +2,16 @@ use std::collections::HashMap;
 
 use egui::{Color32, Context, Frame, Rangef, RichText, Shadow, Ui};
 use macroquad::prelude::*;
+use solarance_shared::TradeSide;
 use spacetimedb_sdk::{DbContext, Table};
 
+pub mod hangar;
+pub mod market_orders;
+pub mod module_proposals;
+pub mod power_grid;
+pub mod price_history;
+pub mod production_report;
+pub mod shipyard;
 pub mod utils;
 
 use crate::{
@@ -38,6 +46,10 @@ pub struct State {
 
     /// A hashmap of station module IDs + item def IDs to the currently selected buy/sell amounts.
     buy_sell_scalars: HashMap<(u64, u32), (u32, u32)>,
+    /// Item def IDs to the currently selected hangar store/withdraw amounts.
+    hangar_scalars: HashMap<u32, (u32, u32)>,
+    /// Station module IDs + item def IDs to the order book's unsent order form.
+    order_drafts: HashMap<(u64, u32), market_orders::OrderDraft>,
 }
 
 impl State {
@@ -46,6 +58,8 @@ impl State {
             currently_selected_module: None,
             selected_ship_id: None,
             buy_sell_scalars: HashMap::new(),
+            hangar_scalars: HashMap::new(),
+            order_drafts: HashMap::new(),
         }
     }
 }
@@ -135,6 +149,19 @@ fn show_station_window(
         //     ui.heading("Station Panel");
         // });
 
+        power_grid::show_power_grid_section(ctx, ui, &station);
+        production_report::show_production_report_section(ctx, ui, &station);
+        hangar::show_hangar_section(
+            ctx,
+            &mut game_state.out_of_play_screen,
+            ui,
+            &ship,
+            &station,
+        );
+        shipyard::show_shipyard_section(ctx, ui, &ship, &station);
+        module_proposals::show_module_proposal_section(ctx, ui, &ship, &station);
+        ui.separator();
+
         // Show tabs for each
         ui.horizontal(|ui| {
             for (index, module) in ctx
@@ -357,7 +384,7 @@ fn buy_and_sell_inventory_item(
     ship: &Ship,
     ui: &mut Ui,
     module: &StationModule,
-    _trading_port: &Option<TradingPort>,
+    trading_port: &Option<TradingPort>,
     inventory: &StationModuleInventoryItem,
     item_def: &ItemDefinition,
 ) {
@@ -368,10 +395,17 @@ fn buy_and_sell_inventory_item(
             .unwrap_or("No description available.".to_string()),
     );
     ui.separator();
+    let quote = utils::market_quote(ctx, module, inventory, item_def);
     ui.horizontal(|ui| {
         ui.label(format!("Base Value: {}c", item_def.base_value));
         ui.spacing();
-        ui.label(format!("Station's Value: {}c", inventory.cached_price));
+        ui.label(format!("Station's Value: {}c", quote.mid_price().round()));
+        ui.spacing();
+        ui.label(format!(
+            "Buys at {}c / Sells at {}c",
+            quote.total(TradeSide::Sell, 1),
+            quote.total(TradeSide::Buy, 1)
+        ));
     });
     ui.label(format!("Volume per Unit: {}v", item_def.volume_per_unit));
 
@@ -422,7 +456,7 @@ fn buy_and_sell_inventory_item(
                     players_current_amount > 0,
                     egui::Slider::new(&mut sell_scalar, 0..=players_current_amount),
                 );
-                ui.label(format!("{}c", sell_scalar * inventory.cached_price));
+                ui.label(format!("{}c", quote.total(TradeSide::Sell, sell_scalar)));
                 sell_item_to_station(ctx, sell_scalar, ship, module, inventory, ui);
 
                 if players_current_amount == 0 {
@@ -463,7 +497,7 @@ fn buy_and_sell_inventory_item(
                         }),
                     ),
                 );
-                ui.label(format!("{}c", buy_scalar * inventory.cached_price));
+                ui.label(format!("{}c", quote.total(TradeSide::Buy, buy_scalar)));
                 buy_item_from_station(ctx, buy_scalar, ship, module, inventory, ui);
 
                 if space_available == 0 {
@@ -485,6 +519,11 @@ fn buy_and_sell_inventory_item(
         });
     });
 
+    price_history::show_price_history(ctx, ui, inventory);
+    if trading_port.is_some() {
+        market_orders::show_order_book(ctx, state, ui, ship, module, inventory, item_def);
+    }
+
     state
         .buy_sell_scalars
         .insert((module.id, item_def.id), (buy_scalar, sell_scalar));
@@ -625,6 +664,7 @@ fn left_panel(ui: &mut Ui, ctx: &DbConnection, game_state: &mut GameState) {
         "Credits: {}",
         get_current_player(ctx).map_or_else(|| 0, |player| player.credits)
     ));
+    hangar::show_hangar_overview(ctx, ui);
 
     egui::ScrollArea::vertical().show(ui, |ui| {
         for (star_system, sectors_with_ships) in sorted_system_to_ships {
@@ -0,0 +1,205 @@
+//! Station hangar panels for the out-of-play screen.
+//!
+//! The station window gets a "Hangar" section for moving cargo between the
+//! Docked Ship and the player's hangar at that station; the left panel lists
+//! everything the player has stored, station by station. Space rules live on
+//! the server (`logic/stations/hangar.rs`) — `hangar_capacity` mirrors them
+//! only so the sliders stay in range.
+
+use super::*;
+
+/// Per-player hangar space at a station as `(slots, volume)`, summed over its
+/// operational storage depots.
+pub fn hangar_capacity(ctx: &DbConnection, station_id: u64) -> (u32, u64) {
+    let mut slots = 0;
+    let mut volume = 0;
+    for module in ctx
+        .db()
+        .station_module()
+        .iter()
+        .filter(|m| m.station_id == station_id && m.is_operational)
+    {
+        if let Some(blueprint) = ctx
+            .db()
+            .station_module_blueprint()
+            .id()
+            .find(&module.blueprint)
+        {
+            if blueprint.specific_type == StationModuleSpecificType::StorageDepot {
+                let module_slots = blueprint.max_internal_storage_slots as u32;
+                slots += module_slots;
+                volume += module_slots as u64
+                    * blueprint
+                        .max_internal_storage_volume_per_slot_m3
+                        .unwrap_or(0) as u64;
+            }
+        }
+    }
+    (slots, volume)
+}
+
+/// The player's hangar rows at `station_id`.
+fn my_hangar_items(ctx: &DbConnection, station_id: u64) -> Vec<StationHangarItem> {
+    let identity = ctx.identity();
+    let mut items: Vec<StationHangarItem> = ctx
+        .db()
+        .station_hangar_item()
+        .iter()
+        .filter(|h| h.station_id == station_id && h.player_id == identity)
+        .collect();
+    items.sort_by_key(|h| h.item_id);
+    items
+}
+
+fn item_name(ctx: &DbConnection, item_id: u32) -> String {
+    ctx.db()
+        .item_definition()
+        .id()
+        .find(&item_id)
+        .map(|def| def.name)
+        .unwrap_or_else(|| format!("Item #{}", item_id))
+}
+
+/// Deposit / withdraw controls for the Docked Ship at `station`.
+pub fn show_hangar_section(
+    ctx: &DbConnection,
+    state: &mut State,
+    ui: &mut Ui,
+    ship: &Ship,
+    station: &Station,
+) {
+    let (slots, volume) = hangar_capacity(ctx, station.id);
+    let stored = my_hangar_items(ctx, station.id);
+    let used_volume: u64 = stored
+        .iter()
+        .map(|h| {
+            h.quantity as u64
+                * ctx
+                    .db()
+                    .item_definition()
+                    .id()
+                    .find(&h.item_id)
+                    .map_or(0, |def| def.volume_per_unit as u64)
+        })
+        .sum();
+
+    egui::CollapsingHeader::new(format!(
+        "Hangar — {} / {}v, {} / {} item types",
+        used_volume,
+        volume,
+        stored.len(),
+        slots
+    ))
+    .id_salt(("hangar", station.id))
+    .show(ui, |ui| {
+        if volume == 0 {
+            ui.label("This station has no storage depot, so you can't store anything here.");
+        }
+
+        ui.label(RichText::new("Stored here").strong());
+        if stored.is_empty() {
+            ui.label("Nothing.");
+        }
+        for item in &stored {
+            ui.horizontal(|ui| {
+                ui.label(format!("{}x {}", item.quantity, item_name(ctx, item.item_id)));
+                let amount = &mut state.hangar_scalars.entry(item.item_id).or_default().1;
+                *amount = (*amount).min(item.quantity);
+                ui.add(egui::Slider::new(amount, 0..=item.quantity));
+                if ui
+                    .add_enabled(*amount > 0, egui::Button::new("WITHDRAW"))
+                    .clicked()
+                {
+                    if let Err(e) = ctx.reducers().withdraw_cargo_from_hangar(
+                        ship.id.into(),
+                        item.item_id.into(),
+                        *amount,
+                    ) {
+                        warn!("Failed to withdraw {}x {}: {}", amount, item.item_id, e);
+                    }
+                    *amount = 0;
+                }
+            });
+        }
+
+        ui.separator();
+        ui.label(RichText::new("In your cargo hold").strong());
+        let mut cargo: Vec<(u32, u32)> = Vec::new();
+        for stack in ctx
+            .db()
+            .ship_cargo_item()
+            .iter()
+            .filter(|c| c.ship_id == ship.id)
+        {
+            match cargo.iter_mut().find(|(id, _)| *id == stack.item_id) {
+                Some(entry) => entry.1 += stack.quantity as u32,
+                None => cargo.push((stack.item_id, stack.quantity as u32)),
+            }
+        }
+        cargo.sort_by_key(|(id, _)| *id);
+        if cargo.is_empty() {
+            ui.label("Nothing.");
+        }
+        for (item_id, quantity) in cargo {
+            ui.horizontal(|ui| {
+                ui.label(format!("{}x {}", quantity, item_name(ctx, item_id)));
+                let amount = &mut state.hangar_scalars.entry(item_id).or_default().0;
+                *amount = (*amount).min(quantity);
+                ui.add_enabled(volume > 0, egui::Slider::new(amount, 0..=quantity));
+                if ui
+                    .add_enabled(volume > 0 && *amount > 0, egui::Button::new("STORE"))
+                    .clicked()
+                {
+                    if let Err(e) = ctx.reducers().deposit_cargo_to_hangar(
+                        ship.id.into(),
+                        item_id.into(),
+                        *amount,
+                    ) {
+                        warn!("Failed to store {}x {}: {}", amount, item_id, e);
+                    }
+                    *amount = 0;
+                }
+            });
+        }
+    });
+}
+
+/// Everything the player has stored, grouped by station.
+pub fn show_hangar_overview(ctx: &DbConnection, ui: &mut Ui) {
+    let identity = ctx.identity();
+    let mut station_ids: Vec<u64> = ctx
+        .db()
+        .station_hangar_item()
+        .iter()
+        .filter(|h| h.player_id == identity)
+        .map(|h| h.station_id)
+        .collect();
+    station_ids.sort();
+    station_ids.dedup();
+
+    egui::CollapsingHeader::new(format!("Hangars ({} stations)", station_ids.len()))
+        .id_salt("hangar_overview")
+        .show(ui, |ui| {
+            if station_ids.is_empty() {
+                ui.label("You have nothing stored at any station.");
+            }
+            for station_id in station_ids {
+                let station_label = match ctx.db().station().id().find(&station_id) {
+                    Some(station) => format!(
+                        "{} ({})",
+                        station_display_name(ctx, &station),
+                        get_sector_name(ctx, &station.sector_id)
+                    ),
+                    None => format!("Station #{}", station_id),
+                };
+                ui.label(RichText::new(station_label).strong());
+                for item in my_hangar_items(ctx, station_id) {
+                    ui.label(format!(
+                        "    {}x {}",
+                        item.quantity,
+                        item_name(ctx, item.item_id)
+                    ));
+                }
+            }
+        });
+}
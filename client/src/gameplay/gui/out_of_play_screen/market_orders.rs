@@ -0,0 +1,167 @@
+//! Order book panel for the trading panel.
+//!
+//! Lists the open `market_order` rows for one item at a trading port, lets
+//! the player cancel their own, and posts new buy and sell orders
+//! (`logic/stations/market_orders.rs` on the server). Buy orders hold their
+//! credits and sell orders their cargo until they fill, expire or are
+//! cancelled; what they buy ends up in the player's hangar here.
+
+use super::*;
+
+/// Mirrors the server's `MAX_ORDER_DURATION_HOURS`.
+const MAX_ORDER_DURATION_HOURS: u32 = 7 * 24;
+
+/// The order form for one (module, item): price per unit, quantity and how
+/// many hours the order stays open.
+#[derive(Clone, Copy)]
+pub struct OrderDraft {
+    price: u32,
+    quantity: u32,
+    hours: u32,
+}
+
+/// Draws the "Order Book" section for `inventory` at the trading port
+/// `module`.
+pub fn show_order_book(
+    ctx: &DbConnection,
+    state: &mut State,
+    ui: &mut Ui,
+    ship: &Ship,
+    module: &StationModule,
+    inventory: &StationModuleInventoryItem,
+    item_def: &ItemDefinition,
+) {
+    let orders: Vec<MarketOrder> = ctx
+        .db()
+        .market_order()
+        .iter()
+        .filter(|o| o.module_id == module.id && o.item_id == inventory.resource_item_id)
+        .collect();
+    let mut asks: Vec<&MarketOrder> = orders
+        .iter()
+        .filter(|o| o.side == MarketOrderSide::Sell)
+        .collect();
+    asks.sort_by_key(|o| (o.price_per_unit, o.created_at));
+    let mut bids: Vec<&MarketOrder> = orders
+        .iter()
+        .filter(|o| o.side == MarketOrderSide::Buy)
+        .collect();
+    bids.sort_by_key(|o| (std::cmp::Reverse(o.price_per_unit), o.created_at));
+
+    egui::CollapsingHeader::new(format!(
+        "Order Book — {} selling, {} buying",
+        asks.len(),
+        bids.len()
+    ))
+    .id_salt(("order_book", module.id, inventory.resource_item_id))
+    .show(ui, |ui| {
+        ui.label(RichText::new("Selling").strong().color(Color32::GREEN));
+        order_rows(ctx, ui, &asks);
+        ui.label(RichText::new("Buying").strong().color(Color32::RED));
+        order_rows(ctx, ui, &bids);
+
+        ui.separator();
+        let mid_price = utils::market_quote(ctx, module, inventory, item_def)
+            .mid_price()
+            .round() as u32;
+        let draft = state
+            .order_drafts
+            .entry((module.id, item_def.id))
+            .or_insert(OrderDraft {
+                price: mid_price.max(1),
+                quantity: 1,
+                hours: 24,
+            });
+        ui.horizontal(|ui| {
+            ui.label("Price:");
+            ui.add(
+                egui::DragValue::new(&mut draft.price)
+                    .range(1..=u32::MAX)
+                    .suffix("c"),
+            );
+            ui.label("Quantity:");
+            ui.add(egui::DragValue::new(&mut draft.quantity).range(1..=u16::MAX as u32));
+            ui.label("Hours:");
+            ui.add(egui::DragValue::new(&mut draft.hours).range(1..=MAX_ORDER_DURATION_HOURS));
+        });
+
+        let escrow = draft.price as u64 * draft.quantity as u64;
+        let credits = get_current_player(ctx).map_or(0, |p| p.credits);
+        let carried: u32 = ctx
+            .db()
+            .ship_cargo_item()
+            .iter()
+            .filter(|c| c.ship_id == ship.id && c.item_id == inventory.resource_item_id)
+            .map(|c| c.quantity as u32)
+            .sum();
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(
+                    escrow <= credits,
+                    egui::Button::new(format!("POST BUY ORDER ({}c held)", escrow)),
+                )
+                .clicked()
+            {
+                post_order(ctx, ship, module, inventory, MarketOrderSide::Buy, draft);
+            }
+            if ui
+                .add_enabled(
+                    draft.quantity <= carried,
+                    egui::Button::new(format!("POST SELL ORDER ({} in hold)", carried)),
+                )
+                .clicked()
+            {
+                post_order(ctx, ship, module, inventory, MarketOrderSide::Sell, draft);
+            }
+        });
+    });
+}
+
+fn order_rows(ctx: &DbConnection, ui: &mut Ui, orders: &[&MarketOrder]) {
+    if orders.is_empty() {
+        ui.label("No orders.");
+    }
+    let identity = ctx.identity();
+    for order in orders {
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "{}x at {}c",
+                order.quantity_remaining, order.price_per_unit
+            ));
+            if order.player_id != identity {
+                ui.label(get_username(ctx, &order.player_id));
+                return;
+            }
+            ui.label(RichText::new("Yours").strong());
+            if ui.button("CANCEL").clicked() {
+                if let Err(e) = ctx.reducers().cancel_market_order(order.id.into()) {
+                    warn!("Failed to cancel market order {}: {}", order.id, e);
+                }
+            }
+        });
+    }
+}
+
+fn post_order(
+    ctx: &DbConnection,
+    ship: &Ship,
+    module: &StationModule,
+    inventory: &StationModuleInventoryItem,
+    side: MarketOrderSide,
+    draft: &OrderDraft,
+) {
+    if let Err(e) = ctx.reducers().place_market_order(
+        module.id.into(),
+        ship.id.into(),
+        inventory.resource_item_id.into(),
+        side,
+        draft.price,
+        draft.quantity,
+        draft.hours,
+    ) {
+        warn!(
+            "Failed to post a market order for {}x {}: {}",
+            draft.quantity, inventory.resource_item_id, e
+        );
+    }
+}
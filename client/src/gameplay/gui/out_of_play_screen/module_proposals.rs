@@ -0,0 +1,193 @@
+//! Module proposal panel for the out-of-play screen.
+//!
+//! While no module is being built at the station, lists the blueprints the
+//! player can propose (`proposable_station_modules`) with their build costs.
+//! Once one is proposed, shows its Contribution Pool and lets the Docked Ship
+//! deposit what it carries. Eligibility lives on the server
+//! (`logic/stations/module_proposals.rs`); the slot and standing checks here
+//! only grey out buttons that would be refused.
+
+use super::*;
+
+/// Mirrors `MODULE_PROPOSAL_MIN_REPUTATION` server-side.
+const MODULE_PROPOSAL_MIN_REPUTATION: i32 = 0;
+
+/// Draws the "Module Construction" section for `station`, acting through the
+/// Docked Ship `ship`.
+pub fn show_module_proposal_section(
+    ctx: &DbConnection,
+    ui: &mut Ui,
+    ship: &Ship,
+    station: &Station,
+) {
+    let module_count = ctx
+        .db()
+        .station_module()
+        .iter()
+        .filter(|m| m.station_id == station.id)
+        .count();
+    let max_modules = station.size.modules() as usize;
+    let proposal = ctx
+        .db()
+        .station_module_under_construction()
+        .iter()
+        .find(|p| p.station_id == station.id);
+
+    egui::CollapsingHeader::new(format!(
+        "Module Construction — {} / {} slots used",
+        module_count, max_modules
+    ))
+    .id_salt(("module_proposals", station.id))
+    .show(ui, |ui| match proposal {
+        Some(proposal) => show_open_proposal(ctx, ui, ship, &proposal),
+        None => show_proposable_blueprints(ctx, ui, ship, station, module_count < max_modules),
+    });
+}
+
+/// The blueprints on offer, each with its bill and a PROPOSE button.
+fn show_proposable_blueprints(
+    ctx: &DbConnection,
+    ui: &mut Ui,
+    ship: &Ship,
+    station: &Station,
+    has_free_slot: bool,
+) {
+    let welcome = may_build_at(ctx, station);
+    if !has_free_slot {
+        ui.label("Every module slot is taken.");
+    } else if !welcome {
+        ui.label("The station's owners won't let your faction build here.");
+    }
+
+    let mut blueprints: Vec<StationModuleBlueprint> =
+        ctx.db().proposable_station_modules().iter().collect();
+    blueprints.sort_by_key(|b| b.id);
+    for blueprint in blueprints {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(&blueprint.name).strong());
+            ui.label(format!("{:?}", blueprint.specific_type));
+            if ui
+                .add_enabled(has_free_slot && welcome, egui::Button::new("PROPOSE"))
+                .clicked()
+            {
+                if let Err(e) = ctx
+                    .reducers()
+                    .propose_station_module(ship.id.into(), blueprint.id.into())
+                {
+                    warn!("Failed to propose a {}: {}", blueprint.name, e);
+                }
+            }
+        });
+        let costs: Vec<String> = blueprint
+            .build_cost_resources
+            .iter()
+            .map(|cost| {
+                format!(
+                    "{}x {}",
+                    cost.quantity,
+                    item_name(ctx, cost.resource_item_id)
+                )
+            })
+            .collect();
+        ui.label(format!("    Costs: {}", costs.join(", ")));
+    }
+}
+
+/// Progress of the module being built, one row per resource.
+fn show_open_proposal(
+    ctx: &DbConnection,
+    ui: &mut Ui,
+    ship: &Ship,
+    proposal: &StationModuleUnderConstruction,
+) {
+    let Some(blueprint) = ctx
+        .db()
+        .station_module_blueprint()
+        .id()
+        .find(&proposal.blueprint)
+    else {
+        return;
+    };
+    ui.label(format!(
+        "Building a {} — proposed by {}",
+        blueprint.name,
+        get_username(ctx, &proposal.proposed_by)
+    ));
+    ui.add(
+        egui::ProgressBar::new(proposal.construction_progress_percentage / 100.0)
+            .text(format!("{:.1}%", proposal.construction_progress_percentage)),
+    );
+
+    for cost in &blueprint.build_cost_resources {
+        let contributed: u32 = ctx
+            .db()
+            .construction_contribution_log()
+            .iter()
+            .filter(|log| {
+                log.module_proposal_id == Some(proposal.id) && log.item_id == cost.resource_item_id
+            })
+            .map(|log| log.quantity)
+            .sum();
+        let remaining = cost.quantity.saturating_sub(contributed);
+        let carried: u32 = ctx
+            .db()
+            .ship_cargo_item()
+            .iter()
+            .filter(|c| c.ship_id == ship.id && c.item_id == cost.resource_item_id)
+            .map(|c| c.quantity as u32)
+            .sum();
+        let deposit = remaining.min(carried);
+
+        ui.horizontal(|ui| {
+            ui.label(item_name(ctx, cost.resource_item_id));
+            ui.label(format!(
+                "{} / {}",
+                contributed.min(cost.quantity),
+                cost.quantity
+            ));
+            if ui
+                .add_enabled(
+                    deposit > 0,
+                    egui::Button::new(format!("CONTRIBUTE {}", deposit)),
+                )
+                .clicked()
+            {
+                if let Err(e) = ctx.reducers().contribute_to_station_module(
+                    ship.id.into(),
+                    cost.resource_item_id.into(),
+                    deposit,
+                ) {
+                    warn!("Failed to contribute to the {}: {}", blueprint.name, e);
+                }
+            }
+        });
+    }
+}
+
+/// Whether the player's faction stands well enough with the station's owner.
+fn may_build_at(ctx: &DbConnection, station: &Station) -> bool {
+    let Some(player) = get_current_player(ctx) else {
+        return false;
+    };
+    if player.faction_id.value == station.owner_faction_id {
+        return true;
+    }
+    let standing = ctx
+        .db()
+        .faction_standing()
+        .iter()
+        .find(|s| {
+            s.faction_one_id == player.faction_id.value
+                && s.faction_two_id == station.owner_faction_id
+        })
+        .map_or(0, |s| s.reputation_score);
+    standing >= MODULE_PROPOSAL_MIN_REPUTATION
+}
+
+fn item_name(ctx: &DbConnection, item_id: u32) -> String {
+    ctx.db()
+        .item_definition()
+        .id()
+        .find(&item_id)
+        .map_or_else(|| format!("Item #{}", item_id), |item| item.name)
+}
@@ -0,0 +1,65 @@
+//! Power grid panel for the out-of-play screen.
+//!
+//! Shows the station's last production-tick ledger (`station_power_grid`):
+//! generation against demand, CPU, the battery (`StationStatus.energy`) and
+//! which modules were browned out. The balancing itself happens on the server
+//! (`logic/stations/power.rs`).
+
+use super::*;
+
+/// Draws the "Power Grid" section for `station`. Draws nothing until the
+/// station has run its first production tick.
+pub fn show_power_grid_section(ctx: &DbConnection, ui: &mut Ui, station: &Station) {
+    let Some(grid) = ctx.db().station_power_grid().id().find(&station.id) else {
+        return;
+    };
+    let battery = ctx
+        .db()
+        .station_status()
+        .id()
+        .find(&station.id)
+        .map_or(0.0, |status| status.energy);
+    let balance_mw = grid.generation_mw - grid.draw_mw;
+    let headline = if !grid.idled_module_ids.is_empty() {
+        RichText::new(format!(
+            "Power Grid — BROWNOUT, {} module(s) idled",
+            grid.idled_module_ids.len()
+        ))
+        .color(Color32::from_rgb(255, 80, 60))
+    } else if balance_mw < 0.0 {
+        RichText::new(format!("Power Grid — {:+.0} MW, on battery", balance_mw))
+            .color(Color32::from_rgb(255, 200, 60))
+    } else {
+        RichText::new(format!("Power Grid — {:+.0} MW", balance_mw))
+    };
+
+    egui::CollapsingHeader::new(headline)
+        .id_salt(("power_grid", station.id))
+        .show(ui, |ui| {
+            ui.label(format!(
+                "Generation {:.0} MW · Draw {:.0} MW · Full demand {:.0} MW",
+                grid.generation_mw, grid.draw_mw, grid.demand_mw
+            ));
+            ui.label(format!(
+                "CPU {:.0} / {:.0} flops",
+                grid.cpu_load_flops, grid.cpu_capacity_flops
+            ));
+            let charge = if grid.battery_capacity_mwh > 0.0 {
+                battery / grid.battery_capacity_mwh
+            } else {
+                0.0
+            };
+            ui.add(egui::ProgressBar::new(charge).text(format!(
+                "Battery {:.0} / {:.0} MWh",
+                battery, grid.battery_capacity_mwh
+            )));
+
+            for module_id in &grid.idled_module_ids {
+                let name = ctx.db().station_module().id().find(module_id).map_or_else(
+                    || format!("Module #{}", module_id),
+                    |m| m.station_slot_identifier,
+                );
+                ui.label(format!("Idled: {}", name));
+            }
+        });
+}
@@ -0,0 +1,82 @@
+//! Price history chart for the trading panel.
+//!
+//! Plots the hourly `station_price_sample` rows the server keeps for an
+//! inventory slot (`refresh_module_prices` in
+//! `logic/stations/module_types/trading_port.rs`): what a unit cost to buy and
+//! paid to sell, oldest on the left.
+
+use egui::{pos2, vec2, Sense, Stroke};
+
+use super::*;
+
+const CHART_HEIGHT: f32 = 80.0;
+const BUY_COLOR: Color32 = Color32::from_rgb(230, 90, 90);
+const SELL_COLOR: Color32 = Color32::from_rgb(90, 200, 110);
+
+/// Draws the "Price History" section for `inventory`. Draws nothing until the
+/// server has taken two samples.
+pub fn show_price_history(ctx: &DbConnection, ui: &mut Ui, inventory: &StationModuleInventoryItem) {
+    let mut samples: Vec<StationPriceSample> = ctx
+        .db()
+        .station_price_sample()
+        .iter()
+        .filter(|sample| sample.inventory_item_id == inventory.id)
+        .collect();
+    if samples.len() < 2 {
+        return;
+    }
+    samples.sort_by_key(|sample| sample.recorded_at);
+
+    egui::CollapsingHeader::new("Price History")
+        .id_salt(("price_history", inventory.id))
+        .show(ui, |ui| {
+            let low = samples.iter().map(|s| s.sell_price).min().unwrap_or(0);
+            let high = samples.iter().map(|s| s.buy_price).max().unwrap_or(0);
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Buy").color(BUY_COLOR));
+                ui.label(RichText::new("Sell").color(SELL_COLOR));
+                ui.label(format!(
+                    "{}c – {}c over the last {} hours",
+                    low,
+                    high,
+                    samples.len() - 1
+                ));
+            });
+
+            let (response, painter) =
+                ui.allocate_painter(vec2(ui.available_width(), CHART_HEIGHT), Sense::hover());
+            let rect = response.rect;
+            painter.rect_stroke(
+                rect,
+                0.0,
+                Stroke::new(1.0, Color32::DARK_GRAY),
+                egui::StrokeKind::Inside,
+            );
+
+            let range = (high - low).max(1) as f32;
+            let step = rect.width() / (samples.len() - 1) as f32;
+            let point = |i: usize, price: u32| {
+                pos2(
+                    rect.left() + i as f32 * step,
+                    rect.bottom() - price.saturating_sub(low) as f32 / range * rect.height(),
+                )
+            };
+            for (price, color) in [
+                (
+                    samples.iter().map(|s| s.buy_price).collect::<Vec<_>>(),
+                    BUY_COLOR,
+                ),
+                (
+                    samples.iter().map(|s| s.sell_price).collect::<Vec<_>>(),
+                    SELL_COLOR,
+                ),
+            ] {
+                let line = price
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| point(i, *p))
+                    .collect();
+                painter.add(egui::Shape::line(line, Stroke::new(1.5, color)));
+            }
+        });
+}
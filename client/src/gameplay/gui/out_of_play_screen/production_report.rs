@@ -0,0 +1,105 @@
+//! Production report panel for the out-of-play screen.
+//!
+//! Shows the station's last production tick (`station_production_report`):
+//! what each producing module made and used, what's holding it back, and
+//! what the station's logistics moved between modules
+//! (`logic/stations/logistics.rs` on the server).
+
+use super::*;
+
+/// Draws the "Production" section for `station`. Draws nothing until the
+/// station has producing modules.
+pub fn show_production_report_section(ctx: &DbConnection, ui: &mut Ui, station: &Station) {
+    let Some(report) = ctx.db().station_production_report().id().find(&station.id) else {
+        return;
+    };
+    if report.modules.is_empty() {
+        return;
+    }
+    let bottlenecked = report
+        .modules
+        .iter()
+        .filter(|m| m.bottleneck.is_some())
+        .count();
+    let headline = if bottlenecked > 0 {
+        RichText::new(format!(
+            "Production — {} of {} modules held back",
+            bottlenecked,
+            report.modules.len()
+        ))
+        .color(Color32::from_rgb(255, 200, 60))
+    } else {
+        RichText::new(format!(
+            "Production — {} modules running",
+            report.modules.len()
+        ))
+    };
+
+    egui::CollapsingHeader::new(headline)
+        .id_salt(("production_report", station.id))
+        .show(ui, |ui| {
+            for throughput in &report.modules {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(module_name(ctx, throughput.module_id)).strong());
+                    if let Some(bottleneck) = &throughput.bottleneck {
+                        ui.label(
+                            RichText::new(bottleneck_label(bottleneck))
+                                .color(Color32::from_rgb(255, 200, 60)),
+                        );
+                    }
+                });
+                if !throughput.produced.is_empty() {
+                    ui.label(format!("    Made: {}", amounts(ctx, &throughput.produced)));
+                }
+                if !throughput.consumed.is_empty() {
+                    ui.label(format!("    Used: {}", amounts(ctx, &throughput.consumed)));
+                }
+            }
+
+            if !report.transfers.is_empty() {
+                ui.separator();
+                ui.label(RichText::new("Logistics").strong());
+                for transfer in &report.transfers {
+                    ui.label(format!(
+                        "{}x {}: {} → {}",
+                        transfer.quantity,
+                        item_name(ctx, transfer.item_id),
+                        module_name(ctx, transfer.from_module_id),
+                        module_name(ctx, transfer.to_module_id)
+                    ));
+                }
+            }
+        });
+}
+
+fn bottleneck_label(bottleneck: &ProductionBottleneck) -> &'static str {
+    match bottleneck {
+        ProductionBottleneck::Unpowered => "Idled by brownout",
+        ProductionBottleneck::NoRecipe => "No recipe set",
+        ProductionBottleneck::StarvedOfInputs => "Starved of inputs",
+        ProductionBottleneck::OutputFull => "Output full",
+    }
+}
+
+fn amounts(ctx: &DbConnection, amounts: &[ResourceAmount]) -> String {
+    amounts
+        .iter()
+        .map(|a| format!("{}x {}", a.quantity, item_name(ctx, a.resource_item_id)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn module_name(ctx: &DbConnection, module_id: u64) -> String {
+    ctx.db().station_module().id().find(&module_id).map_or_else(
+        || format!("Module #{}", module_id),
+        |m| m.station_slot_identifier,
+    )
+}
+
+fn item_name(ctx: &DbConnection, item_id: u32) -> String {
+    ctx.db()
+        .item_definition()
+        .id()
+        .find(&item_id)
+        .map_or_else(|| format!("Item #{}", item_id), |item| item.name)
+}
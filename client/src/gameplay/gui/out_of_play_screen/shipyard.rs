@@ -0,0 +1,75 @@
+//! Shipyard panel for the out-of-play screen.
+//!
+//! Stations with a wharf or shipyard module sell new hulls. A bought ship is
+//! docked at the same station and shows up in the assets tree, where it can be
+//! selected and undocked once the Piloted Ship (if any) has docked.
+
+use super::*;
+
+/// The first operational module at `station_id` that sells ships.
+fn find_shipyard(ctx: &DbConnection, station_id: u64) -> Option<StationModule> {
+    ctx.db()
+        .station_module()
+        .iter()
+        .filter(|m| m.station_id == station_id && m.is_operational)
+        .find(|m| {
+            ctx.db()
+                .station_module_blueprint()
+                .id()
+                .find(&m.blueprint)
+                .is_some_and(|blueprint| {
+                    matches!(
+                        blueprint.specific_type,
+                        StationModuleSpecificType::Wharf
+                            | StationModuleSpecificType::ShipyardFabrication
+                    )
+                })
+        })
+}
+
+/// Hulls for sale at `station`, bought on behalf of the Docked Ship `ship`.
+/// Draws nothing if the station has no shipyard.
+pub fn show_shipyard_section(ctx: &DbConnection, ui: &mut Ui, ship: &Ship, station: &Station) {
+    let Some(shipyard) = find_shipyard(ctx, station.id) else {
+        return;
+    };
+    let credits = get_current_player(ctx).map_or(0, |player| player.credits);
+    let mut for_sale: Vec<(ShipTypeDefinition, u64)> = ctx
+        .db()
+        .ship_type_definition()
+        .iter()
+        .filter_map(|t| t.price.map(|price| (t, price)))
+        .collect();
+    for_sale.sort_by_key(|(t, price)| (*price, t.id));
+
+    egui::CollapsingHeader::new(format!("Shipyard — {} hulls for sale", for_sale.len()))
+        .id_salt(("shipyard", station.id))
+        .show(ui, |ui| {
+            for (ship_type, price) in for_sale {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&ship_type.name).strong());
+                    ui.label(format!(
+                        "{:?} · {}v cargo · {} hull",
+                        ship_type.class, ship_type.cargo_capacity, ship_type.max_health
+                    ));
+                    ui.label(format!("{}c", price));
+                    if ui
+                        .add_enabled(price <= credits, egui::Button::new("BUY"))
+                        .on_disabled_hover_text("Not enough credits.")
+                        .clicked()
+                    {
+                        if let Err(e) = ctx.reducers().buy_ship_from_station_module(
+                            shipyard.id.into(),
+                            ship.id.into(),
+                            ship_type.id.into(),
+                        ) {
+                            warn!("Failed to buy a {}: {}", ship_type.name, e);
+                        }
+                    }
+                });
+                if let Some(description) = &ship_type.description {
+                    ui.label(format!("    {}", description));
+                }
+            }
+        });
+}
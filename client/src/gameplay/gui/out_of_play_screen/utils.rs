@@ -1,4 +1,5 @@
 use egui::{Align, Layout};
+use solarance_shared::{decayed_pressure, pricing::TRADE_SPREAD, reputation_discount, MarketQuote};
 
 use super::*;
 
@@ -94,6 +95,56 @@ pub fn module_can_sell_to_player(ctx: &DbConnection, module: &StationModule, ite
     false
 }
 
+/// Prices `inventory` for the current player the way the server will: stock,
+/// the listing's decayed trade pressure, the spread and the player's standing
+/// with the station's owner (`logic/stations/module_types/trading_port.rs`).
+pub fn market_quote(
+    ctx: &DbConnection,
+    module: &StationModule,
+    inventory: &StationModuleInventoryItem,
+    item_def: &ItemDefinition,
+) -> MarketQuote {
+    let pressure = ctx
+        .db()
+        .trading_port_listing()
+        .id()
+        .find(&inventory.id)
+        .map_or(0.0, |listing| {
+            decayed_pressure(
+                listing.trade_pressure,
+                listing.pressure_updated_at,
+                now_unix_micros(),
+            )
+        });
+    let reputation = match (
+        get_current_player(ctx),
+        ctx.db().station().id().find(&module.station_id),
+    ) {
+        (Some(player), Some(station)) if player.faction_id.value == station.owner_faction_id => 100,
+        (Some(player), Some(station)) => ctx
+            .db()
+            .faction_standing()
+            .iter()
+            .find(|s| {
+                s.faction_one_id == station.owner_faction_id
+                    && s.faction_two_id == player.faction_id.value
+            })
+            .map_or(0, |s| s.reputation_score),
+        _ => 0,
+    };
+    MarketQuote {
+        pressure,
+        spread: TRADE_SPREAD,
+        standing_discount: reputation_discount(reputation),
+        ..MarketQuote::for_stock(
+            item_def.base_value,
+            item_def.margin_percentage,
+            inventory.quantity,
+            inventory.max_quantity,
+        )
+    }
+}
+
 pub fn display_ship_on_tree(ctx: &DbConnection, state: &mut State, ui: &mut Ui, ship: &Ship) {
     let ship_type = ctx.db().ship_type_definition().id().find(&ship.shiptype_id);
 
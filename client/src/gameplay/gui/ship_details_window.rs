@@ -135,8 +135,14 @@ fn ship_contents(
     ui.horizontal(|ui| {
         ui.vertical(|ui| {
             ui.label(format!("Health: {}", player_ship_status.health));
-            ui.label(format!("Shield: {}", player_ship_status.shields));
-            ui.label(format!("Energy: {}", player_ship_status.energy));
+            ui.label(format!(
+                "Shield: {:.1}",
+                player_ship_status.current_shields(now_unix_micros())
+            ));
+            ui.label(format!(
+                "Energy: {:.1}",
+                player_ship_status.current_energy(now_unix_micros())
+            ));
             ui.label(format!(
                 "Cargo: {} / {}",
                 player_ship_status.used_cargo_capacity, player_ship_status.max_cargo_capacity
@@ -145,7 +151,7 @@ fn ship_contents(
         ui.separator();
         ui.vertical(|ui| {
             ui.label(format!("Max Health: {}", ship_type.max_health));
-            ui.label(format!("Max Shield: {}", ship_type.max_shields));
+            ui.label(format!("Max Shield: {}", player_ship_status.shields.cap));
             ui.label(format!("Max Energy: {}", ship_type.max_energy));
         });
         ui.separator();
@@ -192,6 +198,13 @@ fn ship_contents(
             ui.label(format!(
                 "Special Slots: {}/{}",
                 get_all_equipped_of_type(ctx, player_ship_id, EquipmentSlotType::Special)
+                    .iter()
+                    .count()
+                    + get_all_equipped_of_type(
+                        ctx,
+                        player_ship_id,
+                        EquipmentSlotType::CargoExpansion
+                    )
                     .iter()
                     .count(),
                 ship_type.num_special_slots
@@ -292,12 +305,51 @@ fn cargo_contents(
         });
 }
 
+/// What's being dragged on the fitting screen.
+#[derive(Clone, Copy)]
+enum FittingDrag {
+    /// A ship module item sitting in the cargo hold.
+    Cargo(u32),
+    /// A fitted `ShipEquipmentSlot`, by id.
+    Fitted(u64),
+}
+
+/// The slot type a ship module is fitted into. Mirrors the server's
+/// `slot_type_for_module` in `logic/ships/equipment.rs`.
+fn slot_type_for_module(module_type: &ShipModuleType) -> EquipmentSlotType {
+    match module_type {
+        ShipModuleType::Engine => EquipmentSlotType::Engine,
+        ShipModuleType::ShieldGenerator => EquipmentSlotType::Shield,
+        ShipModuleType::WeaponKinetic
+        | ShipModuleType::WeaponEnergy
+        | ShipModuleType::WeaponMissile => EquipmentSlotType::Weapon,
+        ShipModuleType::MiningLaserBasic | ShipModuleType::MiningLaserAdvanced => {
+            EquipmentSlotType::MiningLaser
+        }
+        ShipModuleType::CargoExpander => EquipmentSlotType::CargoExpansion,
+        _ => EquipmentSlotType::Special,
+    }
+}
+
+/// Cargo expanders sit on special hardpoints, so both share one tab and one
+/// slot count.
+fn same_slot_pool(a: &EquipmentSlotType, b: &EquipmentSlotType) -> bool {
+    let pool = |t: &EquipmentSlotType| match t {
+        EquipmentSlotType::CargoExpansion => EquipmentSlotType::Special,
+        other => other.clone(),
+    };
+    pool(a) == pool(b)
+}
+
+/// Drag-and-drop fitting: drag a module from the hold onto the slot list to
+/// fit it, or a fitted module back onto the hold to unfit it. Only a Docked
+/// Ship can be refitted.
 fn equipment_contents(
     ui: &mut Ui,
     ctx: &DbConnection,
     state: &mut State,
     ship_type: ShipTypeDefinition,
-    _player_ship: Ship,
+    ship: Ship,
 ) {
     ui.heading("Equipment");
     ui.separator();
@@ -335,39 +387,120 @@ fn equipment_contents(
         EquipmentSlotType::Shield => ship_type.num_shield_slots,
         EquipmentSlotType::Engine => ship_type.num_engine_slots,
         EquipmentSlotType::MiningLaser => ship_type.num_mining_laser_slots,
-        EquipmentSlotType::Special => ship_type.num_special_slots,
-        EquipmentSlotType::CargoExpansion => todo!(),
+        EquipmentSlotType::Special | EquipmentSlotType::CargoExpansion => {
+            ship_type.num_special_slots
+        }
     };
-    let mut slots = 0;
+    let docked = ship.location == ShipLocation::Station;
+    let mut fitted: Vec<ShipEquipmentSlot> = ctx
+        .db()
+        .ship_equipment_slot()
+        .iter()
+        .filter(|e| {
+            e.ship_id == ship.id && same_slot_pool(&e.slot_type, &state.current_equipment_tab)
+        })
+        .collect();
+    fitted.sort_by_key(|e| (e.slot_type.to_string(), e.slot_index));
 
-    for equipment in ctx.db().ship_equipment_slot().iter() {
-        if state.current_equipment_tab != equipment.slot_type {
-            continue;
+    let item_name = |item_id: u32| {
+        ctx.db()
+            .item_definition()
+            .id()
+            .find(&item_id)
+            .map_or_else(|| format!("Item #{}", item_id), |def| def.name)
+    };
+
+    ui.label(RichText::new("Fitted").strong());
+    let (_, dropped) = ui.dnd_drop_zone::<FittingDrag, ()>(egui::Frame::group(ui.style()), |ui| {
+        for (index, equipment) in fitted.iter().enumerate() {
+            let label = format!(
+                "{} --- {} --- {}",
+                index + 1,
+                equipment.slot_type.to_string(),
+                item_name(equipment.item_id)
+            );
+            if docked {
+                ui.dnd_drag_source(
+                    egui::Id::new(("fitted", equipment.id)),
+                    FittingDrag::Fitted(equipment.id),
+                    |ui| ui.label(label),
+                );
+            } else {
+                ui.label(label);
+            }
         }
-        ui.horizontal(|ui| {
-            ui.label(format!(
-                "{} --- {}",
-                slots + 1,
-                equipment.slot_type.to_string()
-            ));
-            ui.add_enabled(false, egui::Label::new("---"));
-            if let Some(item) = ctx.db().item_definition().id().find(&equipment.item_id) {
-                ui.label(item.name);
+        for empty_slot in fitted.len()..max_slots as usize {
+            ui.add_enabled(
+                false,
+                egui::Label::new(format!("{} --- Empty Slot", empty_slot + 1)),
+            );
+        }
+    });
+    if let Some(payload) = dropped {
+        if let FittingDrag::Cargo(item_id) = *payload {
+            if let Err(e) = ctx.reducers().equip_module(ship.id.into(), item_id.into()) {
+                warn!("Failed to fit item {} to ship {}: {}", item_id, ship.id, e);
             }
-        });
-        slots += 1;
+        }
     }
-    for empty_slot in slots..max_slots {
-        ui.add_enabled(
-            false,
-            egui::Label::new(format!("{} --- Empty Slot", empty_slot + 1)),
-        );
+
+    ui.label(RichText::new("In cargo").strong());
+    let mut modules_in_cargo: Vec<(u32, u16)> = Vec::new();
+    for cargo in ctx
+        .db()
+        .ship_cargo_item()
+        .iter()
+        .filter(|c| c.ship_id == ship.id)
+    {
+        let fits_this_tab = ctx
+            .db()
+            .item_definition()
+            .id()
+            .find(&cargo.item_id)
+            .is_some_and(|def| match &def.category {
+                ItemCategory::ShipModule(module_type) => same_slot_pool(
+                    &slot_type_for_module(module_type),
+                    &state.current_equipment_tab,
+                ),
+                _ => false,
+            });
+        if fits_this_tab {
+            match modules_in_cargo.iter_mut().find(|(id, _)| *id == cargo.item_id) {
+                Some(entry) => entry.1 += cargo.quantity,
+                None => modules_in_cargo.push((cargo.item_id, cargo.quantity)),
+            }
+        }
+    }
+    let (_, dropped) = ui.dnd_drop_zone::<FittingDrag, ()>(egui::Frame::group(ui.style()), |ui| {
+        if modules_in_cargo.is_empty() {
+            ui.add_enabled(false, egui::Label::new("No modules for these slots."));
+        }
+        for (item_id, quantity) in &modules_in_cargo {
+            let label = format!("{}x {}", quantity, item_name(*item_id));
+            if docked {
+                ui.dnd_drag_source(
+                    egui::Id::new(("cargo_module", *item_id)),
+                    FittingDrag::Cargo(*item_id),
+                    |ui| ui.label(label),
+                );
+            } else {
+                ui.label(label);
+            }
+        }
+    });
+    if let Some(payload) = dropped {
+        if let FittingDrag::Fitted(slot_id) = *payload {
+            if let Err(e) = ctx.reducers().unequip_module(ship.id.into(), slot_id.into()) {
+                warn!("Failed to unfit slot {} from ship {}: {}", slot_id, ship.id, e);
+            }
+        }
     }
 
-    ui.spacing();
-    // Maybe add a button to equip an item here?
     ui.separator();
     ui.horizontal(|ui| {
-        ui.label(format!("Slots: {} / {}", slots, max_slots));
+        ui.label(format!("Slots: {} / {}", fitted.len(), max_slots));
+        if !docked {
+            ui.label("Dock to change equipment.");
+        }
     });
 }
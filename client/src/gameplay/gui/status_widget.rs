@@ -66,6 +66,8 @@ pub fn window(
 }
 
 fn ship_status(ui: &mut Ui, ship_type: ShipTypeDefinition, player_ship_status: ShipStatus) {
+    // Shields and energy regenerate between server updates; extrapolate them.
+    let now_micros = now_unix_micros();
     ui.vertical(|ui| {
         add_status_bar(
             ui,
@@ -78,16 +80,16 @@ fn ship_status(ui: &mut Ui, ship_type: ShipTypeDefinition, player_ship_status: S
         add_status_bar(
             ui,
             "Shields",
-            ship_type.max_shields as f32,
-            player_ship_status.shields,
+            player_ship_status.shields.cap,
+            player_ship_status.current_shields(now_micros),
             Color32::from_rgb(0, 64, 192),
             true,
         );
         add_status_bar(
             ui,
             "Energy",
-            ship_type.max_energy as f32,
-            player_ship_status.energy,
+            player_ship_status.energy.cap,
+            player_ship_status.current_energy(now_micros),
             Color32::from_rgb(0, 100, 64),
             true,
         );
@@ -99,6 +101,7 @@ fn ship_function_status(ctx: &DbConnection, ui: &mut Ui, game_state: &mut GameSt
         combat_mode_indicator(ui, game_state);
         mining_beam_button(ui, ctx, game_state);
         autodocking_button(ui, ctx, game_state);
+        autopilot_button(ui, ctx, game_state);
         fire_weapons_button(ui, ctx, game_state);
     });
 }
@@ -123,8 +126,15 @@ fn mining_beam_button(ui: &mut Ui, ctx: &DbConnection, game_state: &mut GameStat
     }
 
     if game_state.mining_active {
+        let label = match get_player_mining_timer(ctx) {
+            Some(timer) => format!(
+                "[X] Mining Beam: On ({:.2} v/s)",
+                timer.extraction_volume_per_sec
+            ),
+            None => "[X] Mining Beam: On".to_string(),
+        };
         if ui
-            .button(RichText::new("[X] Mining Beam: On").color({
+            .button(RichText::new(label).color({
                 if now() % 1.0 < 0.45 {
                     Color32::RED
                 } else {
@@ -211,6 +221,50 @@ fn autodocking_button(ui: &mut Ui, ctx: &DbConnection, game_state: &mut GameStat
     }
 }
 
+fn autopilot_button(ui: &mut Ui, ctx: &DbConnection, game_state: &mut GameState) {
+    if game_state.combat_mode {
+        return;
+    }
+    let Some(ship) = get_player_ship(ctx) else {
+        return;
+    };
+    if ship.location != ShipLocation::Sector {
+        return;
+    }
+
+    // Server-side autopilot: flies to the current target (through jumpgates if
+    // need be) and hands back on arrival or on any movement key.
+    if ctx.db().ship_autopilot().id().find(&ship.id).is_some() {
+        if ui
+            .button(RichText::new("Autopilot: On").color(Color32::LIGHT_GREEN))
+            .clicked()
+        {
+            let _ = ctx.reducers.clear_autopilot();
+        }
+        return;
+    }
+
+    let target = get_current_target(ctx, &mut game_state.current_target_sobj_id);
+    let enabled = target.as_ref().map_or(false, |t| {
+        matches!(
+            t.kind,
+            StellarObjectKinds::Station | StellarObjectKinds::Asteroid | StellarObjectKinds::JumpGate
+        )
+    });
+    ui.add_enabled_ui(enabled, |ui| {
+        if ui
+            .button(RichText::new("Autopilot: Off").color(Color32::LIGHT_GRAY))
+            .clicked()
+        {
+            if let Some(target) = &target {
+                let _ = ctx
+                    .reducers
+                    .set_autopilot_target(AutopilotTarget::StellarObject(target.id));
+            }
+        }
+    });
+}
+
 fn add_targeted_object_status(
     ui: &mut Ui,
     ctx: &DbConnection,
@@ -269,16 +323,16 @@ fn add_targeted_object_status(
                     add_status_bar(
                         ui,
                         "Shields",
-                        ship_type.max_shields as f32,
-                        ship_status.shields,
+                        ship_status.shields.cap,
+                        ship_status.current_shields(now_unix_micros()),
                         Color32::from_rgb(0, 64, 192),
                         true,
                     );
                     add_status_bar(
                         ui,
                         "Energy",
-                        ship_type.max_energy as f32,
-                        ship_status.energy,
+                        ship_status.energy.cap,
+                        ship_status.current_energy(now_unix_micros()),
                         Color32::from_rgb(0, 100, 64),
                         true,
                     );
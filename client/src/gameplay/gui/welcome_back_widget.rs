@@ -7,13 +7,18 @@
 //! that row is always the freshest one when this panel checks. Dismissing the
 //! panel just hides it for the session; there is no server-side read state to
 //! mutate.
+//!
+//! When the structured `my_welcome_back` payload has streamed in, the panel
+//! renders it in sections (construction, stations, sector control, top
+//! contributors, assets) instead of the DSM text. The DSM body is only the
+//! fallback for the frames before the payload arrives.
 
 use egui::{Align2, Color32, Context, RichText};
-use spacetimedb_sdk::DbContext;
+use spacetimedb_sdk::{DbContext, Table};
 
 use crate::{
     gameplay::direct_server_messages::DirectServerMessageUtils,
-    server::bindings::DbConnection,
+    server::bindings::*,
     stdb::utils::get_current_player,
 };
 
@@ -48,11 +53,11 @@ pub fn draw(egui_ctx: &Context, ctx: &DbConnection, state: &mut State) {
     let Some(player) = get_current_player(ctx) else {
         return;
     };
-    let Some(message) =
-        DirectServerMessageUtils::get_latest_welcome_back(ctx, player.last_login)
-    else {
+    let payload = ctx.db().my_welcome_back().iter().next();
+    let message = DirectServerMessageUtils::get_latest_welcome_back(ctx, player.last_login);
+    if payload.is_none() && message.is_none() {
         return;
-    };
+    }
 
     let mut close_requested = false;
 
@@ -72,9 +77,13 @@ pub fn draw(egui_ctx: &Context, ctx: &DbConnection, state: &mut State) {
             ui.separator();
             ui.add_space(4.0);
 
-            // The server joins the summary lines with '\n'; egui renders the
-            // newlines directly. Keep it text-only and easy to read at a glance.
-            ui.label(RichText::new(&message.body).size(15.0));
+            if let Some(payload) = &payload {
+                draw_payload(ui, payload);
+            } else if let Some(message) = &message {
+                // The server joins the summary lines with '\n'; egui renders
+                // the newlines directly.
+                ui.label(RichText::new(&message.body).size(15.0));
+            }
 
             ui.add_space(8.0);
             ui.separator();
@@ -89,3 +98,107 @@ pub fn draw(egui_ctx: &Context, ctx: &DbConnection, state: &mut State) {
         state.dismissed = true;
     }
 }
+
+/// The structured summary, one section per kind of change. Empty sections are
+/// skipped.
+fn draw_payload(ui: &mut egui::Ui, payload: &WelcomeBackPayload) {
+    if payload.since.is_some() {
+        ui.label(format!(
+            "You were away for {}. The galaxy ran {} economy ticks without you; your own assets stayed put.",
+            describe_duration(payload.away_secs),
+            payload.offline_ticks
+        ));
+    } else {
+        ui.label("Welcome to the galaxy, pilot!");
+    }
+
+    if !payload.site_changes.is_empty() {
+        section(ui, "Construction");
+        for change in &payload.site_changes {
+            let from = match change.from_percentage {
+                Some(from) => format!("{:.0}%", from),
+                None => "new".to_string(),
+            };
+            let mut line = format!(
+                "{} ({}): {} → {:.0}%",
+                change.station_name, change.sector_name, from, change.to_percentage
+            );
+            if change.came_online {
+                line.push_str(", now online");
+            }
+            if change.your_share_percentage > 0.0 {
+                line.push_str(&format!(" (your share {:.1}%)", change.your_share_percentage));
+            }
+            let color = if change.is_your_faction {
+                Color32::from_rgb(120, 220, 140)
+            } else {
+                Color32::LIGHT_GRAY
+            };
+            ui.label(RichText::new(line).color(color));
+        }
+    }
+
+    if !payload.module_changes.is_empty() {
+        section(ui, "Stations");
+        for change in &payload.module_changes {
+            ui.label(format!(
+                "{}: {} → {} modules ({} → {} operational)",
+                change.station_name,
+                change.modules_before,
+                change.modules_now,
+                change.operational_before,
+                change.operational_now
+            ));
+        }
+    }
+
+    if !payload.control_changes.is_empty() {
+        section(ui, "Sector control");
+        for change in &payload.control_changes {
+            ui.label(format!(
+                "{}: {} → {}",
+                change.sector_name, change.from_faction, change.to_faction
+            ));
+        }
+    }
+
+    if !payload.top_contributors.is_empty() {
+        section(ui, "Top contributors");
+        for (rank, contributor) in payload.top_contributors.iter().enumerate() {
+            let line = format!(
+                "{}. {} — {} units",
+                rank + 1,
+                contributor.username,
+                contributor.quantity
+            );
+            if contributor.is_you {
+                ui.label(RichText::new(line).strong());
+            } else {
+                ui.label(line);
+            }
+        }
+    }
+
+    if !payload.asset_lines.is_empty() {
+        section(ui, "Your assets");
+        for line in &payload.asset_lines {
+            ui.label(line);
+        }
+    }
+}
+
+fn section(ui: &mut egui::Ui, title: &str) {
+    ui.add_space(6.0);
+    ui.label(RichText::new(title).strong().color(Color32::from_rgb(120, 190, 255)));
+}
+
+fn describe_duration(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, (secs % 86_400) / 3_600, (secs % 3_600) / 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes.max(1))
+    }
+}
@@ -6,7 +6,18 @@ use macroquad::{
 use crate::server::bindings::*;
 use crate::stdb::utils::*;
 
-use crate::gameplay::{resources::Resources, state::GameState};
+use crate::gameplay::{
+    resources::Resources,
+    state::{GameState, SeenStationSize},
+};
+
+/// Mining rate (ore volume per second) at which the beam is drawn at full
+/// strength. Two Advanced Mining Lasers on soft ore get there.
+const MINING_BEAM_FULL_VOLUME_PER_SEC: f32 = 1.5;
+
+/// The `size_radius` an asteroid texture is drawn at its native size for.
+/// Asteroids shrink as they're mined, so they're scaled against this.
+const ASTEROID_TEXTURE_RADIUS: f32 = 16.0;
 
 pub fn draw_mining_laser(game_state: &mut GameState<'_>, player_pose: &RenderPose) {
     if !game_state.mining_active {
@@ -21,24 +32,32 @@ pub fn draw_mining_laser(game_state: &mut GameState<'_>, player_pose: &RenderPos
     if target.kind != StellarObjectKinds::Asteroid {
         return;
     }
+    // Scale the beam by what the lasers actually cut on the last mining tick:
+    // a cold beam (misaimed, out of energy, no tick yet) only flickers.
+    let intensity = get_player_mining_timer(game_state.ctx).map_or(0.0, |timer| {
+        (timer.extraction_volume_per_sec / MINING_BEAM_FULL_VOLUME_PER_SEC).min(1.0)
+    });
     let now_micros = now_unix_micros();
     if let Some(target_pose) = pose_for_object(game_state.ctx, &target, now_micros) {
+        let flicker = ((now() * 100.0) % 255.0) as f32 / 255.0;
         draw_line(
             target_pose.pos.x,
             target_pose.pos.y,
             player_pose.pos.x,
             player_pose.pos.y,
-            6.0,
-            Color::from_rgba(128, 0, 0, ((now() * 100.0) % 255.0) as u8),
-        );
-        draw_line(
-            target_pose.pos.x,
-            target_pose.pos.y,
-            player_pose.pos.x,
-            player_pose.pos.y,
-            ((now() as f32) * 100.0) % 3.0,
-            RED,
+            2.0 + 6.0 * intensity,
+            Color::new(0.5, 0.0, 0.0, flicker * (0.25 + 0.75 * intensity)),
         );
+        if intensity > 0.0 {
+            draw_line(
+                target_pose.pos.x,
+                target_pose.pos.y,
+                player_pose.pos.x,
+                player_pose.pos.y,
+                (((now() as f32) * 100.0) % 3.0) * intensity,
+                RED,
+            );
+        }
     }
 }
 
@@ -256,19 +275,22 @@ pub fn draw_asteroid(pose: &RenderPose, asteroid: Asteroid, game_state: &mut Gam
         .gfx_key
         .unwrap_or("asteroid.1".to_string())
         .as_str()];
+    let scale = asteroid.size_radius / ASTEROID_TEXTURE_RADIUS;
+    let (w, h) = (tex.width() * scale, tex.height() * scale);
     draw_texture_ex(
         tex,
-        position.x - tex.width() * 0.5,
-        position.y - tex.height() * 0.5,
+        position.x - w * 0.5,
+        position.y - h * 0.5,
         WHITE,
         DrawTextureParams {
             rotation: angle,
+            dest_size: Some(vec2(w, h)),
             ..DrawTextureParams::default()
         },
     );
 
     if game_state.current_target_sobj_id == Some(asteroid.id) {
-        let size = (tex.width() + tex.height()) * 0.5;
+        let size = (w + h) * 0.5;
         draw_targeting_bracket(
             position,
             size,
@@ -335,24 +357,58 @@ pub fn draw_jumpgate(pose: &RenderPose, jumpgate: JumpGate, game_state: &mut Gam
     }
 }
 
-pub fn draw_station(pose: &RenderPose, station: Station, game_state: &mut GameState) {
-    let resources = storage::get::<Resources>();
-    let position = pose.pos;
+/// How long a station that just grew takes to ease up to its new sprite.
+const STATION_GROWTH_SECS: f64 = 2.0;
 
-    let gfx_key = match station.size {
+fn station_gfx_key(size: &StationSize) -> &'static str {
+    match size {
         StationSize::Capital => "station.capital",
         StationSize::Large => "station.large",
         StationSize::Medium => "station.medium",
         StationSize::Small => "station.small",
         StationSize::Outpost => "station.outpost",
         StationSize::Satellite => "station.satellite",
+    }
+}
+
+pub fn draw_station(pose: &RenderPose, station: Station, game_state: &mut GameState) {
+    let resources = storage::get::<Resources>();
+    let position = pose.pos;
+
+    let tex = &resources.station_textures[station_gfx_key(&station.size)];
+
+    // A station that grew while in view eases from its old sprite's width up
+    // to the new one. The first sighting just records the size.
+    let now = get_time();
+    let seen = game_state
+        .station_sizes
+        .entry(station.id)
+        .or_insert_with(|| SeenStationSize {
+            size: station.size.clone(),
+            grew: None,
+        });
+    if seen.size != station.size {
+        let old_tex = &resources.station_textures[station_gfx_key(&seen.size)];
+        seen.size = station.size.clone();
+        seen.grew = Some((now, old_tex.width() / tex.width()));
+    }
+    let scale = match seen.grew {
+        Some((start, from_scale)) if now - start < STATION_GROWTH_SECS => {
+            let t = ((now - start) / STATION_GROWTH_SECS) as f32;
+            from_scale + (1.0 - from_scale) * t
+        }
+        _ => 1.0,
     };
-    let tex = &resources.station_textures[gfx_key];
-    draw_texture(
+
+    draw_texture_ex(
         tex,
-        position.x - tex.width() * 0.5,
-        position.y - tex.height() * 0.5,
+        position.x - tex.width() * 0.5 * scale,
+        position.y - tex.height() * 0.5 * scale,
         WHITE,
+        DrawTextureParams {
+            dest_size: Some(Vec2::new(tex.width() * scale, tex.height() * scale)),
+            ..Default::default()
+        },
     );
 
     if game_state.current_target_sobj_id == Some(station.sobj_id) {
@@ -2,7 +2,7 @@ use macroquad::{camera::Camera2D, prelude::*};
 use std::collections::HashMap;
 
 use crate::gameplay::gui::*;
-use crate::server::bindings::{self, DbConnection, VisualEffectType};
+use crate::server::bindings::{self, DbConnection, StationSize, VisualEffectType};
 
 #[derive(Debug, Clone)]
 pub struct FiringEffect {
@@ -13,6 +13,15 @@ pub struct FiringEffect {
     pub effect_type: VisualEffectType,
 }
 
+/// The size a station had when we last drew it, so a growth stage can be
+/// animated.
+#[derive(Debug, Clone)]
+pub struct SeenStationSize {
+    pub size: StationSize,
+    /// When it last grew, and its old sprite's width relative to the new one.
+    pub grew: Option<(f64, f32)>,
+}
+
 pub struct GameState<'a> {
     // Game-Wide States
     pub done: bool,
@@ -53,6 +62,7 @@ pub struct GameState<'a> {
 
     // Visual Effects
     pub firing_effects: HashMap<u64, FiringEffect>,
+    pub station_sizes: HashMap<u64, SeenStationSize>,
 }
 
 pub fn initialize<'a>(ctx: &'a DbConnection) -> GameState<'a> {
@@ -96,5 +106,6 @@ pub fn initialize<'a>(ctx: &'a DbConnection) -> GameState<'a> {
         movement_flags: (false, false, false, false),
 
         firing_effects: HashMap::new(),
+        station_sizes: HashMap::new(),
     }
 }
@@ -48,6 +48,30 @@ impl From<MovementState> for solarance_shared::MovementState {
     }
 }
 
+impl From<&RegenState> for solarance_shared::RegenState {
+    fn from(r: &RegenState) -> Self {
+        solarance_shared::RegenState {
+            value: r.value,
+            rate: r.rate,
+            cap: r.cap,
+            last_update_time: r.last_update_time,
+        }
+    }
+}
+
+impl From<&JumpGate> for solarance_shared::GateEdge {
+    fn from(gate: &JumpGate) -> Self {
+        solarance_shared::GateEdge {
+            gate_id: gate.id,
+            from_sector: gate.current_sector_id,
+            to_sector: gate.target_sector_id,
+            position: (&gate.position).into(),
+            arrival_pos: (&gate.target_gate_arrival_pos).into(),
+            is_active: gate.is_active,
+        }
+    }
+}
+
 /// Impls ///
 
 // Legacy StellarObjectVelocity / StellarObjectTransformHiRes /
@@ -75,6 +99,18 @@ impl Ship {
     }
 }
 
+impl ShipStatus {
+    /// Shields extrapolated to `now_micros` (see `solarance_shared::predict_regen`).
+    pub fn current_shields(&self, now_micros: i64) -> f32 {
+        solarance_shared::predict_regen(&(&self.shields).into(), now_micros)
+    }
+
+    /// Energy extrapolated to `now_micros` (see `solarance_shared::predict_regen`).
+    pub fn current_energy(&self, now_micros: i64) -> f32 {
+        solarance_shared::predict_regen(&(&self.energy).into(), now_micros)
+    }
+}
+
 impl fmt::Display for ShipClass {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         Debug::fmt(self, f)
@@ -100,6 +136,16 @@ impl StationSize {
         }
     }
 
+    /// Mirrors the server's `StationSize::next_growth_size`.
+    pub fn next_growth_size(&self) -> Option<StationSize> {
+        match self {
+            StationSize::Outpost => Some(StationSize::Small),
+            StationSize::Small => Some(StationSize::Medium),
+            StationSize::Medium => Some(StationSize::Large),
+            StationSize::Large | StationSize::Capital | StationSize::Satellite => None,
+        }
+    }
+
     pub fn base_cost(&self) -> u32 {
         (self.modules().pow(2) as u32) * 100_000 + 300_000
     }
@@ -163,6 +209,12 @@ impl From<u64> for ShipId {
     }
 }
 
+impl From<u32> for StationModuleBlueprintId {
+    fn from(id: u32) -> Self {
+        StationModuleBlueprintId { value: id }
+    }
+}
+
 impl From<u64> for StationModuleId {
     fn from(id: u64) -> Self {
         StationModuleId { value: id }
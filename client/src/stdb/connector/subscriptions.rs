@@ -33,6 +33,13 @@ pub(super) fn subscribe_to_tables(ctx: &DbConnection) {
         WHERE s.player_id = '{}'",
         ctx.identity()
     );
+    let player_ship_autopilot = format!(
+        "SELECT a.*
+        FROM ship_autopilot a
+        JOIN ship s ON a.id = s.id
+        WHERE s.player_id = '{}'",
+        ctx.identity()
+    );
     let ship_cargo_item_ship = format!(
         "SELECT i.* 
         FROM ship_cargo_item i
@@ -40,6 +47,13 @@ pub(super) fn subscribe_to_tables(ctx: &DbConnection) {
         WHERE s.player_id = '{}'",
         ctx.identity()
     );
+    // Personal station hangars — only the caller's own rows.
+    let station_hangar_item = format!(
+        "SELECT h.*
+        FROM station_hangar_item h
+        WHERE h.player_id = '{}'",
+        ctx.identity()
+    );
     let ship_cargo_item_docked = format!(
         "SELECT i.* 
         FROM ship_cargo_item i
@@ -112,6 +126,22 @@ pub(super) fn subscribe_to_tables(ctx: &DbConnection) {
             "SELECT * FROM my_sector_chat",
             "SELECT * FROM my_faction_chat",
             "SELECT * FROM my_direct_server_messages",
+            "SELECT * FROM my_direct_messages",
+            "SELECT * FROM my_chat_blocks",
+            // Archived channel pages the player asked to look back at.
+            "SELECT * FROM my_chat_history",
+            // Moderation: our own Mutes and Bans. A Ban disconnects us.
+            "SELECT * FROM my_player_sanctions",
+            // The structured welcome-back summary composed at connect.
+            "SELECT * FROM my_welcome_back",
+            // Contribution leaderboards and the player's own contribution log.
+            "SELECT * FROM construction_site_leaderboards",
+            "SELECT * FROM faction_contribution_leaderboards",
+            "SELECT * FROM weekly_contribution_leaderboard",
+            "SELECT * FROM all_time_contribution_leaderboard",
+            "SELECT * FROM my_contribution_history",
+            // Blueprints players can propose at a station.
+            "SELECT * FROM proposable_station_modules",
             "SELECT * FROM faction",
             "SELECT * FROM faction_standing",
             "SELECT * FROM item_definition",
@@ -127,12 +157,16 @@ pub(super) fn subscribe_to_tables(ctx: &DbConnection) {
             "SELECT * FROM ship_type_definition",
             "SELECT * FROM ship_status",
             player_ship.as_str(),
+            player_ship_autopilot.as_str(),
             ship.as_str(),
             ship_cargo_item_ship.as_str(),
             ship_cargo_item_docked.as_str(),
             "SELECT * FROM ship_equipment_slot",
+            "SELECT * FROM ship_mining_timer",
             "SELECT * FROM trading_port_module",
             "SELECT * FROM trading_port_listing",
+            "SELECT * FROM station_price_sample",
+            "SELECT * FROM market_order",
             // "SELECT * FROM storage_depot_module",
             // "SELECT * FROM embassy_presence",
             // "SELECT * FROM embassy_module",
@@ -152,9 +186,13 @@ pub(super) fn subscribe_to_tables(ctx: &DbConnection) {
             "SELECT * FROM station_module_blueprint",
             "SELECT * FROM station_module",
             "SELECT * FROM station_module_inventory_item",
+            station_hangar_item.as_str(),
             "SELECT * FROM station_module_under_construction",
             "SELECT * FROM station",
             "SELECT * FROM station_status",
+            "SELECT * FROM station_power_grid",
+            "SELECT * FROM station_production_report",
+            "SELECT * FROM module_logistics_priority",
             "SELECT * FROM station_under_construction",
             "SELECT * FROM construction_requirement",
             "SELECT * FROM construction_contribution_log",
@@ -201,6 +201,15 @@ pub fn get_player_ship(ctx: &DbConnection) -> Option<Ship> {
         .find(|s| s.player_id == identity && s.location == ShipLocation::Sector)
 }
 
+/// The mining operation of the player's Piloted Ship, if it's mining.
+pub fn get_player_mining_timer(ctx: &DbConnection) -> Option<ShipMiningTimer> {
+    let ship = get_player_ship(ctx)?;
+    ctx.db()
+        .ship_mining_timer()
+        .iter()
+        .find(|timer| timer.ship_sobj_id == ship.sobj_id)
+}
+
 /// Re-query the player's current target by id, returning a **fresh** row every
 /// call. If the stored id no longer resolves — the row was evicted on a sector
 /// jump, undock, or subscription rotation — the reference is cleared and `None`
@@ -252,6 +261,19 @@ pub fn get_my_docked_ships(ctx: &DbConnection) -> Vec<Ship> {
         .collect()
 }
 
+/// Every ship the calling player owns, piloted or docked, in id order.
+pub fn get_my_fleet(ctx: &DbConnection) -> Vec<Ship> {
+    let identity = ctx.identity();
+    let mut fleet: Vec<Ship> = ctx
+        .db()
+        .ship()
+        .iter()
+        .filter(|s| s.player_id == identity)
+        .collect();
+    fleet.sort_by_key(|s| s.id);
+    fleet
+}
+
 pub fn get_all_equipped_of_type(
     ctx: &DbConnection,
     ship_id: u64,
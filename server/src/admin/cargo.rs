@@ -5,10 +5,11 @@ use spacetimedb::{Identity, ReducerContext};
 use spacetimedsl::*;
 
 use crate::logic::ships::cargo::attempt_to_load_cargo_into_ship;
+use crate::tables::admin::AdminPermission;
 use crate::tables::items::*;
 use crate::tables::players::{get_player_ship_and_sobj, PlayerId};
 use crate::tables::ships::*;
-use crate::utility::try_server_only;
+use crate::utility::require_admin_permission;
 
 /// Admin/seed reducer for M1 spike testing: spawn `quantity` units of
 /// item `item_id` into the in-sector ship belonging to `target_player_id`.
@@ -24,7 +25,15 @@ pub fn admin_spawn_cargo_in_player_ship(
     quantity: u16,
 ) -> Result<(), String> {
     let dsl = dsl(ctx);
-    try_server_only(&dsl)?;
+    require_admin_permission(
+        &dsl,
+        AdminPermission::SpawnItems,
+        "admin_spawn_cargo_in_player_ship",
+        format!(
+            "target_player={} item_id={} quantity={}",
+            target_player_id_str, item_id, quantity
+        ),
+    )?;
     let target_player_id = Identity::from_str(target_player_id_str).map_err(|e| {
         format!(
             "admin_spawn_cargo_in_player_ship: invalid target_player_id_str {:?}: {}",
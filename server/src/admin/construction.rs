@@ -5,11 +5,12 @@ use spacetimedsl::*;
 use crate::logic::stations::contribution::{create_construction_site, reset_construction_site};
 use crate::logic::stations::*;
 use crate::logic::stellarobjects::stellar_object_creation::create_sobj;
+use crate::tables::admin::AdminPermission;
 use crate::tables::{
     economy::ResourceAmount, factions::FactionId, sectors::*, stations::*,
     stellarobjects::StellarObjectKinds,
 };
-use crate::utility::try_server_only;
+use crate::utility::require_admin_permission;
 
 /// Drop a fresh construction site into the world at runtime. Mirrors the
 /// init-time seed in `definitions/galaxy.rs` so the designer can spawn extra
@@ -26,7 +27,12 @@ pub fn admin_create_construction_site(
     requirements: Vec<ResourceAmount>,
 ) -> Result<(), String> {
     let dsl = dsl(ctx);
-    try_server_only(&dsl)?;
+    require_admin_permission(
+        &dsl,
+        AdminPermission::EditGalaxy,
+        "admin_create_construction_site",
+        format!("sector_id={} name={:?}", sector_id, name),
+    )?;
 
     if name.trim().is_empty() {
         return Err("admin_create_construction_site: name must not be empty".to_string());
@@ -61,27 +67,6 @@ pub fn admin_create_construction_site(
     Ok(())
 }
 
-/// Map a well-known module key to its creator function. Shared by
-/// `admin_place_station` (fitting a new station) and `admin_add_station_module`
-/// (fitting an existing one) so the two paths can't drift. The error lists the
-/// valid keys so a typo is self-diagnosing in the logs.
-fn module_creator_from_key(key: &str) -> Result<ModuleCreationFn<ReducerContext>, String> {
-    Ok(match key {
-        "trading" => create_trading_module(),
-        "iron_refinery" => create_iron_refinery_module(),
-        "ice_refinery" => create_ice_refinery_module(),
-        "silicon_refinery" => create_silicon_refinery_module(),
-        "solar_array" => create_small_solar_array_module(),
-        "advanced_manufacturing" => create_advanced_manufacturing_module(),
-        other => {
-            return Err(format!(
-                "unknown module key {:?} (known: trading, iron_refinery, ice_refinery, silicon_refinery, solar_array, advanced_manufacturing)",
-                other
-            ));
-        }
-    })
-}
-
 /// Galaxy Creator (#34): place a *finished*, operational station directly —
 /// bypassing the construction phase — optionally fitted with modules. Mirrors
 /// the init-time `create_station_with_modules` seeds so the designer can drop a
@@ -102,7 +87,15 @@ pub fn admin_place_station(
     modules: Vec<String>,
 ) -> Result<(), String> {
     let dsl = dsl(ctx);
-    try_server_only(&dsl)?;
+    require_admin_permission(
+        &dsl,
+        AdminPermission::EditGalaxy,
+        "admin_place_station",
+        format!(
+            "sector_id={} name={:?} modules={:?}",
+            sector_id, name, modules
+        ),
+    )?;
 
     if name.trim().is_empty() {
         return Err("admin_place_station: name must not be empty".to_string());
@@ -163,7 +156,12 @@ pub fn admin_add_station_module(
     module_key: String,
 ) -> Result<(), String> {
     let dsl = dsl(ctx);
-    try_server_only(&dsl)?;
+    require_admin_permission(
+        &dsl,
+        AdminPermission::EditGalaxy,
+        "admin_add_station_module",
+        format!("station_id={} module={:?}", station_id, module_key),
+    )?;
 
     let station = dsl.get_station_by_id(&StationId::new(station_id))?;
 
@@ -181,25 +179,68 @@ pub fn admin_add_station_module(
     Ok(())
 }
 
-/// Wipe the contribution log for a station and zero its progress bar so the
-/// completion moment can be replayed without `--clear-database`.
+/// Set how early a station module is fed by its station's logistics: higher
+/// priorities get inputs first when supplies run short (see
+/// `logic::stations::logistics`).
 #[spacetimedb::reducer]
-pub fn admin_reset_construction_site(
+pub fn admin_set_module_logistics_priority(
     ctx: &ReducerContext,
-    station_id: u64,
+    module_id: u64,
+    priority: u8,
 ) -> Result<(), String> {
     let dsl = dsl(ctx);
-    try_server_only(&dsl)?;
+    require_admin_permission(
+        &dsl,
+        AdminPermission::EditGalaxy,
+        "admin_set_module_logistics_priority",
+        format!("module_id={} priority={}", module_id, priority),
+    )?;
+
+    let module = dsl.get_station_module_by_id(&StationModuleId::new(module_id))?;
+    match dsl.get_module_logistics_priority_by_id(module.get_id()) {
+        Ok(mut row) => {
+            row.set_priority(priority);
+            dsl.update_module_logistics_priority_by_id(row)?;
+        }
+        Err(_) => {
+            dsl.create_module_logistics_priority(CreateModuleLogisticsPriority {
+                id: module.get_id(),
+                priority,
+            })?;
+        }
+    }
+
+    info!(
+        "admin_set_module_logistics_priority: caller={} module_id={} priority={}",
+        ctx.sender().to_abbreviated_hex(),
+        module_id,
+        priority,
+    );
+    Ok(())
+}
+
+/// Wipe the contribution log for a station and zero its progress bar so the
+/// completion moment can be replayed without `--clear-database`.
+#[spacetimedb::reducer]
+pub fn admin_reset_construction_site(ctx: &ReducerContext, station_id: u64) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    require_admin_permission(
+        &dsl,
+        AdminPermission::EditGalaxy,
+        "admin_reset_construction_site",
+        format!("station_id={}", station_id),
+    )?;
 
     let station_id = StationId::new(station_id);
     // Surface a clean error if the target isn't actually a construction site.
-    dsl.get_station_under_construction_by_id(&station_id).map_err(|e| {
-        format!(
-            "admin_reset_construction_site: station {} is not under construction ({})",
-            station_id.value(),
-            e
-        )
-    })?;
+    dsl.get_station_under_construction_by_id(&station_id)
+        .map_err(|e| {
+            format!(
+                "admin_reset_construction_site: station {} is not under construction ({})",
+                station_id.value(),
+                e
+            )
+        })?;
 
     reset_construction_site(&dsl, &station_id)?;
 
@@ -5,11 +5,12 @@ use spacetimedb::ReducerContext;
 use spacetimedsl::*;
 
 use crate::logic::stellarobjects::stellar_object_creation::create_sobj;
+use crate::tables::admin::AdminPermission;
 use crate::tables::{
     factions::FactionId, jumpgates::*, sectors::*, star_system::StarSystemId,
     stellarobjects::StellarObjectKinds,
 };
-use crate::utility::try_server_only;
+use crate::utility::require_admin_permission;
 
 pub fn create_jumpgate_internal<T: spacetimedsl::WriteContext>(
     dsl: &DSL<T>,
@@ -19,9 +20,7 @@ pub fn create_jumpgate_internal<T: spacetimedsl::WriteContext>(
     target_sector_id: u64,
     t_x: f32,
     t_y: f32,
-) -> Result<(), String> {
-    try_server_only(dsl)?;
-
+) -> Result<JumpGate, String> {
     let current_sector_id = SectorId::new(sector_id);
 
     let sobj = create_sobj(dsl, StellarObjectKinds::JumpGate, &current_sector_id)?;
@@ -58,9 +57,9 @@ pub fn create_jumpgate_internal<T: spacetimedsl::WriteContext>(
         } else {
             // Vertical gate
             if py < 0.0 {
-               PI / 2.0 // north gate → face north (-y), screen up
+                PI / 2.0 // north gate → face north (-y), screen up
             } else {
-               2.0 * PI - (PI * 0.5) // south gate → face south (+y), screen down
+                2.0 * PI - (PI * 0.5) // south gate → face south (+y), screen down
             }
         }
     };
@@ -70,7 +69,7 @@ pub fn create_jumpgate_internal<T: spacetimedsl::WriteContext>(
     // arrival coords through the same classifier.
     let arrival_rotation = inward_facing_rotation(t_x, t_y);
 
-    dsl.create_jump_gate(CreateJumpGate {
+    Ok(dsl.create_jump_gate(CreateJumpGate {
         id: sobj.get_id(),
         current_sector_id,
         target_sector_id: SectorId::new(target_sector_id),
@@ -80,9 +79,7 @@ pub fn create_jumpgate_internal<T: spacetimedsl::WriteContext>(
         is_active: true,
         position: Vec2 { x, y },
         rotation: 0.0,
-    })?;
-
-    Ok(())
+    })?)
 }
 
 /// Creates a jump gate in a sector that connects to another sector.
@@ -98,7 +95,17 @@ pub fn create_jumpgate_in_sector(
     t_y: f32,
 ) -> Result<(), String> {
     let dsl = dsl(ctx);
-    create_jumpgate_internal(&dsl, sector_id, x, y, target_sector_id, t_x, t_y)
+    require_admin_permission(
+        &dsl,
+        AdminPermission::EditGalaxy,
+        "create_jumpgate_in_sector",
+        format!(
+            "sector_id={} pos=({:.1},{:.1}) target_sector_id={}",
+            sector_id, x, y, target_sector_id
+        ),
+    )?;
+    create_jumpgate_internal(&dsl, sector_id, x, y, target_sector_id, t_x, t_y)?;
+    Ok(())
 }
 
 /// Galaxy Creator (#34): drop a brand-new sector into an existing star system
@@ -122,7 +129,12 @@ pub fn admin_create_sector(
     y: f32,
 ) -> Result<(), String> {
     let dsl = dsl(ctx);
-    try_server_only(&dsl)?;
+    require_admin_permission(
+        &dsl,
+        AdminPermission::EditGalaxy,
+        "admin_create_sector",
+        format!("system_id={} name={:?}", system_id, name),
+    )?;
 
     if name.trim().is_empty() {
         return Err("admin_create_sector: name must not be empty".to_string());
@@ -175,7 +187,12 @@ pub fn admin_connect_sectors(
     sector_b_id: u64,
 ) -> Result<(), String> {
     let dsl = dsl(ctx);
-    try_server_only(&dsl)?;
+    require_admin_permission(
+        &dsl,
+        AdminPermission::EditGalaxy,
+        "admin_connect_sectors",
+        format!("sector_a_id={} sector_b_id={}", sector_a_id, sector_b_id),
+    )?;
 
     if sector_a_id == sector_b_id {
         return Err(format!(
@@ -0,0 +1,67 @@
+//! Admin-only hot reload of item, ship type, station module and recipe
+//! definitions.
+//!
+//! Authorization: `AdminPermission::EditDefinitions` via
+//! `require_admin_permission`.
+
+use log::info;
+use spacetimedb::ReducerContext;
+use spacetimedsl::*;
+
+use crate::definitions::content::{self, DefinitionSet};
+use crate::tables::admin::AdminPermission;
+use crate::utility::require_admin_permission;
+
+/// Re-applies a definition set to the live tables without republishing.
+/// Each argument is the full text of the matching RON file; `None` uses the
+/// copy embedded in the module. The whole set is validated before anything
+/// is written, and existing rows are updated in place so ships, cargo and
+/// modules keep pointing at the same ids.
+#[spacetimedb::reducer]
+pub fn admin_reload_definitions(
+    ctx: &ReducerContext,
+    items: Option<String>,
+    ship_types: Option<String>,
+    station_modules: Option<String>,
+    recipes: Option<String>,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    require_admin_permission(
+        &dsl,
+        AdminPermission::EditDefinitions,
+        "admin_reload_definitions",
+        format!(
+            "custom: items={} ship_types={} station_modules={} recipes={}",
+            items.is_some(),
+            ship_types.is_some(),
+            station_modules.is_some(),
+            recipes.is_some()
+        ),
+    )?;
+
+    let set = DefinitionSet::parse(
+        items.as_deref().unwrap_or(content::ITEMS_RON),
+        ship_types.as_deref().unwrap_or(content::SHIP_TYPES_RON),
+        station_modules
+            .as_deref()
+            .unwrap_or(content::STATION_MODULES_RON),
+        recipes.as_deref().unwrap_or(content::RECIPES_RON),
+    )
+    .map_err(|e| format!("admin_reload_definitions: {e}"))?;
+    let [items_summary, ships_summary, modules_summary, recipes_summary] =
+        content::apply(&dsl, &set)?;
+
+    info!(
+        "admin_reload_definitions: caller={} items={:?} ship_types={:?} station_modules={:?} recipes={:?} (custom: items={} ship_types={} station_modules={} recipes={})",
+        ctx.sender().to_abbreviated_hex(),
+        items_summary,
+        ships_summary,
+        modules_summary,
+        recipes_summary,
+        items.is_some(),
+        ship_types.is_some(),
+        station_modules.is_some(),
+        recipes.is_some(),
+    );
+    Ok(())
+}
@@ -0,0 +1,41 @@
+//! Admin-only galaxy layout import. The Galaxy Creator exports the live
+//! galaxy in the same format; see `definitions::galaxy` for how a layout is
+//! applied on top of existing rows.
+//!
+//! Authorization: `AdminPermission::EditGalaxy` via `require_admin_permission`.
+
+use log::info;
+use solarance_shared::GalaxyLayout;
+use spacetimedb::ReducerContext;
+use spacetimedsl::*;
+
+use crate::definitions::galaxy::apply_layout;
+use crate::tables::admin::AdminPermission;
+use crate::utility::require_admin_permission;
+
+/// Applies a galaxy layout (the text of a `galaxy.ron` file) to the live
+/// galaxy. The layout is validated in full before anything is written, and a
+/// failure part-way rolls the whole import back.
+#[spacetimedb::reducer]
+pub fn admin_import_galaxy(ctx: &ReducerContext, layout: String) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    require_admin_permission(
+        &dsl,
+        AdminPermission::EditGalaxy,
+        "admin_import_galaxy",
+        format!("{} bytes", layout.len()),
+    )?;
+
+    let layout =
+        GalaxyLayout::from_ron(&layout).map_err(|e| format!("admin_import_galaxy: {e}"))?;
+    let summary = apply_layout(&dsl, &layout).map_err(|e| format!("admin_import_galaxy: {e}"))?;
+
+    info!(
+        "admin_import_galaxy: caller={} systems={} sectors={} {:?}",
+        ctx.sender().to_abbreviated_hex(),
+        layout.star_systems.len(),
+        layout.sectors().count(),
+        summary,
+    );
+    Ok(())
+}
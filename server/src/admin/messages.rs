@@ -1,8 +1,8 @@
 //! Admin-only reducers for sending Direct Server Messages.
 //!
-//! Authorization: server-only via `try_server_only` — only the module itself can
-//! call these (the typical use case is a debug pipeline or scripted admin tool
-//! that schedules a reducer call with the module identity).
+//! Authorization: `AdminPermission::SendServerMessages` via
+//! `require_admin_permission` — the module itself, or any staff role (this is
+//! the one permission Moderators hold).
 //!
 //! Per #101 redesign:
 //! - `DirectServerMessage` is 1-to-1; "send to many" loops over recipients.
@@ -14,13 +14,14 @@ use spacetimedsl::*;
 
 use crate::{
     tables::{
+        admin::AdminPermission,
         messages::{send_direct_server_message, MessageSeverity},
         players::PlayerId,
     },
-    utility::try_server_only,
+    utility::require_admin_permission,
 };
 
-/// Send a Direct Server Message to one player (server-only).
+/// Send a Direct Server Message to one player.
 #[spacetimedb::reducer]
 pub fn admin_send_direct_server_message(
     ctx: &ReducerContext,
@@ -29,7 +30,17 @@ pub fn admin_send_direct_server_message(
     body: String,
 ) -> Result<(), String> {
     let dsl = dsl(ctx);
-    try_server_only(&dsl)?;
+    require_admin_permission(
+        &dsl,
+        AdminPermission::SendServerMessages,
+        "admin_send_direct_server_message",
+        format!(
+            "to={} {:?}: {}",
+            target_player_id.to_abbreviated_hex(),
+            severity,
+            body
+        ),
+    )?;
 
     if body.trim().is_empty() {
         return Err("Cannot send empty admin message".to_string());
@@ -38,7 +49,7 @@ pub fn admin_send_direct_server_message(
     send_direct_server_message(&dsl, &PlayerId::new(target_player_id), severity, body)
 }
 
-/// Send the same Direct Server Message to a list of players (server-only).
+/// Send the same Direct Server Message to a list of players.
 /// Each recipient gets its own row — DSM is 1-to-1 by design.
 #[spacetimedb::reducer]
 pub fn admin_send_direct_server_message_to_group(
@@ -48,7 +59,17 @@ pub fn admin_send_direct_server_message_to_group(
     body: String,
 ) -> Result<(), String> {
     let dsl = dsl(ctx);
-    try_server_only(&dsl)?;
+    require_admin_permission(
+        &dsl,
+        AdminPermission::SendServerMessages,
+        "admin_send_direct_server_message_to_group",
+        format!(
+            "to {} players {:?}: {}",
+            target_player_ids.len(),
+            severity,
+            body
+        ),
+    )?;
 
     if target_player_ids.is_empty() {
         return Err("Cannot send admin message to empty recipient list".to_string());
@@ -59,12 +80,7 @@ pub fn admin_send_direct_server_message_to_group(
     }
 
     for target in target_player_ids {
-        send_direct_server_message(
-            &dsl,
-            &PlayerId::new(target),
-            severity.clone(),
-            body.clone(),
-        )?;
+        send_direct_server_message(&dsl, &PlayerId::new(target), severity.clone(), body.clone())?;
     }
     Ok(())
 }
@@ -1,4 +1,8 @@
 pub mod cargo;
 pub mod construction;
 pub mod creation;
+pub mod definitions;
+pub mod galaxy;
 pub mod messages;
+pub mod moderation;
+pub mod roles;
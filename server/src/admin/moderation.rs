@@ -0,0 +1,198 @@
+//! Moderation reducers: mutes, bans, kicks and the chat filter.
+//!
+//! Authorization: `AdminPermission::ModeratePlayers` via
+//! `require_admin_permission` (Moderators and up).
+
+use log::info;
+use spacetimedb::{Identity, ReducerContext};
+use spacetimedsl::*;
+
+use crate::{
+    logic::moderation::sanction_until,
+    tables::{
+        admin::AdminPermission,
+        messages::{send_direct_server_message, MessageSeverity},
+        moderation::*,
+        players::{GetPlayerRowOptionById, PlayerId},
+    },
+    utility::require_admin_permission,
+};
+
+/// How long a kick keeps the player out.
+pub const KICK_DURATION_SECS: u64 = 5 * 60;
+
+/// Mutes or bans `target` for `duration_secs` (`None` until lifted). A
+/// registered player is told why by Direct Server Message.
+#[spacetimedb::reducer]
+pub fn admin_sanction_player(
+    ctx: &ReducerContext,
+    target: Identity,
+    kind: SanctionKind,
+    duration_secs: Option<u64>,
+    reason: String,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    require_admin_permission(
+        &dsl,
+        AdminPermission::ModeratePlayers,
+        "admin_sanction_player",
+        format!(
+            "target={} {:?} for {:?}s: {}",
+            target.to_abbreviated_hex(),
+            kind,
+            duration_secs,
+            reason
+        ),
+    )?;
+    sanction(&dsl, target, kind, duration_secs, reason)
+}
+
+/// Kicks `target`: a `KICK_DURATION_SECS` Ban. The game client disconnects
+/// itself when it sees the Ban, and can't reconnect until it runs out.
+#[spacetimedb::reducer]
+pub fn admin_kick_player(
+    ctx: &ReducerContext,
+    target: Identity,
+    reason: String,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    require_admin_permission(
+        &dsl,
+        AdminPermission::ModeratePlayers,
+        "admin_kick_player",
+        format!("target={}: {}", target.to_abbreviated_hex(), reason),
+    )?;
+    sanction(
+        &dsl,
+        target,
+        SanctionKind::Ban,
+        Some(KICK_DURATION_SECS),
+        reason,
+    )
+}
+
+/// Lifts a sanction early.
+#[spacetimedb::reducer]
+pub fn admin_lift_sanction(ctx: &ReducerContext, sanction_id: u64) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    require_admin_permission(
+        &dsl,
+        AdminPermission::ModeratePlayers,
+        "admin_lift_sanction",
+        format!("sanction_id={}", sanction_id),
+    )?;
+
+    let sanction = dsl.get_player_sanction_by_id(PlayerSanctionId::new(sanction_id))?;
+    dsl.delete_player_sanction_by_id(&sanction)?;
+
+    info!(
+        "admin_lift_sanction: caller={} target={} {:?}",
+        ctx.sender().to_abbreviated_hex(),
+        sanction.get_target().to_abbreviated_hex(),
+        sanction.get_kind()
+    );
+    Ok(())
+}
+
+/// Replaces the chat filter settings. See `ChatFilterConfig` for the fields.
+#[spacetimedb::reducer]
+pub fn admin_set_chat_filter(
+    ctx: &ReducerContext,
+    banned_words: Vec<String>,
+    max_messages_per_window: u32,
+    rate_limit_window_secs: u32,
+    max_message_length: u32,
+    reject_repeats: bool,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    require_admin_permission(
+        &dsl,
+        AdminPermission::ModeratePlayers,
+        "admin_set_chat_filter",
+        format!(
+            "{} banned words, {} per {}s, max length {}, reject repeats {}",
+            banned_words.len(),
+            max_messages_per_window,
+            rate_limit_window_secs,
+            max_message_length,
+            reject_repeats
+        ),
+    )?;
+
+    if max_messages_per_window == 0 || max_message_length == 0 {
+        return Err(
+            "admin_set_chat_filter: the message limit and max length must be > 0".to_string(),
+        );
+    }
+
+    let mut config = dsl.get_chat_filter_config_by_id(ChatFilterConfigId::new(0))?;
+    config.banned_words = banned_words
+        .into_iter()
+        .map(|word| word.trim().to_string())
+        .filter(|word| !word.is_empty())
+        .collect();
+    config.max_messages_per_window = max_messages_per_window;
+    config.rate_limit_window_secs = rate_limit_window_secs;
+    config.max_message_length = max_message_length;
+    config.reject_repeats = reject_repeats;
+    dsl.update_chat_filter_config_by_id(config)?;
+    Ok(())
+}
+
+///////////////////////////////////////////////////////////
+// Utilities
+///////////////////////////////////////////////////////////
+
+fn sanction<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    target: Identity,
+    kind: SanctionKind,
+    duration_secs: Option<u64>,
+    reason: String,
+) -> Result<(), String> {
+    if reason.trim().is_empty() {
+        return Err("A sanction needs a reason".to_string());
+    }
+    let issued_by = dsl.ctx().sender()?;
+    if target == issued_by {
+        return Err("You can't sanction yourself".to_string());
+    }
+
+    let sanction = dsl.create_player_sanction(CreatePlayerSanction {
+        galaxy_id: 0,
+        target,
+        kind: kind.clone(),
+        until: sanction_until(dsl.ctx().timestamp()?, duration_secs),
+        reason: reason.clone(),
+        issued_by,
+    })?;
+
+    let player_id = PlayerId::new(target);
+    if dsl.get_player_by_id(&player_id).is_ok() {
+        let verb = match kind {
+            SanctionKind::Mute => "muted",
+            SanctionKind::Ban => "banned",
+        };
+        send_direct_server_message(
+            dsl,
+            &player_id,
+            MessageSeverity::Critical,
+            format!(
+                "You have been {} {}: {}",
+                verb,
+                sanction.describe_until(),
+                reason
+            ),
+        )?;
+    }
+
+    info!(
+        "Sanctioned {}: {:?} {} by {} ({})",
+        target.to_abbreviated_hex(),
+        kind,
+        sanction.describe_until(),
+        issued_by.to_abbreviated_hex(),
+        reason
+    );
+    Ok(())
+}
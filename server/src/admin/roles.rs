@@ -0,0 +1,118 @@
+//! Owner-only management of `AdminRole` rows.
+//!
+//! Authorization: `AdminPermission::ManageRoles`, which only Owners (and the
+//! module itself) hold. Whoever publishes the module is seeded as the first
+//! Owner by `init`, and the last Owner can't be removed or demoted.
+
+use log::info;
+use spacetimedb::{Identity, ReducerContext};
+use spacetimedsl::*;
+
+use crate::tables::admin::*;
+use crate::utility::require_admin_permission;
+
+/// Gives `identity` the role `role`, replacing any role it already has.
+#[spacetimedb::reducer]
+pub fn admin_grant_role(
+    ctx: &ReducerContext,
+    identity: Identity,
+    role: AdminRoleKind,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    require_admin_permission(
+        &dsl,
+        AdminPermission::ManageRoles,
+        "admin_grant_role",
+        format!("identity={} role={:?}", identity.to_abbreviated_hex(), role),
+    )?;
+
+    match dsl.get_admin_role_by_id(AdminRoleId::new(identity)) {
+        Ok(mut admin_role) => {
+            if admin_role.role == AdminRoleKind::Owner
+                && role != AdminRoleKind::Owner
+                && is_last_owner(&dsl)
+            {
+                return Err("Cannot demote the last Owner.".to_string());
+            }
+            admin_role.role = role.clone();
+            admin_role.granted_by = ctx.sender();
+            dsl.update_admin_role_by_id(admin_role)?;
+        }
+        Err(_) => {
+            dsl.create_admin_role(CreateAdminRole {
+                id: identity,
+                role: role.clone(),
+                granted_by: ctx.sender(),
+            })?;
+        }
+    }
+
+    info!(
+        "admin_grant_role: caller={} identity={} role={:?}",
+        ctx.sender().to_abbreviated_hex(),
+        identity.to_abbreviated_hex(),
+        role
+    );
+    Ok(())
+}
+
+/// Removes whatever role `identity` has.
+#[spacetimedb::reducer]
+pub fn admin_revoke_role(ctx: &ReducerContext, identity: Identity) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    require_admin_permission(
+        &dsl,
+        AdminPermission::ManageRoles,
+        "admin_revoke_role",
+        format!("identity={}", identity.to_abbreviated_hex()),
+    )?;
+
+    let admin_role = dsl.get_admin_role_by_id(AdminRoleId::new(identity))?;
+    if admin_role.role == AdminRoleKind::Owner && is_last_owner(&dsl) {
+        return Err("Cannot revoke the last Owner.".to_string());
+    }
+    dsl.delete_admin_role_by_id(&admin_role)?;
+
+    info!(
+        "admin_revoke_role: caller={} identity={} role={:?}",
+        ctx.sender().to_abbreviated_hex(),
+        identity.to_abbreviated_hex(),
+        admin_role.role
+    );
+    Ok(())
+}
+
+///////////////////////////////////////////////////////////
+// Utilities
+///////////////////////////////////////////////////////////
+
+/// Makes `owner` an Owner unless some identity already is. Called from
+/// `init` with the publishing identity.
+pub fn seed_owner_role<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    owner: Identity,
+) -> Result<(), String> {
+    if dsl
+        .get_all_admin_roles()
+        .any(|admin_role| admin_role.role == AdminRoleKind::Owner)
+    {
+        return Ok(());
+    }
+    if let Ok(existing) = dsl.get_admin_role_by_id(AdminRoleId::new(owner)) {
+        dsl.delete_admin_role_by_id(&existing)?;
+    }
+    dsl.create_admin_role(CreateAdminRole {
+        id: owner,
+        role: AdminRoleKind::Owner,
+        granted_by: owner,
+    })?;
+    info!("Seeded {} as the first Owner.", owner.to_abbreviated_hex());
+    Ok(())
+}
+
+fn is_last_owner<T: spacetimedsl::WriteContext>(dsl: &DSL<T>) -> bool {
+    dsl.get_all_admin_roles()
+        .filter(|admin_role| admin_role.role == AdminRoleKind::Owner)
+        .count()
+        <= 1
+}
@@ -0,0 +1,789 @@
+//! # Content Definitions
+//!
+//! Items, ship types, station module blueprints and production recipes are
+//! data, not code: they
+//! live in RON files under `server/content/`, embedded into the module at
+//! build time. `definitions::init` installs them on first publish and
+//! `admin_reload_definitions` re-applies them (or a pasted replacement) to a
+//! live database.
+//!
+//! ## Loading
+//! `DefinitionSet::parse` deserializes all four files, then `validate` checks
+//! the set as a whole — ids, cross-references and `ItemMetadata` — and reports
+//! every problem at once. Nothing is written unless the whole set is valid.
+//!
+//! ## Applying
+//! `apply` upserts by primary key: existing rows are updated in place, new
+//! ids are created. Rows missing from the set are left alone — ships, cargo
+//! and modules still point at them, and deleting a definition would cascade
+//! into player data.
+
+use std::{collections::HashSet, mem::discriminant};
+
+use log::{info, warn};
+use serde::Deserialize;
+use spacetimedsl::*;
+
+use crate::{
+    definitions::station_module_types::calculate_basic_module_cost,
+    logic::stations::module_types::manufacturing::*,
+    tables::{economy::ResourceAmount, items::*, ships::*, stations::*},
+};
+
+pub const ITEMS_RON: &str = include_str!("../../content/items.ron");
+pub const SHIP_TYPES_RON: &str = include_str!("../../content/ship_types.ron");
+pub const STATION_MODULES_RON: &str = include_str!("../../content/station_modules.ron");
+pub const RECIPES_RON: &str = include_str!("../../content/recipes.ron");
+
+//////////////////////////////////////////////////////////////
+// File formats
+//////////////////////////////////////////////////////////////
+
+/// One entry of `items.ron`. Mirrors `ItemDefinition`.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ItemDef {
+    pub id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub category: ItemCategory,
+    pub base_value: u32,
+    pub margin_percentage: u8,
+    pub volume_per_unit: u16,
+    pub units_per_stack: u8,
+    #[serde(default)]
+    pub metadata: Vec<ItemMetadata>,
+    #[serde(default)]
+    pub gfx_key: Option<String>,
+}
+
+/// One entry of `ship_types.ron`. Mirrors `ShipTypeDefinition`.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ShipTypeDef {
+    pub id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub class: ShipClass,
+    pub max_health: u16,
+    pub max_shields: u16,
+    pub max_energy: u16,
+    pub shield_regen_per_sec: f32,
+    pub energy_regen_per_sec: f32,
+    pub base_speed: f32,
+    pub base_acceleration: f32,
+    pub base_angular_acceleration: f32,
+    pub base_max_turn_rate: f32,
+    pub cargo_capacity: u16,
+    pub num_weapon_slots: u8,
+    pub num_large_weapon_slots: u8,
+    pub num_turret_slots: u8,
+    pub num_large_turret_slots: u8,
+    pub num_shield_slots: u8,
+    pub num_engine_slots: u8,
+    pub num_mining_laser_slots: u8,
+    pub num_special_slots: u8,
+    pub sprite_width: u16,
+    pub sprite_height: u16,
+    #[serde(default)]
+    pub gfx_key: Option<String>,
+    #[serde(default)]
+    pub price: Option<u64>,
+}
+
+/// What a station module costs to build.
+#[derive(Deserialize, Debug, Clone)]
+pub enum BuildCost {
+    /// The standard bill from `calculate_basic_module_cost`, scaled by the
+    /// blueprint's `operational_hp`.
+    Basic { complexity: u8 },
+    /// An explicit list of resources.
+    Items(Vec<ResourceAmount>),
+}
+
+/// One entry of `station_modules.ron`. Mirrors `StationModuleBlueprint`,
+/// except that the build cost may be given as a formula.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct StationModuleDef {
+    pub id: u32,
+    pub name: String,
+    pub description: String,
+    pub category: StationModuleCategory,
+    pub specific_type: StationModuleSpecificType,
+    pub build_cost: BuildCost,
+    pub build_time_seconds: u32,
+    pub power_consumption_mw_operational: f32,
+    pub power_consumption_mw_idle: f32,
+    #[serde(default)]
+    pub power_generation_mw: f32,
+    pub cpu_load_flops: f32,
+    pub required_station_tech_level: u8,
+    pub max_internal_storage_slots: u16,
+    #[serde(default)]
+    pub max_internal_storage_volume_per_slot_m3: Option<u32>,
+    #[serde(default)]
+    pub provides_station_morale_boost: Option<i16>,
+    #[serde(default)]
+    pub icon_asset_id: Option<String>,
+    pub construction_hp: u32,
+    pub operational_hp: u32,
+}
+
+impl StationModuleDef {
+    pub fn build_cost_resources(&self) -> Vec<ResourceAmount> {
+        match &self.build_cost {
+            BuildCost::Basic { complexity } => {
+                calculate_basic_module_cost(self.operational_hp, *complexity)
+            }
+            BuildCost::Items(items) => items.clone(),
+        }
+    }
+}
+
+/// One entry of `recipes.ron`. Mirrors `ProductionRecipeDefinition`.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RecipeDef {
+    pub id: u32,
+    pub name: String,
+    pub inputs: Vec<ResourceAmount>,
+    pub output_item_id: u32,
+    pub output_quantity: u32,
+    pub base_production_time_seconds: u32,
+    pub required_module_specific_type: StationModuleSpecificType,
+    #[serde(default)]
+    pub required_tech_id_to_unlock: Option<u32>,
+}
+
+//////////////////////////////////////////////////////////////
+// Definition sets
+//////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone)]
+pub struct DefinitionSet {
+    pub items: Vec<ItemDef>,
+    pub ship_types: Vec<ShipTypeDef>,
+    pub station_modules: Vec<StationModuleDef>,
+    pub recipes: Vec<RecipeDef>,
+}
+
+impl DefinitionSet {
+    /// The set compiled into the module from `server/content/`.
+    pub fn embedded() -> Result<Self, String> {
+        Self::parse(ITEMS_RON, SHIP_TYPES_RON, STATION_MODULES_RON, RECIPES_RON)
+    }
+
+    /// Parses and validates the four content files.
+    pub fn parse(
+        items: &str,
+        ship_types: &str,
+        station_modules: &str,
+        recipes: &str,
+    ) -> Result<Self, String> {
+        let set = Self {
+            items: parse_file("items", items)?,
+            ship_types: parse_file("ship_types", ship_types)?,
+            station_modules: parse_file("station_modules", station_modules)?,
+            recipes: parse_file("recipes", recipes)?,
+        };
+        set.validate()?;
+        Ok(set)
+    }
+
+    /// Checks the set as a whole. The error lists every problem found, one
+    /// per line, so a bad edit can be fixed in one pass.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut problems = Vec::new();
+
+        let mut item_ids = HashSet::new();
+        let mut item_names = HashSet::new();
+        for item in &self.items {
+            let at = format!("item {} ({:?})", item.id, item.name);
+            if !item_ids.insert(item.id) {
+                problems.push(format!("{at}: duplicate id"));
+            }
+            if item.name.trim().is_empty() || !item_names.insert(item.name.as_str()) {
+                problems.push(format!("{at}: name must be non-empty and unique"));
+            }
+            if item.volume_per_unit == 0 || item.units_per_stack == 0 {
+                problems.push(format!(
+                    "{at}: volume_per_unit and units_per_stack must be > 0"
+                ));
+            }
+            if item.margin_percentage > 100 {
+                problems.push(format!("{at}: margin_percentage over 100"));
+            }
+            validate_metadata(item, &at, &mut problems);
+        }
+
+        let mut ship_ids = HashSet::new();
+        for ship in &self.ship_types {
+            let at = format!("ship type {} ({:?})", ship.id, ship.name);
+            if !ship_ids.insert(ship.id) {
+                problems.push(format!("{at}: duplicate id"));
+            }
+            if ship.name.trim().is_empty() {
+                problems.push(format!("{at}: name must be non-empty"));
+            }
+            if ship.max_health == 0 || ship.sprite_width == 0 || ship.sprite_height == 0 {
+                problems.push(format!("{at}: max_health and sprite size must be > 0"));
+            }
+            let motion = [
+                ship.base_speed,
+                ship.base_acceleration,
+                ship.base_angular_acceleration,
+                ship.base_max_turn_rate,
+            ];
+            if motion.iter().any(|v| !v.is_finite() || *v <= 0.0) {
+                problems.push(format!(
+                    "{at}: speeds, accelerations and turn rate must be > 0"
+                ));
+            }
+            let regen = [ship.shield_regen_per_sec, ship.energy_regen_per_sec];
+            if regen.iter().any(|v| !v.is_finite() || *v < 0.0) {
+                problems.push(format!("{at}: regen rates must be finite and >= 0"));
+            }
+        }
+
+        let mut module_ids = HashSet::new();
+        let mut module_names = HashSet::new();
+        for module in &self.station_modules {
+            let at = format!("station module {} ({:?})", module.id, module.name);
+            if !module_ids.insert(module.id) {
+                problems.push(format!("{at}: duplicate id"));
+            }
+            if module.name.trim().is_empty() || !module_names.insert(module.name.as_str()) {
+                problems.push(format!("{at}: name must be non-empty and unique"));
+            }
+            let cost = module.build_cost_resources();
+            if cost.is_empty() {
+                problems.push(format!("{at}: build cost is empty"));
+            }
+            for resource in &cost {
+                if !item_ids.contains(&resource.resource_item_id) {
+                    problems.push(format!(
+                        "{at}: build cost references unknown item {}",
+                        resource.resource_item_id
+                    ));
+                }
+                if resource.quantity == 0 {
+                    problems.push(format!(
+                        "{at}: build cost has zero of item {}",
+                        resource.resource_item_id
+                    ));
+                }
+            }
+            if module.power_consumption_mw_idle > module.power_consumption_mw_operational {
+                problems.push(format!("{at}: idle power draw exceeds operational draw"));
+            }
+            if module.operational_hp == 0 || module.construction_hp > module.operational_hp {
+                problems.push(format!(
+                    "{at}: construction_hp must not exceed a non-zero operational_hp"
+                ));
+            }
+        }
+
+        let mut recipe_ids = HashSet::new();
+        let mut recipe_names = HashSet::new();
+        for recipe in &self.recipes {
+            let at = format!("recipe {} ({:?})", recipe.id, recipe.name);
+            if !recipe_ids.insert(recipe.id) {
+                problems.push(format!("{at}: duplicate id"));
+            }
+            if recipe.name.trim().is_empty() || !recipe_names.insert(recipe.name.as_str()) {
+                problems.push(format!("{at}: name must be non-empty and unique"));
+            }
+            if recipe.inputs.is_empty() {
+                problems.push(format!("{at}: has no inputs"));
+            }
+            for input in &recipe.inputs {
+                if !item_ids.contains(&input.resource_item_id) {
+                    problems.push(format!(
+                        "{at}: input references unknown item {}",
+                        input.resource_item_id
+                    ));
+                }
+                if input.quantity == 0 {
+                    problems.push(format!(
+                        "{at}: input has zero of item {}",
+                        input.resource_item_id
+                    ));
+                }
+            }
+            if !item_ids.contains(&recipe.output_item_id) {
+                problems.push(format!(
+                    "{at}: output references unknown item {}",
+                    recipe.output_item_id
+                ));
+            }
+            if recipe.output_quantity == 0 || recipe.base_production_time_seconds == 0 {
+                problems.push(format!(
+                    "{at}: output_quantity and base_production_time_seconds must be > 0"
+                ));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} problem(s) in definition set:\n{}",
+                problems.len(),
+                problems.join("\n")
+            ))
+        }
+    }
+}
+
+fn parse_file<T: serde::de::DeserializeOwned>(name: &str, text: &str) -> Result<Vec<T>, String> {
+    ron::from_str(text).map_err(|e| format!("{name}.ron: {e}"))
+}
+
+/// Metadata must suit the item: equipment stats only on ship modules,
+/// mining stats only on mining lasers, each variant at most once, and values
+/// in their documented ranges.
+fn validate_metadata(item: &ItemDef, at: &str, problems: &mut Vec<String>) {
+    use ItemMetadata::*;
+
+    let module_type = match &item.category {
+        ItemCategory::ShipModule(module_type) => Some(module_type),
+        ItemCategory::Resource(_) => None,
+    };
+    let is_weapon = matches!(
+        module_type,
+        Some(
+            ShipModuleType::WeaponKinetic
+                | ShipModuleType::WeaponEnergy
+                | ShipModuleType::WeaponMissile
+        )
+    );
+    let is_mining_laser = matches!(
+        module_type,
+        Some(ShipModuleType::MiningLaserBasic | ShipModuleType::MiningLaserAdvanced)
+    );
+
+    let mut seen = Vec::new();
+    for entry in &item.metadata {
+        if seen.contains(&discriminant(entry)) {
+            problems.push(format!("{at}: metadata {entry:?} appears more than once"));
+        }
+        seen.push(discriminant(entry));
+
+        let problem = match entry {
+            Weapon(_) | MissileLauncher(_) if !is_weapon => {
+                Some("only weapon modules may declare a weapon type")
+            }
+            MiningSpeedMultiplier(_) if !is_mining_laser => {
+                Some("only mining lasers have a mining speed")
+            }
+            MiningSpeedMultiplier(m) if !(0.001..=10.0).contains(m) => {
+                Some("mining speed must be within 0.001..=10.0")
+            }
+            Hardness(_) if item.category != ItemCategory::Resource(ResourceCategory::RawOre) => {
+                Some("only raw ores have a hardness")
+            }
+            Hardness(h) if !h.is_finite() || *h <= 0.0 => Some("hardness must be finite and > 0"),
+            BaseDamage(_)
+            | KineticDamageMod(_)
+            | ShieldDamageMod(_)
+            | BaseDamageBoost(_)
+            | MaximumRange(_)
+            | CooldownMs(_)
+            | LockOnAngleBoundRads(_)
+            | AreaOfEffect(_)
+            | FallOff(_)
+            | ShieldBoost(_)
+            | ShieldRegenBoost(_)
+            | EnergyRegenBoost(_)
+            | CargoCapacityBoost(_)
+            | EnergyConsumption(_)
+                if module_type.is_none() =>
+            {
+                Some("equipment stats are only valid on ship modules")
+            }
+            LockOnAngleBoundRads(a) if !(*a > 0.0 && *a <= std::f32::consts::PI) => {
+                Some("lock-on angle must be within (0, π]")
+            }
+            BaseDamage(v) | KineticDamageMod(v) | ShieldDamageMod(v) | BaseDamageBoost(v)
+            | MaximumRange(v) | AreaOfEffect(v) | FallOff(v) | ShieldBoost(v)
+            | ShieldRegenBoost(v) | EnergyRegenBoost(v) | EnergyConsumption(v)
+                if !v.is_finite() || *v < 0.0 =>
+            {
+                Some("value must be finite and non-negative")
+            }
+            Quality(q) if *q > 100 => Some("quality must be within 0..=100"),
+            Stacks(n) if *n != item.units_per_stack => Some("Stacks must match units_per_stack"),
+            _ => None,
+        };
+        if let Some(problem) = problem {
+            problems.push(format!("{at}: {entry:?}: {problem}"));
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////
+// Applying
+//////////////////////////////////////////////////////////////
+
+/// Row counts from `apply`, per table.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ApplySummary {
+    pub created: usize,
+    pub updated: usize,
+    /// Rows already in the table that the set doesn't mention.
+    pub untouched: usize,
+}
+
+/// Upserts every definition in `set`. Existing rows keep their primary keys,
+/// so everything referencing them stays valid.
+pub fn apply<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    set: &DefinitionSet,
+) -> Result<[ApplySummary; 4], String> {
+    let items = apply_items(dsl, &set.items)?;
+    let ship_types = apply_ship_types(dsl, &set.ship_types)?;
+    let station_modules = apply_station_modules(dsl, &set.station_modules)?;
+    let recipes = apply_recipes(dsl, &set.recipes)?;
+
+    info!(
+        "Definitions applied: items {:?}, ship types {:?}, station modules {:?}, recipes {:?}",
+        items, ship_types, station_modules, recipes
+    );
+    for (table, summary) in [
+        ("items", items),
+        ("ship types", ship_types),
+        ("station modules", station_modules),
+        ("recipes", recipes),
+    ] {
+        if summary.untouched > 0 {
+            warn!(
+                "{} existing {} are not in the definition set and were kept as-is",
+                summary.untouched, table
+            );
+        }
+    }
+    Ok([items, ship_types, station_modules, recipes])
+}
+
+fn apply_items<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    defs: &[ItemDef],
+) -> Result<ApplySummary, String> {
+    let mut summary = ApplySummary::default();
+    for def in defs {
+        match dsl.get_item_definition_by_id(ItemDefinitionId::new(def.id)) {
+            Ok(mut row) => {
+                row.name = def.name.clone();
+                row.description = def.description.clone();
+                row.category = def.category.clone();
+                row.base_value = def.base_value;
+                row.margin_percentage = def.margin_percentage;
+                row.volume_per_unit = def.volume_per_unit;
+                row.units_per_stack = def.units_per_stack;
+                row.metadata = def.metadata.clone();
+                row.gfx_key = def.gfx_key.clone();
+                dsl.update_item_definition_by_id(row)?;
+                summary.updated += 1;
+            }
+            Err(_) => {
+                dsl.create_item_definition(CreateItemDefinition {
+                    id: def.id,
+                    name: def.name.clone(),
+                    description: def.description.clone(),
+                    category: def.category.clone(),
+                    base_value: def.base_value,
+                    margin_percentage: def.margin_percentage,
+                    volume_per_unit: def.volume_per_unit,
+                    units_per_stack: def.units_per_stack,
+                    metadata: def.metadata.clone(),
+                    gfx_key: def.gfx_key.clone(),
+                })?;
+                summary.created += 1;
+            }
+        }
+    }
+    summary.untouched = dsl.count_of_all_item_definitions() as usize - defs.len();
+    Ok(summary)
+}
+
+fn apply_ship_types<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    defs: &[ShipTypeDef],
+) -> Result<ApplySummary, String> {
+    let mut summary = ApplySummary::default();
+    for def in defs {
+        match dsl.get_ship_type_definition_by_id(ShipTypeDefinitionId::new(def.id)) {
+            Ok(mut row) => {
+                row.name = def.name.clone();
+                row.description = def.description.clone();
+                row.class = def.class.clone();
+                row.max_health = def.max_health;
+                row.max_shields = def.max_shields;
+                row.max_energy = def.max_energy;
+                row.shield_regen_per_sec = def.shield_regen_per_sec;
+                row.energy_regen_per_sec = def.energy_regen_per_sec;
+                row.base_speed = def.base_speed;
+                row.base_acceleration = def.base_acceleration;
+                row.base_angular_acceleration = def.base_angular_acceleration;
+                row.base_max_turn_rate = def.base_max_turn_rate;
+                row.cargo_capacity = def.cargo_capacity;
+                row.num_weapon_slots = def.num_weapon_slots;
+                row.num_large_weapon_slots = def.num_large_weapon_slots;
+                row.num_turret_slots = def.num_turret_slots;
+                row.num_large_turret_slots = def.num_large_turret_slots;
+                row.num_shield_slots = def.num_shield_slots;
+                row.num_engine_slots = def.num_engine_slots;
+                row.num_mining_laser_slots = def.num_mining_laser_slots;
+                row.num_special_slots = def.num_special_slots;
+                row.sprite_width = def.sprite_width;
+                row.sprite_height = def.sprite_height;
+                row.gfx_key = def.gfx_key.clone();
+                row.price = def.price;
+                dsl.update_ship_type_definition_by_id(row)?;
+                summary.updated += 1;
+            }
+            Err(_) => {
+                dsl.create_ship_type_definition(CreateShipTypeDefinition {
+                    id: def.id,
+                    name: def.name.clone(),
+                    description: def.description.clone(),
+                    class: def.class.clone(),
+                    max_health: def.max_health,
+                    max_shields: def.max_shields,
+                    max_energy: def.max_energy,
+                    shield_regen_per_sec: def.shield_regen_per_sec,
+                    energy_regen_per_sec: def.energy_regen_per_sec,
+                    base_speed: def.base_speed,
+                    base_acceleration: def.base_acceleration,
+                    base_angular_acceleration: def.base_angular_acceleration,
+                    base_max_turn_rate: def.base_max_turn_rate,
+                    cargo_capacity: def.cargo_capacity,
+                    num_weapon_slots: def.num_weapon_slots,
+                    num_large_weapon_slots: def.num_large_weapon_slots,
+                    num_turret_slots: def.num_turret_slots,
+                    num_large_turret_slots: def.num_large_turret_slots,
+                    num_shield_slots: def.num_shield_slots,
+                    num_engine_slots: def.num_engine_slots,
+                    num_mining_laser_slots: def.num_mining_laser_slots,
+                    num_special_slots: def.num_special_slots,
+                    sprite_width: def.sprite_width,
+                    sprite_height: def.sprite_height,
+                    gfx_key: def.gfx_key.clone(),
+                    price: def.price,
+                })?;
+                summary.created += 1;
+            }
+        }
+    }
+    summary.untouched = dsl.count_of_all_ship_type_definitions() as usize - defs.len();
+    Ok(summary)
+}
+
+fn apply_station_modules<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    defs: &[StationModuleDef],
+) -> Result<ApplySummary, String> {
+    let mut summary = ApplySummary::default();
+    for def in defs {
+        match dsl.get_station_module_blueprint_by_id(StationModuleBlueprintId::new(def.id)) {
+            Ok(mut row) => {
+                row.name = def.name.clone();
+                row.description = def.description.clone();
+                row.category = def.category.clone();
+                row.specific_type = def.specific_type.clone();
+                row.build_cost_resources = def.build_cost_resources();
+                row.build_time_seconds = def.build_time_seconds;
+                row.power_consumption_mw_operational = def.power_consumption_mw_operational;
+                row.power_consumption_mw_idle = def.power_consumption_mw_idle;
+                row.power_generation_mw = def.power_generation_mw;
+                row.cpu_load_flops = def.cpu_load_flops;
+                row.required_station_tech_level = def.required_station_tech_level;
+                row.max_internal_storage_slots = def.max_internal_storage_slots;
+                row.max_internal_storage_volume_per_slot_m3 =
+                    def.max_internal_storage_volume_per_slot_m3;
+                row.provides_station_morale_boost = def.provides_station_morale_boost;
+                row.icon_asset_id = def.icon_asset_id.clone();
+                row.construction_hp = def.construction_hp;
+                row.operational_hp = def.operational_hp;
+                dsl.update_station_module_blueprint_by_id(row)?;
+                summary.updated += 1;
+            }
+            Err(_) => {
+                dsl.create_station_module_blueprint(CreateStationModuleBlueprint {
+                    id: def.id,
+                    name: def.name.clone(),
+                    description: def.description.clone(),
+                    category: def.category.clone(),
+                    specific_type: def.specific_type.clone(),
+                    build_cost_resources: def.build_cost_resources(),
+                    build_time_seconds: def.build_time_seconds,
+                    power_consumption_mw_operational: def.power_consumption_mw_operational,
+                    power_consumption_mw_idle: def.power_consumption_mw_idle,
+                    power_generation_mw: def.power_generation_mw,
+                    cpu_load_flops: def.cpu_load_flops,
+                    required_station_tech_level: def.required_station_tech_level,
+                    max_internal_storage_slots: def.max_internal_storage_slots,
+                    max_internal_storage_volume_per_slot_m3: def
+                        .max_internal_storage_volume_per_slot_m3,
+                    provides_station_morale_boost: def.provides_station_morale_boost,
+                    icon_asset_id: def.icon_asset_id.clone(),
+                    construction_hp: def.construction_hp,
+                    operational_hp: def.operational_hp,
+                })?;
+                summary.created += 1;
+            }
+        }
+    }
+    summary.untouched = dsl.get_all_station_module_blueprints().count() - defs.len();
+    Ok(summary)
+}
+
+fn apply_recipes<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    defs: &[RecipeDef],
+) -> Result<ApplySummary, String> {
+    let mut summary = ApplySummary::default();
+    for def in defs {
+        match dsl.get_production_recipe_definition_by_id(ProductionRecipeDefinitionId::new(def.id))
+        {
+            Ok(mut row) => {
+                row.name = def.name.clone();
+                row.input_resources = def.inputs.clone();
+                row.output_resource_id = def.output_item_id;
+                row.output_quantity = def.output_quantity;
+                row.base_production_time_seconds = def.base_production_time_seconds;
+                row.required_module_specific_type = def.required_module_specific_type.clone();
+                row.required_tech_id_to_unlock = def.required_tech_id_to_unlock;
+                dsl.update_production_recipe_definition_by_id(row)?;
+                summary.updated += 1;
+            }
+            Err(_) => {
+                dsl.create_production_recipe_definition(CreateProductionRecipeDefinition {
+                    id: def.id,
+                    name: def.name.clone(),
+                    input_resources: def.inputs.clone(),
+                    output_resource_id: ItemDefinitionId::new(def.output_item_id),
+                    output_quantity: def.output_quantity,
+                    base_production_time_seconds: def.base_production_time_seconds,
+                    required_module_specific_type: def.required_module_specific_type.clone(),
+                    required_tech_id_to_unlock: def.required_tech_id_to_unlock,
+                })?;
+                summary.created += 1;
+            }
+        }
+    }
+    summary.untouched = dsl.get_all_production_recipe_definitions().count() - defs.len();
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definitions::{item_types::*, recipes::*, ship_types::*, station_module_types::*};
+
+    const ONE_ITEM: &str = r#"[(id: 1, name: "Ore", category: Resource(RawOre), base_value: 1,
+        margin_percentage: 10, volume_per_unit: 1, units_per_stack: 16)]"#;
+
+    fn module(build_cost: &str) -> String {
+        format!(
+            r#"[(id: 1, name: "Depot", description: "", category: LogisticsAndStorage,
+            specific_type: StorageDepot, build_cost: {build_cost}, build_time_seconds: 0,
+            power_consumption_mw_operational: 2.0, power_consumption_mw_idle: 1.0,
+            cpu_load_flops: 1.0, required_station_tech_level: 0, max_internal_storage_slots: 1,
+            construction_hp: 1, operational_hp: 10)]"#
+        )
+    }
+
+    #[test]
+    fn embedded_content_is_valid() {
+        let set = DefinitionSet::embedded().unwrap();
+        assert_eq!(set.items.len(), 63);
+        assert_eq!(set.ship_types.len(), 3);
+        assert_eq!(set.station_modules.len(), 23);
+        assert_eq!(set.recipes.len(), 1);
+    }
+
+    #[test]
+    fn embedded_content_defines_every_named_id() {
+        let set = DefinitionSet::embedded().unwrap();
+        let items: HashSet<u32> = set.items.iter().map(|i| i.id).collect();
+        for id in [
+            ITEM_ENERGY_CELL,
+            ITEM_IRON_ORE,
+            ITEM_ICE_ORE,
+            ITEM_SILICON_ORE,
+            ITEM_IRON_INGOT,
+            ITEM_FOOD_RATIONS,
+            ITEM_METAL_PLATES,
+            ITEM_METAL_HULL_STRUCTURE,
+            SMOD_BASIC_MINING_LASER,
+            SMOD_ADVANCED_MINING_LASER,
+            SMOD_AUTOCANNON,
+            SMOD_IONIC_BLASTER,
+            SMOD_PLASMA_CANNON,
+            SMOD_BASIC_SHIELD_GENERATOR,
+            SMOD_CARGO_POD,
+        ] {
+            assert!(items.contains(&id), "item {id} missing from items.ron");
+        }
+        let modules: HashSet<u32> = set.station_modules.iter().map(|m| m.id).collect();
+        for id in [
+            MODULE_TRADING_PORT,
+            MODULE_REFINERY_MINOR,
+            MODULE_SOLAR_ARRAY_SMALL,
+        ] {
+            assert!(
+                modules.contains(&id),
+                "module {id} missing from station_modules.ron"
+            );
+        }
+        assert!(set.ship_types.iter().any(|s| s.id == SHIP_TYPE_COLUMN));
+        assert!(set.recipes.iter().any(|r| r.id == RECIPE_METAL_PLATES));
+    }
+
+    #[test]
+    fn unknown_build_cost_item_is_rejected() {
+        let bad = module("Items([(resource_item_id: 99, quantity: 5)])");
+        let err = DefinitionSet::parse(ONE_ITEM, "[]", &bad, "[]").unwrap_err();
+        assert!(err.contains("unknown item 99"), "{err}");
+
+        let good = module("Items([(resource_item_id: 1, quantity: 5)])");
+        assert!(DefinitionSet::parse(ONE_ITEM, "[]", &good, "[]").is_ok());
+    }
+
+    #[test]
+    fn recipes_must_reference_known_items() {
+        let recipe = |input: u32, output: u32| {
+            format!(
+                r#"[(id: 1, name: "Plates", inputs: [(resource_item_id: {input}, quantity: 2)],
+                output_item_id: {output}, output_quantity: 1, base_production_time_seconds: 60,
+                required_module_specific_type: FactoryBasicComponents)]"#
+            )
+        };
+        let err = DefinitionSet::parse(ONE_ITEM, "[]", "[]", &recipe(98, 99)).unwrap_err();
+        assert!(err.contains("input references unknown item 98"), "{err}");
+        assert!(err.contains("output references unknown item 99"), "{err}");
+
+        assert!(DefinitionSet::parse(ONE_ITEM, "[]", "[]", &recipe(1, 1)).is_ok());
+    }
+
+    #[test]
+    fn metadata_must_suit_the_item() {
+        let ore_with_laser_stats = ONE_ITEM.replace(
+            "units_per_stack: 16)",
+            "units_per_stack: 16, metadata: [MiningSpeedMultiplier(2.0), Quality(101)])",
+        );
+        let err = DefinitionSet::parse(&ore_with_laser_stats, "[]", "[]", "[]").unwrap_err();
+        assert!(err.starts_with("2 problem(s)"), "{err}");
+    }
+
+    #[test]
+    fn typos_fail_to_parse() {
+        let typo = ONE_ITEM.replace("base_value", "base_valeu");
+        let err = DefinitionSet::parse(&typo, "[]", "[]", "[]").unwrap_err();
+        assert!(err.starts_with("items.ron:"), "{err}");
+    }
+}
@@ -1,594 +1,385 @@
+//! # Galaxy Seeding
+//!
+//! The galaxy's hand-authored layout — star systems, sectors, asteroid fields,
+//! nebulae, seeded stations and the jumpgate network — lives in
+//! `content/galaxy.ron` (format: `solarance_shared::galaxy`). `init` applies it
+//! on first publish; `admin_import_galaxy` applies a designer's layout to a
+//! live database through the same `apply_layout`.
+//!
+//! ## Applying to a populated galaxy
+//! `apply_layout` adds and updates, it never removes:
+//! - Star systems are matched by name and sectors by id; both are updated in
+//!   place. Their system-map objects and in-sector nebulae are pure
+//!   decoration and are replaced wholesale.
+//! - Asteroid fields are reconfigured and topped up; existing asteroids stay.
+//! - Stations are matched by name within their sector. An existing station
+//!   gets its size, owner and placement updated, but its modules and
+//!   construction progress are gameplay state and are left alone.
+//! - A gate link is only created between sectors not already connected. Gates
+//!   the link places (`a_gate` / `b_gate`) get that position and active flag,
+//!   whether they are new or already there.
+//!
+//! Anything in the database but not in the layout is kept.
+
 use log::info;
 use spacetimedb::*;
 use spacetimedsl::*;
 
-use solarance_shared::Vec2;
+use solarance_shared::galaxy::{self as layout, GalaxyLayout};
 
 use crate::{
-    definitions::{
-        factions::{
-            FACTION_FACTIONLESS, FACTION_INDEPENDENT_WORLDS_ALLIANCE, FACTION_LRAK_COMBINE, FACTION_REDIAR_FEDERATION
-        },
-        item_types::{
-            ITEM_CARBON_ORE, ITEM_GOLD_ORE, ITEM_ICE_ORE, ITEM_IRON_ORE, ITEM_SILICON_ORE,
-            ITEM_URANIUM_ORE, ITEM_VIVEIUM_ORE,
-        },
+    admin::creation::create_jumpgate_internal,
+    logic::{
+        sectors::asteroid_fields::fill_asteroid_sector,
+        stations::{contribution::create_construction_site, *},
+        stellarobjects::stellar_object_creation::create_sobj,
     },
-    logic::{sectors::asteroid_fields::fill_asteroid_sector, stations::{contribution::create_construction_site, *}, stellarobjects::stellar_object_creation::create_sobj},
     tables::{
-        economy::ResourceAmount, factions::*, sectors::*, star_system::*, stations::*,
-        stellarobjects::*,
+        economy::ResourceAmount, factions::*, jumpgates::*, sectors::*, star_system::*,
+        stations::*, stellarobjects::*,
     },
 };
 
+pub const GALAXY_RON: &str = include_str!("../../content/galaxy.ron");
+
 //////////////////////////////////////////////////////////////
 // Init
 //////////////////////////////////////////////////////////////
 
 pub fn init(dsl: &DSL<'_, ReducerContext>) -> Result<(), String> {
-    demo_sectors(dsl)?;
+    let layout = GalaxyLayout::from_ron(GALAXY_RON)?;
+    apply_layout(dsl, &layout)?;
 
     info!("Sectors Loaded: {}", dsl.get_all_sectors().count());
     Ok(())
 }
 
 //////////////////////////////////////////////////////////////
-// Utility
+// Applying layouts
 //////////////////////////////////////////////////////////////
 
-/// Seeds the MVP ten-sector Procyon system (#106). One star system, ten
-/// functionally-differentiated sectors bracketed by the Lrak (east) and Rediar
-/// (west) capitals with a neutral IWA middle, fully connected by a hub/spoke
-/// jumpgate network.
-///
-/// "Neutral"/"None" sector control in the design table maps to the
-/// **Independent Worlds Alliance** (IWA) — the unaligned governing faction —
-/// since `Sector.controlling_faction_id` is a non-null FK.
-fn demo_sectors(dsl: &DSL<'_, ReducerContext>) -> Result<(), String> {
-    let lrak = FactionId::new(FACTION_LRAK_COMBINE);
-    let rediar = FactionId::new(FACTION_REDIAR_FEDERATION);
-    let neutral = FactionId::new(FACTION_FACTIONLESS);
-    let iwa = FactionId::new(FACTION_INDEPENDENT_WORLDS_ALLIANCE);
-
-    let procyon = create_procyon_star_system(dsl, &lrak)?;
-    let procyon_sectors = create_procyon_sectors(dsl, &procyon, &lrak, &rediar, &neutral)?;
-
-    setup_sector_connections(dsl, &procyon_sectors)?;
-    populate_sectors_with_asteroids(dsl, &procyon_sectors)?;
-    create_sector_stations(dsl, &procyon_sectors, &lrak, &rediar, &iwa)?;
-    place_sector_nebulae(dsl, &procyon_sectors)?;
-
-    // Omicron — the second star system (#121). One cross-system gate out of
-    // The Hinge exercises every multi-system code path (system-map filtering,
-    // "[System] Sector" gate labels, cross-system travel).
-    let omicron_sectors = seed_omicron_system(dsl, &neutral)?;
-    connect_sectors_with_warpgates(dsl, &procyon_sectors.the_hinge, &omicron_sectors.dark_sun)?;
+/// Row counts from `apply_layout`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutSummary {
+    pub star_systems_created: usize,
+    pub star_systems_updated: usize,
+    pub sectors_created: usize,
+    pub sectors_updated: usize,
+    pub stations_created: usize,
+    pub stations_updated: usize,
+    pub gate_links_created: usize,
+}
 
-    Ok(())
+/// Applies a validated layout. See the module docs for how it treats rows
+/// that already exist.
+pub fn apply_layout(
+    dsl: &DSL<'_, ReducerContext>,
+    layout: &GalaxyLayout,
+) -> Result<LayoutSummary, String> {
+    let mut summary = LayoutSummary::default();
+
+    for system_layout in &layout.star_systems {
+        let system = upsert_star_system(dsl, system_layout, &mut summary)?;
+        for sector_layout in &system_layout.sectors {
+            let sector = upsert_sector(dsl, &system, sector_layout, &mut summary)?;
+            apply_asteroid_field(dsl, &sector, sector_layout)?;
+            replace_nebulae(dsl, &sector, sector_layout)?;
+            for station_layout in &sector_layout.stations {
+                apply_station(dsl, &sector, station_layout, &mut summary)?;
+            }
+        }
+    }
+
+    for link in &layout.jumpgates {
+        let a = dsl.get_sector_by_id(&SectorId::new(link.a))?;
+        let b = dsl.get_sector_by_id(&SectorId::new(link.b))?;
+        if !are_connected(dsl, &a, &b) {
+            create_gate_link(dsl, &a, &b, link)?;
+            summary.gate_links_created += 1;
+        }
+        for (from, to, end) in [(&a, &b, &link.a_gate), (&b, &a, &link.b_gate)] {
+            if let Some(end) = end {
+                place_gate(dsl, from, to, end)?;
+            }
+        }
+    }
+
+    info!("Galaxy layout applied: {:?}", summary);
+    Ok(summary)
 }
 
-/// Creates the Procyon star system with all its celestial objects
-fn create_procyon_star_system(
+fn upsert_star_system(
     dsl: &DSL<'_, ReducerContext>,
-    faction_id: &FactionId,
+    system_layout: &layout::StarSystemLayout,
+    summary: &mut LayoutSummary,
 ) -> Result<StarSystem, String> {
-    let procyon = dsl.create_star_system(CreateStarSystem {
-        name: "Procyon".to_string(),
-        map_coordinates: Vec2::new(13.0, 37.0),
-        spectral: SpectralKind::G,
-        luminosity: 5,
-        controlling_faction_id: faction_id.clone(),
-    })?;
-
-    // Create celestial objects in the star system
-    let _star = dsl.create_star_system_object(CreateStarSystemObject {
-        system_id: procyon.get_id(),
-        kind: StarSystemObjectKind::Star,
-        orbit_au: 0.0,
-        rotation_or_width_km: 0.0,
-        gfx_key: Some("star.1".to_string()),
-    });
-    let _planet1 = dsl.create_star_system_object(CreateStarSystemObject {
-        system_id: procyon.get_id(),
-        kind: StarSystemObjectKind::Planet,
-        orbit_au: 128.0,
-        rotation_or_width_km: 0.0,
-        gfx_key: Some("planet.1".to_string()),
-    });
-    let _planet2 = dsl.create_star_system_object(CreateStarSystemObject {
-        system_id: procyon.get_id(),
-        kind: StarSystemObjectKind::Planet,
-        orbit_au: -24.0,
-        rotation_or_width_km: (90f32).to_radians(),
-        gfx_key: Some("planet.2".to_string()),
-    });
-    let _moon = dsl.create_star_system_object(CreateStarSystemObject {
-        system_id: procyon.get_id(),
-        kind: StarSystemObjectKind::Moon,
-        orbit_au: 130.0,
-        rotation_or_width_km: (3.0_f32).to_radians(),
-        gfx_key: None,
-    });
-    let _astbelt = dsl.create_star_system_object(CreateStarSystemObject {
-        system_id: procyon.get_id(),
-        kind: StarSystemObjectKind::AsteroidBelt,
-        orbit_au: 48.0,
-        rotation_or_width_km: 12.0,
-        gfx_key: None,
-    });
+    let system = match dsl.get_star_system_by_name(&system_layout.name) {
+        Ok(mut system) => {
+            system.map_coordinates = system_layout.map_coordinates;
+            system.spectral = system_layout.spectral.into();
+            system.luminosity = system_layout.luminosity;
+            system.controlling_faction_id = system_layout.controlling_faction_id;
+            summary.star_systems_updated += 1;
+            dsl.update_star_system_by_id(system)?
+        }
+        Err(_) => {
+            summary.star_systems_created += 1;
+            dsl.create_star_system(CreateStarSystem {
+                name: system_layout.name.clone(),
+                map_coordinates: system_layout.map_coordinates,
+                spectral: system_layout.spectral.into(),
+                luminosity: system_layout.luminosity,
+                controlling_faction_id: FactionId::new(system_layout.controlling_faction_id),
+            })?
+        }
+    };
 
-    let _nebbelt = dsl.create_star_system_object(CreateStarSystemObject {
-        system_id: procyon.get_id(),
-        kind: StarSystemObjectKind::NebulaBelt,
-        orbit_au: 12.0,
-        rotation_or_width_km: 8.0,
-        gfx_key: None,
-    });
+    let stale: Vec<_> = dsl
+        .get_star_system_objects_by_system_id(&system.get_id())
+        .map(|object| object.get_id())
+        .collect();
+    for id in stale {
+        dsl.delete_star_system_object_by_id(&id)?;
+    }
+    for object in &system_layout.objects {
+        dsl.create_star_system_object(CreateStarSystemObject {
+            system_id: system.get_id(),
+            kind: object.kind.into(),
+            orbit_au: object.orbit_au,
+            rotation_or_width_km: object.rotation_or_width_km,
+            gfx_key: object.gfx_key.clone(),
+        })?;
+    }
 
-    Ok(procyon)
+    Ok(system)
 }
 
-/// The ten seeded sectors, held by name so the wiring steps below
-/// (connections, asteroids, stations) read clearly instead of juggling a
-/// positional tuple. IDs 0..3 are kept stable for the four pre-existing
-/// sectors so non-cleared databases don't orphan ships.
-struct ProcyonSectors {
-    tarols_belt: Sector,   // 0 — neutral, asteroid belt (was "Alpha")
-    ore_trench: Sector,    // 1 — Lrak, rare-ore rich (was "Beta")
-    lrakhold: Sector,      // 2 — Lrak capital (was "Gamma/Homeworld")
-    echo_bay: Sector,      // 3 — Rediar capital (was "Delta/Federation Prime")
-    the_hinge: Sector,     // 4 — neutral hub, central traffic
-    karrens_reach: Sector, // 5 — Lrak frontier refinery (under construction)
-    stilwater: Sector,     // 6 — Rediar outer hub
-    quiet_belt: Sector,    // 7 — neutral mining spoke
-    iron_furrow: Sector,   // 8 — Rediar refinery (under construction)
-    pale_crossing: Sector, // 9 — Rediar outer hub
+fn upsert_sector(
+    dsl: &DSL<'_, ReducerContext>,
+    system: &StarSystem,
+    sector_layout: &layout::SectorLayout,
+    summary: &mut LayoutSummary,
+) -> Result<Sector, String> {
+    match dsl.get_sector_by_id(&SectorId::new(sector_layout.id)) {
+        Ok(mut sector) => {
+            if sector.get_system_id() != system.get_id() {
+                return Err(format!(
+                    "sector {} ({:?}) is in another star system; sectors can't be moved between systems",
+                    sector_layout.id, sector_layout.name
+                ));
+            }
+            sector.name = sector_layout.name.clone();
+            sector.description = sector_layout.description.clone();
+            sector.controlling_faction_id = sector_layout.controlling_faction_id;
+            sector.security_level = sector_layout.security_level;
+            sector.sunlight = sector_layout.sunlight;
+            sector.anomalous = sector_layout.anomalous;
+            sector.nebula = sector_layout.nebula;
+            sector.rare_ore = sector_layout.rare_ore;
+            sector.x = sector_layout.position.x;
+            sector.y = sector_layout.position.y;
+            sector.background_gfx_key = sector_layout.background_gfx_key.clone();
+            summary.sectors_updated += 1;
+            Ok(dsl.update_sector_by_id(sector)?)
+        }
+        Err(_) => {
+            summary.sectors_created += 1;
+            Ok(dsl.create_sector(CreateSector {
+                id: sector_layout.id,
+                system_id: system.get_id(),
+                name: sector_layout.name.clone(),
+                description: sector_layout.description.clone(),
+                controlling_faction_id: FactionId::new(sector_layout.controlling_faction_id),
+                security_level: sector_layout.security_level,
+                sunlight: sector_layout.sunlight,
+                anomalous: sector_layout.anomalous,
+                nebula: sector_layout.nebula,
+                rare_ore: sector_layout.rare_ore,
+                x: sector_layout.position.x,
+                y: sector_layout.position.y,
+                background_gfx_key: sector_layout.background_gfx_key.clone(),
+            })?)
+        }
+    }
 }
 
-/// Creates the ten Procyon sectors per the #106 design table.
-fn create_procyon_sectors(
+/// Asteroid field rows can't be updated in place, so a reconfigured field is
+/// recreated; its asteroids belong to the sector and survive.
+fn apply_asteroid_field(
     dsl: &DSL<'_, ReducerContext>,
-    procyon: &StarSystem,
-    lrak: &FactionId,
-    rediar: &FactionId,
-    neutral: &FactionId,
-) -> Result<ProcyonSectors, String> {
-    // Small helper so each sector is one readable line of differentiators.
-    let mk = |id: u64,
-                  name: &str,
-                  faction: &FactionId,
-                  security: u8,
-                  sunlight: f32,
-                  anomalous: f32,
-                  nebula: f32,
-                  rare_ore: f32,
-                  x: f32,
-                  y: f32|
-     -> Result<Sector, String> {
-        dsl.create_sector(CreateSector {
-            id,
-            system_id: procyon.get_id(),
-            name: name.to_string(),
-            description: None,
-            controlling_faction_id: faction.clone(),
-            security_level: security,
-            sunlight,
-            anomalous,
-            nebula,
-            rare_ore,
-            x,
-            y,
-            background_gfx_key: None,
-        })
-        .map_err(|e| e.to_string())
+    sector: &Sector,
+    sector_layout: &layout::SectorLayout,
+) -> Result<(), String> {
+    let Some(field) = &sector_layout.asteroid_field else {
+        return Ok(());
     };
-
-    // Pre-existing four (IDs kept; Tarol's Belt flipped Lrak -> neutral).
-    let tarols_belt = mk(0, "Tarol's Belt", neutral, 5, 0.9, 0.1, 0.1, 0.2, 4.0, -30.0)?;
-    let ore_trench = mk(1, "Ore Trench", lrak, 6, 0.8, 0.0, 0.1, 0.6, 75.0, -20.0)?;
-    let lrakhold = mk(2, "Lrakhold", lrak, 10, 0.9, 0.1, 0.1, 0.1, 126.0, 8.0)?;
-    let echo_bay = mk(3, "Echo Bay", rediar, 9, 0.9, 0.0, 0.1, 0.1, -120.0, 8.0)?;
-
-    // Six new MVP sectors.
-    let the_hinge = mk(4, "The Hinge", neutral, 5, 0.4, 0.0, 0.6, 0.0, -4.0, 10.0)?;
-    let karrens_reach = mk(5, "Karren's Reach", lrak, 7, 0.7, 0.0, 0.1, 0.2, 100.0, 30.0)?;
-    let stilwater = mk(6, "Stilwater", rediar, 8, 0.6, 0.4, 0.3, 0.0, -70.0, 40.0)?;
-    let quiet_belt = mk(7, "Quiet Belt", neutral, 4, 0.5, 0.1, 0.2, 0.8, 40.0, -12.0)?;
-    let iron_furrow = mk(8, "Iron Furrow", rediar, 6, 0.8, 0.0, 0.0, 0.1, -48.0, 5.0)?;
-    let pale_crossing = mk(9, "Pale Crossing", rediar, 7, 0.6, 0.0, 0.4, 0.0, -85.0, -5.0)?;
-
-    Ok(ProcyonSectors {
-        tarols_belt,
-        ore_trench,
-        lrakhold,
-        echo_bay,
-        the_hinge,
-        karrens_reach,
-        stilwater,
-        quiet_belt,
-        iron_furrow,
-        pale_crossing,
-    })
+    if dsl.get_asteroid_sector_by_id(&sector.get_id()).is_ok() {
+        dsl.delete_asteroid_sector_by_id(&sector.get_id())?;
+    }
+    let created = dsl.create_asteroid_sector(CreateAsteroidSector {
+        id: sector.get_id(),
+        sparseness: field.sparseness,
+        rarity: field.rarity,
+        cluster_extent: field.cluster_extent,
+        cluster_inner: field.cluster_inner,
+        ore_weights: field
+            .ore_weights
+            .iter()
+            .map(|w| OreWeight {
+                item_id: w.item_id,
+                weight: w.weight,
+            })
+            .collect(),
+    })?;
+    fill_asteroid_sector(dsl, &created)
 }
 
-/// Wires the hub/spoke jumpgate network. Every sector is reachable; hubs (The
-/// Hinge, Pale Crossing, Stilwater, Ore Trench) carry 3+ gates, spokes
-/// (Karren's Reach, Quiet Belt, Iron Furrow) 1-2. The two capitals bracket the
-/// chain. Connectivity is validated separately by #108.
-fn setup_sector_connections(
+fn replace_nebulae(
     dsl: &DSL<'_, ReducerContext>,
-    s: &ProcyonSectors,
+    sector: &Sector,
+    sector_layout: &layout::SectorLayout,
 ) -> Result<(), String> {
-    let link = |a: &Sector, b: &Sector| connect_sectors_with_warpgates(dsl, a, b);
-
-    // Rediar (west) cluster.
-    link(&s.echo_bay, &s.pale_crossing)?;
-    link(&s.echo_bay, &s.stilwater)?;
-    link(&s.pale_crossing, &s.stilwater)?;
-    link(&s.pale_crossing, &s.iron_furrow)?;
-    link(&s.stilwater, &s.iron_furrow)?;
-
-    // Bridge through the neutral middle.
-    link(&s.pale_crossing, &s.the_hinge)?;
-    link(&s.the_hinge, &s.tarols_belt)?;
-    link(&s.the_hinge, &s.quiet_belt)?;
-
-    // Lrak (east) cluster.
-    link(&s.tarols_belt, &s.ore_trench)?;
-    link(&s.quiet_belt, &s.ore_trench)?;
-    link(&s.ore_trench, &s.lrakhold)?;
-    link(&s.ore_trench, &s.karrens_reach)?;
-    link(&s.lrakhold, &s.karrens_reach)?;
-
+    let stale: Vec<_> = dsl
+        .get_sector_nebulae_by_sector_id(&sector.get_id())
+        .map(|nebula| nebula.get_id())
+        .collect();
+    for id in stale {
+        dsl.delete_sector_nebula_by_id(&id)?;
+    }
+    for nebula in &sector_layout.nebulae {
+        dsl.create_sector_nebula(CreateSectorNebula {
+            sector_id: sector.get_id(),
+            position: nebula.position,
+            gfx_key: nebula.gfx_key.clone(),
+            scale: nebula.scale,
+            rotation_radians: nebula.rotation_radians,
+            tint: nebula.tint,
+        })?;
+    }
     Ok(())
 }
 
-/// Stocks the mining sectors with asteroid fields. Each field can declare an
-/// explicit per-sector ore composition (`ore_weights`); sectors left with an
-/// empty composition fall back to the global rarity-skewed distribution, so
-/// `sparseness`/`rarity` still drive density and rare-ore skew there.
-fn populate_sectors_with_asteroids(
+fn apply_station(
     dsl: &DSL<'_, ReducerContext>,
-    s: &ProcyonSectors,
+    sector: &Sector,
+    station_layout: &layout::StationLayout,
+    summary: &mut LayoutSummary,
 ) -> Result<(), String> {
-    // Relative ore weights within a sector — values need not sum to 100.
-    let ore = |pairs: &[(u32, u16)]| -> Vec<OreWeight> {
-        pairs
-            .iter()
-            .map(|(item_id, weight)| OreWeight {
-                item_id: *item_id,
-                weight: *weight,
-            })
-            .collect()
+    let owner = FactionId::new(station_layout.owner_faction_id);
+    let existing = dsl
+        .get_stations_by_sector_id(&sector.get_id())
+        .find(|station| station.name == station_layout.name);
+
+    let station = match existing {
+        Some(mut station) => {
+            station.size = station_layout.size.into();
+            station.owner_faction_id = station_layout.owner_faction_id;
+            station.position = station_layout.position;
+            station.rotation = station_layout.rotation;
+            let station = dsl.update_station_by_id(station)?;
+            verify(dsl, &station)?;
+            summary.stations_updated += 1;
+            station
+        }
+        None if !station_layout.construction_requirements.is_empty() => {
+            summary.stations_created += 1;
+            create_construction_site(
+                dsl,
+                station_layout.size.into(),
+                sector,
+                &create_sobj(dsl, StellarObjectKinds::Station, &sector.get_id())?,
+                owner.clone(),
+                &station_layout.name,
+                station_layout.position,
+                station_layout.rotation,
+                station_layout
+                    .construction_requirements
+                    .iter()
+                    .map(|r| ResourceAmount::new(r.item_id, r.quantity))
+                    .collect(),
+            )?
+        }
+        None => {
+            let mut module_creators = Vec::with_capacity(station_layout.modules.len());
+            for key in &station_layout.modules {
+                module_creators.push(
+                    module_creator_from_key(key)
+                        .map_err(|e| format!("station {:?}: {e}", station_layout.name))?,
+                );
+            }
+            summary.stations_created += 1;
+            create_station_with_modules(
+                dsl,
+                station_layout.size.into(),
+                sector,
+                &create_sobj(dsl, StellarObjectKinds::Station, &sector.get_id())?,
+                owner.clone(),
+                &station_layout.name,
+                None,
+                station_layout.position,
+                station_layout.rotation,
+                module_creators,
+            )?
+        }
     };
 
-    let field = |sector: &Sector,
-                 sparseness: u8,
-                 rarity: u8,
-                 cluster_extent: f32,
-                 cluster_inner: Option<f32>,
-                 ore_weights: Vec<OreWeight>|
-     -> Result<(), String> {
-        let created = dsl.create_asteroid_sector(CreateAsteroidSector {
-            id: sector.get_id(),
-            sparseness,
-            rarity,
-            cluster_extent,
-            cluster_inner,
-            ore_weights,
-        })?;
-        fill_asteroid_sector(dsl, &created)
-    };
-
-    // Empty composition → global default distribution (rarity-skewed).
-    field(&s.tarols_belt, 2, 25, 3000.0, Some(1000.0), vec![])?; // moderate, mixed (default mix)
-    field(
-        &s.ore_trench,
-        5,
-        50,
-        5000.0,
-        None,
-        ore(&[
-            (ITEM_URANIUM_ORE, 30),
-            (ITEM_VIVEIUM_ORE, 25),
-            (ITEM_GOLD_ORE, 20),
-            (ITEM_IRON_ORE, 25),
-        ]),
-    )?; // dense, rare-ore rich
-    field(
-        &s.quiet_belt,
-        6,
-        70,
-        5000.0,
-        None,
-        ore(&[
-            (ITEM_GOLD_ORE, 30),
-            (ITEM_SILICON_ORE, 30),
-            (ITEM_VIVEIUM_ORE, 20),
-            (ITEM_ICE_ORE, 20),
-        ]),
-    )?; // high yield
-    field(
-        &s.iron_furrow,
-        4,
-        10,
-        4000.0,
-        None,
-        ore(&[
-            (ITEM_IRON_ORE, 70),
-            (ITEM_SILICON_ORE, 20),
-            (ITEM_GOLD_ORE, 10),
-        ]),
-    )?; // iron-heavy, common
-    field(
-        &s.karrens_reach,
-        3,
-        40,
-        3500.0,
-        Some(800.0),
-        ore(&[
-            (ITEM_CARBON_ORE, 50),
-            (ITEM_ICE_ORE, 30),
-            (ITEM_IRON_ORE, 20),
-        ]),
-    )?; // carbon-bearing
-
+    if station_layout.capital {
+        stamp_capital(dsl, &owner, &station)?;
+    }
     Ok(())
 }
 
-/// Places the seeded stations: two faction capitals (stamped onto their
-/// factions as spawn anchors), two operational neutral/Lrak service stations,
-/// and two under-construction refinery sites for the contribution loop.
-fn create_sector_stations(
+/// Creates both gates of `link`, each at its layout position if it has one
+/// and otherwise where `connect_sectors_with_warpgates` would put it. Ships
+/// arrive at the other end's gate.
+fn create_gate_link(
     dsl: &DSL<'_, ReducerContext>,
-    s: &ProcyonSectors,
-    lrak: &FactionId,
-    rediar: &FactionId,
-    neutral: &FactionId,
+    a: &Sector,
+    b: &Sector,
+    link: &layout::GateLink,
 ) -> Result<(), String> {
-    // --- Faction capitals (spawn anchors) ---------------------------------
-    let lrak_capital = create_station_with_modules(
-        dsl,
-        StationSize::Capital,
-        &s.lrakhold,
-        &create_sobj(dsl, StellarObjectKinds::Station, &s.lrakhold.get_id())?,
-        lrak.clone(),
-        "Lrakhold City",
-        None,
-        Vec2::new(455.0, -1337.0),
-        0.0,
-        vec![create_trading_module()],
-    )?;
-    stamp_capital(dsl, lrak, &lrak_capital)?;
-
-    let rediar_capital = create_station_with_modules(
-        dsl,
-        StationSize::Capital,
-        &s.echo_bay,
-        &create_sobj(dsl, StellarObjectKinds::Station, &s.echo_bay.get_id())?,
-        rediar.clone(),
-        "Echo Bay Prime",
-        None,
-        Vec2::new(-455.0, 1337.0),
-        0.0,
-        vec![create_trading_module()],
-    )?;
-    stamp_capital(dsl, rediar, &rediar_capital)?;
-
-    // --- Operational service stations -------------------------------------
-    // Tarol's Belt: the neutral refinery rest-stop (kept from the original seed).
-    let _tarols_refinery = create_station_with_modules(
-        dsl,
-        StationSize::Outpost,
-        &s.tarols_belt,
-        &create_sobj(dsl, StellarObjectKinds::Station, &s.tarols_belt.get_id())?,
-        neutral.clone(),
-        "Tarol's Rest & Refinery Stop",
-        None,
-        Vec2::new(0.0, 0.0),
-        0.0,
-        vec![
-            create_iron_refinery_module(),
-            create_ice_refinery_module(),
-            create_silicon_refinery_module(),
-        ],
-    )?;
-
-    // The Hinge: the large neutral trading bazaar at the network's crossroads.
-    let _hinge_bazaar = create_station_with_modules(
-        dsl,
-        StationSize::Large,
-        &s.the_hinge,
-        &create_sobj(dsl, StellarObjectKinds::Station, &s.the_hinge.get_id())?,
-        neutral.clone(),
-        "The Hinge Bazaar",
-        None,
-        Vec2::new(0.0, 0.0),
-        0.0,
-        vec![create_trading_module()],
-    )?;
-
-    // Ore Trench: a Lrak trading exchange feeding off the rare-ore field.
-    let _ore_trench_exchange = create_station_with_modules(
-        dsl,
-        StationSize::Medium,
-        &s.ore_trench,
-        &create_sobj(dsl, StellarObjectKinds::Station, &s.ore_trench.get_id())?,
-        lrak.clone(),
-        "Ore Trench Exchange",
-        None,
-        Vec2::new(613.0, 1337.0),
-        0.0,
-        vec![create_trading_module()],
-    )?;
-
-    // --- Under-construction refinery sites (contribution loop targets) ----
-    let _karren_site = create_construction_site(
-        dsl,
-        StationSize::Medium,
-        &s.karrens_reach,
-        &create_sobj(dsl, StellarObjectKinds::Station, &s.karrens_reach.get_id())?,
-        lrak.clone(),
-        "Karren Refinery (Under Construction)",
-        Vec2::new(1500.0, 0.0),
-        0.0,
-        vec![
-            ResourceAmount::new(ITEM_IRON_ORE, 150),
-            ResourceAmount::new(ITEM_SILICON_ORE, 100),
-            ResourceAmount::new(ITEM_CARBON_ORE, 50),
-        ],
-    )?;
-
-    let _furrow_site = create_construction_site(
-        dsl,
-        StationSize::Medium,
-        &s.iron_furrow,
-        &create_sobj(dsl, StellarObjectKinds::Station, &s.iron_furrow.get_id())?,
-        rediar.clone(),
-        "Iron Furrow Refinery (Under Construction)",
-        Vec2::new(-1500.0, 0.0),
-        0.0,
-        vec![
-            ResourceAmount::new(ITEM_IRON_ORE, 200),
-            ResourceAmount::new(ITEM_GOLD_ORE, 50),
-        ],
-    )?;
+    let (a_default, b_default) = warpgate_positions(a, b);
+    let a_pos = link.a_gate.map_or(a_default, |end| end.position.to_glam());
+    let b_pos = link.b_gate.map_or(b_default, |end| end.position.to_glam());
 
+    let (a_id, b_id) = (a.get_id().value(), b.get_id().value());
+    create_jumpgate_internal(dsl, a_id, a_pos.x, a_pos.y, b_id, b_pos.x, b_pos.y)?;
+    create_jumpgate_internal(dsl, b_id, b_pos.x, b_pos.y, a_id, a_pos.x, a_pos.y)?;
     Ok(())
 }
 
-/// Seeds decorative in-sector nebulae (#107). The Hinge sits inside the
-/// system's nebula belt (orbit_au 12.0) and gets significant coverage;
-/// Lrakhold and Echo Bay each get a single wisp. Pure render flavor —
-/// nothing reads these rows server-side.
-fn place_sector_nebulae(
+/// Moves the gate in `from` that leads to `to` to where the layout puts it
+/// and switches it on or off to match. Ships coming the other way arrive at
+/// the new position.
+fn place_gate(
     dsl: &DSL<'_, ReducerContext>,
-    s: &ProcyonSectors,
+    from: &Sector,
+    to: &Sector,
+    end: &layout::GateEndLayout,
 ) -> Result<(), String> {
-    let nebula = |sector: &Sector,
-                  x: f32,
-                  y: f32,
-                  gfx_key: &str,
-                  scale: f32,
-                  rotation_deg: f32,
-                  tint: u32|
-     -> Result<(), String> {
-        dsl.create_sector_nebula(CreateSectorNebula {
-            sector_id: sector.get_id(),
-            position: Vec2::new(x, y),
-            gfx_key: gfx_key.to_string(),
-            scale,
-            rotation_radians: rotation_deg.to_radians(),
-            tint,
-        })
-        .map(|_| ())
-        .map_err(|e| e.to_string())
+    let gates_between = |from: &Sector, to: &Sector| -> Vec<JumpGate> {
+        dsl.get_jump_gates_by_current_sector_id(&from.get_id())
+            .filter(|gate| gate.get_target_sector_id() == to.get_id())
+            .collect()
     };
-
-    // The Hinge — the only sector within the nebula belt; blanket it.
-    nebula(&s.the_hinge, -2600.0, 1900.0, "nebula.1", 6.0, 20.0, 0xFFFFFFA0)?;
-    nebula(&s.the_hinge, 1400.0, -2300.0, "nebula.1", 5.0, 140.0, 0xE0E8FF90)?;
-    nebula(&s.the_hinge, 3300.0, 1000.0, "nebula.1", 4.0, 275.0, 0xFFFFFF80)?;
-    nebula(&s.the_hinge, -900.0, -3400.0, "nebula.1", 4.5, 65.0, 0xD8E0FF88)?;
-    nebula(&s.the_hinge, 400.0, 2900.0, "nebula.1", 3.5, 200.0, 0xFFFFFF70)?;
-
-    // Single wisps on the belt-adjacent capitals.
-    nebula(&s.lrakhold, 2800.0, -1600.0, "nebula.7", 4.0, 310.0, 0xFFFFFF90)?;
-    nebula(&s.echo_bay, -2200.0, 2400.0, "nebula.6", 4.0, 45.0, 0xFFFFFF90)?;
-
+    for mut gate in gates_between(from, to) {
+        gate.set_position(end.position);
+        gate.set_is_active(end.is_active);
+        dsl.update_jump_gate_by_id(gate)?;
+    }
+    for mut gate in gates_between(to, from) {
+        gate.set_target_gate_arrival_pos(end.position);
+        dsl.update_jump_gate_by_id(gate)?;
+    }
     Ok(())
 }
 
-/// The Omicron sectors, mirrored from `ProcyonSectors` so future wiring reads
-/// the same way. Sector IDs continue after Procyon's 0..9.
-struct OmicronSectors {
-    dark_sun: Sector, // 10 — factionless, the lightless inner sector
-    ashfall: Sector,  // 11 — factionless, dim outer ember
-}
-
-/// Seeds the Omicron star system (#121): a dim M-dwarf neighbor of Procyon
-/// holding two factionless frontier sectors, wired to each other by a normal
-/// gate. Kept as structured as the Procyon seed so multi-system testing
-/// (map filtering, cross-system labels, travel) has a real second system to
-/// lean on. The cross-system gate (Dark Sun <-> The Hinge) is wired by
-/// `demo_sectors`, next to the rest of the gate network.
-fn seed_omicron_system(
-    dsl: &DSL<'_, ReducerContext>,
-    neutral: &FactionId,
-) -> Result<OmicronSectors, String> {
-    let omicron = dsl.create_star_system(CreateStarSystem {
-        name: "Omicron".to_string(),
-        map_coordinates: Vec2::new(21.0, 34.0),
-        spectral: SpectralKind::M,
-        luminosity: 6,
-        controlling_faction_id: neutral.clone(),
-    })?;
-
-    let _star = dsl.create_star_system_object(CreateStarSystemObject {
-        system_id: omicron.get_id(),
-        kind: StarSystemObjectKind::Star,
-        orbit_au: 0.0,
-        rotation_or_width_km: 0.0,
-        gfx_key: Some("star.2".to_string()),
-    });
-    let _planet = dsl.create_star_system_object(CreateStarSystemObject {
-        system_id: omicron.get_id(),
-        kind: StarSystemObjectKind::Planet,
-        orbit_au: 60.0,
-        rotation_or_width_km: (210f32).to_radians(),
-        gfx_key: Some("planet.3".to_string()),
-    });
-    let _nebbelt = dsl.create_star_system_object(CreateStarSystemObject {
-        system_id: omicron.get_id(),
-        kind: StarSystemObjectKind::NebulaBelt,
-        orbit_au: 30.0,
-        rotation_or_width_km: 14.0,
-        gfx_key: None,
-    });
-
-    // Same one-line-per-sector helper shape as `create_procyon_sectors`,
-    // plus a description so the map details panel's description path has
-    // seeded data to render.
-    let mk = |id: u64,
-              name: &str,
-              description: Option<&str>,
-              security: u8,
-              sunlight: f32,
-              anomalous: f32,
-              nebula: f32,
-              rare_ore: f32,
-              x: f32,
-              y: f32|
-     -> Result<Sector, String> {
-        dsl.create_sector(CreateSector {
-            id,
-            system_id: omicron.get_id(),
-            name: name.to_string(),
-            description: description.map(str::to_string),
-            controlling_faction_id: neutral.clone(),
-            security_level: security,
-            sunlight,
-            anomalous,
-            nebula,
-            rare_ore,
-            x,
-            y,
-            background_gfx_key: None,
-        })
-        .map_err(|e| e.to_string())
+fn are_connected(dsl: &DSL<'_, ReducerContext>, a: &Sector, b: &Sector) -> bool {
+    let leads_to = |from: &Sector, to: &Sector| {
+        dsl.get_jump_gates_by_current_sector_id(&from.get_id())
+            .any(|gate| gate.get_target_sector_id() == to.get_id())
     };
-
-    let dark_sun = mk(
-        10,
-        "Dark Sun",
-        Some("Omicron's failing ember barely reaches this sector. Sensor returns are unreliable, and nobody polices what they can't see."),
-        1, 0.1, 0.6, 0.3, 0.3, 0.0, 0.0,
-    )?;
-    let ashfall = mk(11, "Ashfall", None, 1, 0.2, 0.3, 0.2, 0.4, 45.0, 20.0)?;
-
-    connect_sectors_with_warpgates(dsl, &dark_sun, &ashfall)?;
-
-    Ok(OmicronSectors { dark_sun, ashfall })
+    leads_to(a, b) || leads_to(b, a)
 }
 
 /// Stamps a station as the faction's capital — the spawn anchor for that
@@ -603,3 +394,46 @@ fn stamp_capital(
     dsl.update_faction_by_id(faction)?;
     Ok(())
 }
+
+//////////////////////////////////////////////////////////////
+// Layout enums
+//////////////////////////////////////////////////////////////
+
+impl From<layout::SpectralKind> for SpectralKind {
+    fn from(kind: layout::SpectralKind) -> Self {
+        match kind {
+            layout::SpectralKind::O => SpectralKind::O,
+            layout::SpectralKind::B => SpectralKind::B,
+            layout::SpectralKind::A => SpectralKind::A,
+            layout::SpectralKind::F => SpectralKind::F,
+            layout::SpectralKind::G => SpectralKind::G,
+            layout::SpectralKind::K => SpectralKind::K,
+            layout::SpectralKind::M => SpectralKind::M,
+        }
+    }
+}
+
+impl From<layout::CelestialKind> for StarSystemObjectKind {
+    fn from(kind: layout::CelestialKind) -> Self {
+        match kind {
+            layout::CelestialKind::Star => StarSystemObjectKind::Star,
+            layout::CelestialKind::Planet => StarSystemObjectKind::Planet,
+            layout::CelestialKind::Moon => StarSystemObjectKind::Moon,
+            layout::CelestialKind::AsteroidBelt => StarSystemObjectKind::AsteroidBelt,
+            layout::CelestialKind::NebulaBelt => StarSystemObjectKind::NebulaBelt,
+        }
+    }
+}
+
+impl From<layout::StationSize> for StationSize {
+    fn from(size: layout::StationSize) -> Self {
+        match size {
+            layout::StationSize::Capital => StationSize::Capital,
+            layout::StationSize::Large => StationSize::Large,
+            layout::StationSize::Medium => StationSize::Medium,
+            layout::StationSize::Small => StationSize::Small,
+            layout::StationSize::Outpost => StationSize::Outpost,
+            layout::StationSize::Satellite => StationSize::Satellite,
+        }
+    }
+}
@@ -3,15 +3,15 @@ use spacetimedsl::*;
 
 pub mod factions;
 pub mod galaxy;
+pub mod content;
 pub mod item_types;
+pub mod recipes;
 pub mod ship_types;
 pub mod station_module_types;
 
 pub fn init(dsl: &DSL<'_, ReducerContext>) -> Result<(), String> {
     factions::init(dsl)?;
-    station_module_types::init(dsl)?;
-    item_types::init(dsl)?;
-    ship_types::init(dsl)?;
+    content::apply(dsl, &content::DefinitionSet::embedded()?)?;
 
     // Init galaxy only AFTER all other definitions (it seeds asteroids, which need ctx.rng()).
     galaxy::init(dsl)?;
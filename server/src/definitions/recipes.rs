@@ -0,0 +1,4 @@
+//! Stable ids for the production recipes in `content/recipes.ron`.
+
+/// Iron ingots and energy cells into metal plates.
+pub const RECIPE_METAL_PLATES: u32 = 1;
@@ -8,3 +8,6 @@ pub mod tables;
 pub mod utility;
 
 pub mod lifecycle;
+
+#[cfg(test)]
+mod test_support;
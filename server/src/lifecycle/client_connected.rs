@@ -1,14 +1,27 @@
 use spacetimedb::*;
 use spacetimedsl::*;
 
-use crate::logic::players::welcome_back::send_welcome_back_message;
-use crate::tables::{global_config::*, players::*};
+use crate::logic::{moderation::active_sanction, players::welcome_back::send_welcome_back_message};
+use crate::tables::{global_config::*, moderation::SanctionKind, players::*};
 
 #[spacetimedb::reducer(client_connected)]
 pub fn identity_connected(ctx: &ReducerContext) -> Result<(), String> {
     let dsl = dsl(ctx);
     // Called everytime a new client connects
 
+    // Returning an error refuses the connection.
+    if let Some(ban) = active_sanction(&dsl, ctx.sender(), SanctionKind::Ban) {
+        log::info!(
+            "Refused connection from banned identity {}",
+            ctx.sender().to_abbreviated_hex()
+        );
+        return Err(format!(
+            "You are banned {}: {}",
+            ban.describe_until(),
+            ban.get_reason()
+        ));
+    }
+
     // TODO: When someone logs in set their player to online
 
     if let Ok(mut player) = dsl.get_player_by_id(PlayerId::new(ctx.sender())) {
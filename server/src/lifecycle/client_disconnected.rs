@@ -1,16 +1,26 @@
 use spacetimedb::*;
 use spacetimedsl::*;
 
-use crate::tables::{global_config::*, players::*};
+use crate::{
+    logic::{
+        chat_history::clear_chat_history_for, players::welcome_back::record_welcome_back_snapshot,
+    },
+    tables::{global_config::*, players::*},
+};
 
 #[spacetimedb::reducer(client_disconnected)]
 pub fn identity_disconnected(ctx: &ReducerContext) -> Result<(), String> {
     let dsl = dsl(ctx);
     // Called everytime a client disconnects
 
-    if let Ok(_player) = dsl.get_player_by_id(PlayerId::new(ctx.sender())) {
+    if let Ok(player) = dsl.get_player_by_id(PlayerId::new(ctx.sender())) {
         // Remove unneccessary timers and etc.
+
+        // What the player saw as they left; the next welcome-back diffs
+        // against it.
+        record_welcome_back_snapshot(&dsl, &player.get_id())?;
     }
+    clear_chat_history_for(&dsl, ctx.sender())?;
 
     if let Some(mut config) = dsl.get_all_global_configurations().next() {
         if *config.get_active_players() > 0 {
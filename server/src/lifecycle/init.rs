@@ -1,7 +1,10 @@
 use spacetimedb::*;
 use spacetimedsl::*;
 
-use crate::{definitions, tables::global_config::*};
+use crate::{
+    admin::roles::seed_owner_role, definitions, logic::moderation::seed_chat_filter_config,
+    tables::global_config::*,
+};
 
 use super::timers;
 
@@ -11,6 +14,8 @@ pub fn init(ctx: &ReducerContext) -> Result<(), String> {
 
     definitions::init(&dsl)?;
     timers::initialize(&dsl)?;
+    seed_owner_role(&dsl, ctx.sender())?;
+    seed_chat_filter_config(&dsl)?;
 
     // Create a Global Config row, or reinitalize the one if it exists.
     if dsl.get_all_global_configurations().count() == 0 {
@@ -17,7 +17,11 @@ use log::info;
 use spacetimedsl::*;
 
 use crate::{
-    logic::{cargo_crates::*, factions::*, sectors::*},
+    logic::{
+        cargo_crates::*, chat_history::create_channel_message_archive_timer, factions::*,
+        moderation::create_player_sanction_expiry_timer, sectors::*,
+        stations::leaderboards::create_contribution_tally_timer,
+    },
     tables::factions::*,
 };
 
@@ -35,12 +39,28 @@ pub fn initialize<T: spacetimedsl::WriteContext>(dsl: &DSL<T>) -> Result<(), Str
     // (update_combat_cooldowns) was removed as the top CPU consumer and isn't
     // needed for the MVP — combat will be reworked later (#167).
 
+    // Collision sweeps have no global timer: each sector's 5 Hz sweep is
+    // started by the first moving snapshot written there and stops itself
+    // once everything there is at rest (`ensure_sector_collision_timer`).
+
+    // Autopilot steering has no timer here either: `set_autopilot_target`
+    // starts the 5 Hz tick and it stops itself once no ship is engaged.
+
     // Cargo crate despawn sweeper (every 30 minutes). Replaces the per-crate
     // despawn check that used to ride on the 20 Hz transform tick.
     dsl.create_cargo_crate_despawn_sweeper_timer(CreateCargoCrateDespawnSweeperTimer {
         scheduled_at: spacetimedb::ScheduleAt::Interval(Duration::from_secs(30 * 60).into()),
     })?;
 
+    // Expired mutes and bans (every minute), so views stop counting them.
+    create_player_sanction_expiry_timer(dsl)?;
+
+    // Channel messages past retention move to the archive (every hour).
+    create_channel_message_archive_timer(dsl)?;
+
+    // Weekly contribution leaderboard rollover (every hour).
+    create_contribution_tally_timer(dsl)?;
+
     Ok(())
 }
 
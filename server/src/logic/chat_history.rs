@@ -0,0 +1,286 @@
+//! # Chat history
+//!
+//! The live channel tables only hold the last `CHAT_RETENTION_DAYS` of
+//! messages. `channel_message_archive_sweeper` moves anything older into
+//! `ArchivedChannelMessage` every hour, and players page back through the
+//! archive with `request_chat_history`, which copies one page at a time into
+//! their `ChatHistoryMessage` rows (`my_chat_history`).
+//!
+//! A player can only page through a channel they can currently read, scoped
+//! exactly like the live views: their Faction, and the Sector / StarSystem
+//! their ship is in.
+
+use std::{collections::HashSet, time::Duration};
+
+use log::info;
+use spacetimedb::{Identity, ReducerContext, Timestamp};
+use spacetimedsl::*;
+
+use crate::{
+    logic::moderation::{active_sanction, refuse_banned},
+    tables::{messages::*, moderation::SanctionKind, players::*, sectors::*},
+    utility::try_server_only,
+};
+
+/// How long messages stay in the live channel tables.
+pub const CHAT_RETENTION_DAYS: u64 = 7;
+
+/// How many archived messages one `request_chat_history` call loads.
+pub const CHAT_HISTORY_PAGE_SIZE: usize = 50;
+
+///////////////////////////////////////////////////////////
+// Reducers
+///////////////////////////////////////////////////////////
+
+/// Loads the next page of archived `channel` messages older than
+/// `before_id` (a live-table id; `None` starts from the newest archived
+/// message) into the caller's `my_chat_history`.
+#[spacetimedb::reducer]
+pub fn request_chat_history(
+    ctx: &ReducerContext,
+    channel: ChatChannel,
+    before_id: Option<u64>,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    let owner = ctx.sender();
+    refuse_banned(&dsl, owner)?;
+    let scope_id = readable_scope(&dsl, owner, channel)?;
+
+    // Pages from a channel the caller has since left (another Sector, say)
+    // would only confuse the tab, so drop them.
+    for stale in dsl
+        .get_chat_history_messages_by_owner(&owner)
+        .filter(|m| *m.get_channel() == channel && *m.get_scope_id() != scope_id)
+    {
+        dsl.delete_chat_history_message_by_id(&stale)?;
+    }
+
+    // Asking for the same page twice (a double click, a reconnect) must not
+    // show its messages twice.
+    let held: HashSet<u64> = dsl
+        .get_chat_history_messages_by_owner(&owner)
+        .filter(|m| *m.get_channel() == channel)
+        .map(|m| *m.get_original_id())
+        .collect();
+
+    let mut page: Vec<ArchivedChannelMessage> = dsl
+        .get_archived_channel_messages_by_scope_id(&scope_id)
+        .filter(|m| *m.get_channel() == channel)
+        .filter(|m| before_id.is_none_or(|before| *m.get_original_id() < before))
+        .filter(|m| !held.contains(m.get_original_id()))
+        .collect();
+    page.sort_by_key(|m| std::cmp::Reverse(*m.get_original_id()));
+    page.truncate(CHAT_HISTORY_PAGE_SIZE);
+
+    for archived in &page {
+        dsl.create_chat_history_message(CreateChatHistoryMessage {
+            owner,
+            channel,
+            scope_id,
+            original_id: *archived.get_original_id(),
+            sender: archived.get_sender().clone(),
+            body: archived.get_body().clone(),
+            sent_at: *archived.get_sent_at(),
+        })?;
+    }
+    Ok(())
+}
+
+/// Drops every history page the caller has loaded.
+#[spacetimedb::reducer]
+pub fn clear_chat_history(ctx: &ReducerContext) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    refuse_banned(&dsl, ctx.sender())?;
+    clear_chat_history_for(&dsl, ctx.sender())
+}
+
+///////////////////////////////////////////////////////////
+// Timers
+///////////////////////////////////////////////////////////
+
+#[dsl(plural_name = channel_message_archive_timers, method(update = false))]
+#[spacetimedb::table(
+    accessor = channel_message_archive_timer,
+    scheduled(channel_message_archive_sweeper)
+)]
+pub struct ChannelMessageArchiveTimer {
+    #[primary_key]
+    #[auto_inc]
+    #[create_wrapper]
+    id: u64,
+    scheduled_at: spacetimedb::ScheduleAt,
+}
+
+pub fn create_channel_message_archive_timer<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+) -> Result<(), String> {
+    dsl.create_channel_message_archive_timer(CreateChannelMessageArchiveTimer {
+        scheduled_at: spacetimedb::ScheduleAt::Interval(Duration::from_hours(1).into()),
+    })?;
+    Ok(())
+}
+
+/// Moves every channel message older than `CHAT_RETENTION_DAYS` into
+/// `ArchivedChannelMessage`.
+#[spacetimedb::reducer]
+pub fn channel_message_archive_sweeper(
+    ctx: &ReducerContext,
+    _timer: ChannelMessageArchiveTimer,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    try_server_only(&dsl)?;
+
+    let cutoff = ctx.timestamp - Duration::from_secs(CHAT_RETENTION_DAYS * 24 * 60 * 60);
+    let archived = archive_channel_messages_before(&dsl, cutoff)?;
+    if archived > 0 {
+        info!(
+            "channel_message_archive_sweeper: archived {} messages",
+            archived
+        );
+    }
+    Ok(())
+}
+
+///////////////////////////////////////////////////////////
+// Utilities
+///////////////////////////////////////////////////////////
+
+/// Moves every live channel message posted before `cutoff` into
+/// `ArchivedChannelMessage`. Returns how many moved.
+pub fn archive_channel_messages_before<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    cutoff: Timestamp,
+) -> Result<usize, String> {
+    let mut archived = 0;
+
+    for message in dsl
+        .get_all_galaxy_channel_messages()
+        .filter(|m| *m.get_created_at() < cutoff)
+    {
+        archive(
+            dsl,
+            ChatChannel::Galaxy,
+            *message.get_galaxy_id() as u64,
+            message.get_id().value(),
+            message.get_sender(),
+            message.get_body(),
+            *message.get_created_at(),
+        )?;
+        dsl.delete_galaxy_channel_message_by_id(&message)?;
+        archived += 1;
+    }
+    for message in dsl
+        .get_all_star_system_channel_messages()
+        .filter(|m| *m.get_created_at() < cutoff)
+    {
+        archive(
+            dsl,
+            ChatChannel::StarSystem,
+            message.get_system_id().value() as u64,
+            message.get_id().value(),
+            message.get_sender(),
+            message.get_body(),
+            *message.get_created_at(),
+        )?;
+        dsl.delete_star_system_channel_message_by_id(&message)?;
+        archived += 1;
+    }
+    for message in dsl
+        .get_all_sector_channel_messages()
+        .filter(|m| *m.get_created_at() < cutoff)
+    {
+        archive(
+            dsl,
+            ChatChannel::Sector,
+            message.get_sector_id().value(),
+            message.get_id().value(),
+            message.get_sender(),
+            message.get_body(),
+            *message.get_created_at(),
+        )?;
+        dsl.delete_sector_channel_message_by_id(&message)?;
+        archived += 1;
+    }
+    for message in dsl
+        .get_all_faction_channel_messages()
+        .filter(|m| *m.get_created_at() < cutoff)
+    {
+        archive(
+            dsl,
+            ChatChannel::Faction,
+            message.get_faction_id().value() as u64,
+            message.get_id().value(),
+            message.get_sender(),
+            message.get_body(),
+            *message.get_created_at(),
+        )?;
+        dsl.delete_faction_channel_message_by_id(&message)?;
+        archived += 1;
+    }
+
+    Ok(archived)
+}
+
+/// Drops `owner`'s loaded history pages. Called from `identity_disconnected`
+/// so pages never outlive the session that asked for them.
+pub fn clear_chat_history_for<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    owner: Identity,
+) -> Result<(), String> {
+    for message in dsl.get_chat_history_messages_by_owner(&owner) {
+        dsl.delete_chat_history_message_by_id(&message)?;
+    }
+    Ok(())
+}
+
+/// The scope id of the `channel` the caller can read right now, mirroring the
+/// live channel views.
+fn readable_scope<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    caller: Identity,
+    channel: ChatChannel,
+) -> Result<u64, String> {
+    let player_id = PlayerId::new(caller);
+    let player = dsl
+        .get_player_by_id(&player_id)
+        .map_err(|_| "You must be registered to read chat history.".to_string())?;
+
+    match channel {
+        ChatChannel::Galaxy => {
+            if active_sanction(dsl, caller, SanctionKind::Ban).is_some() {
+                return Err("You can't read the Galaxy channel while banned.".to_string());
+            }
+            Ok(0)
+        }
+        ChatChannel::StarSystem | ChatChannel::Sector => {
+            let (ship, _) = get_player_ship_and_sobj(dsl, &player_id)
+                .map_err(|_| "You need a piloted ship to read that channel.".to_string())?;
+            if channel == ChatChannel::Sector {
+                return Ok(ship.get_sector_id().value());
+            }
+            let sector = dsl.get_sector_by_id(&ship.get_sector_id())?;
+            Ok(sector.get_system_id().value() as u64)
+        }
+        ChatChannel::Faction => Ok(player.get_faction_id().value() as u64),
+    }
+}
+
+fn archive<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    channel: ChatChannel,
+    scope_id: u64,
+    original_id: u64,
+    sender: &MessageSender,
+    body: &str,
+    sent_at: Timestamp,
+) -> Result<(), String> {
+    dsl.create_archived_channel_message(CreateArchivedChannelMessage {
+        scope_id,
+        channel,
+        original_id,
+        sender: sender.clone(),
+        body: body.to_string(),
+        sent_at,
+    })?;
+    Ok(())
+}
@@ -3,19 +3,26 @@
 //! One reducer per channel a player can post into. Each derives the audience
 //! key (sector / system / faction) from server-side state — the client never
 //! tells us "I'm in sector N", because the server already knows.
+//!
+//! Player→player Direct Messages and the block/mute list reducers live at the
+//! bottom. Recipients are addressed by `Player.username`, the only handle
+//! players ever see of each other.
+//!
+//! Every message goes through `moderate_chat_message` (sanctions, rate limit,
+//! word filter) before it's posted.
 
 use log::info;
 use spacetimedb::ReducerContext;
 use spacetimedsl::*;
 
+use crate::logic::moderation::{moderate_chat_message, refuse_banned};
 use crate::tables::{
     messages::{
-        post_faction_channel, post_galaxy_channel, post_sector_channel,
-        post_star_system_channel, MessageSender,
+        has_chat_block, post_faction_channel, post_galaxy_channel, post_sector_channel,
+        post_star_system_channel, send_direct_message_row, ChatBlockKind, MessageSender, *,
     },
     players::*,
     sectors::{SectorId, *},
-    ships::*,
 };
 
 /// Send a message to the **Galaxy** channel — visible to every logged-in player.
@@ -26,6 +33,7 @@ pub fn send_galaxy_chat(ctx: &ReducerContext, message: String) -> Result<(), Str
 
     // Resolve the player row both to enforce "logged-in to post" and to log a username.
     let username = get_username(&dsl, sender);
+    let message = moderate_chat_message(&dsl, sender, message)?;
 
     info!("GalaxyChat [{}]: {}", username, message);
 
@@ -34,7 +42,7 @@ pub fn send_galaxy_chat(ctx: &ReducerContext, message: String) -> Result<(), Str
 
 /// Send a message to the caller's current **StarSystem** channel.
 ///
-/// Derived: the system the player's ship is currently in. No ship → reject.
+/// Derived: the system the player's Piloted Ship is in. Docked → reject.
 #[spacetimedb::reducer]
 pub fn send_star_system_chat(ctx: &ReducerContext, message: String) -> Result<(), String> {
     let dsl = dsl(ctx);
@@ -42,13 +50,16 @@ pub fn send_star_system_chat(ctx: &ReducerContext, message: String) -> Result<()
     let player_id = PlayerId::new(sender);
     let username = get_username(&dsl, sender);
 
-    let ship = dsl
-        .get_ships_by_player_id(&player_id)
-        .next()
-        .ok_or_else(|| format!("Player {} has no ship — cannot post to star system chat", username))?;
+    let (ship, _) = get_player_ship_and_sobj(&dsl, &player_id).map_err(|_| {
+        format!(
+            "Player {} has no piloted ship — cannot post to star system chat",
+            username
+        )
+    })?;
     let sector = dsl.get_sector_by_id(&ship.get_sector_id().clone())?;
     // `sector.get_system_id()` already returns the typed wrapper.
     let system_id = sector.get_system_id().clone();
+    let message = moderate_chat_message(&dsl, sender, message)?;
 
     info!(
         "StarSystemChat #{} [{}]: {}",
@@ -62,7 +73,7 @@ pub fn send_star_system_chat(ctx: &ReducerContext, message: String) -> Result<()
 
 /// Send a message to the caller's current **Sector** channel.
 ///
-/// Derived: the sector the player's ship is currently in. No ship → reject.
+/// Derived: the sector the player's Piloted Ship is in. Docked → reject.
 #[spacetimedb::reducer]
 pub fn send_sector_chat(ctx: &ReducerContext, message: String) -> Result<(), String> {
     let dsl = dsl(ctx);
@@ -70,11 +81,14 @@ pub fn send_sector_chat(ctx: &ReducerContext, message: String) -> Result<(), Str
     let player_id = PlayerId::new(sender);
     let username = get_username(&dsl, sender);
 
-    let ship = dsl
-        .get_ships_by_player_id(&player_id)
-        .next()
-        .ok_or_else(|| format!("Player {} has no ship — cannot post to sector chat", username))?;
+    let (ship, _) = get_player_ship_and_sobj(&dsl, &player_id).map_err(|_| {
+        format!(
+            "Player {} has no piloted ship — cannot post to sector chat",
+            username
+        )
+    })?;
     let sector_id = SectorId::new(ship.get_sector_id().value());
+    let message = moderate_chat_message(&dsl, sender, message)?;
 
     info!(
         "SectorChat #{} [{}]: {}",
@@ -100,6 +114,7 @@ pub fn send_faction_chat(ctx: &ReducerContext, message: String) -> Result<(), St
         .get_player_by_id(PlayerId::new(sender))
         .map_err(|_| format!("Player {} is not registered — cannot post to faction chat", username))?;
     let faction_id = player.get_faction_id().clone();
+    let message = moderate_chat_message(&dsl, sender, message)?;
 
     info!(
         "FactionChat #{} [{}]: {}",
@@ -110,3 +125,139 @@ pub fn send_faction_chat(ctx: &ReducerContext, message: String) -> Result<(), St
 
     post_faction_channel(&dsl, faction_id, MessageSender::Player(sender), message)
 }
+
+/// Send a **Direct Message** to another player, addressed by username.
+///
+/// Rejected when the recipient doesn't exist, is the sender, or has blocked
+/// the sender. Mutes are not checked here — a muted message is still
+/// delivered, it's just filtered out of the recipient's view.
+#[spacetimedb::reducer]
+pub fn send_direct_message(
+    ctx: &ReducerContext,
+    to_username: String,
+    message: String,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    let sender = dsl
+        .get_player_by_id(PlayerId::new(ctx.sender()))
+        .map_err(|_| "You must be registered to send direct messages".to_string())?;
+
+    if message.trim().is_empty() {
+        return Err("Cannot send an empty direct message".to_string());
+    }
+
+    let recipient = find_player_by_username(&dsl, &to_username)?;
+    if recipient.get_id() == sender.get_id() {
+        return Err("You can't send a direct message to yourself".to_string());
+    }
+    if has_chat_block(
+        &dsl,
+        &recipient.get_id(),
+        &sender.get_id().value(),
+        ChatBlockKind::Block,
+    ) {
+        return Err(format!(
+            "{} is not accepting direct messages from you",
+            recipient.get_username()
+        ));
+    }
+    let message = moderate_chat_message(&dsl, ctx.sender(), message)?;
+
+    // Unlike channel chat, a DM is private: log who and how long, not what.
+    info!(
+        "DirectMessage [{} -> {}]: {} chars",
+        sender.get_username(),
+        recipient.get_username(),
+        message.chars().count()
+    );
+
+    send_direct_message_row(&dsl, &sender.get_id(), &recipient.get_id(), message)
+}
+
+/// Stop `username` from sending you Direct Messages.
+#[spacetimedb::reducer]
+pub fn block_player(ctx: &ReducerContext, username: String) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    let sender = ctx.sender();
+    refuse_banned(&dsl, sender)?;
+    add_chat_block(&dsl, PlayerId::new(sender), username, ChatBlockKind::Block)
+}
+
+/// Lift a block placed with `block_player`.
+#[spacetimedb::reducer]
+pub fn unblock_player(ctx: &ReducerContext, username: String) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    let sender = ctx.sender();
+    refuse_banned(&dsl, sender)?;
+    remove_chat_block(&dsl, PlayerId::new(sender), username, ChatBlockKind::Block)
+}
+
+/// Hide `username`'s channel messages and Direct Messages from your views.
+#[spacetimedb::reducer]
+pub fn mute_player(ctx: &ReducerContext, username: String) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    let sender = ctx.sender();
+    refuse_banned(&dsl, sender)?;
+    add_chat_block(&dsl, PlayerId::new(sender), username, ChatBlockKind::Mute)
+}
+
+/// Lift a mute placed with `mute_player`.
+#[spacetimedb::reducer]
+pub fn unmute_player(ctx: &ReducerContext, username: String) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    let sender = ctx.sender();
+    refuse_banned(&dsl, sender)?;
+    remove_chat_block(&dsl, PlayerId::new(sender), username, ChatBlockKind::Mute)
+}
+
+//////////////////////////////////////////////////////////////
+// Utility
+//////////////////////////////////////////////////////////////
+
+fn find_player_by_username<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    username: &str,
+) -> Result<Player, String> {
+    dsl.get_player_by_username(username.trim())
+        .map_err(|_| format!("No player named '{}'", username.trim()))
+}
+
+fn add_chat_block<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    owner: PlayerId,
+    username: String,
+    kind: ChatBlockKind,
+) -> Result<(), String> {
+    let target = find_player_by_username(dsl, &username)?;
+    if target.get_id() == owner {
+        return Err("You can't block or mute yourself".to_string());
+    }
+    if has_chat_block(dsl, &owner, &target.get_id().value(), kind.clone()) {
+        return Ok(()); // Already on the list — idempotent.
+    }
+
+    dsl.create_player_chat_block(CreatePlayerChatBlock {
+        owner,
+        target: target.get_id().value(),
+        kind,
+    })?;
+    Ok(())
+}
+
+fn remove_chat_block<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    owner: PlayerId,
+    username: String,
+    kind: ChatBlockKind,
+) -> Result<(), String> {
+    let target = find_player_by_username(dsl, &username)?;
+
+    let entries: Vec<PlayerChatBlock> = dsl
+        .get_player_chat_blocks_by_owner(&owner)
+        .filter(|b| *b.get_target() == target.get_id().value() && *b.get_kind() == kind)
+        .collect();
+    for entry in entries {
+        dsl.delete_player_chat_block_by_id(&entry)?;
+    }
+    Ok(())
+}
@@ -117,7 +117,8 @@ pub fn validate_combat_action<T: spacetimedsl::WriteContext>(
     }
 
     // Basic energy check (detailed energy check happens in weapon/missile processing)
-    if *ship_status.get_energy() <= 0.0 {
+    let now = dsl.ctx().timestamp()?.to_micros_since_unix_epoch();
+    if ship_status.current_energy(now) <= 0.0 {
         return Err(CombatError::InsufficientEnergy);
     }
 
@@ -137,6 +138,7 @@ pub fn has_sufficient_energy_for_action(
     ship_status: &ShipStatus,
     required_energy: f32,
     is_missile: bool,
+    current_time: i64,
 ) -> Result<(), CombatError> {
     // Check cooldowns first
     if is_missile {
@@ -150,7 +152,7 @@ pub fn has_sufficient_energy_for_action(
     }
 
     // Check energy
-    if *ship_status.get_energy() < required_energy {
+    if ship_status.current_energy(current_time) < required_energy {
         return Err(CombatError::InsufficientEnergy);
     }
 
@@ -433,14 +435,12 @@ pub fn process_weapon_fire<T: spacetimedsl::WriteContext>(
         return Err(CombatError::InsufficientEnergy); // Cooldown is treated as energy-related constraint
     }
 
-    // Check if ship has sufficient energy
-    if *source_ship_status.get_energy() < damage_calc.energy_cost {
+    // Check if ship has sufficient energy, and consume it
+    let now = dsl.ctx().timestamp()?.to_micros_since_unix_epoch();
+    if !source_ship_status.try_spend_energy(damage_calc.energy_cost, now) {
         return Err(CombatError::InsufficientEnergy);
     }
 
-    // Consume energy
-    source_ship_status.set_energy(*source_ship_status.get_energy() - damage_calc.energy_cost);
-
     // Set weapon cooldown (extract from weapon metadata or use default)
     let mut weapon_cooldown_ms = 1000; // Default 1 second cooldown
     for metadata in weapon_item_def.get_metadata() {
@@ -538,14 +538,12 @@ pub fn process_missile_fire<T: spacetimedsl::WriteContext>(
         }
     }
 
-    // Check if ship has sufficient energy
-    if *source_ship_status.get_energy() < energy_cost {
+    // Check if ship has sufficient energy, and consume it
+    let now = dsl.ctx().timestamp()?.to_micros_since_unix_epoch();
+    if !source_ship_status.try_spend_energy(energy_cost, now) {
         return Err(CombatError::InsufficientEnergy);
     }
 
-    // Consume energy
-    source_ship_status.set_energy(*source_ship_status.get_energy() - energy_cost);
-
     // Set missile cooldown (extract from missile metadata or use default)
     let mut missile_cooldown_ms = 2000; // Default 2 second cooldown for missiles
     for metadata in missile_item_def.get_metadata() {
@@ -592,7 +590,8 @@ pub fn apply_damage_to_ship<T: spacetimedsl::WriteContext>(
     target_ship_status: &mut ShipStatus,
     damage_calc: &DamageCalculation,
 ) -> Result<bool, CombatError> {
-    let current_shields = *target_ship_status.get_shields();
+    let now = dsl.ctx().timestamp()?.to_micros_since_unix_epoch();
+    let current_shields = target_ship_status.current_shields(now);
     let current_hull = *target_ship_status.get_health();
 
     // Calculate actual damage application
@@ -600,8 +599,10 @@ pub fn apply_damage_to_ship<T: spacetimedsl::WriteContext>(
         damage_calc.apply_to_target(current_shields, current_hull);
 
     // Apply shield damage
-    let new_shields = (current_shields - shield_damage_applied).max(0.0);
-    target_ship_status.set_shields(new_shields);
+    target_ship_status
+        .shields
+        .adjust(-shield_damage_applied, now);
+    let new_shields = target_ship_status.shields.value;
 
     // Apply hull damage
     let new_hull = (current_hull - hull_damage_applied).max(0.0);
@@ -1,9 +1,11 @@
 pub mod cargo_crates;
+pub mod chat_history;
 pub mod chat_messages;
 pub mod combat;
 pub mod factions;
 pub mod game_loop;
 pub mod logic_utilities;
+pub mod moderation;
 pub mod players;
 pub mod sectors;
 pub mod ships;
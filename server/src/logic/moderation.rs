@@ -0,0 +1,232 @@
+//! # Moderation
+//!
+//! Everything a player types into chat goes through `moderate_chat_message`
+//! first: Mutes and Bans, the rate limit and repeat check, then banned-word
+//! masking. Bans are also enforced on connect (`identity_connected`) and in
+//! the Galaxy channel view. Staff issue sanctions and tune the filter with
+//! the reducers in `admin/moderation.rs`.
+
+use std::time::Duration;
+
+use log::info;
+use spacetimedb::{Identity, ReducerContext, Timestamp};
+use spacetimedsl::*;
+
+use crate::{tables::moderation::*, utility::try_server_only};
+
+///////////////////////////////////////////////////////////
+// Timers
+///////////////////////////////////////////////////////////
+
+/// Deletes expired sanctions, so views (which can't see the clock) stop
+/// counting them.
+#[dsl(plural_name = player_sanction_expiry_timers, method(update = false))]
+#[spacetimedb::table(
+    accessor = player_sanction_expiry_timer,
+    scheduled(player_sanction_expiry_sweeper)
+)]
+pub struct PlayerSanctionExpiryTimer {
+    #[primary_key]
+    #[auto_inc]
+    #[create_wrapper]
+    id: u64,
+    scheduled_at: spacetimedb::ScheduleAt,
+}
+
+pub fn create_player_sanction_expiry_timer<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+) -> Result<(), String> {
+    dsl.create_player_sanction_expiry_timer(CreatePlayerSanctionExpiryTimer {
+        scheduled_at: spacetimedb::ScheduleAt::Interval(Duration::from_secs(60).into()),
+    })?;
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn player_sanction_expiry_sweeper(
+    ctx: &ReducerContext,
+    _timer: PlayerSanctionExpiryTimer,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    try_server_only(&dsl)?;
+
+    let expired: Vec<PlayerSanction> = dsl
+        .get_all_player_sanctions()
+        .filter(|sanction| !sanction.is_active(ctx.timestamp))
+        .collect();
+    for sanction in &expired {
+        dsl.delete_player_sanction_by_id(sanction)?;
+    }
+    if !expired.is_empty() {
+        info!(
+            "player_sanction_expiry_sweeper: removed {} expired sanctions",
+            expired.len()
+        );
+    }
+    Ok(())
+}
+
+///////////////////////////////////////////////////////////
+// Utilities
+///////////////////////////////////////////////////////////
+
+/// Creates the chat filter config row if it doesn't exist yet.
+pub fn seed_chat_filter_config<T: spacetimedsl::WriteContext>(dsl: &DSL<T>) -> Result<(), String> {
+    if dsl
+        .get_chat_filter_config_by_id(ChatFilterConfigId::new(0))
+        .is_ok()
+    {
+        return Ok(());
+    }
+    dsl.create_chat_filter_config(CreateChatFilterConfig {
+        id: 0,
+        banned_words: Vec::new(),
+        max_messages_per_window: 5,
+        rate_limit_window_secs: 10,
+        max_message_length: 500,
+        reject_repeats: true,
+    })?;
+    Ok(())
+}
+
+/// The first active sanction of `kind` on `target`, if any.
+pub fn active_sanction<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    target: Identity,
+    kind: SanctionKind,
+) -> Option<PlayerSanction> {
+    let now = dsl.ctx().timestamp().ok()?;
+    dsl.get_player_sanctions_by_target(&target)
+        .find(|sanction| *sanction.get_kind() == kind && sanction.is_active(now))
+}
+
+/// Refuses `sender` while they are banned. `identity_connected` turns away new
+/// connections; this stops a session that was open when the Ban was placed,
+/// for the reducers that act for the sender.
+pub fn refuse_banned<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    sender: Identity,
+) -> Result<(), String> {
+    match active_sanction(dsl, sender, SanctionKind::Ban) {
+        Some(ban) => {
+            info!(
+                "Refused a call from banned identity {}",
+                sender.to_abbreviated_hex()
+            );
+            Err(format!(
+                "You are banned {}: {}",
+                ban.describe_until(),
+                ban.get_reason()
+            ))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Runs a player's chat message through sanctions, the rate limit and the
+/// word filter. Returns the message to post, with banned words masked.
+pub fn moderate_chat_message<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    sender: Identity,
+    message: String,
+) -> Result<String, String> {
+    let now = dsl.ctx().timestamp()?;
+    for kind in [SanctionKind::Ban, SanctionKind::Mute] {
+        if let Some(sanction) = active_sanction(dsl, sender, kind) {
+            return Err(format!(
+                "You can't chat: {:?} {} ({}).",
+                sanction.get_kind(),
+                sanction.describe_until(),
+                sanction.get_reason()
+            ));
+        }
+    }
+
+    let config = dsl.get_chat_filter_config_by_id(ChatFilterConfigId::new(0))?;
+    if message.chars().count() > config.max_message_length as usize {
+        return Err(format!(
+            "Messages can be at most {} characters long.",
+            config.max_message_length
+        ));
+    }
+
+    let window = Duration::from_secs(config.rate_limit_window_secs as u64);
+    match dsl.get_chat_rate_limit_by_id(ChatRateLimitId::new(sender)) {
+        Ok(mut limit) => {
+            if now.duration_since(limit.window_start).unwrap_or_default() >= window {
+                limit.window_start = now;
+                limit.messages_in_window = 0;
+            } else if limit.messages_in_window >= config.max_messages_per_window {
+                return Err(format!(
+                    "You're sending messages too quickly. The limit is {} every {}s.",
+                    config.max_messages_per_window, config.rate_limit_window_secs
+                ));
+            } else if config.reject_repeats && limit.last_message == message {
+                return Err("You just sent that message.".to_string());
+            }
+            limit.messages_in_window += 1;
+            limit.last_message = message.clone();
+            dsl.update_chat_rate_limit_by_id(limit)?;
+        }
+        Err(_) => {
+            dsl.create_chat_rate_limit(CreateChatRateLimit {
+                id: sender,
+                window_start: now,
+                messages_in_window: 1,
+                last_message: message.clone(),
+            })?;
+        }
+    }
+
+    Ok(mask_banned_words(&message, &config.banned_words))
+}
+
+/// Replaces every whole-word, case-insensitive match of a banned word with
+/// asterisks of the same length.
+pub fn mask_banned_words(message: &str, banned_words: &[String]) -> String {
+    if banned_words.is_empty() {
+        return message.to_string();
+    }
+    let banned: Vec<String> = banned_words.iter().map(|w| w.to_lowercase()).collect();
+
+    let mut masked = String::with_capacity(message.len());
+    let mut word = String::new();
+    let flush = |word: &mut String, masked: &mut String| {
+        if banned.contains(&word.to_lowercase()) {
+            masked.extend(std::iter::repeat_n('*', word.chars().count()));
+        } else {
+            masked.push_str(word);
+        }
+        word.clear();
+    };
+    for c in message.chars() {
+        if c.is_alphanumeric() || c == '\'' {
+            word.push(c);
+        } else {
+            flush(&mut word, &mut masked);
+            masked.push(c);
+        }
+    }
+    flush(&mut word, &mut masked);
+    masked
+}
+
+/// `now + secs`, or `None` for an indefinite sanction.
+pub fn sanction_until(now: Timestamp, duration_secs: Option<u64>) -> Option<Timestamp> {
+    duration_secs.map(|secs| now + Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_whole_words_only() {
+        let banned = vec!["Frak".to_string()];
+        assert_eq!(
+            mask_banned_words("frak this, FRAK! Frakking ok", &banned),
+            "**** this, ****! Frakking ok"
+        );
+        assert_eq!(mask_banned_words("clean", &[]), "clean");
+    }
+}
@@ -2,6 +2,7 @@ use spacetimedb::{Identity, ReducerContext, log};
 use spacetimedsl::*;
 
 use crate::definitions::factions::FACTION_FACTIONLESS;
+use crate::logic::moderation::refuse_banned;
 use crate::tables::{
     factions::*,
     messages::{post_faction_channel, MessageSender},
@@ -24,6 +25,7 @@ pub fn register_playername(
     faction_id: u32,
 ) -> Result<(), String> {
     let dsl = dsl(ctx);
+    refuse_banned(&dsl, ctx.sender())?;
 
     // TODO: Check if the identity already has a player!!!!
 
@@ -9,18 +9,35 @@
 //! gated by cost analysis.
 //!
 //! ## What it reports (per the MVP design doc, #92)
+//! - How long the player was away, in wall-clock time and economy ticks.
+//! - What changed since they last looked, diffed by `OfflineTickCalculator`
+//!   against their `WelcomeBackSnapshot`: construction sites that progressed
+//!   or came online (with the player's share of each), stations whose module
+//!   counts changed, and sectors that changed hands.
+//! - The top contributors to construction since the player's `last_login`.
 //! - Each construction site still under construction: name + progress %, with
 //!   the player's own-faction sites flagged (M3 soft-default — anyone may
 //!   contribute to any site; the flag is purely informational).
-//! - The number of contribution events logged since the player's `last_login`.
 //! - The player's current cargo, aggregated across all their ships.
+//! - What the player has stored in station hangars, station by station.
+//!
+//! The same content is stored as a structured `WelcomeBackPayload` for the
+//! client's welcome-back panel, and as text in the Direct Server Message for
+//! the Inbox.
+//!
+//! ## Snapshots
+//! `identity_disconnected` records what the world looked like when the
+//! player left ([`record_welcome_back_snapshot`]); the next connect diffs the
+//! world against it. The connect records a fresh snapshot too, so a session
+//! that never sees its disconnect still leaves a baseline behind.
 //!
 //! ## Deliberately omitted
 //! The design doc also names "trades since last login, credits earned, ships
-//! visited" as welcome-back content. Markets, credit-generating activity, and
-//! ship traffic are all out of MVP scope, so these would only ever read zero.
-//! We omit them rather than print honest-but-useless zeros — see #92 for the
-//! decision. Re-add them here when the systems that move those numbers ship.
+//! visited" as welcome-back content. Ship traffic is out of MVP scope, so
+//! "ships visited" would only ever read zero; we omit it rather than print an
+//! honest-but-useless zero — see #92 for the decision. Market orders do trade
+//! and earn credits while a player is away, but each fill already sends its
+//! own Direct Server Message, so the summary doesn't repeat them.
 //!
 //! ## How the client identifies *the* welcome-back DM
 //! Post-#101, `DirectServerMessage` carries no discriminator. The client picks
@@ -28,15 +45,111 @@
 //! `last_login` and treats it as the welcome-back. That's the message this
 //! function emits exactly once per connect.
 
+use std::time::Duration;
+
 use spacetimedb::Timestamp;
 use spacetimedsl::*;
 
 // Glob-import the table modules whose generated DSL extension traits we call —
 // the per-table `Get*` traits must be in scope, not just the row/ID types.
-use crate::tables::{
-    items::*, messages::send_direct_server_info, players::Player, sectors::GetSectorRowOptionById, ships::*, stations::*
+use crate::{
+    logic::stations::STATION_PRODUCTION_INTERVAL_SECS,
+    tables::{
+        factions::*, items::*, messages::send_direct_server_info, players::*, sectors::*, ships::*,
+        stations::*, welcome_back::*,
+    },
 };
 
+/// How many names the "top contributors" list shows.
+const TOP_CONTRIBUTORS: usize = 3;
+
+/// Smallest progress change worth reporting, in percentage points.
+const MIN_PROGRESS_CHANGE: f32 = 0.5;
+
+/// Diffs what a player last saw (a `WelcomeBackSnapshot`) against the world
+/// now, and counts the economy ticks that ran in between. Pure — the DSL
+/// reads and name lookups live in the composer below.
+pub struct OfflineTickCalculator {
+    pub since: Timestamp,
+    pub now: Timestamp,
+}
+
+impl OfflineTickCalculator {
+    pub fn away_secs(&self) -> u64 {
+        self.now
+            .duration_since(self.since)
+            .unwrap_or_default()
+            .as_secs()
+    }
+
+    /// Station production ticks the galaxy ran while the player was away.
+    pub fn offline_ticks(&self) -> u64 {
+        self.away_secs() / STATION_PRODUCTION_INTERVAL_SECS
+    }
+
+    /// Sites that are new, moved at least `MIN_PROGRESS_CHANGE` points, or
+    /// came online, as `(before, now)`.
+    pub fn site_changes<'a>(
+        before: &'a [SiteSnapshot],
+        now: &'a [SiteSnapshot],
+    ) -> Vec<(Option<&'a SiteSnapshot>, &'a SiteSnapshot)> {
+        now.iter()
+            .filter_map(|site| {
+                let old = before.iter().find(|b| b.station_id == site.station_id);
+                let changed = match old {
+                    Some(old) => {
+                        (site.progress_percentage - old.progress_percentage).abs()
+                            >= MIN_PROGRESS_CHANGE
+                            || site.is_operational != old.is_operational
+                    }
+                    None => true,
+                };
+                changed.then_some((old, site))
+            })
+            .collect()
+    }
+
+    /// Stations whose module or operational-module count changed, as
+    /// `(before, now)`. A station that didn't exist before counts from zero.
+    pub fn station_changes(
+        before: &[StationModulesSnapshot],
+        now: &[StationModulesSnapshot],
+    ) -> Vec<(StationModulesSnapshot, StationModulesSnapshot)> {
+        now.iter()
+            .filter_map(|station| {
+                let old = before
+                    .iter()
+                    .find(|b| b.station_id == station.station_id)
+                    .cloned()
+                    .unwrap_or(StationModulesSnapshot {
+                        station_id: station.station_id,
+                        module_count: 0,
+                        operational_modules: 0,
+                    });
+                (old != *station).then(|| (old, station.clone()))
+            })
+            .collect()
+    }
+
+    /// Sectors whose controlling faction changed, as
+    /// `(sector_id, from_faction_id, to_faction_id)`.
+    pub fn control_changes(
+        before: &[SectorControlSnapshot],
+        now: &[SectorControlSnapshot],
+    ) -> Vec<(u64, u32, u32)> {
+        now.iter()
+            .filter_map(|sector| {
+                let old = before.iter().find(|b| b.sector_id == sector.sector_id)?;
+                (old.controlling_faction_id != sector.controlling_faction_id).then_some((
+                    sector.sector_id,
+                    old.controlling_faction_id,
+                    sector.controlling_faction_id,
+                ))
+            })
+            .collect()
+    }
+}
+
 /// Compose and deliver the welcome-back `DirectServerMessage` for `player`.
 ///
 /// `player` must be read with the *pre-connect* `last_login` still intact —
@@ -53,19 +166,364 @@ pub fn send_welcome_back_message<T: spacetimedsl::WriteContext>(
     player: &Player,
 ) -> Result<(), String> {
     let since = player.last_login;
+    let now = dsl.ctx().timestamp()?;
+
+    let mut asset_lines = vec![compose_cargo_summary(dsl, player)];
+    if let Some(hangars) = compose_hangar_summary(dsl, player) {
+        asset_lines.push(hangars);
+    }
+
+    let mut payload = WelcomeBackContent {
+        since,
+        asset_lines,
+        ..Default::default()
+    };
+    let snapshot = dsl.get_welcome_back_snapshot_by_id(player.get_id()).ok();
+    if let Some(since) = since {
+        let calculator = OfflineTickCalculator { since, now };
+        payload.away_secs = calculator.away_secs();
+        payload.offline_ticks = calculator.offline_ticks();
+        payload.top_contributors = compose_top_contributors(dsl, player, since);
+        if let Some(snapshot) = &snapshot {
+            compose_world_changes(dsl, player, snapshot, &mut payload);
+        }
+    }
 
     let mut lines: Vec<String> = Vec::new();
     lines.push(format!("Welcome back, {}.", player.username));
-
+    lines.extend(describe_changes(&payload));
     lines.push(compose_construction_summary(dsl, player));
+    lines.extend(payload.asset_lines.iter().cloned());
 
-    if let Some(since) = since {
-        lines.push(compose_contribution_summary(dsl, since));
+    store_welcome_back_payload(dsl, player, payload)?;
+    record_welcome_back_snapshot(dsl, &player.get_id())?;
+
+    send_direct_server_info(dsl, &player.get_id(), lines.join("\n"))
+}
+
+/// Stores what the world looks like now as `player_id`'s baseline for their
+/// next welcome-back.
+pub fn record_welcome_back_snapshot<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    player_id: &PlayerId,
+) -> Result<(), String> {
+    let WorldState {
+        sites,
+        stations,
+        sectors,
+    } = capture_world_state(dsl);
+
+    match dsl.get_welcome_back_snapshot_by_id(player_id) {
+        Ok(mut snapshot) => {
+            snapshot.sites = sites;
+            snapshot.stations = stations;
+            snapshot.sectors = sectors;
+            dsl.update_welcome_back_snapshot_by_id(snapshot)?;
+        }
+        Err(_) => {
+            dsl.create_welcome_back_snapshot(CreateWelcomeBackSnapshot {
+                id: player_id.clone(),
+                sites,
+                stations,
+                sectors,
+            })?;
+        }
     }
+    Ok(())
+}
 
-    lines.push(compose_cargo_summary(dsl, player));
+/// The parts of the world a `WelcomeBackSnapshot` records.
+struct WorldState {
+    sites: Vec<SiteSnapshot>,
+    stations: Vec<StationModulesSnapshot>,
+    sectors: Vec<SectorControlSnapshot>,
+}
 
-    send_direct_server_info(dsl, &player.get_id(), lines.join("\n"))
+fn capture_world_state<T: spacetimedsl::WriteContext>(dsl: &DSL<T>) -> WorldState {
+    let sites: Vec<SiteSnapshot> = dsl
+        .get_all_stations_under_construction()
+        .map(|uc| SiteSnapshot {
+            station_id: uc.get_id().value(),
+            progress_percentage: uc.construction_progress_percentage,
+            is_operational: uc.is_operational,
+        })
+        .collect();
+    let stations: Vec<StationModulesSnapshot> = dsl
+        .get_all_stations()
+        .map(|station| {
+            let modules: Vec<StationModule> = dsl
+                .get_station_modules_by_station_id(station.get_id())
+                .collect();
+            StationModulesSnapshot {
+                station_id: station.get_id().value(),
+                module_count: modules.len() as u32,
+                operational_modules: modules.iter().filter(|m| m.is_operational).count() as u32,
+            }
+        })
+        .collect();
+    let sectors: Vec<SectorControlSnapshot> = dsl
+        .get_all_sectors()
+        .map(|sector| SectorControlSnapshot {
+            sector_id: sector.get_id().value(),
+            controlling_faction_id: sector.controlling_faction_id,
+        })
+        .collect();
+
+    WorldState {
+        sites,
+        stations,
+        sectors,
+    }
+}
+
+/// Everything in a `WelcomeBackPayload` except its id, while it's being
+/// composed.
+#[derive(Default)]
+struct WelcomeBackContent {
+    since: Option<Timestamp>,
+    away_secs: u64,
+    offline_ticks: u64,
+    site_changes: Vec<SiteProgressChange>,
+    module_changes: Vec<StationModuleChange>,
+    control_changes: Vec<SectorControlChange>,
+    top_contributors: Vec<ContributorTotal>,
+    asset_lines: Vec<String>,
+}
+
+fn store_welcome_back_payload<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    player: &Player,
+    content: WelcomeBackContent,
+) -> Result<(), String> {
+    match dsl.get_welcome_back_payload_by_id(player.get_id()) {
+        Ok(mut payload) => {
+            payload.since = content.since;
+            payload.away_secs = content.away_secs;
+            payload.offline_ticks = content.offline_ticks;
+            payload.site_changes = content.site_changes;
+            payload.module_changes = content.module_changes;
+            payload.control_changes = content.control_changes;
+            payload.top_contributors = content.top_contributors;
+            payload.asset_lines = content.asset_lines;
+            dsl.update_welcome_back_payload_by_id(payload)?;
+        }
+        Err(_) => {
+            dsl.create_welcome_back_payload(CreateWelcomeBackPayload {
+                id: player.get_id(),
+                since: content.since,
+                away_secs: content.away_secs,
+                offline_ticks: content.offline_ticks,
+                site_changes: content.site_changes,
+                module_changes: content.module_changes,
+                control_changes: content.control_changes,
+                top_contributors: content.top_contributors,
+                asset_lines: content.asset_lines,
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Fills the three diff sections of `payload` by running the calculator over
+/// `snapshot` and the world now, resolving names as it goes. Rows that have
+/// vanished since are skipped.
+fn compose_world_changes<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    player: &Player,
+    snapshot: &WelcomeBackSnapshot,
+    payload: &mut WelcomeBackContent,
+) {
+    let station_name = |id: u64| {
+        dsl.get_station_by_id(StationId::new(id))
+            .map(|station| station.get_name().clone())
+            .unwrap_or_else(|_| format!("station #{}", id))
+    };
+    let sector_name = |id: u64| {
+        dsl.get_sector_by_id(SectorId::new(id))
+            .map(|sector| sector.get_name().clone())
+            .unwrap_or_else(|_| format!("sector #{}", id))
+    };
+    let faction_name = |id: u32| {
+        dsl.get_faction_by_id(FactionId::new(id))
+            .map(|faction| faction.name.clone())
+            .unwrap_or_else(|_| format!("faction #{}", id))
+    };
+
+    let now = capture_world_state(dsl);
+
+    for (before, site) in OfflineTickCalculator::site_changes(&snapshot.sites, &now.sites) {
+        let Ok(station) = dsl.get_station_by_id(StationId::new(site.station_id)) else {
+            continue;
+        };
+        payload.site_changes.push(SiteProgressChange {
+            station_id: site.station_id,
+            station_name: station.get_name().clone(),
+            sector_name: sector_name(station.get_sector_id().value()),
+            from_percentage: before.map(|b| b.progress_percentage),
+            to_percentage: site.progress_percentage,
+            came_online: site.is_operational && !before.is_some_and(|b| b.is_operational),
+            your_share_percentage: contribution_share(dsl, &station.get_id(), &player.get_id()),
+            is_your_faction: station.get_owner_faction_id().value() == player.faction_id.value(),
+        });
+    }
+
+    for (before, after) in OfflineTickCalculator::station_changes(&snapshot.stations, &now.stations)
+    {
+        payload.module_changes.push(StationModuleChange {
+            station_id: after.station_id,
+            station_name: station_name(after.station_id),
+            modules_before: before.module_count,
+            modules_now: after.module_count,
+            operational_before: before.operational_modules,
+            operational_now: after.operational_modules,
+        });
+    }
+
+    for (sector_id, from, to) in
+        OfflineTickCalculator::control_changes(&snapshot.sectors, &now.sectors)
+    {
+        payload.control_changes.push(SectorControlChange {
+            sector_id,
+            sector_name: sector_name(sector_id),
+            from_faction: faction_name(from),
+            to_faction: faction_name(to),
+        });
+    }
+}
+
+/// `player`'s all-time contributions to a site as a percentage of everything
+/// it requires.
+fn contribution_share<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    station_id: &StationId,
+    player_id: &PlayerId,
+) -> f32 {
+    let required: u32 = dsl
+        .get_construction_requirements_by_station_id(station_id)
+        .map(|req| req.quantity_required)
+        .sum();
+    if required == 0 {
+        return 0.0;
+    }
+    let contributed: u32 = dsl
+        .get_construction_contribution_logs_by_station_id(station_id)
+        .filter(|log| log.get_player_id() == *player_id && log.get_module_proposal_id().is_none())
+        .map(|log| *log.get_quantity())
+        .sum();
+    (contributed as f32 / required as f32 * 100.0).min(100.0)
+}
+
+/// The players who contributed the most units since `since`, most first.
+fn compose_top_contributors<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    player: &Player,
+    since: Timestamp,
+) -> Vec<ContributorTotal> {
+    let mut totals: Vec<(PlayerId, u32)> = Vec::new();
+    for log in dsl
+        .get_all_construction_contribution_logs()
+        .filter(|log| *log.get_contributed_at() > since)
+    {
+        match totals.iter_mut().find(|(id, _)| *id == log.get_player_id()) {
+            Some(entry) => entry.1 += *log.get_quantity(),
+            None => totals.push((log.get_player_id(), *log.get_quantity())),
+        }
+    }
+    totals.sort_by_key(|(_, quantity)| std::cmp::Reverse(*quantity));
+
+    totals
+        .into_iter()
+        .take(TOP_CONTRIBUTORS)
+        .map(|(id, quantity)| ContributorTotal {
+            username: dsl
+                .get_player_by_id(&id)
+                .map(|p| p.username)
+                .unwrap_or_else(|_| "(unknown)".to_string()),
+            quantity,
+            is_you: id == player.get_id(),
+        })
+        .collect()
+}
+
+/// The text-summary lines for the away time and the diff sections.
+fn describe_changes(payload: &WelcomeBackContent) -> Vec<String> {
+    let mut lines = Vec::new();
+    if payload.since.is_none() {
+        return lines;
+    }
+    lines.push(format!(
+        "You were away for {} ({} economy ticks).",
+        describe_duration(payload.away_secs),
+        payload.offline_ticks
+    ));
+
+    for change in &payload.site_changes {
+        let mut line = match change.from_percentage {
+            Some(from) => format!(
+                "  • {} in {} went {:.0}%→{:.0}%",
+                change.station_name, change.sector_name, from, change.to_percentage
+            ),
+            None => format!(
+                "  • {} in {} was founded and is at {:.0}%",
+                change.station_name, change.sector_name, change.to_percentage
+            ),
+        };
+        if change.came_online {
+            line.push_str(" and came online");
+        }
+        if change.your_share_percentage > 0.0 {
+            line.push_str(&format!(
+                "; you contributed {:.0}% of it",
+                change.your_share_percentage
+            ));
+        }
+        line.push('.');
+        lines.push(line);
+    }
+    for change in &payload.module_changes {
+        lines.push(format!(
+            "  • {} now has {} modules ({} online), up from {} ({} online).",
+            change.station_name,
+            change.modules_now,
+            change.operational_now,
+            change.modules_before,
+            change.operational_before
+        ));
+    }
+    for change in &payload.control_changes {
+        lines.push(format!(
+            "  • {} passed from {} to {}.",
+            change.sector_name, change.from_faction, change.to_faction
+        ));
+    }
+
+    if payload.top_contributors.is_empty() {
+        lines.push("No new contributions since your last visit.".to_string());
+    } else {
+        let names: Vec<String> = payload
+            .top_contributors
+            .iter()
+            .map(|c| format!("{} ({})", c.username, c.quantity))
+            .collect();
+        lines.push(format!(
+            "Top contributors since you left: {}.",
+            names.join(", ")
+        ));
+    }
+    lines
+}
+
+/// "3d 4h", "2h 15m", "5m".
+fn describe_duration(secs: u64) -> String {
+    let duration = Duration::from_secs(secs);
+    let days = duration.as_secs() / 86_400;
+    let hours = duration.as_secs() % 86_400 / 3_600;
+    let minutes = duration.as_secs() % 3_600 / 60;
+    match (days, hours) {
+        (0, 0) => format!("{}m", minutes),
+        (0, _) => format!("{}h {}m", hours, minutes),
+        _ => format!("{}d {}h", days, hours),
+    }
 }
 
 /// One line per construction site still under way — own-faction sites flagged
@@ -117,23 +575,6 @@ fn compose_construction_summary<T: spacetimedsl::WriteContext>(
     }
 }
 
-/// Count contribution events logged strictly after `since`.
-fn compose_contribution_summary<T: spacetimedsl::WriteContext>(
-    dsl: &DSL<T>,
-    since: Timestamp,
-) -> String {
-    let events = dsl
-        .get_all_construction_contribution_logs()
-        .filter(|log| *log.get_contributed_at() > since)
-        .count();
-
-    match events {
-        0 => "No new contributions since your last visit.".to_string(),
-        1 => "1 contribution was logged since your last visit.".to_string(),
-        n => format!("{} contributions were logged since your last visit.", n),
-    }
-}
-
 /// Aggregate the player's cargo across every ship they own and name the items.
 fn compose_cargo_summary<T: spacetimedsl::WriteContext>(dsl: &DSL<T>, player: &Player) -> String {
     // (item_id, total_quantity), summed across all of the player's ships.
@@ -167,3 +608,77 @@ fn compose_cargo_summary<T: spacetimedsl::WriteContext>(dsl: &DSL<T>, player: &P
 
     format!("Your cargo: {}.", parts.join(", "))
 }
+
+/// One line per station where the player has something stored, or `None` if
+/// their hangars are all empty.
+fn compose_hangar_summary<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    player: &Player,
+) -> Option<String> {
+    // (station_id, [(item_id, quantity)]), in first-seen order.
+    let mut stations: Vec<(u64, Vec<(u32, u32)>)> = Vec::new();
+    for stored in dsl.get_station_hangar_items_by_player_id(&player.get_id()) {
+        let station_id = stored.get_station_id().value();
+        let entry = match stations.iter_mut().position(|(id, _)| *id == station_id) {
+            Some(i) => &mut stations[i].1,
+            None => {
+                stations.push((station_id, Vec::new()));
+                &mut stations.last_mut().unwrap().1
+            }
+        };
+        entry.push((stored.get_item_id().value(), stored.quantity));
+    }
+
+    if stations.is_empty() {
+        return None;
+    }
+
+    let lines: Vec<String> = stations
+        .iter()
+        .map(|(station_id, items)| {
+            let station_name = dsl
+                .get_station_by_id(&StationId::new(*station_id))
+                .map(|station| station.get_name().clone())
+                .unwrap_or_else(|_| format!("station #{}", station_id));
+            let parts: Vec<String> = items
+                .iter()
+                .map(|(id, qty)| {
+                    let name = dsl
+                        .get_item_definition_by_id(&ItemDefinitionId::new(*id))
+                        .map(|def| def.get_name().clone())
+                        .unwrap_or_else(|_| format!("item #{}", id));
+                    format!("{} {}", qty, name)
+                })
+                .collect();
+            format!("  • {}: {}", station_name, parts.join(", "))
+        })
+        .collect();
+
+    Some(format!("Stored in your hangars:\n{}", lines.join("\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn site_changes_skip_sites_that_barely_moved() {
+        let site = |station_id, progress_percentage, is_operational| SiteSnapshot {
+            station_id,
+            progress_percentage,
+            is_operational,
+        };
+        let before = vec![site(1, 40.0, false), site(2, 10.0, false)];
+        let now = vec![
+            site(1, 100.0, true),
+            site(2, 10.2, false),
+            site(3, 5.0, false),
+        ];
+
+        let changes = OfflineTickCalculator::site_changes(&before, &now);
+        let ids: Vec<u64> = changes.iter().map(|(_, s)| s.station_id).collect();
+        assert_eq!(ids, vec![1, 3]);
+        assert_eq!(changes[0].0.map(|b| b.progress_percentage), Some(40.0));
+        assert!(changes[1].0.is_none());
+    }
+}
@@ -1,19 +1,70 @@
-use std::f32::consts::PI;
+//! # Asteroid fields
+//!
+//! A sector with an `AsteroidSector` row keeps a field of asteroids topped up
+//! to its `target_population`.
+//!
+//! Mining goes through [`extract_from_asteroid`]. An asteroid's `size_radius`
+//! follows the ore left in it ([`asteroid_radius`]), so rocks visibly shrink,
+//! and each time mining takes one past an `ASTEROID_FRACTURE_THRESHOLDS`
+//! fraction of its `initial_resources` a fragment breaks off the far side
+//! carrying part of what's left.
+//!
+//! An exhausted asteroid is removed with [`remove_exhausted_asteroid`], which
+//! queues an `AsteroidRespawn` for `ASTEROID_RESPAWN_DELAY` later. The hourly
+//! [`asteroid_sector_upkeep`] spawns due replacements while the field is short,
+//! and a replacement's ore drifts toward the sector's `rare_ore` potential:
+//! with that chance a second ore is rolled and the more valuable of the two
+//! kept. Mined-out fields therefore come back a little richer.
+
+use std::{collections::HashMap, f32::consts::PI, time::Duration};
 
 use solarance_shared::Vec2;
 use spacetimedb::rand::Rng;
-use spacetimedb::ReducerContext;
+use spacetimedb::{ReducerContext, TimeDuration};
 use spacetimedsl::*;
 
 use crate::{
     definitions::item_types::*,
-    tables::{asteroids::*, items::*, sectors::*},
+    tables::{asteroids::*, items::*, sectors::*, stellarobjects::*},
     utility::try_server_only,
 };
 
+/// `size_radius` per cube root of a unit of ore: a 1000-unit asteroid is 16
+/// across its radius.
+pub const ASTEROID_RADIUS_PER_CBRT_UNIT: f32 = 1.6;
+
+/// No asteroid shrinks below this, however little is left in it.
+pub const ASTEROID_MIN_RADIUS: f32 = 4.0;
+
+/// Fractions of `initial_resources` at which mining knocks a fragment off.
+pub const ASTEROID_FRACTURE_THRESHOLDS: [f32; 2] = [0.5, 0.25];
+
+/// The part of what's left that a fragment carries away.
+pub const ASTEROID_FRAGMENT_SHARE: f32 = 0.3;
+
+/// Fragments smaller than this aren't worth breaking off.
+pub const ASTEROID_MIN_FRAGMENT_RESOURCES: u16 = 50;
+
+/// How long an exhausted asteroid takes to be replaced.
+pub const ASTEROID_RESPAWN_DELAY: Duration = Duration::from_secs(2 * 60 * 60);
+
+/// The `size_radius` of an asteroid holding `resources` units of ore.
+pub fn asteroid_radius(resources: u16) -> f32 {
+    (ASTEROID_RADIUS_PER_CBRT_UNIT * (resources as f32).cbrt()).max(ASTEROID_MIN_RADIUS)
+}
+
+/// Whether going from `before` to `after` units passes one of the
+/// `ASTEROID_FRACTURE_THRESHOLDS` of `initial`.
+fn crosses_fracture_threshold(initial: u16, before: u16, after: u16) -> bool {
+    ASTEROID_FRACTURE_THRESHOLDS.iter().any(|fraction| {
+        let threshold = initial as f32 * fraction;
+        before as f32 >= threshold && (after as f32) < threshold
+    })
+}
+
 /// Target number of asteroids a field tries to maintain, derived from its
 /// sparseness (e.g. sparseness 5 → 50 asteroids).
-fn target_population(asteroid_sector: &AsteroidSector) -> usize {
+pub fn target_population(asteroid_sector: &AsteroidSector) -> usize {
     (asteroid_sector.get_sparseness() * 10).into()
 }
 
@@ -66,13 +117,39 @@ fn roll_ore_item(dsl: &DSL<'_, ReducerContext>, asteroid_sector: &AsteroidSector
     }
 }
 
+/// Rolls the ore for a respawned asteroid: with a `rare_ore` chance a second
+/// ore is rolled and the one with the higher `base_value` is kept.
+fn roll_respawn_ore_item(
+    dsl: &DSL<'_, ReducerContext>,
+    asteroid_sector: &AsteroidSector,
+    rare_ore: f32,
+) -> u32 {
+    let first = roll_ore_item(dsl, asteroid_sector);
+    if dsl.ctx().rng().gen_range(0.0..1.0) >= rare_ore {
+        return first;
+    }
+    let second = roll_ore_item(dsl, asteroid_sector);
+    let value = |item_id: u32| {
+        dsl.get_item_definition_by_id(ItemDefinitionId::new(item_id))
+            .map(|item| *item.get_base_value())
+            .unwrap_or(0)
+    };
+    if value(second) > value(first) {
+        second
+    } else {
+        first
+    }
+}
+
 /// Spawns a single asteroid at a random position inside the field, with ore
-/// type drawn from the sector's composition (or the global fallback). Pure spawn
-/// logic — authorization is the calling reducer's responsibility. Returns `None`
-/// if the creation failed.
+/// type drawn from the sector's composition (or the global fallback), drifted
+/// toward rarer ores by `rare_ore` (0.0 for none). Pure spawn logic —
+/// authorization is the calling reducer's responsibility. Returns `None` if the
+/// creation failed.
 fn spawn_random_asteroid_in_field(
     dsl: &DSL<'_, ReducerContext>,
     asteroid_sector: &AsteroidSector,
+    rare_ore: f32,
 ) -> Option<Asteroid> {
     let field = *asteroid_sector.get_cluster_extent();
     let dist = match asteroid_sector.get_cluster_inner() {
@@ -81,7 +158,7 @@ fn spawn_random_asteroid_in_field(
     };
     let pos = Vec2::from_glam(glam::Vec2::from_angle(dsl.ctx().rng().gen_range(0.0..2.0 * PI)) * dist);
 
-    let item = ItemDefinitionId::new(roll_ore_item(dsl, asteroid_sector));
+    let item = ItemDefinitionId::new(roll_respawn_ore_item(dsl, asteroid_sector, rare_ore));
 
     let amount = dsl.ctx().rng().gen_range(500..2000);
 
@@ -106,7 +183,7 @@ pub fn fill_asteroid_sector(
     let sector_id = asteroid_sector.get_id();
 
     while dsl.get_asteroids_by_current_sector_id(&sector_id).count() < target {
-        if spawn_random_asteroid_in_field(dsl, asteroid_sector).is_none() {
+        if spawn_random_asteroid_in_field(dsl, asteroid_sector, 0.0).is_none() {
             // Bail rather than spin forever: a persistent creation failure would
             // never advance the count and would hang the init transaction.
             return Err(format!(
@@ -117,12 +194,140 @@ pub fn fill_asteroid_sector(
         }
     }
 
+    write_asteroid_field_report(dsl, asteroid_sector)
+}
+
+/// Takes up to `amount` units out of `asteroid`, shrinking it and breaking off
+/// a fragment if that passes a fracture threshold. The fragment flies off the
+/// side facing away from `miner_pos`. Returns how much was taken.
+///
+/// An asteroid mined down to nothing is left in place at zero; the caller
+/// removes it with [`remove_exhausted_asteroid`].
+pub fn extract_from_asteroid(
+    dsl: &DSL<'_, ReducerContext>,
+    mut asteroid: Asteroid,
+    amount: u16,
+    miner_pos: &Vec2,
+) -> Result<u16, String> {
+    let before = *asteroid.get_current_resources();
+    let taken = amount.min(before);
+    let mut after = before - taken;
+
+    let fragment = (after as f32 * ASTEROID_FRAGMENT_SHARE) as u16;
+    if fragment >= ASTEROID_MIN_FRAGMENT_RESOURCES
+        && crosses_fracture_threshold(*asteroid.get_initial_resources(), before, after)
+    {
+        after -= fragment;
+        let parent_radius = asteroid_radius(after);
+        let away = asteroid
+            .position
+            .sub(miner_pos)
+            .to_glam()
+            .normalize_or(glam::Vec2::X);
+        let direction =
+            glam::Vec2::from_angle(dsl.ctx().rng().gen_range(-PI / 3.0..PI / 3.0)).rotate(away);
+        let pos = asteroid.position.to_glam()
+            + direction * (parent_radius + asteroid_radius(fragment) + 8.0);
+        create_asteroid(
+            dsl,
+            Vec2::from_glam(pos),
+            asteroid.get_current_sector_id(),
+            format!("asteroid.{}", dsl.ctx().rng().gen_range(1..=5)),
+            asteroid.get_resource_item_id(),
+            fragment,
+        )
+        .ok_or_else(|| {
+            format!(
+                "extract_from_asteroid: couldn't break a fragment off asteroid {}",
+                asteroid.get_id().value()
+            )
+        })?;
+    }
+
+    asteroid.set_current_resources(after);
+    asteroid.set_size_radius(asteroid_radius(after));
+    dsl.update_asteroid_by_id(asteroid)?;
+    Ok(taken)
+}
+
+/// Deletes a mined-out asteroid and queues its replacement for
+/// `ASTEROID_RESPAWN_DELAY` from now.
+pub fn remove_exhausted_asteroid(
+    dsl: &DSL<'_, ReducerContext>,
+    asteroid: &Asteroid,
+) -> Result<(), String> {
+    dsl.delete_stellar_object_by_id(&asteroid.get_id())?;
+    dsl.create_asteroid_respawn(CreateAsteroidRespawn {
+        sector_id: asteroid.get_current_sector_id(),
+        respawn_at: dsl
+            .ctx()
+            .timestamp
+            .checked_add(TimeDuration::from_duration(ASTEROID_RESPAWN_DELAY))
+            .ok_or("remove_exhausted_asteroid: respawn time overflowed")?,
+    })?;
+    Ok(())
+}
+
+/// Rewrites the sector's `AsteroidFieldReport`.
+pub fn write_asteroid_field_report(
+    dsl: &DSL<'_, ReducerContext>,
+    asteroid_sector: &AsteroidSector,
+) -> Result<(), String> {
+    let sector_id = asteroid_sector.get_id();
+    let mut asteroid_count = 0;
+    let mut current_resources = 0;
+    let mut initial_resources = 0;
+    let mut by_ore: HashMap<u32, FieldOreShare> = HashMap::new();
+    for asteroid in dsl.get_asteroids_by_current_sector_id(&sector_id) {
+        asteroid_count += 1;
+        current_resources += *asteroid.get_current_resources() as u64;
+        initial_resources += *asteroid.get_initial_resources() as u64;
+        let item_id = asteroid.get_resource_item_id().value();
+        let share = by_ore.entry(item_id).or_insert(FieldOreShare {
+            item_id,
+            asteroids: 0,
+            resources: 0,
+        });
+        share.asteroids += 1;
+        share.resources += *asteroid.get_current_resources() as u64;
+    }
+    let mut ore_mix: Vec<FieldOreShare> = by_ore.into_values().collect();
+    ore_mix.sort_by(|a, b| {
+        b.resources
+            .cmp(&a.resources)
+            .then(a.item_id.cmp(&b.item_id))
+    });
+
+    let target_population = target_population(asteroid_sector) as u32;
+    let pending_respawns = dsl.get_asteroid_respawns_by_sector_id(&sector_id).count() as u32;
+    match dsl.get_asteroid_field_report_by_id(&sector_id) {
+        Ok(mut report) => {
+            report.set_asteroid_count(asteroid_count);
+            report.set_target_population(target_population);
+            report.set_pending_respawns(pending_respawns);
+            report.set_current_resources(current_resources);
+            report.set_initial_resources(initial_resources);
+            report.set_ore_mix(ore_mix);
+            dsl.update_asteroid_field_report_by_id(report)?;
+        }
+        Err(_) => {
+            dsl.create_asteroid_field_report(CreateAsteroidFieldReport {
+                id: sector_id,
+                asteroid_count,
+                target_population,
+                pending_respawns,
+                current_resources,
+                initial_resources,
+                ore_mix,
+            })?;
+        }
+    }
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::pick_weighted_ore;
+    use super::{crosses_fracture_threshold, pick_weighted_ore};
     use crate::tables::sectors::OreWeight;
 
     fn w(item_id: u32, weight: u16) -> OreWeight {
@@ -147,11 +352,23 @@ mod tests {
         assert_eq!(pick_weighted_ore(&weights, 0), Some(2));
         assert_eq!(pick_weighted_ore(&vec![w(1, 0)], 0), None);
     }
+
+    #[test]
+    fn fractures_only_when_a_threshold_is_passed() {
+        assert!(!crosses_fracture_threshold(1000, 1000, 501));
+        assert!(crosses_fracture_threshold(1000, 501, 499));
+        assert!(crosses_fracture_threshold(1000, 500, 499));
+        // Already below half, not yet below a quarter.
+        assert!(!crosses_fracture_threshold(1000, 400, 300));
+        assert!(crosses_fracture_threshold(1000, 900, 100));
+    }
 }
 
-/// Maintains asteroid populations in a sector. When a field has dropped below
-/// its target (e.g. after mining), spawns a single replacement. Runs hourly via
-/// `sector_upkeep`; bulk initial population happens at init via
+/// Maintains asteroid populations in a sector. Spawns the replacements whose
+/// `AsteroidRespawn` delay is up while the field is below its target, queues
+/// one more if asteroids went missing without being mined out (so nothing
+/// would replace them), and rewrites the `AsteroidFieldReport`. Runs hourly
+/// via `sector_upkeep`; bulk initial population happens at init via
 /// [`fill_asteroid_sector`].
 pub fn asteroid_sector_upkeep(
     dsl: &DSL<'_, ReducerContext>,
@@ -160,16 +377,33 @@ pub fn asteroid_sector_upkeep(
     try_server_only(dsl)?;
 
     let asteroid_sector = dsl.get_asteroid_sector_by_id(sector_id)?;
-    if dsl.get_asteroids_by_current_sector_id(sector_id).count()
-        < target_population(&asteroid_sector)
-    {
-        spawn_random_asteroid_in_field(dsl, &asteroid_sector).ok_or_else(|| {
-            format!(
-                "asteroid_sector_upkeep: create_asteroid failed in sector {}",
-                sector_id.value()
-            )
+    let rare_ore = *dsl.get_sector_by_id(sector_id)?.get_rare_ore();
+    let target = target_population(&asteroid_sector);
+    let now = dsl.ctx().timestamp;
+
+    let due: Vec<AsteroidRespawn> = dsl
+        .get_asteroid_respawns_by_sector_id(sector_id)
+        .filter(|respawn| *respawn.get_respawn_at() <= now)
+        .collect();
+    for respawn in due {
+        dsl.delete_asteroid_respawn_by_id(&respawn.get_id())?;
+        if dsl.get_asteroids_by_current_sector_id(sector_id).count() < target {
+            spawn_random_asteroid_in_field(dsl, &asteroid_sector, rare_ore).ok_or_else(|| {
+                format!(
+                    "asteroid_sector_upkeep: create_asteroid failed in sector {}",
+                    sector_id.value()
+                )
+            })?;
+        }
+    }
+
+    let pending = dsl.get_asteroid_respawns_by_sector_id(sector_id).count();
+    if dsl.get_asteroids_by_current_sector_id(sector_id).count() + pending < target {
+        dsl.create_asteroid_respawn(CreateAsteroidRespawn {
+            sector_id: sector_id.clone(),
+            respawn_at: now,
         })?;
     }
 
-    Ok(())
+    write_asteroid_field_report(dsl, &asteroid_sector)
 }
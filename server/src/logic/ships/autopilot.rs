@@ -0,0 +1,549 @@
+//! Server-side waypoint autopilot.
+//!
+//! `set_autopilot_target` records a `ShipAutopilot` row for the sender's
+//! Piloted Ship and starts a 5 Hz scheduled tick if none is running. The tick
+//! steers every engaged ship by writing the same kind of acceleration
+//! snapshot that `update_ship_movement_controller` writes for key presses —
+//! clients keep dead-reckoning exactly as they do for manual flight. Once no
+//! ship is engaged the tick deletes its own timer.
+//!
+//! ## Steering
+//! Each tick tries all nine combinations of {thrust, coast, brake} ×
+//! {turn left, no turn, turn right}, predicts one tick ahead with
+//! `predict_movement`, and scores where the ship would come to rest if it
+//! then braked and counter-steered at full strength. The cheapest candidate
+//! wins. Far from the goal that turns the ship to face it and thrusts; once
+//! the braking distance covers what's left it brakes, so the turn → thrust →
+//! brake phases fall out of the scoring rather than being hand-sequenced.
+//!
+//! ## Other sectors
+//! A target in another sector is reached by the shortest route over active
+//! gates (`plan_jump_route`, which wraps `solarance_shared::plan_route`). The
+//! route is re-planned if a gate on it is switched off. Each gate on the
+//! route is flown to like any other waypoint and then used via
+//! `jump_ship_through_gate`. A ship that is short on energy parks at the gate
+//! until it has recharged.
+//!
+//! ## Hand-back
+//! On arrival the ship is stopped, the row is deleted and the input mirror is
+//! cleared so the next key press registers. Any manual input while engaged
+//! deletes the row as well (see `update_ship_movement_controller`).
+
+use std::{
+    f32::consts::{PI, TAU},
+    time::Duration,
+};
+
+use log::{info, warn};
+use solarance_shared::{plan_route, predict_movement, MovementState, Vec2};
+use spacetimedb::ReducerContext;
+use spacetimedsl::*;
+
+use crate::{
+    logic::{
+        moderation::refuse_banned,
+        ships::{
+            mining::MINING_RANGE,
+            station_interactions::{jump_ship_through_gate, DOCK_RANGE, JUMPGATE_USE_RANGE},
+        },
+        stellarobjects::movement::{
+            get_ship_movement_snapshot, get_sobj_position, write_ship_movement_snapshot,
+        },
+    },
+    tables::{
+        asteroids::*,
+        jumpgates::*,
+        messages::{send_direct_server_info, send_direct_server_warning},
+        players::*,
+        sectors::*,
+        ships::*,
+        stations::*,
+        stellarobjects::*,
+    },
+    utility::try_server_only,
+};
+
+/// How often the autopilot re-plans. Matches the "Interactions" 5 fps tier.
+const AUTOPILOT_TICK_MS: u64 = 200;
+
+/// A ship inside its arrival range and slower than this (px/s) has arrived.
+const ARRIVAL_SPEED: f32 = 8.0;
+
+/// Arrival range (px) for a bare `AutopilotTarget::Position`.
+const POSITION_ARRIVAL_RANGE: f32 = 40.0;
+
+/// Gap (px) kept between the ship's hull and a solid target's collision
+/// radius, so the collision sweep never has to stop an autopiloted ship.
+const STANDOFF_MARGIN: f32 = 60.0;
+
+//////////////////////////////////////////////////////////////
+// Timers
+//////////////////////////////////////////////////////////////
+
+#[dsl(plural_name = ship_autopilot_timers, method(update = false))]
+#[spacetimedb::table(accessor = ship_autopilot_timer, scheduled(ship_autopilot_timer_reducer))]
+pub struct ShipAutopilotTimer {
+    #[primary_key]
+    #[auto_inc]
+    #[create_wrapper]
+    id: u64,
+    scheduled_at: spacetimedb::ScheduleAt,
+}
+
+/// Starts the steering tick unless it is already running.
+pub fn ensure_ship_autopilot_timer<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+) -> Result<(), String> {
+    if dsl.get_all_ship_autopilot_timers().next().is_some() {
+        return Ok(());
+    }
+    dsl.create_ship_autopilot_timer(CreateShipAutopilotTimer {
+        scheduled_at: spacetimedb::ScheduleAt::Interval(
+            Duration::from_millis(AUTOPILOT_TICK_MS).into(),
+        ),
+    })?;
+    Ok(())
+}
+
+//////////////////////////////////////////////////////////////
+// Reducers
+//////////////////////////////////////////////////////////////
+
+/// Used by a player client. Engages the autopilot on the sender's Piloted
+/// Ship, replacing any previous target. Stellar object targets must be a
+/// station, asteroid or jumpgate; they may be in another sector as long as
+/// an active jumpgate route leads there.
+#[spacetimedb::reducer]
+pub fn set_autopilot_target(ctx: &ReducerContext, target: AutopilotTarget) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    refuse_banned(&dsl, ctx.sender())?;
+    let (ship, _) = get_player_ship_and_sobj(&dsl, &PlayerId::new(ctx.sender()))?;
+
+    let destination_sector_id = match &target {
+        AutopilotTarget::StellarObject(sobj_id) => {
+            let sobj = dsl.get_stellar_object_by_id(&StellarObjectId::new(*sobj_id))?;
+            match sobj.get_kind() {
+                StellarObjectKinds::Station
+                | StellarObjectKinds::Asteroid
+                | StellarObjectKinds::JumpGate => sobj.get_sector_id(),
+                kind => return Err(format!("Autopilot can't target a {:?}", kind)),
+            }
+        }
+        AutopilotTarget::Position(_) => ship.get_sector_id(),
+    };
+
+    let start_pos = get_ship_movement_snapshot(&dsl, &ship.get_id())?.pos;
+    let end_pos = target_position(&dsl, &target)?;
+    let route = plan_jump_route(
+        &dsl,
+        &ship.get_sector_id(),
+        start_pos,
+        &destination_sector_id,
+        Some(end_pos),
+    )
+    .ok_or_else(|| {
+        format!(
+            "No active jumpgate route to sector #{}",
+            destination_sector_id.value()
+        )
+    })?;
+
+    if let Ok(existing) = dsl.get_ship_autopilot_by_id(&ship.get_id()) {
+        dsl.delete_ship_autopilot_by_id(existing.get_id())?;
+    }
+    dsl.create_ship_autopilot(CreateShipAutopilot {
+        id: ship.get_id(),
+        target,
+        destination_sector_id: destination_sector_id.clone(),
+        route,
+    })?;
+    ensure_ship_autopilot_timer(&dsl)?;
+
+    info!(
+        "Ship #{} autopilot engaged for sector #{}",
+        ship.get_id().value(),
+        destination_sector_id.value()
+    );
+    Ok(())
+}
+
+/// Used by a player client. Disengages the autopilot on the sender's Piloted
+/// Ship. The ship keeps its current speed, exactly as if the thrust key had
+/// been released.
+#[spacetimedb::reducer]
+pub fn clear_autopilot(ctx: &ReducerContext) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    refuse_banned(&dsl, ctx.sender())?;
+    let (ship, _) = get_player_ship_and_sobj(&dsl, &PlayerId::new(ctx.sender()))?;
+    disengage_autopilot(&dsl, &ship, false)
+}
+
+/// Scheduled reducer that steers every engaged autopilot one tick. A failure
+/// for one ship disengages that ship only. Deletes its own timer once no
+/// ship is engaged.
+#[spacetimedb::reducer]
+pub fn ship_autopilot_timer_reducer(
+    ctx: &ReducerContext,
+    timer: ShipAutopilotTimer,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    try_server_only(&dsl)?;
+
+    let autopilots: Vec<ShipAutopilot> = dsl.get_all_ship_autopilots().collect();
+    for autopilot in autopilots {
+        let ship = match dsl.get_ship_by_id(&autopilot.get_id()) {
+            Ok(ship) => ship,
+            Err(e) => {
+                warn!(
+                    "Autopilot for ship #{} dropped: {}",
+                    autopilot.get_id().value(),
+                    e
+                );
+                dsl.delete_ship_autopilot_by_id(autopilot.get_id())?;
+                continue;
+            }
+        };
+        if let Err(e) = steer_ship(&dsl, &ship, autopilot) {
+            warn!(
+                "Autopilot for ship #{} disengaged: {}",
+                ship.get_id().value(),
+                e
+            );
+            disengage_autopilot(&dsl, &ship, false)?;
+            send_direct_server_warning(
+                &dsl,
+                &ship.get_player_id(),
+                format!("Autopilot disengaged: {}", e),
+            )?;
+        }
+    }
+
+    if dsl.get_all_ship_autopilots().next().is_none() {
+        dsl.delete_ship_autopilot_timer_by_id(&timer)?;
+    }
+
+    Ok(())
+}
+
+//////////////////////////////////////////////////////////////
+// Utility
+//////////////////////////////////////////////////////////////
+
+/// Where the autopilot is flying to this tick.
+struct Waypoint {
+    center: Vec2,
+    /// How far from `center` the ship should come to rest.
+    standoff: f32,
+    /// How close to `center` counts as having reached it.
+    arrival_range: f32,
+    /// Set when the waypoint is a gate on the route rather than the target.
+    gate: Option<JumpGate>,
+}
+
+/// One autopilot tick for `ship`.
+fn steer_ship<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    ship: &Ship,
+    mut autopilot: ShipAutopilot,
+) -> Result<(), String> {
+    // Docked (or otherwise out of the sector) by some other path — nothing
+    // left to steer.
+    if *ship.get_location() != ShipLocation::Sector {
+        dsl.delete_ship_autopilot_by_id(autopilot.get_id())?;
+        return Ok(());
+    }
+
+    let snapshot = get_ship_movement_snapshot(dsl, &ship.get_id())?;
+
+    if !route_is_valid(dsl, ship, &autopilot) {
+        autopilot.route = plan_jump_route(
+            dsl,
+            &ship.get_sector_id(),
+            snapshot.pos,
+            &autopilot.get_destination_sector_id(),
+            Some(target_position(dsl, autopilot.get_target())?),
+        )
+        .ok_or_else(|| "no active jumpgate route to the target".to_string())?;
+        autopilot = dsl.update_ship_autopilot_by_id(autopilot)?;
+    }
+
+    let ship_type = dsl.get_ship_type_definition_by_id(ship.get_shiptype_id())?;
+    let waypoint = next_waypoint(dsl, &ship_type, &autopilot)?;
+
+    if snapshot.pos.distance_to(&waypoint.center) <= waypoint.arrival_range {
+        if let Some(gate) = &waypoint.gate {
+            // Short on energy: fall through and hold position at the gate.
+            let jumped = jump_ship_through_gate(dsl, ship, gate)?;
+            if jumped {
+                autopilot.route.remove(0);
+                dsl.update_ship_autopilot_by_id(autopilot)?;
+                return Ok(());
+            }
+        } else if snapshot.velocity <= ARRIVAL_SPEED {
+            disengage_autopilot(dsl, ship, true)?;
+            send_direct_server_info(
+                dsl,
+                &ship.get_player_id(),
+                "Autopilot: arrived at destination.".to_string(),
+            )?;
+            return Ok(());
+        }
+    }
+
+    let goal = standoff_point(&snapshot.pos, &waypoint);
+    let (linear_a, angular_a) = plan_controls(
+        &snapshot,
+        &goal,
+        *ship_type.get_base_acceleration(),
+        *ship_type.get_base_angular_acceleration(),
+    );
+
+    // Only write a new snapshot when the commands change, same as the input
+    // reducer — re-stamping every tick would make clients jitter.
+    if linear_a != ship.movement.acceleration || angular_a != ship.movement.angular_acceleration {
+        write_ship_movement_snapshot(dsl, &ship.get_id(), |state| {
+            state.acceleration = linear_a;
+            state.angular_acceleration = angular_a;
+        })?;
+    }
+
+    Ok(())
+}
+
+/// The stored route still starts in the ship's sector and only uses active
+/// gates. False after a gate is switched off mid-route.
+fn route_is_valid<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    ship: &Ship,
+    autopilot: &ShipAutopilot,
+) -> bool {
+    let mut sector_id = ship.get_sector_id().value();
+    for gate_id in autopilot.get_route() {
+        let Ok(gate) = dsl.get_jump_gate_by_id(&StellarObjectId::new(*gate_id)) else {
+            return false;
+        };
+        if gate.get_current_sector_id().value() != sector_id || !*gate.get_is_active() {
+            return false;
+        }
+        sector_id = gate.get_target_sector_id().value();
+    }
+    sector_id == autopilot.get_destination_sector_id().value()
+}
+
+/// The first gate on the route, or the target itself once the route is done.
+fn next_waypoint<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    ship_type: &ShipTypeDefinition,
+    autopilot: &ShipAutopilot,
+) -> Result<Waypoint, String> {
+    if let Some(gate_id) = autopilot.get_route().first() {
+        let gate = dsl.get_jump_gate_by_id(&StellarObjectId::new(*gate_id))?;
+        return Ok(Waypoint {
+            center: *gate.get_position(),
+            standoff: 0.0,
+            arrival_range: JUMPGATE_USE_RANGE / 2.0,
+            gate: Some(gate),
+        });
+    }
+
+    let hull = ship_type.collision_radius() + STANDOFF_MARGIN;
+    let (center, standoff, arrival_range) = match autopilot.get_target() {
+        AutopilotTarget::Position(pos) => (*pos, 0.0, POSITION_ARRIVAL_RANGE),
+        AutopilotTarget::StellarObject(sobj_id) => {
+            let sobj_id = StellarObjectId::new(*sobj_id);
+            match dsl.get_stellar_object_by_id(&sobj_id)?.get_kind() {
+                StellarObjectKinds::Station => {
+                    let station = dsl.get_station_by_sobj_id(&sobj_id)?;
+                    let standoff = station.get_size().collision_radius() + hull;
+                    (*station.get_position(), standoff, DOCK_RANGE * 0.9)
+                }
+                StellarObjectKinds::Asteroid => {
+                    let asteroid = dsl.get_asteroid_by_id(&sobj_id)?;
+                    let standoff = asteroid.get_size_radius() + hull;
+                    (*asteroid.get_position(), standoff, MINING_RANGE * 0.8)
+                }
+                StellarObjectKinds::JumpGate => {
+                    let gate = dsl.get_jump_gate_by_id(&sobj_id)?;
+                    (*gate.get_position(), 0.0, JUMPGATE_USE_RANGE / 2.0)
+                }
+                kind => return Err(format!("can't fly to a {:?}", kind)),
+            }
+        }
+    };
+
+    Ok(Waypoint {
+        center,
+        standoff,
+        // Never make the ship stop outside the range it has to reach.
+        arrival_range: arrival_range.max(standoff + POSITION_ARRIVAL_RANGE),
+        gate: None,
+    })
+}
+
+/// The point `waypoint.standoff` px from its centre on the ship's side.
+fn standoff_point(pos: &Vec2, waypoint: &Waypoint) -> Vec2 {
+    let center = waypoint.center.to_glam();
+    let away = (pos.to_glam() - center).normalize_or_zero();
+    Vec2::from_glam(center + away * waypoint.standoff)
+}
+
+/// Picks the (linear, angular) acceleration pair that best closes on `goal`.
+/// See the module docs for the scoring.
+fn plan_controls(state: &MovementState, goal: &Vec2, accel: f32, angular_accel: f32) -> (f32, f32) {
+    let lookahead = state.last_update_time + (AUTOPILOT_TICK_MS as i64) * 1_000;
+
+    let mut best = (0.0, 0.0);
+    let mut best_cost = f32::INFINITY;
+    for linear_a in [accel, 0.0, -accel] {
+        for angular_a in [angular_accel, 0.0, -angular_accel] {
+            let candidate = MovementState {
+                acceleration: linear_a,
+                angular_acceleration: angular_a,
+                ..*state
+            };
+            let (pos, rotation, velocity, omega) = predict_movement(&candidate, lookahead);
+
+            // Where the ship comes to rest if it brakes and counter-steers
+            // from here.
+            let stop_distance = velocity * velocity / (2.0 * accel);
+            let rest_rotation = rotation + omega * omega.abs() / (2.0 * angular_accel);
+            let rest_pos = pos.to_glam() + glam::Vec2::from_angle(rotation) * stop_distance;
+            let miss = rest_pos.distance(goal.to_glam());
+
+            // Heading error only matters while there is distance left to
+            // cover; weight it by that distance so both terms share units.
+            let to_goal = goal.to_glam() - pos.to_glam();
+            let remaining = to_goal.length();
+            let heading_error = if remaining > POSITION_ARRIVAL_RANGE {
+                wrap_angle(to_goal.y.atan2(to_goal.x) - rest_rotation).abs()
+            } else {
+                0.0
+            };
+
+            let cost = miss + heading_error * remaining;
+            if cost < best_cost {
+                best_cost = cost;
+                best = (linear_a, angular_a);
+            }
+        }
+    }
+    best
+}
+
+/// Wraps an angle into [-π, π).
+fn wrap_angle(theta: f32) -> f32 {
+    (theta + PI).rem_euclid(TAU) - PI
+}
+
+/// Shortest route over active jumpgates from `start_pos` in `from` to `to`
+/// (and on to `end_pos`, if known), as the ordered gate sobj ids to fly
+/// through. `Some(vec![])` when already there, `None` when `to` can't be
+/// reached.
+pub fn plan_jump_route<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    from: &SectorId,
+    start_pos: Vec2,
+    to: &SectorId,
+    end_pos: Option<Vec2>,
+) -> Option<Vec<u64>> {
+    let gates: Vec<_> = dsl
+        .get_all_jump_gates()
+        .map(|gate| gate.as_gate_edge())
+        .collect();
+    plan_route(&gates, from.value(), start_pos, to.value(), end_pos).map(|route| route.gates)
+}
+
+/// Where `target` is right now, for weighing the final leg of a route.
+fn target_position<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    target: &AutopilotTarget,
+) -> Result<Vec2, String> {
+    match target {
+        AutopilotTarget::Position(pos) => Ok(*pos),
+        AutopilotTarget::StellarObject(sobj_id) => {
+            get_sobj_position(dsl, &StellarObjectId::new(*sobj_id))
+        }
+    }
+}
+
+/// Hands the ship back to manual control: deletes its autopilot row (if any),
+/// zeroes the commanded accelerations and clears the input mirror so the next
+/// key press isn't swallowed by the no-op check. `full_stop` also zeroes the
+/// remaining speed, for arrivals.
+pub fn disengage_autopilot<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    ship: &Ship,
+    full_stop: bool,
+) -> Result<(), String> {
+    if let Ok(autopilot) = dsl.get_ship_autopilot_by_id(&ship.get_id()) {
+        dsl.delete_ship_autopilot_by_id(autopilot.get_id())?;
+    }
+
+    if *ship.get_location() == ShipLocation::Sector {
+        write_ship_movement_snapshot(dsl, &ship.get_id(), |state| {
+            state.acceleration = 0.0;
+            state.angular_acceleration = 0.0;
+            if full_stop {
+                state.velocity = 0.0;
+                state.angular_velocity = 0.0;
+            }
+        })?;
+    }
+
+    if let Ok(mut controller) = dsl.get_ship_movement_controller_by_id(&ship.get_player_id()) {
+        controller.forward = false;
+        controller.backward = false;
+        controller.left = false;
+        controller.right = false;
+        dsl.update_ship_movement_controller_by_id(controller)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at_rest(rotation: f32) -> MovementState {
+        MovementState {
+            pos: Vec2::ZERO,
+            rotation,
+            last_update_time: 1_000_000,
+            max_speed: 100.0,
+            max_turn_rate: PI,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn thrusts_straight_at_a_goal_ahead() {
+        let (linear, angular) = plan_controls(&at_rest(0.0), &Vec2::new(2_000.0, 0.0), 20.0, 6.0);
+        assert_eq!(linear, 20.0);
+        assert_eq!(angular, 0.0);
+    }
+
+    #[test]
+    fn turns_toward_a_goal_behind() {
+        let (linear, angular) = plan_controls(&at_rest(0.0), &Vec2::new(0.0, 2_000.0), 20.0, 6.0);
+        assert!(linear <= 0.0);
+        assert_eq!(angular, 6.0);
+    }
+
+    #[test]
+    fn brakes_when_the_goal_is_inside_stopping_distance() {
+        let state = MovementState {
+            velocity: 100.0,
+            ..at_rest(0.0)
+        };
+        // 100 px/s at 20 px/s² needs 250 px to stop.
+        let (linear, _) = plan_controls(&state, &Vec2::new(200.0, 0.0), 20.0, 6.0);
+        assert_eq!(linear, -20.0);
+    }
+
+    #[test]
+    fn wrap_angle_stays_in_range() {
+        assert!((wrap_angle(3.0 * PI / 2.0) + PI / 2.0).abs() < 1e-5);
+        assert!((wrap_angle(-3.0 * PI / 2.0) - PI / 2.0).abs() < 1e-5);
+    }
+}
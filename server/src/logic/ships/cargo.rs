@@ -8,6 +8,7 @@ use spacetimedb::*;
 use spacetimedsl::*;
 
 use crate::logic::cargo_crates::attempt_to_pickup_cargo_crate;
+use crate::logic::moderation::refuse_banned;
 use crate::logic::stellarobjects::stellar_object_creation::create_sobj;
 use crate::tables::global_config::*;
 use crate::tables::items::*;
@@ -29,6 +30,7 @@ pub fn try_to_pickup_crate(
     cargo_crate_id: CargoCrateId,
 ) -> Result<(), String> {
     let dsl = dsl(ctx);
+    refuse_banned(&dsl, ctx.sender())?;
     let player_id = PlayerId::new(ctx.sender());
     let (ship_object, _) = get_player_ship_and_sobj(&dsl, &player_id)?;
     let cargo_crate = dsl.get_cargo_crate_by_id(cargo_crate_id)?;
@@ -49,9 +51,7 @@ pub fn jettison_cargo_from_ship(
     amount: u16,
 ) -> Result<(), String> {
     let dsl = dsl(ctx);
-    let ship = dsl.get_ship_by_id(ShipId::new(ship_id))?;
-
-    is_server_or_sobj_owner(&dsl, Some(ship.get_sobj_id()))?;
+    let ship = is_server_or_ship_owner(&dsl, &ShipId::new(ship_id), ShipAccess::Piloted)?;
 
     let mut ship_cargo = dsl.get_ship_cargo_item_by_id(ShipCargoItemId::new(ship_cargo_id))?;
     let item_def = dsl.get_item_definition_by_id(ship_cargo.get_item_id())?;
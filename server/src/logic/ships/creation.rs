@@ -4,10 +4,13 @@ use spacetimedb::{Identity, ReducerContext};
 use spacetimedsl::*;
 
 use crate::{
-    definitions::item_types::*,
+    definitions::{item_types::*, ship_types::SHIP_TYPE_COLUMN},
     logic::{
-        chat_messages::send_galaxy_chat,
-        ships::{cargo::*, movement_controllers::initialize_controller_for_player, status::*},
+        moderation::refuse_banned,
+        ships::{
+            cargo::*, equipment::apply_loadout_stats,
+            movement_controllers::initialize_controller_for_player, status::*,
+        },
         stellarobjects::stellar_object_creation::create_sobj,
     },
     tables::{
@@ -83,6 +86,7 @@ pub fn create_player_controlled_ship(
     username: String, // TODO ReMOVE
 ) -> Result<(), String> {
     let dsl = dsl(ctx);
+    refuse_banned(&dsl, ctx.sender())?;
     let player_id = PlayerId::new(identity);
     let player = match dsl.get_player_by_id(&player_id) {
         Ok(p) => p,
@@ -115,7 +119,7 @@ pub fn create_player_controlled_ship(
     if let Ok(sobj) = create_sobj(&dsl, StellarObjectKinds::Ship, &spawn_sector) {
         initialize_controller_for_player(&dsl, &player_id, &sobj)?;
 
-        let ship_type = dsl.get_ship_type_definition_by_id(ShipTypeDefinitionId::new(1001))?;
+        let ship_type = dsl.get_ship_type_definition_by_id(ShipTypeDefinitionId::new(SHIP_TYPE_COLUMN))?;
         let (ship, mut status) = create_ship_from_sobj(
             &dsl,
             &ship_type,
@@ -158,9 +162,16 @@ pub fn create_player_controlled_ship(
             slot_index: 0,
             item_id: ItemDefinitionId::new(SMOD_IONIC_BLASTER),
         })?;
+        apply_loadout_stats(&dsl, &ship.get_id())?;
 
         info!("Successfully created ship!");
-        send_galaxy_chat(dsl.ctx(), format!("{} has created a ship!", username))?;
+        // Posted directly rather than through `send_galaxy_chat`: an
+        // announcement shouldn't be rate limited or blocked by a Mute.
+        post_galaxy_channel(
+            &dsl,
+            MessageSender::Player(ctx.sender()),
+            format!("{} has created a ship!", username),
+        )?;
         Ok(())
     } else {
         let error_message =
@@ -211,15 +222,14 @@ pub fn create_ship_from_sobj<T: spacetimedsl::WriteContext>(
         movement,
     })?;
 
-    create_status_timer_for_ship(dsl, &ship.get_id(), &ship_type.get_id())?;
-
+    let (shields, energy) = fresh_regen_states(ship_type, movement.last_update_time);
     let ship_status = dsl.create_ship_status(CreateShipStatus {
         id: ship.get_id(),
         sector_id: sobj.get_sector_id(),
         player_id: player_id.clone(),
         health: *ship_type.get_max_health() as f32,
-        shields: *ship_type.get_max_shields() as f32,
-        energy: *ship_type.get_max_energy() as f32,
+        shields,
+        energy,
         weapon_cooldown_ms: 0,
         missile_cooldown_ms: 0,
         used_cargo_capacity: 0,
@@ -229,7 +239,8 @@ pub fn create_ship_from_sobj<T: spacetimedsl::WriteContext>(
     Ok((ship, ship_status))
 }
 
-/// Creates a brand new ship instance docked at a station.
+/// Creates a brand new ship instance docked at a station. Like any Docked
+/// Ship it has no stellar object until it undocks.
 pub fn create_ship_docked_at_station<T: spacetimedsl::WriteContext>(
     dsl: &DSL<T>,
     ship_type: ShipTypeDefinition,
@@ -242,7 +253,7 @@ pub fn create_ship_docked_at_station<T: spacetimedsl::WriteContext>(
     let ship = dsl.create_ship(CreateShip {
         shiptype_id: ship_type.get_id(),
         location: ShipLocation::Station,
-        sobj_id: station.get_sobj_id(),
+        sobj_id: StellarObjectId::new(0), // Docked ships have no sobj
         station_id: station.get_id(),
         sector_id: station.get_sector_id(),
         player_id: player_id.clone(),
@@ -250,15 +261,15 @@ pub fn create_ship_docked_at_station<T: spacetimedsl::WriteContext>(
         movement: solarance_shared::MovementState::default(),
     })?;
 
-    create_status_timer_for_ship(dsl, &ship.get_id(), &ship_type.get_id())?;
-
+    let now = dsl.ctx().timestamp()?.to_micros_since_unix_epoch();
+    let (shields, energy) = fresh_regen_states(&ship_type, now);
     let ship_status = dsl.create_ship_status(CreateShipStatus {
         id: ship.get_id(),
         sector_id: station.get_sector_id(),
         player_id: player_id.clone(),
         health: *ship_type.get_max_health() as f32,
-        shields: *ship_type.get_max_shields() as f32,
-        energy: *ship_type.get_max_energy() as f32,
+        shields,
+        energy,
         weapon_cooldown_ms: 0,
         missile_cooldown_ms: 0,
         used_cargo_capacity: 0,
@@ -0,0 +1,341 @@
+//! # Ship Fitting
+//!
+//! Ship modules are items: they sit in the cargo hold like anything else until
+//! they are fitted into a `ShipEquipmentSlot`. Fitting and unfitting only
+//! happen on a Docked Ship — there's no swapping guns mid-fight.
+//!
+//! ## Slots
+//! Each module type goes into one slot type (`slot_type_for_module`), and a
+//! ship type declares how many slots of each kind it has. Cargo expanders are
+//! bolted onto special hardpoints, so `CargoExpansion` and `Special` modules
+//! share `num_special_slots`.
+//!
+//! ## Derived stats
+//! Whatever the fitted modules add up to is folded into `ShipStatus`
+//! (`max_cargo_capacity`, the shield cap and both regen rates) by
+//! `apply_loadout_stats` every time the loadout changes. Mining reads each
+//! fitted laser on its own (`fitted_mining_lasers`).
+
+use log::info;
+use spacetimedb::ReducerContext;
+use spacetimedsl::*;
+
+use crate::{
+    logic::ships::cargo::{attempt_to_load_cargo_into_ship, remove_cargo_from_ship},
+    tables::{items::*, messages::*, ships::*},
+    utility::{is_server_or_ship_owner, ShipAccess},
+};
+
+/// What a ship's fitted modules add up to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LoadoutStats {
+    /// Added to the ship type's `cargo_capacity`.
+    pub cargo_capacity_boost: u16,
+    /// Added to the ship type's `max_shields`.
+    pub shield_boost: f32,
+    /// Added to the ship type's `shield_regen_per_sec`.
+    pub shield_regen_boost: f32,
+    /// Added to the ship type's `energy_regen_per_sec`.
+    pub energy_regen_boost: f32,
+}
+
+impl Default for LoadoutStats {
+    fn default() -> Self {
+        LoadoutStats {
+            cargo_capacity_boost: 0,
+            shield_boost: 0.0,
+            shield_regen_boost: 0.0,
+            energy_regen_boost: 0.0,
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////
+// Reducers
+///////////////////////////////////////////////////////////
+
+/// Fits one `item_id` from a Docked Ship's cargo hold into the first free
+/// slot of the matching type.
+#[spacetimedb::reducer]
+pub fn equip_module(
+    ctx: &ReducerContext,
+    ship_id: ShipId,
+    item_id: ItemDefinitionId,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    let ship = is_server_or_ship_owner(&dsl, &ship_id, ShipAccess::Docked)?;
+    let player_id = ship.get_player_id();
+    let ship_type = dsl.get_ship_type_definition_by_id(&ship.get_shiptype_id())?;
+    let item_def = dsl.get_item_definition_by_id(&item_id)?;
+
+    let slot_type = match item_def.get_category() {
+        ItemCategory::ShipModule(module_type) => Some(slot_type_for_module(module_type)),
+        ItemCategory::Resource(_) => None,
+    };
+    let Some(slot_type) = slot_type else {
+        let msg = format!("{} isn't a ship module.", item_def.get_name());
+        let _ = send_direct_server_warning(&dsl, &player_id, msg.clone());
+        return Err(msg);
+    };
+
+    let capacity = slot_capacity(&ship_type, &slot_type);
+    let fitted = fitted_in_pool(&dsl, &ship_id, &slot_type);
+    if fitted.len() >= capacity as usize {
+        let msg = format!(
+            "The {} has no free {:?} slot for {} ({} of {} in use).",
+            ship_type.get_name(),
+            slot_type,
+            item_def.get_name(),
+            fitted.len(),
+            capacity
+        );
+        let _ = send_direct_server_warning(&dsl, &player_id, msg.clone());
+        return Err(msg);
+    }
+    // Indices are shared across the pool, like the capacity above.
+    let Some(slot_index) =
+        (0..capacity).find(|index| !fitted.iter().any(|s| s.slot_index == *index))
+    else {
+        let msg = format!(
+            "The {} has no free {:?} slot index for {}.",
+            ship_type.get_name(),
+            slot_type,
+            item_def.get_name()
+        );
+        let _ = send_direct_server_warning(&dsl, &player_id, msg.clone());
+        return Err(msg);
+    };
+
+    if let Err(cargo_err) = remove_cargo_from_ship(
+        &dsl,
+        &mut dsl.get_ship_status_by_id(&ship_id)?,
+        &item_def,
+        1,
+    ) {
+        let msg = format!("Cannot fit {}: {}", item_def.get_name(), cargo_err);
+        let _ = send_direct_server_warning(&dsl, &player_id, msg.clone());
+        return Err(msg);
+    }
+
+    dsl.create_ship_equipment_slot(CreateShipEquipmentSlot {
+        ship_id: ship_id.clone(),
+        slot_type: slot_type.clone(),
+        slot_index,
+        item_id: item_id.clone(),
+    })?;
+    apply_loadout_stats(&dsl, &ship_id)?;
+
+    info!(
+        "equip_module: ship={} item={} slot={:?}#{}",
+        ship_id.value(),
+        item_id.value(),
+        slot_type,
+        slot_index
+    );
+    send_direct_server_info(
+        &dsl,
+        &player_id,
+        format!(
+            "Fitted {} to ship #{}.",
+            item_def.get_name(),
+            ship_id.value()
+        ),
+    )?;
+
+    Ok(())
+}
+
+/// Removes the module in `slot_id` from a Docked Ship and puts it back in the
+/// cargo hold.
+#[spacetimedb::reducer]
+pub fn unequip_module(
+    ctx: &ReducerContext,
+    ship_id: ShipId,
+    slot_id: ShipEquipmentSlotId,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    let ship = is_server_or_ship_owner(&dsl, &ship_id, ShipAccess::Docked)?;
+    let player_id = ship.get_player_id();
+    let ship_type = dsl.get_ship_type_definition_by_id(&ship.get_shiptype_id())?;
+    let slot = dsl.get_ship_equipment_slot_by_id(&slot_id)?;
+    let item_def = dsl.get_item_definition_by_id(&slot.get_item_id())?;
+    let mut ship_status = dsl.get_ship_status_by_id(&ship_id)?;
+
+    // The hold has to fit the module itself *and* shrink by whatever it was
+    // adding, so work out the capacity without it first.
+    let used = ship_status.calculate_used_cargo_space(&dsl);
+    let without = loadout_stats_without(&dsl, &ship_id, Some(&slot_id))?;
+    let capacity_without = ship_type.get_cargo_capacity() + without.cargo_capacity_boost;
+
+    let problem = if slot.get_ship_id() != ship_id {
+        Some(format!(
+            "Slot #{} isn't on ship #{}.",
+            slot_id.value(),
+            ship_id.value()
+        ))
+    } else if *ship.get_location() != ShipLocation::Station {
+        Some(format!(
+            "Ship #{} must be docked to change its equipment.",
+            ship_id.value()
+        ))
+    } else if used + item_def.get_volume_per_unit() > capacity_without {
+        Some(format!(
+            "Cannot unfit {}: the cargo hold would need {}v but only has {}v without it.",
+            item_def.get_name(),
+            used + item_def.get_volume_per_unit(),
+            capacity_without
+        ))
+    } else {
+        None
+    };
+    if let Some(msg) = problem {
+        let _ = send_direct_server_warning(&dsl, &player_id, msg.clone());
+        return Err(msg);
+    }
+
+    dsl.delete_ship_equipment_slot_by_id(&slot_id)?;
+    ship_status = apply_loadout_stats(&dsl, &ship_id)?;
+    attempt_to_load_cargo_into_ship(ctx, &dsl, &mut ship_status, &ship_id, &item_def, 1, false)?;
+
+    info!(
+        "unequip_module: ship={} item={} slot={:?}#{}",
+        ship_id.value(),
+        item_def.get_id().value(),
+        slot.get_slot_type(),
+        slot.slot_index
+    );
+    send_direct_server_info(
+        &dsl,
+        &player_id,
+        format!(
+            "Moved {} from ship #{}'s fittings to its cargo hold.",
+            item_def.get_name(),
+            ship_id.value()
+        ),
+    )?;
+
+    Ok(())
+}
+
+///////////////////////////////////////////////////////////
+// Utilities
+///////////////////////////////////////////////////////////
+
+/// The slot type a ship module is fitted into.
+pub fn slot_type_for_module(module_type: &ShipModuleType) -> EquipmentSlotType {
+    match module_type {
+        ShipModuleType::Engine => EquipmentSlotType::Engine,
+        ShipModuleType::ShieldGenerator => EquipmentSlotType::Shield,
+        ShipModuleType::WeaponKinetic
+        | ShipModuleType::WeaponEnergy
+        | ShipModuleType::WeaponMissile => EquipmentSlotType::Weapon,
+        ShipModuleType::MiningLaserBasic | ShipModuleType::MiningLaserAdvanced => {
+            EquipmentSlotType::MiningLaser
+        }
+        ShipModuleType::CargoExpander => EquipmentSlotType::CargoExpansion,
+        ShipModuleType::ScannerBasic
+        | ShipModuleType::ScannerAdvanced
+        | ShipModuleType::TractorBeam
+        | ShipModuleType::CloakingDevice
+        | ShipModuleType::RepairSystem
+        | ShipModuleType::WarpDrive
+        | ShipModuleType::JumpDrive => EquipmentSlotType::Special,
+    }
+}
+
+/// How many modules of `slot_type` a ship type can carry. Special and cargo
+/// expansion modules draw on the same pool.
+pub fn slot_capacity(ship_type: &ShipTypeDefinition, slot_type: &EquipmentSlotType) -> u8 {
+    match slot_type {
+        EquipmentSlotType::Weapon => ship_type.num_weapon_slots,
+        EquipmentSlotType::Shield => ship_type.num_shield_slots,
+        EquipmentSlotType::Engine => ship_type.num_engine_slots,
+        EquipmentSlotType::MiningLaser => ship_type.num_mining_laser_slots,
+        EquipmentSlotType::Special | EquipmentSlotType::CargoExpansion => {
+            ship_type.num_special_slots
+        }
+    }
+}
+
+/// Sums up what the modules fitted to `ship_id` contribute.
+pub fn loadout_stats<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    ship_id: &ShipId,
+) -> Result<LoadoutStats, String> {
+    loadout_stats_without(dsl, ship_id, None)
+}
+
+/// Recomputes `ShipStatus.max_cargo_capacity`, the shield cap and both regen
+/// rates from the ship type plus its fitted modules, clamping shields to the
+/// new cap.
+pub fn apply_loadout_stats<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    ship_id: &ShipId,
+) -> Result<ShipStatus, String> {
+    let ship = dsl.get_ship_by_id(ship_id)?;
+    let ship_type = dsl.get_ship_type_definition_by_id(&ship.get_shiptype_id())?;
+    let stats = loadout_stats(dsl, ship_id)?;
+
+    let mut ship_status = dsl.get_ship_status_by_id(ship_id)?;
+    ship_status.max_cargo_capacity = ship_type.get_cargo_capacity() + stats.cargo_capacity_boost;
+    let now = dsl.ctx().timestamp()?.to_micros_since_unix_epoch();
+    ship_status.shields.retune(
+        ship_type.get_shield_regen_per_sec() + stats.shield_regen_boost,
+        *ship_type.get_max_shields() as f32 + stats.shield_boost,
+        now,
+    );
+    ship_status.energy.retune(
+        ship_type.get_energy_regen_per_sec() + stats.energy_regen_boost,
+        *ship_type.get_max_energy() as f32,
+        now,
+    );
+    Ok(dsl.update_ship_status_by_id(ship_status)?)
+}
+
+/// The modules fitted to `ship_id` that count against the same slot pool as
+/// `slot_type`.
+fn fitted_in_pool<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    ship_id: &ShipId,
+    slot_type: &EquipmentSlotType,
+) -> Vec<ShipEquipmentSlot> {
+    let pool = |t: &EquipmentSlotType| match t {
+        EquipmentSlotType::CargoExpansion => EquipmentSlotType::Special,
+        other => other.clone(),
+    };
+    dsl.get_ship_equipment_slots_by_ship_id(ship_id)
+        .filter(|slot| pool(slot.get_slot_type()) == pool(slot_type))
+        .collect()
+}
+
+fn loadout_stats_without<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    ship_id: &ShipId,
+    skip: Option<&ShipEquipmentSlotId>,
+) -> Result<LoadoutStats, String> {
+    let mut stats = LoadoutStats::default();
+    for slot in dsl.get_ship_equipment_slots_by_ship_id(ship_id) {
+        if skip.is_some_and(|skip| *skip == slot.get_id()) {
+            continue;
+        }
+        let item_def = dsl.get_item_definition_by_id(&slot.get_item_id())?;
+        for metadata in item_def.get_metadata() {
+            match metadata {
+                ItemMetadata::CargoCapacityBoost(boost) => {
+                    stats.cargo_capacity_boost += boost;
+                }
+                ItemMetadata::ShieldBoost(boost) => {
+                    stats.shield_boost += boost;
+                }
+                ItemMetadata::ShieldRegenBoost(boost) => {
+                    stats.shield_regen_boost += boost;
+                }
+                ItemMetadata::EnergyRegenBoost(boost) => {
+                    stats.energy_regen_boost += boost;
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(stats)
+}
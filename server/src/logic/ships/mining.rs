@@ -1,11 +1,12 @@
-use std::time::Duration;
+use std::{f32::consts::PI, time::Duration};
 
 use log::{info, warn};
+use solarance_shared::{MovementState, Vec2};
 use spacetimedb::*;
 use spacetimedsl::*;
 
 use crate::{
-    logic::ships::add_cargo_timer::*,
+    logic::{sectors::asteroid_fields::*, ships::add_cargo_timer::*},
     tables::{asteroids::*, items::*, messages::*, players::*, ships::*, stellarobjects::*},
     utility::try_server_only,
 };
@@ -16,8 +17,29 @@ use crate::{
 /// drift away while extraction continues.
 pub const MINING_RANGE: f32 = 300.0;
 
+/// Seconds between mining ticks.
+pub const MINING_TICK_SECS: u64 = 3;
+
+/// Ore volume one laser with a `MiningSpeedMultiplier` of 1.0 cuts from an ore
+/// of `Hardness` 1.0 every mining tick.
+pub const MINING_VOLUME_PER_TICK: f32 = 1.0;
+
+/// One fitted mining laser, as its `ItemMetadata` describes it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MiningLaser {
+    pub speed_multiplier: f32,
+    pub energy_per_tick: f32,
+    /// Half-angle the ship must point within for the laser to cut. Lasers
+    /// without a `LockOnAngleBoundRads` cut in any direction.
+    pub lock_on_angle_bound_rads: f32,
+}
+
 #[dsl(plural_name = ship_mining_timers, method(update = true))]
-#[spacetimedb::table(accessor = ship_mining_timer, scheduled(ship_mining_timer_reducer))]
+#[spacetimedb::table(
+    accessor = ship_mining_timer,
+    public,
+    scheduled(ship_mining_timer_reducer)
+)]
 pub struct ShipMiningTimer {
     #[primary_key]
     #[auto_inc]
@@ -34,7 +56,11 @@ pub struct ShipMiningTimer {
     /// FK to StellarObject
     asteroid_sobj_id: u64,
 
-    pub mining_progress: f32, // How much of the asteroid has been mined (0 to 1.0)
+    pub mining_progress: f32, // Ore volume cut towards the next whole unit, carried between ticks
+
+    /// Ore volume per second the lasers cut on the last tick. Clients scale
+    /// the mining beam by it.
+    pub extraction_volume_per_sec: f32,
 }
 
 pub fn create_mining_timer_for_ship<T: spacetimedsl::WriteContext>(
@@ -57,15 +83,124 @@ pub fn create_mining_timer_for_ship<T: spacetimedsl::WriteContext>(
     }
 
     Ok(dsl.create_ship_mining_timer(CreateShipMiningTimer {
-        scheduled_at: spacetimedb::ScheduleAt::Interval(Duration::from_secs(3).into()),
+        scheduled_at: spacetimedb::ScheduleAt::Interval(
+            Duration::from_secs(MINING_TICK_SECS).into(),
+        ),
         ship_sobj_id: ship_sobj_id.clone(),
         asteroid_sobj_id: asteroid_sobj_id.clone(),
         mining_progress: 0.0,
+        extraction_volume_per_sec: 0.0,
     })?)
 }
 
+/// The mining lasers fitted to `ship_id`, in slot order.
+pub fn fitted_mining_lasers<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    ship_id: &ShipId,
+) -> Result<Vec<MiningLaser>, String> {
+    let mut slots: Vec<ShipEquipmentSlot> = dsl
+        .get_ship_equipment_slots_by_ship_id(ship_id)
+        .filter(|slot| *slot.get_slot_type() == EquipmentSlotType::MiningLaser)
+        .collect();
+    slots.sort_by_key(|slot| *slot.get_slot_index());
+
+    let mut lasers = Vec::new();
+    for slot in slots {
+        let item_def = dsl.get_item_definition_by_id(&slot.get_item_id())?;
+        let mut laser = MiningLaser {
+            speed_multiplier: 1.0,
+            energy_per_tick: 0.0,
+            lock_on_angle_bound_rads: PI,
+        };
+        for metadata in item_def.get_metadata() {
+            match metadata {
+                ItemMetadata::MiningSpeedMultiplier(mul) => laser.speed_multiplier = *mul,
+                ItemMetadata::EnergyConsumption(energy) => laser.energy_per_tick = *energy,
+                ItemMetadata::LockOnAngleBoundRads(rads) => laser.lock_on_angle_bound_rads = *rads,
+                _ => {}
+            }
+        }
+        lasers.push(laser);
+    }
+    Ok(lasers)
+}
+
+/// Whether a cone of half-angle `bound_rads` pointing along `rotation` from
+/// `from` touches the circle of `radius` around `target`.
+pub fn cone_touches_circle(
+    from: &Vec2,
+    rotation: f32,
+    bound_rads: f32,
+    target: &Vec2,
+    radius: f32,
+) -> bool {
+    let to_target = target.sub(from);
+    let distance = to_target.length();
+    if distance <= radius {
+        return true;
+    }
+    let off_axis = Vec2::new(rotation.cos(), rotation.sin())
+        .signed_angle_to(&to_target)
+        .abs();
+    off_axis <= bound_rads + (radius / distance).asin()
+}
+
+/// The ore's `Hardness`, or 1.0 if it doesn't declare one.
+pub fn ore_hardness(item_def: &ItemDefinition) -> f32 {
+    item_def
+        .get_metadata()
+        .iter()
+        .find_map(|metadata| match metadata {
+            ItemMetadata::Hardness(hardness) => Some(*hardness),
+            _ => None,
+        })
+        .unwrap_or(1.0)
+}
+
+/// The `lasers` that point at `asteroid` from where the ship is, per
+/// `LockOnAngleBoundRads`.
+fn aimed_lasers<'a>(
+    lasers: &'a [MiningLaser],
+    ship: &MovementState,
+    asteroid: &Asteroid,
+) -> Vec<&'a MiningLaser> {
+    lasers
+        .iter()
+        .filter(|laser| {
+            cone_touches_circle(
+                &ship.pos,
+                ship.rotation,
+                laser.lock_on_angle_bound_rads,
+                asteroid.get_position(),
+                *asteroid.get_size_radius(),
+            )
+        })
+        .collect()
+}
+
+/// Why none of the `aimed` lasers can cut with `energy` to spend, or `None`
+/// if one can.
+fn stall_reason(aimed: &[&MiningLaser], energy: f32) -> Option<String> {
+    let cheapest = aimed
+        .iter()
+        .map(|laser| laser.energy_per_tick)
+        .fold(f32::MAX, f32::min);
+    if aimed.is_empty() {
+        Some("Your mining lasers aren't pointed at the asteroid.".to_string())
+    } else if cheapest > energy {
+        Some(format!(
+            "Your ship does not have enough energy to mine. {:.1} energy / {:.1} required",
+            energy, cheapest
+        ))
+    } else {
+        None
+    }
+}
+
 /// Scheduled reducer that processes ship mining operations against asteroids.
-/// Runs every 3 seconds to extract resources based on mining equipment and energy consumption.
+/// Runs every `MINING_TICK_SECS`: each fitted mining laser that's aimed at the
+/// asteroid and can be powered cuts ore, and whole units go to the cargo hold
+/// via `create_timer_to_add_cargo_to_ship`.
 #[spacetimedb::reducer]
 pub fn ship_mining_timer_reducer(
     ctx: &ReducerContext,
@@ -78,7 +213,7 @@ pub fn ship_mining_timer_reducer(
         .get_ships_by_sobj_id(timer.get_ship_sobj_id())
         .next()
         .ok_or("Couldn't find ship.".to_string())?;
-    let mut asteroid_object = dsl.get_asteroid_by_id(timer.get_asteroid_sobj_id())?;
+    let asteroid_object = dsl.get_asteroid_by_id(timer.get_asteroid_sobj_id())?;
 
     // Range is enforced continuously, not just at start: if the ship has drifted
     // beyond mining range, cancel the operation (delete the timer) and notify.
@@ -109,7 +244,7 @@ pub fn ship_mining_timer_reducer(
     if *asteroid_object.get_current_resources() == 0 {
         dsl.delete_ship_mining_timer_by_id(timer.get_id())?;
 
-        let _ = dsl.delete_stellar_object_by_id(&asteroid_object.get_id());
+        remove_exhausted_asteroid(&dsl, &asteroid_object)?;
 
         let _ = send_direct_server_info(
             &dsl,
@@ -123,29 +258,9 @@ pub fn ship_mining_timer_reducer(
         return Ok(());
     }
 
-    // Get the volume of the asteroid's item type
+    // Get the volume and hardness of the asteroid's item type
     let item_def = dsl.get_item_definition_by_id(asteroid_object.get_resource_item_id())?;
-
-    // Do the logic to determine speed of mining based on mining equipment, item id, etc.
-    let mut energy_consumption = 1.0f32;
-    let mut mining_speed = 1.0f32;
-    for item in dsl.get_ship_equipment_slots_by_ship_id(ship_object.get_id()) {
-        if *item.get_slot_type() == EquipmentSlotType::MiningLaser {
-            let laser_def = dsl.get_item_definition_by_id(item.get_item_id())?;
-            laser_def
-                .get_metadata()
-                .iter()
-                .for_each(|metadata| match metadata {
-                    ItemMetadata::MiningSpeedMultiplier(mul) => {
-                        mining_speed *= mul;
-                    }
-                    ItemMetadata::EnergyConsumption(consumption) => {
-                        energy_consumption += consumption;
-                    }
-                    _ => {}
-                });
-        }
-    }
+    let hardness = ore_hardness(&item_def);
 
     // Find the ship instance so we can check energy and update mining progress
     let mut ship_status =
@@ -158,63 +273,82 @@ pub fn ship_mining_timer_reducer(
                 ))
             })?;
 
-    if ship_status.get_energy() < &energy_consumption {
+    // Lasers unfitted mid-run: nothing will ever cut, so stop for good.
+    let lasers = fitted_mining_lasers(&dsl, &ship_object.get_id())?;
+    if lasers.is_empty() {
+        dsl.delete_ship_mining_timer_by_id(timer.get_id())?;
         let _ = send_direct_server_info(
             &dsl,
             &ship_object.get_player_id(),
-            format!(
-                "Your ship does not have enough energy to mine. {} energy / {} required",
-                ship_status.get_energy(),
-                energy_consumption
-            ),
+            "Mining stopped — your ship has no mining laser fitted.".to_string(),
         );
-        return Err(format!(
-            "Ship {:?} does not have enough energy to mine. Req: {}, Current: {}",
-            ship_object.get_id(),
-            energy_consumption,
-            ship_status.get_energy()
-        ));
+        return Ok(());
     }
 
-    ship_status.set_energy(ship_status.get_energy() - energy_consumption);
-    timer.set_mining_progress(timer.get_mining_progress() + mining_speed);
+    // Fire every laser that's on target, in slot order, while there's energy.
+    let aimed = aimed_lasers(&lasers, &ship_snapshot, &asteroid_object);
 
-    let get_volume_per_unit = &(*item_def.get_volume_per_unit() as f32);
-    if timer.get_mining_progress() >= get_volume_per_unit {
-        let mut diff = timer.get_mining_progress() / get_volume_per_unit;
+    let now = ctx.timestamp.to_micros_since_unix_epoch();
+    let energy = ship_status.current_energy(now);
+    let mut energy_consumption = 0.0;
+    let mut volume_mined = 0.0;
+    for laser in &aimed {
+        if energy_consumption + laser.energy_per_tick > energy {
+            continue;
+        }
+        energy_consumption += laser.energy_per_tick;
+        volume_mined += MINING_VOLUME_PER_TICK * laser.speed_multiplier / hardness;
+    }
 
-        if diff > (*asteroid_object.get_current_resources() as f32) {
-            diff = *asteroid_object.get_current_resources() as f32;
-            asteroid_object.set_current_resources(0);
-            info!("Asteroid exhausted! Mining timer will be removed next cycle.");
-        } else {
-            asteroid_object
-                .set_current_resources(asteroid_object.get_current_resources() - (diff as u16));
+    let was_extracting = *timer.get_extraction_volume_per_sec() > 0.0;
+    timer.set_extraction_volume_per_sec(volume_mined / MINING_TICK_SECS as f32);
+    if volume_mined == 0.0 {
+        // Only say so when the beam goes cold; `try_mining_asteroid` already
+        // said so if it never lit.
+        if was_extracting {
+            if let Some(reason) = stall_reason(&aimed, energy) {
+                let _ = send_direct_server_info(&dsl, &ship_object.get_player_id(), reason);
+            }
         }
+        dsl.update_ship_mining_timer_by_id(timer)?;
+        return Ok(());
+    }
+
+    ship_status.energy.adjust(-energy_consumption, now);
+    timer.set_mining_progress(timer.get_mining_progress() + volume_mined);
 
-        let _ = dsl.update_asteroid_by_id(asteroid_object)?; // TODO handle this properly
-        create_timer_to_add_cargo_to_ship(
+    // Whole units go to the hold; the remainder carries over to the next tick.
+    let volume_per_unit = *item_def.get_volume_per_unit() as f32;
+    let whole_units = (timer.get_mining_progress() / volume_per_unit).floor();
+    if whole_units >= 1.0 {
+        let remaining = *asteroid_object.get_current_resources();
+        let mined = extract_from_asteroid(
             &dsl,
-            ship_object.get_id(),
-            item_def.get_id(),
-            diff.floor() as u16,
+            asteroid_object,
+            whole_units as u16,
+            &ship_snapshot.pos,
         )?;
-
-        timer.set_mining_progress(0.0); //timer.get_mining_progress() - diff.floor()); // Just reset it to 0 instead of letting it roll over
+        if mined == remaining {
+            timer.set_mining_progress(0.0);
+            info!("Asteroid exhausted! Mining timer will be removed next cycle.");
+        } else {
+            timer.set_mining_progress(timer.get_mining_progress() - whole_units * volume_per_unit);
+        }
+        create_timer_to_add_cargo_to_ship(&dsl, ship_object.get_id(), item_def.get_id(), mined)?;
 
         let _ = send_direct_server_info(
             &dsl,
             &ship_object.get_player_id(),
             format!(
                 "Your ship has mined {}x of {}. Attempting to load...",
-                diff.floor() as u16,
+                mined,
                 item_def.get_name()
             ),
         );
         info!(
             "Ship #{:?} mined {}x of {}. Current progress to next item: {}",
             ship_object.get_id(),
-            diff.floor() as u16,
+            mined,
             item_def.get_name(),
             timer.get_mining_progress()
         );
@@ -237,7 +371,10 @@ pub fn try_mining_asteroid(
     let player_id = PlayerId::new(ctx.sender());
     let (ship_object, ship_sobj) = get_player_ship_and_sobj(&dsl, &player_id)?;
 
-    // TODO: Check if the player ship has a mining laser installed.
+    let lasers = fitted_mining_lasers(&dsl, &ship_object.get_id())?;
+    if lasers.is_empty() {
+        return Err("Your ship has no mining laser fitted.".to_string());
+    }
 
     let asteroid_sobj = dsl.get_stellar_object_by_id(asteroid_sobj_id)?;
 
@@ -275,6 +412,17 @@ pub fn try_mining_asteroid(
             );
             let _ =
                 create_mining_timer_for_ship(&dsl, &ship_sobj.get_id(), &asteroid_sobj.get_id())?;
+
+            // The tick only reports a beam going cold, so warn here about
+            // one that won't light at all.
+            let now = ctx.timestamp.to_micros_since_unix_epoch();
+            let energy = dsl
+                .get_ship_status_by_id(ship_object.get_id())?
+                .current_energy(now);
+            let aimed = aimed_lasers(&lasers, &ship_snapshot, &asteroid);
+            if let Some(reason) = stall_reason(&aimed, energy) {
+                let _ = send_direct_server_info(&dsl, &player_id, reason);
+            }
         }
 
         Ok(())
@@ -1,6 +1,8 @@
 pub mod add_cargo_timer;
+pub mod autopilot;
 pub mod cargo;
 pub mod creation;
+pub mod equipment;
 pub mod lifecycle;
 pub mod mining;
 pub mod movement;
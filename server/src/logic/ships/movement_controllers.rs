@@ -2,7 +2,7 @@ use spacetimedb::ReducerContext;
 use spacetimedsl::*;
 
 use crate::{
-    logic::stellarobjects::movement::write_ship_movement_snapshot,
+    logic::{moderation::refuse_banned, stellarobjects::movement::write_ship_movement_snapshot},
     tables::{players::*, ships::*, stellarobjects::*},
 };
 
@@ -20,6 +20,7 @@ pub fn update_ship_movement_controller(
     right: bool,
 ) -> Result<(), String> {
     let dsl = dsl(ctx);
+    refuse_banned(&dsl, ctx.sender())?;
     let player_id = PlayerId::new(ctx.sender());
 
     // Mutual exclusion: pressing both directions on an axis cancels out. Doing
@@ -66,6 +67,11 @@ pub fn update_ship_movement_controller(
         })?;
     let ship_type = dsl.get_ship_type_definition_by_id(ship.get_shiptype_id())?;
 
+    // Any manual input takes the ship back from the autopilot.
+    if let Ok(autopilot) = dsl.get_ship_autopilot_by_id(&ship.get_id()) {
+        dsl.delete_ship_autopilot_by_id(autopilot.get_id())?;
+    }
+
     // Map (forward, backward) → linear acceleration sign × base_acceleration.
     // Map (left, right) → angular acceleration sign × base_angular_acceleration.
     // Released keys yield 0, so the always-on dampening inside
@@ -4,7 +4,8 @@ use spacetimedsl::*;
 
 use crate::{
     logic::{
-        ships::{movement_controllers::initialize_controller_for_player, status::*},
+        moderation::refuse_banned,
+        ships::movement_controllers::initialize_controller_for_player,
         stellarobjects::{
             movement::{transit_ship_to_sector, write_ship_movement_snapshot},
             stellar_object_creation::create_sobj,
@@ -19,12 +20,15 @@ use crate::{
         stations::*,
         stellarobjects::*,
     },
-    utility::is_server_or_ship_owner,
+    utility::{is_server_or_ship_owner, ShipAccess},
 };
 
 ///////////////////////////////////////////////////////////////////////////////////
 ///  Reducers
 
+/// How close (px) a ship must be to a station's centre to dock.
+pub const DOCK_RANGE: f32 = 500.0;
+
 /// Tries to dock to station using the player's current ship.
 pub fn try_to_dock_to_station(ctx: &ReducerContext, station: &Station) -> Result<(), String> {
     let dsl = dsl(ctx);
@@ -65,6 +69,7 @@ pub fn try_to_dock_to_station(ctx: &ReducerContext, station: &Station) -> Result
 #[spacetimedb::reducer]
 pub fn dock_ship(ctx: &ReducerContext, target_sobj_id: u64) -> Result<(), String> {
     let dsl = dsl(ctx);
+    refuse_banned(&dsl, ctx.sender())?;
     let station = dsl
         .get_station_by_sobj_id(&StellarObjectId::new(target_sobj_id))
         .map_err(|_| "Target is not a station".to_string())?;
@@ -75,7 +80,6 @@ pub fn dock_ship(ctx: &ReducerContext, target_sobj_id: u64) -> Result<(), String
         crate::logic::stellarobjects::movement::get_ship_movement_snapshot(&dsl, &ship_object.get_id())?;
     let dist = ship_snapshot.pos.distance_to(station.get_position());
 
-    const DOCK_RANGE: f32 = 500.0;
     if dist > DOCK_RANGE {
         return Err(format!("Too far to dock ({dist:.0} > {DOCK_RANGE})"));
     }
@@ -88,10 +92,11 @@ pub fn dock_ship(ctx: &ReducerContext, target_sobj_id: u64) -> Result<(), String
 #[spacetimedb::reducer]
 pub fn undock_ship(ctx: &ReducerContext, ship: Ship) -> Result<(), String> {
     let dsl = dsl(ctx);
-    is_server_or_ship_owner(&dsl, Some(ship.get_id().clone()))?;
+    // Only the id of the client's copy is trusted; the rest comes from the table.
+    let ship = is_server_or_ship_owner(&dsl, &ship.get_id(), ShipAccess::Any)?;
 
     // Exit early if the player is already controlling an in-sector ship.
-    let player_id = PlayerId::new(ctx.sender());
+    let player_id = ship.get_player_id();
     let already_in_sector = dsl
         .get_ships_by_player_id(&player_id)
         .any(|s| *s.get_location() == ShipLocation::Sector);
@@ -120,6 +125,7 @@ pub fn undock_ship(ctx: &ReducerContext, ship: Ship) -> Result<(), String> {
 #[spacetimedb::reducer]
 pub fn use_jumpgate(ctx: &ReducerContext, jumpgate_sobj_id: u64) -> Result<(), String> {
     let dsl = dsl(ctx);
+    refuse_banned(&dsl, ctx.sender())?;
     let jumpgate = dsl
         .get_jump_gate_by_id(&StellarObjectId::new(jumpgate_sobj_id))
         .map_err(|_| format!("No jumpgate at sobj #{}", jumpgate_sobj_id))?;
@@ -129,19 +135,37 @@ pub fn use_jumpgate(ctx: &ReducerContext, jumpgate_sobj_id: u64) -> Result<(), S
 /// Hard proximity check for jumpgate activation. Same units as ship pos
 /// (pixels). Matches the dock range in spirit — you have to fly up to the
 /// gate before it'll fire.
-const JUMPGATE_USE_RANGE: f32 = 300.0;
+pub const JUMPGATE_USE_RANGE: f32 = 300.0;
 const JUMPGATE_USE_ENERGY: f32 = 50.0;
 
 pub fn try_to_use_jumpgate(ctx: &ReducerContext, jumpgate: &JumpGate) -> Result<(), String> {
     let dsl = dsl(ctx);
     let (ship_object, _) = get_player_ship_and_sobj(&dsl, &PlayerId::new(ctx.sender()))?;
+    jump_ship_through_gate(&dsl, &ship_object, jumpgate)?;
+    Ok(())
+} // try_to_use_jumpgate
+
+/////////////////////////////////////////////////////////////////////////////
+///  Utilities
+
+/// Sends `ship` through `jumpgate` if it is in range and has the energy.
+/// Shared by the `use_jumpgate` reducer and the autopilot, which calls it
+/// with no player sender.
+///
+/// Returns whether the jump happened; `Ok(false)` means the ship is short on
+/// energy and may retry once it has recharged.
+pub fn jump_ship_through_gate<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    ship_object: &Ship,
+    jumpgate: &JumpGate,
+) -> Result<bool, String> {
     let mut ship_status = dsl.get_ship_status_by_id(ship_object.get_id())?;
 
     // Predicted-forward ship pos vs. the gate's static position. Reject if
     // the ship is too far away — this used to be missing, so a player on
     // the far side of the sector could still trigger the jump.
     let ship_snapshot = crate::logic::stellarobjects::movement::get_ship_movement_snapshot(
-        &dsl,
+        dsl,
         &ship_object.get_id(),
     )?;
     let dist = ship_snapshot.pos.distance_to(jumpgate.get_position());
@@ -153,18 +177,19 @@ pub fn try_to_use_jumpgate(ctx: &ReducerContext, jumpgate: &JumpGate) -> Result<
     }
 
     // Jump once they have more than JUMPGATE_USE_ENERGY energy
-    if *ship_status.get_energy() > JUMPGATE_USE_ENERGY {
+    let now = dsl.ctx().timestamp()?.to_micros_since_unix_epoch();
+    if ship_status.current_energy(now) > JUMPGATE_USE_ENERGY {
         let arrival_pos = *jumpgate.get_target_gate_arrival_pos();
         let arrival_rotation = *jumpgate.get_target_gate_arrival_rotation();
         let destination_sector = dsl.get_sector_by_id(jumpgate.get_target_sector_id())?;
 
-        ship_status.set_energy(ship_status.get_energy() - JUMPGATE_USE_ENERGY);
+        ship_status.energy.adjust(-JUMPGATE_USE_ENERGY, now);
         dsl.update_ship_status_by_id(ship_status)?;
 
         // Single helper does all the sector_id updates + clean-stop snapshot
         // so a partial failure can't leave the ship half-transitioned.
         transit_ship_to_sector(
-            &dsl,
+            dsl,
             &ship_object.get_id(),
             &destination_sector.get_id(),
             arrival_pos,
@@ -172,7 +197,7 @@ pub fn try_to_use_jumpgate(ctx: &ReducerContext, jumpgate: &JumpGate) -> Result<
         )?;
 
         send_direct_server_info(
-            &dsl,
+            dsl,
             &ship_object.get_player_id(),
             format!(
                 "Jumped successfully via jumpgate to sector #{}: {}",
@@ -180,15 +205,12 @@ pub fn try_to_use_jumpgate(ctx: &ReducerContext, jumpgate: &JumpGate) -> Result<
                 destination_sector.get_name()
             ),
         )?;
+        Ok(true)
     } else {
         // TODO: Send a message to the player saying they don't have JUMPGATE_USE_ENERGY
+        Ok(false)
     }
-
-    Ok(())
-} // try_to_use_jumpgate
-
-/////////////////////////////////////////////////////////////////////////////
-///  Utilities
+}
 
 /// Creates the Ship object plus removes the Ship and StellarObject but keeps the cargo, health, etc.
 pub fn dock_to_station<T: spacetimedsl::WriteContext>(
@@ -236,7 +258,6 @@ pub fn undock_from_station<T: spacetimedsl::WriteContext>(
     docked: &Ship,
 ) -> Result<Ship, String> {
     let station = dsl.get_station_by_id(docked.get_station_id())?;
-    let ship_type = dsl.get_ship_type_definition_by_id(docked.get_shiptype_id())?;
 
     let sobj = create_sobj(dsl, StellarObjectKinds::Ship, &station.get_sector_id())?;
 
@@ -260,14 +281,6 @@ pub fn undock_from_station<T: spacetimedsl::WriteContext>(
         state.angular_acceleration = 0.0;
     })?;
 
-    // Ensure there's still a ship status timer.
-    if dsl
-        .get_ship_status_timer_by_ship_id(docked.get_id())
-        .is_err()
-    {
-        let _ = create_status_timer_for_ship(dsl, &ship.get_id(), &ship_type.get_id());
-    }
-
     let _ = initialize_controller_for_player(dsl, &docked.get_player_id(), &sobj);
 
     send_direct_server_info(
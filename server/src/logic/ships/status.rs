@@ -1,81 +1,35 @@
-use std::time::Duration;
-
-use spacetimedb::*;
-use spacetimedsl::*;
-
-use crate::{tables::ships::*, utility::try_server_only};
-
-#[dsl(plural_name = ship_status_timers, method(update = false))]
-#[spacetimedb::table(accessor = ship_status_timer, scheduled(ship_status_timer_reducer))]
-pub struct ShipStatusTimer {
-    #[primary_key]
-    #[auto_inc]
-    #[create_wrapper]
-    id: u64,
-    scheduled_at: spacetimedb::ScheduleAt,
-
-    #[unique]
-    #[use_wrapper(ShipId)]
-    /// FK to Ship
-    ship_id: u64,
-
-    #[use_wrapper(ShipTypeDefinitionId)]
-    /// FK to Ship Type
-    ship_type_id: u32,
-}
-
-pub fn create_status_timer_for_ship<T: spacetimedsl::WriteContext>(
-    dsl: &DSL<T>,
-    ship_id: &ShipId,
-    type_id: &ShipTypeDefinitionId,
-) -> Result<ShipStatusTimer, String> {
-    let timer = dsl.create_ship_status_timer(CreateShipStatusTimer {
-        scheduled_at: spacetimedb::ScheduleAt::Interval(Duration::from_millis(500).into()),
-        ship_id: ship_id.clone(),
-        ship_type_id: type_id.clone(),
-    })?;
-
-    Ok(timer)
-}
-
-/// Scheduled reducer that handles ship status updates like shield and energy regeneration.
-/// Runs every 500ms to gradually restore shields and energy based on ship type specifications.
-#[spacetimedb::reducer]
-pub fn ship_status_timer_reducer(
-    ctx: &ReducerContext,
-    timer: ShipStatusTimer,
-) -> Result<(), String> {
-    let dsl = dsl(ctx);
-    try_server_only(&dsl)?;
-
-    // Get ship rows
-    let mut changes = false;
-    let ship_type = dsl.get_ship_type_definition_by_id(timer.get_ship_type_id())?;
-    let mut ship_status = dsl.get_ship_status_by_id(timer.get_ship_id())?;
-
-    // TODO: Grab shield regen from attached shield modules and the current ship type
-    if *ship_status.get_shields() < (*ship_type.get_max_shields() as f32) {
-        ship_status.set_shields(*ship_status.get_shields() + 0.525175);
-        changes = true;
-    }
-    if *ship_status.get_energy() > (*ship_type.get_max_shields() as f32) {
-        ship_status.set_shields(*ship_type.get_max_shields() as f32);
-        changes = true;
-    }
-
-    // TODO: Grab energy regen from attached special modules and the current ship type
-    if *ship_status.get_energy() < (*ship_type.get_max_energy() as f32) {
-        ship_status.set_energy(ship_status.get_energy() + 0.1275);
-        changes = true;
-    }
-    if *ship_status.get_energy() > (*ship_type.get_max_energy() as f32) {
-        ship_status.set_energy(*ship_type.get_max_energy() as f32);
-        changes = true;
-    }
-
-    if (changes) {
-        dsl.update_ship_status_by_id(ship_status)?;
-    }
-
-    Ok(())
+//! # Ship shields and energy
+//!
+//! `ShipStatus.shields` and `.energy` are `RegenState` snapshots: nothing
+//! ticks them. Code that needs the value reads it at the reducer's timestamp
+//! (`ShipStatus::current_energy`, `current_shields`) and code that changes it
+//! re-snapshots (`RegenState::adjust`, `ShipStatus::try_spend_energy`).
+//! Clients extrapolate the same snapshots with `predict_regen` for their
+//! gauges.
+//!
+//! Rates and caps come from the ship type plus fitted modules and are
+//! refreshed by `apply_loadout_stats` whenever the loadout changes.
+
+use solarance_shared::RegenState;
+
+use crate::tables::ships::*;
+
+/// Full shields and energy for a freshly built, unfitted ship of `ship_type`
+/// as of `current_time`.
+pub fn fresh_regen_states(
+    ship_type: &ShipTypeDefinition,
+    current_time: i64,
+) -> (RegenState, RegenState) {
+    (
+        RegenState::full(
+            *ship_type.get_max_shields() as f32,
+            *ship_type.get_shield_regen_per_sec(),
+            current_time,
+        ),
+        RegenState::full(
+            *ship_type.get_max_energy() as f32,
+            *ship_type.get_energy_regen_per_sec(),
+            current_time,
+        ),
+    )
 }
@@ -4,24 +4,16 @@ use spacetimedb::{reducer, ReducerContext};
 use spacetimedsl::*;
 
 use crate::{
-    logic::combat::actions::*,
-    tables::{
-        players::*, ships::*,
-        stellarobjects::*,
-    },
+    logic::{combat::actions::*, moderation::refuse_banned},
+    tables::{players::*, stellarobjects::*},
 };
 
 #[reducer]
 pub fn fire_weapons(ctx: &ReducerContext, target_sobj_id: u64) -> Result<(), String> {
     let dsl = dsl(ctx);
+    refuse_banned(&dsl, ctx.sender())?;
 
-    let ship = dsl
-        .get_ships_by_player_id(PlayerId::new(ctx.sender()))
-        .next()
-        .ok_or(format!(
-            "Ship couldn't be found for playerId:: {}",
-            ctx.sender()
-        ))?;
+    let (ship, _) = get_player_ship_and_sobj(&dsl, &PlayerId::new(ctx.sender()))?;
 
     let target_sobj = dsl.get_stellar_object_by_id(StellarObjectId::new(target_sobj_id))?;
 
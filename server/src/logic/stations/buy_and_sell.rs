@@ -1,11 +1,33 @@
 use crate::{
-    logic::ships::cargo::{attempt_to_load_cargo_into_ship, remove_cargo_from_ship},
+    logic::{
+        ships::cargo::{attempt_to_load_cargo_into_ship, remove_cargo_from_ship},
+        stations::module_types::trading_port::*,
+    },
     tables::{items::*, messages::*, players::*, ships::*, stations::*},
-    utility::is_server_or_ship_owner,
+    utility::{is_server_or_ship_owner, ShipAccess},
     *,
 };
+use solarance_shared::TradeSide;
 use spacetimedb::{log::info, ReducerContext};
 
+/// The player's standing discount at the station `station_module` belongs
+/// to. A station that won't trade with them says so to the player.
+pub fn trading_standing<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    station_module: &StationModule,
+    player: &Player,
+) -> Result<f32, String> {
+    let station = dsl.get_station_by_id(station_module.get_station_id())?;
+    standing_discount(
+        dsl,
+        &station.get_owner_faction_id(),
+        player.get_faction_id(),
+    )
+    .inspect_err(|error| {
+        let _ = send_direct_server_warning(dsl, &player.get_id(), error.clone());
+    })
+}
+
 ///////////////////////////////////////////////////////////
 // Reducers ///
 ///////////////////////////////////////////////////////////
@@ -21,34 +43,18 @@ pub fn buy_item_from_station_module(
     quantity: u32,
 ) -> Result<(), String> {
     let dsl = dsl(ctx);
-    is_server_or_ship_owner(&dsl, Some(ship_id.clone()))?;
-
-    let ship = dsl.get_ship_by_id(&ship_id)?;
-
-    // Validate that the docked ship is at the same station as the module
     let station_module = dsl.get_station_module_by_id(&station_module_id)?;
-    if ship.get_station_id() != station_module.get_station_id() {
-        let player_id = ship.get_player_id().clone();
-        let error_message = format!(
-            "Cannot buy from station module: Your ship is docked at station {} but the module is at station {}.",
-            ship.get_station_id(),
-            station_module.get_station_id()
-        );
-
-        // Send server message for error feedback
-        send_direct_server_warning(
-            &dsl,
-            &player_id,
-            error_message.clone(),
-        )?;
-
-        return Err(error_message);
-    }
+    let ship = is_server_or_ship_owner(
+        &dsl,
+        &ship_id,
+        ShipAccess::DockedAt(station_module.get_station_id()),
+    )?;
 
     // Get Trading Port Module and it's inventory item that matches the item_id
     //let trading_port_module = dsl.get_trading_port_module_by_id(&station_module_id)?;
 
-    // TODO: Check faction standing.
+    let mut player = dsl.get_player_by_id(ship.get_player_id())?;
+    let standing = trading_standing(&dsl, &station_module, &player)?;
 
     let mut item_listing = dsl
         .get_station_module_inventory_items_by_module_id(&station_module_id)
@@ -99,11 +105,12 @@ pub fn buy_item_from_station_module(
         ));
     }
 
-    // Use cached current price for performance
-    let total_price = item_listing.get_cached_price() * quantity;
+    // Priced unit by unit as the station's stock falls
+    let now = ctx.timestamp.to_micros_since_unix_epoch();
+    let quote = market_quote(&dsl, &item_listing, &item_def, standing, now);
+    let total_price = quote.total(TradeSide::Buy, quantity);
 
     // Check if the player has enough credits
-    let mut player = dsl.get_player_by_id(ship.get_player_id())?;
     if (total_price as u64) > *player.get_credits() {
         let player_id = ship.get_player_id().clone();
         let error_message = format!(
@@ -160,8 +167,17 @@ pub fn buy_item_from_station_module(
     // TOOD: Add credits to station
     dsl.update_player_by_id(player)?;
 
+    record_trade(
+        &dsl,
+        &item_listing.get_id(),
+        &quote,
+        TradeSide::Buy,
+        quantity,
+        now,
+    )?;
     item_listing.set_quantity(item_listing.get_quantity() - quantity);
-    item_listing.set_cached_price(item_listing.calculate_current_price(&item_def));
+    let after = market_quote(&dsl, &item_listing, &item_def, 0.0, now);
+    item_listing.set_cached_price(after.mid_price().round() as u32);
     dsl.update_station_module_inventory_item_by_id(item_listing)?;
 
     send_direct_server_info(
@@ -190,11 +206,13 @@ pub fn sell_item_to_station_module(
     quantity: u32,
 ) -> Result<(), String> {
     let dsl = dsl(ctx);
-    is_server_or_ship_owner(&dsl, Some(ship_id.clone()))?;
-    let ship = dsl.get_ship_by_id(&ship_id)?;
     let station_module = dsl.get_station_module_by_id(&station_module_id)?;
+    let ship = is_server_or_ship_owner(
+        &dsl,
+        &ship_id,
+        ShipAccess::DockedAt(station_module.get_station_id()),
+    )?;
 
-    // Validate that the docked ship is at the same station as the module
     info!(
         "Attempting to sell {}x {} from ship {} to trading port {}",
         quantity,
@@ -203,28 +221,11 @@ pub fn sell_item_to_station_module(
         station_module_id
     );
 
-    if ship.get_station_id() != station_module.get_station_id() {
-        let player_id = ship.get_player_id().clone();
-        let error_message = format!(
-            "Cannot sell to station module: Your ship is docked at station {} but the module is at station {}.",
-            ship.get_station_id(),
-            station_module.get_station_id()
-        );
-
-        // Send server message for error feedback
-        send_direct_server_warning(
-            &dsl,
-            &player_id,
-            error_message.clone(),
-        )?;
-
-        return Err(error_message);
-    }
-
     // Get Trading Port Module and it's inventory item that matches the item_id
     ////let trading_port_module = dsl.get_trading_port_module_by_id(&station_module_id)?;
 
-    // TODO: Check faction standing.
+    let mut player = dsl.get_player_by_id(ship.get_player_id())?;
+    let standing = trading_standing(&dsl, &station_module, &player)?;
 
     let mut item_listing = dsl
         .get_station_module_inventory_items_by_module_id(&station_module_id)
@@ -274,8 +275,10 @@ pub fn sell_item_to_station_module(
         ));
     }
 
-    // Use cached current price for performance
-    let total_price = item_listing.get_cached_price() * quantity; // cache buy/sell prices separately
+    // Priced unit by unit as the station's stock rises
+    let now = ctx.timestamp.to_micros_since_unix_epoch();
+    let quote = market_quote(&dsl, &item_listing, &item_def, standing, now);
+    let total_price = quote.total(TradeSide::Sell, quantity);
 
     // Check if the station has enough credits
     //if total_price <= *station.get_credits() {
@@ -303,12 +306,20 @@ pub fn sell_item_to_station_module(
         return Err(cargo_err);
     }
 
-    let mut player = dsl.get_player_by_id(ship.get_player_id())?;
     player.set_credits(player.get_credits() + &(total_price as u64));
     dsl.update_player_by_id(player)?;
 
+    record_trade(
+        &dsl,
+        &item_listing.get_id(),
+        &quote,
+        TradeSide::Sell,
+        quantity,
+        now,
+    )?;
     item_listing.set_quantity(item_listing.get_quantity() + quantity);
-    item_listing.set_cached_price(item_listing.calculate_current_price(&item_def));
+    let after = market_quote(&dsl, &item_listing, &item_def, 0.0, now);
+    item_listing.set_cached_price(after.mid_price().round() as u32);
     dsl.update_station_module_inventory_item_by_id(item_listing)?;
 
     send_direct_server_info(
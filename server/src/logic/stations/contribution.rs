@@ -4,6 +4,8 @@ use spacetimedsl::*;
 use crate::{
     logic::ships::cargo::remove_cargo_from_ship,
     logic::stations::create_station_with_modules,
+    logic::stations::growth::{complete_growth_stage, open_growth_pool},
+    logic::stations::leaderboards::{rebuild_contribution_tallies, record_contribution_tally},
     logic::stellarobjects::movement::get_ship_movement_snapshot,
     tables::{
         economy::ResourceAmount,
@@ -78,13 +80,18 @@ pub fn compute_construction_progress(
 // DSL-bound helpers
 ///////////////////////////////////////////////////////////
 
-/// Sum every contribution row for the given station, grouped by item id.
+/// Sum every contribution row for the given station's `stage` pool, grouped
+/// by item id.
 fn aggregate_contributions<T: spacetimedsl::WriteContext>(
     dsl: &DSL<T>,
     station_id: &StationId,
+    stage: u32,
 ) -> Vec<(u32, u32)> {
     let mut totals: Vec<(u32, u32)> = Vec::new();
-    for log in dsl.get_construction_contribution_logs_by_station_id(station_id) {
+    for log in dsl
+        .get_construction_contribution_logs_by_station_id(station_id)
+        .filter(|log| *log.get_stage() == stage && log.get_module_proposal_id().is_none())
+    {
         let item_id = log.get_item_id().value();
         let qty = *log.get_quantity();
         if let Some(entry) = totals.iter_mut().find(|(id, _)| *id == item_id) {
@@ -96,40 +103,56 @@ fn aggregate_contributions<T: spacetimedsl::WriteContext>(
     totals
 }
 
-/// Collect the requirement spec for a station as `(item_id, required)` pairs.
+/// Collect the requirement spec for a station's `stage` pool as
+/// `(item_id, required)` pairs.
 fn collect_requirements<T: spacetimedsl::WriteContext>(
     dsl: &DSL<T>,
     station_id: &StationId,
+    stage: u32,
 ) -> Vec<(u32, u32)> {
     dsl.get_construction_requirements_by_station_id(station_id)
-        .map(|req| (req.get_resource_item_id().value(), *req.get_quantity_required()))
+        .filter(|req| req.stage == stage)
+        .map(|req| {
+            (
+                req.get_resource_item_id().value(),
+                *req.get_quantity_required(),
+            )
+        })
         .collect()
 }
 
-/// Recompute progress for a single station from current table state and
-/// persist the new percentage. If progress hits 100% and the site was not
-/// already flagged operational, flip the bit and broadcast a system
-/// completion message to every logged-in player.
+/// Recompute progress of the station's open pool from current table state
+/// and persist the new percentage. If the initial build hits 100%, flip the
+/// site operational, broadcast a system completion message to every
+/// logged-in player and open the first growth pool. If a growth pool hits
+/// 100%, grow the station (see `growth.rs`).
 fn refresh_station_progress<T: spacetimedsl::WriteContext>(
     dsl: &DSL<T>,
     station_id: &StationId,
 ) -> Result<f32, String> {
-    let requirements = collect_requirements(dsl, station_id);
-    let contributions = aggregate_contributions(dsl, station_id);
+    let mut under_construction = dsl.get_station_under_construction_by_id(station_id)?;
+    let stage = *under_construction.get_stage();
+    let requirements = collect_requirements(dsl, station_id, stage);
+    let contributions = aggregate_contributions(dsl, station_id, stage);
     let progress = compute_construction_progress(&requirements, &contributions);
 
-    let mut under_construction = dsl.get_station_under_construction_by_id(station_id)?;
     let was_operational = *under_construction.get_is_operational();
     under_construction.set_construction_progress_percentage(progress);
 
     let now_complete = progress >= 100.0 && !was_operational;
+    let grown_to = match progress >= 100.0 && was_operational {
+        true => under_construction.get_growing_to().clone(),
+        false => None,
+    };
     if now_complete {
         under_construction.set_is_operational(true);
     }
 
-    dsl.update_station_under_construction_by_id(under_construction)?;
+    let under_construction = dsl.update_station_under_construction_by_id(under_construction)?;
 
-    if now_complete {
+    if let Some(size) = grown_to {
+        complete_growth_stage(dsl, station_id, under_construction, size)?;
+    } else if now_complete {
         let station = dsl.get_station_by_id(station_id)?;
         // Construction completion is a genuinely async, everyone-relevant event:
         // post it to the Galaxy channel as System. Replaces the old per-player
@@ -143,6 +166,7 @@ fn refresh_station_progress<T: spacetimedsl::WriteContext>(
                 station_id.value()
             ),
         )?;
+        open_growth_pool(dsl, &station, under_construction)?;
     }
 
     Ok(progress)
@@ -191,6 +215,8 @@ pub fn create_construction_site<T: spacetimedsl::WriteContext + 'static>(
         id: station.get_id(),
         is_operational: false,
         construction_progress_percentage: 0.0,
+        stage: 0,
+        growing_to: None,
     })?;
 
     for req in requirements {
@@ -205,21 +231,33 @@ pub fn create_construction_site<T: spacetimedsl::WriteContext + 'static>(
             station_id: station.get_id(),
             resource_item_id: ItemDefinitionId::new(req.resource_item_id),
             quantity_required: req.quantity,
+            stage: 0,
         })?;
     }
 
     Ok(station)
 }
 
-/// Wipe every contribution row for the station and zero the progress bar.
-/// Used by `admin_reset_construction_site` so the designer can replay the
-/// completion moment without re-publishing the module.
+/// Wipe every contribution row for the station's open pool and zero the
+/// progress bar. Used by `admin_reset_construction_site` so the designer can
+/// replay the completion (or growth) moment without re-publishing the module.
+/// Earlier stages are left alone; a fully grown station has nothing to reset.
 pub fn reset_construction_site<T: spacetimedsl::WriteContext>(
     dsl: &DSL<T>,
     station_id: &StationId,
 ) -> Result<(), String> {
+    let mut under_construction = dsl.get_station_under_construction_by_id(station_id)?;
+    if !under_construction.has_open_pool() {
+        return Err(format!(
+            "reset_construction_site: station {} is fully grown, no pool to reset",
+            station_id.value()
+        ));
+    }
+    let stage = *under_construction.get_stage();
+
     let log_ids: Vec<_> = dsl
         .get_construction_contribution_logs_by_station_id(station_id)
+        .filter(|log| *log.get_stage() == stage && log.get_module_proposal_id().is_none())
         .map(|log| log.get_id().clone())
         .collect();
     let cleared = log_ids.len();
@@ -227,14 +265,17 @@ pub fn reset_construction_site<T: spacetimedsl::WriteContext>(
         dsl.delete_construction_contribution_log_by_id(&id)?;
     }
 
-    let mut under_construction = dsl.get_station_under_construction_by_id(station_id)?;
-    under_construction.set_is_operational(false);
+    if stage == 0 {
+        under_construction.set_is_operational(false);
+    }
     under_construction.set_construction_progress_percentage(0.0);
     dsl.update_station_under_construction_by_id(under_construction)?;
+    rebuild_contribution_tallies(dsl)?;
 
     log::info!(
-        "reset_construction_site: station_id={} cleared {} contribution log rows",
+        "reset_construction_site: station_id={} stage={} cleared {} contribution log rows",
         station_id.value(),
+        stage,
         cleared
     );
     Ok(())
@@ -281,9 +322,9 @@ pub fn contribute_to_station(
             )
         })?;
 
-    if *under_construction.get_is_operational() {
+    if !under_construction.has_open_pool() {
         let msg = format!(
-            "Station {} is already operational — no further contributions accepted.",
+            "Station {} is fully grown — no further contributions accepted.",
             station_id.value()
         );
         let _ = send_direct_server_warning(&dsl, &player_id, msg.clone());
@@ -321,7 +362,9 @@ pub fn contribute_to_station(
 
     let requirement = dsl
         .get_construction_requirements_by_station_id(&station_id)
-        .find(|r| r.get_resource_item_id() == item_id);
+        .find(|r| {
+            r.get_resource_item_id() == item_id && r.stage == *under_construction.get_stage()
+        });
     let requirement = match requirement {
         Some(r) => r,
         None => {
@@ -336,7 +379,7 @@ pub fn contribute_to_station(
         }
     };
 
-    let contributions = aggregate_contributions(&dsl, &station_id);
+    let contributions = aggregate_contributions(&dsl, &station_id, *under_construction.get_stage());
     let already_contributed: u32 = contributions
         .iter()
         .filter(|(id, _)| *id == item_id.value())
@@ -397,8 +440,18 @@ pub fn contribute_to_station(
         player_id: player_id.clone(),
         item_id,
         quantity: effective_qty,
+        stage: *under_construction.get_stage(),
+        module_proposal_id: None,
         contributed_at: ctx.timestamp,
     })?;
+    record_contribution_tally(
+        &dsl,
+        &station,
+        &player_id,
+        &item_def,
+        effective_qty,
+        ctx.timestamp,
+    )?;
 
     let new_progress = refresh_station_progress(&dsl, &station_id)?;
 
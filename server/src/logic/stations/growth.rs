@@ -0,0 +1,123 @@
+//! # Station growth
+//!
+//! A construction site doesn't stop at operational. Finishing the initial
+//! build opens a growth Contribution Pool for the next `StationSize`
+//! (Outpost → Small → Medium → Large); filling it grows the station one size
+//! up, which raises `StationSize::max_module_amount`, and opens the pool for
+//! the size after that. Each pool is its own `stage` on
+//! `StationUnderConstruction`, `ConstructionRequirement` and
+//! `ConstructionContributionLog`, so contributions to one stage never count
+//! toward the next.
+//!
+//! Clients pick the station sprite from `Station.size`, so growing a station
+//! is all it takes to change how it looks.
+
+use spacetimedsl::*;
+
+use crate::{
+    definitions::item_types::*,
+    logic::stations::verify,
+    tables::{
+        economy::ResourceAmount,
+        items::ItemDefinitionId,
+        messages::{post_galaxy_channel, MessageSender},
+        stations::*,
+    },
+};
+
+/// What it takes to grow a station into `target`: ore and energy cells,
+/// scaled by how many module slots the new size has.
+pub fn growth_requirements(target: &StationSize) -> Vec<ResourceAmount> {
+    let slots = target.max_module_amount() as u32;
+    vec![
+        ResourceAmount::new(ITEM_IRON_ORE, 40 * slots),
+        ResourceAmount::new(ITEM_SILICON_ORE, 25 * slots),
+        ResourceAmount::new(ITEM_CARBON_ORE, 15 * slots),
+        ResourceAmount::new(ITEM_ENERGY_CELL, 10 * slots),
+    ]
+}
+
+/// Opens the next growth pool for `station`, or marks it fully grown when its
+/// size has nowhere left to go.
+pub fn open_growth_pool<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    station: &Station,
+    mut under_construction: StationUnderConstruction,
+) -> Result<(), String> {
+    let Some(next) = station.get_size().next_growth_size() else {
+        under_construction.set_growing_to(None);
+        dsl.update_station_under_construction_by_id(under_construction)?;
+        return Ok(());
+    };
+
+    let stage = *under_construction.get_stage() + 1;
+    under_construction.set_stage(stage);
+    under_construction.set_growing_to(Some(next.clone()));
+    under_construction.set_construction_progress_percentage(0.0);
+    dsl.update_station_under_construction_by_id(under_construction)?;
+
+    for req in growth_requirements(&next) {
+        dsl.create_construction_requirement(CreateConstructionRequirement {
+            station_id: station.get_id(),
+            resource_item_id: ItemDefinitionId::new(req.resource_item_id),
+            quantity_required: req.quantity,
+            stage,
+        })?;
+    }
+
+    log::info!(
+        "open_growth_pool: station_id={} stage={} growing to {:?}",
+        station.get_id().value(),
+        stage,
+        next
+    );
+    Ok(())
+}
+
+/// The current growth pool filled: grow the station into `size`, announce
+/// it, and open the pool for the size after.
+pub fn complete_growth_stage<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    station_id: &StationId,
+    under_construction: StationUnderConstruction,
+    size: StationSize,
+) -> Result<(), String> {
+    let mut station = dsl.get_station_by_id(station_id)?;
+    station.size = size;
+    let station = dsl.update_station_by_id(station)?;
+    verify(dsl, &station)?;
+
+    post_galaxy_channel(
+        dsl,
+        MessageSender::System,
+        format!(
+            "'{}' (station #{}) has grown into a {:?} station with room for {} modules.",
+            station.get_name(),
+            station_id.value(),
+            station.get_size(),
+            station.get_size().max_module_amount()
+        ),
+    )?;
+
+    open_growth_pool(dsl, &station, under_construction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn growth_stops_at_large() {
+        let mut size = StationSize::Outpost;
+        let mut path = Vec::new();
+        while let Some(next) = size.next_growth_size() {
+            path.push(next.clone());
+            size = next;
+        }
+        assert_eq!(
+            path,
+            vec![StationSize::Small, StationSize::Medium, StationSize::Large]
+        );
+        assert_eq!(StationSize::Capital.next_growth_size(), None);
+    }
+}
@@ -0,0 +1,333 @@
+//! # Station Hangars
+//!
+//! Every player has a personal hangar at every station: cargo a Docked Ship
+//! deposits stays there, keyed by (station, player, item), until a Docked Ship
+//! of the same player withdraws it at that same station. No credits change
+//! hands — this is storage, not trade.
+//!
+//! ## Space
+//! A hangar's space comes from the station's operational `StorageDepot`
+//! modules. Each depot adds its blueprint's `max_internal_storage_slots`
+//! (distinct item types) and `slots × max_internal_storage_volume_per_slot_m3`
+//! (volume) to *each* player's hangar — depots rent space out per player
+//! rather than sharing one pool. A station without a depot has no hangar
+//! space at all; items already stored there can still be withdrawn.
+
+use log::info;
+use spacetimedb::ReducerContext;
+use spacetimedsl::*;
+
+use crate::{
+    logic::ships::cargo::{attempt_to_load_cargo_into_ship, remove_cargo_from_ship},
+    tables::{items::*, messages::*, players::*, ships::*, stations::*},
+    utility::{is_server_or_ship_owner, ShipAccess},
+};
+
+/// How much a player's hangar at one station can hold, or how much of it is
+/// in use.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HangarSpace {
+    /// Distinct item types.
+    pub slots: u32,
+    /// In u64: a hangar row's quantity has no cap, so its volume can pass
+    /// `u32::MAX`.
+    pub volume: u64,
+}
+
+///////////////////////////////////////////////////////////
+// Reducers
+///////////////////////////////////////////////////////////
+
+/// Moves cargo from a Docked Ship into its owner's hangar at the station it's
+/// docked at.
+#[spacetimedb::reducer]
+pub fn deposit_cargo_to_hangar(
+    ctx: &ReducerContext,
+    ship_id: ShipId,
+    item_id: ItemDefinitionId,
+    quantity: u32,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    let ship = is_server_or_ship_owner(&dsl, &ship_id, ShipAccess::Docked)?;
+    let player_id = ship.get_player_id();
+    let station = docked_station(&dsl, &ship, quantity)?;
+    let item_def = dsl.get_item_definition_by_id(&item_id)?;
+
+    let capacity = hangar_capacity(&dsl, &station.get_id());
+    let used = hangar_usage(&dsl, &station.get_id(), &player_id);
+    let existing = find_hangar_item(&dsl, &station.get_id(), &player_id, &item_id);
+    let added_volume = quantity as u64 * *item_def.get_volume_per_unit() as u64;
+
+    let problem = if capacity.volume == 0 {
+        Some(format!(
+            "{} has no storage depot, so there is no hangar space here.",
+            station.get_name()
+        ))
+    } else if existing.is_none() && used.slots >= capacity.slots {
+        Some(format!(
+            "Your hangar at {} already holds {} kinds of item, its limit.",
+            station.get_name(),
+            capacity.slots
+        ))
+    } else if used.volume + added_volume > capacity.volume {
+        Some(format!(
+            "Not enough hangar space at {}: {}x {} needs {}v but only {}v of {}v is free.",
+            station.get_name(),
+            quantity,
+            item_def.get_name(),
+            added_volume,
+            capacity.volume - used.volume.min(capacity.volume),
+            capacity.volume
+        ))
+    } else {
+        None
+    };
+    if let Some(msg) = problem {
+        let _ = send_direct_server_warning(&dsl, &player_id, msg.clone());
+        return Err(msg);
+    }
+
+    if let Err(cargo_err) = remove_cargo_from_ship(
+        &dsl,
+        &mut dsl.get_ship_status_by_id(&ship_id)?,
+        &item_def,
+        quantity as u16,
+    ) {
+        let msg = format!(
+            "Cannot store {}x {}: {}",
+            quantity,
+            item_def.get_name(),
+            cargo_err
+        );
+        let _ = send_direct_server_warning(&dsl, &player_id, msg.clone());
+        return Err(msg);
+    }
+
+    add_to_hangar(&dsl, &station.get_id(), &player_id, &item_id, quantity)?;
+
+    info!(
+        "deposit_cargo_to_hangar: ship={} station={} item={} quantity={}",
+        ship_id.value(),
+        station.get_id().value(),
+        item_id.value(),
+        quantity
+    );
+    send_direct_server_info(
+        &dsl,
+        &player_id,
+        format!(
+            "Stored {}x {} in your hangar at {}.",
+            quantity,
+            item_def.get_name(),
+            station.get_name()
+        ),
+    )?;
+
+    Ok(())
+}
+
+/// Moves cargo from the owner's hangar at the station into a Docked Ship.
+#[spacetimedb::reducer]
+pub fn withdraw_cargo_from_hangar(
+    ctx: &ReducerContext,
+    ship_id: ShipId,
+    item_id: ItemDefinitionId,
+    quantity: u32,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    let ship = is_server_or_ship_owner(&dsl, &ship_id, ShipAccess::Docked)?;
+    let player_id = ship.get_player_id();
+    let station = docked_station(&dsl, &ship, quantity)?;
+    let item_def = dsl.get_item_definition_by_id(&item_id)?;
+
+    let stored = find_hangar_item(&dsl, &station.get_id(), &player_id, &item_id)
+        .filter(|stored| stored.quantity >= quantity);
+    let Some(mut stored) = stored else {
+        let msg = format!(
+            "Your hangar at {} doesn't hold {}x {}.",
+            station.get_name(),
+            quantity,
+            item_def.get_name()
+        );
+        let _ = send_direct_server_warning(&dsl, &player_id, msg.clone());
+        return Err(msg);
+    };
+
+    if let Err(cargo_err) = attempt_to_load_cargo_into_ship(
+        ctx,
+        &dsl,
+        &mut dsl.get_ship_status_by_id(&ship_id)?,
+        &ship_id,
+        &item_def,
+        quantity as u16,
+        false,
+    ) {
+        let msg = format!(
+            "Cannot withdraw {}x {}: {}",
+            quantity,
+            item_def.get_name(),
+            cargo_err
+        );
+        let _ = send_direct_server_warning(&dsl, &player_id, msg.clone());
+        return Err(msg);
+    }
+
+    if stored.quantity == quantity {
+        dsl.delete_station_hangar_item_by_id(&stored)?;
+    } else {
+        stored.quantity -= quantity;
+        dsl.update_station_hangar_item_by_id(stored)?;
+    }
+
+    info!(
+        "withdraw_cargo_from_hangar: ship={} station={} item={} quantity={}",
+        ship_id.value(),
+        station.get_id().value(),
+        item_id.value(),
+        quantity
+    );
+    send_direct_server_info(
+        &dsl,
+        &player_id,
+        format!(
+            "Loaded {}x {} from your hangar at {}.",
+            quantity,
+            item_def.get_name(),
+            station.get_name()
+        ),
+    )?;
+
+    Ok(())
+}
+
+///////////////////////////////////////////////////////////
+// Utilities
+///////////////////////////////////////////////////////////
+
+/// Hangar space each player gets at `station_id`, summed over its
+/// operational storage depots.
+pub fn hangar_capacity<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    station_id: &StationId,
+) -> HangarSpace {
+    let mut capacity = HangarSpace::default();
+    for module in dsl.get_station_modules_by_station_id(station_id) {
+        if !*module.get_is_operational() {
+            continue;
+        }
+        let Ok(blueprint) = dsl.get_station_module_blueprint_by_id(&module.get_blueprint()) else {
+            continue;
+        };
+        if blueprint.specific_type != StationModuleSpecificType::StorageDepot {
+            continue;
+        }
+        let slots = blueprint.max_internal_storage_slots as u32;
+        capacity.slots += slots;
+        capacity.volume += slots as u64
+            * blueprint
+                .max_internal_storage_volume_per_slot_m3
+                .unwrap_or(0) as u64;
+    }
+    capacity
+}
+
+/// What `player_id` has stored at `station_id`.
+pub fn hangar_usage<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    station_id: &StationId,
+    player_id: &PlayerId,
+) -> HangarSpace {
+    let mut used = HangarSpace::default();
+    for stored in dsl
+        .get_station_hangar_items_by_station_id(station_id)
+        .filter(|stored| stored.get_player_id() == *player_id)
+    {
+        used.slots += 1;
+        if let Ok(item_def) = dsl.get_item_definition_by_id(&stored.get_item_id()) {
+            used.volume += stored.quantity as u64 * *item_def.get_volume_per_unit() as u64;
+        }
+    }
+    used
+}
+
+/// How many units of `item_def` still fit in `player_id`'s hangar at
+/// `station_id`: whatever the free volume takes, or none if the item would
+/// need a new slot and every slot is taken.
+pub fn hangar_room<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    station_id: &StationId,
+    player_id: &PlayerId,
+    item_def: &ItemDefinition,
+) -> u32 {
+    let capacity = hangar_capacity(dsl, station_id);
+    let used = hangar_usage(dsl, station_id, player_id);
+    let stacked = find_hangar_item(dsl, station_id, player_id, &item_def.get_id()).is_some();
+    if !stacked && used.slots >= capacity.slots {
+        return 0;
+    }
+    let free = capacity.volume.saturating_sub(used.volume);
+    match *item_def.get_volume_per_unit() as u64 {
+        0 => u32::MAX,
+        per_unit => (free / per_unit).min(u32::MAX as u64) as u32,
+    }
+}
+
+/// Puts `quantity` of `item_id` into `player_id`'s hangar at `station_id`.
+/// Doesn't check hangar space: deposits check it first with their own
+/// messages, and market orders only deliver what `hangar_room` allows.
+pub fn add_to_hangar<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    station_id: &StationId,
+    player_id: &PlayerId,
+    item_id: &ItemDefinitionId,
+    quantity: u32,
+) -> Result<(), String> {
+    match find_hangar_item(dsl, station_id, player_id, item_id) {
+        Some(mut stored) => {
+            stored.quantity += quantity;
+            dsl.update_station_hangar_item_by_id(stored)?;
+        }
+        None => {
+            dsl.create_station_hangar_item(CreateStationHangarItem {
+                station_id: station_id.clone(),
+                player_id: player_id.clone(),
+                item_id: item_id.clone(),
+                quantity,
+            })?;
+        }
+    }
+    Ok(())
+}
+
+fn find_hangar_item<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    station_id: &StationId,
+    player_id: &PlayerId,
+    item_id: &ItemDefinitionId,
+) -> Option<StationHangarItem> {
+    dsl.get_station_hangar_items_by_station_id(station_id)
+        .find(|stored| stored.get_player_id() == *player_id && stored.get_item_id() == *item_id)
+}
+
+/// The station `ship` is docked at, after checking the transfer is sensible.
+fn docked_station<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    ship: &Ship,
+    quantity: u32,
+) -> Result<Station, String> {
+    let player_id = ship.get_player_id();
+    let problem = if quantity == 0 || quantity > u16::MAX as u32 {
+        Some(format!(
+            "Cannot move {} items at once (1 to {}).",
+            quantity,
+            u16::MAX
+        ))
+    } else {
+        None
+    };
+    if let Some(msg) = problem {
+        let _ = send_direct_server_warning(dsl, &player_id, msg.clone());
+        return Err(msg);
+    }
+    Ok(dsl.get_station_by_id(&ship.get_station_id())?)
+}
@@ -0,0 +1,188 @@
+//! # Contribution leaderboards
+//!
+//! `contribute_to_station` adds every contribution to the caller's
+//! `ContributionTally` rows on all four boards (the site, the faction that
+//! owns it, this week, all time). `contribution_tally_sweeper` drops past
+//! weeks, and `rebuild_contribution_tallies` recomputes everything from
+//! `ConstructionContributionLog` when the log itself is edited.
+
+use std::time::Duration;
+
+use log::info;
+use spacetimedb::{ReducerContext, Timestamp};
+use spacetimedsl::*;
+
+use crate::{
+    tables::{items::*, leaderboards::*, players::PlayerId, stations::*},
+    utility::try_server_only,
+};
+
+const WEEK_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// The Unix epoch fell on a Thursday; shifting by three days makes weeks
+/// start on Monday.
+const WEEK_OFFSET_SECS: u64 = 3 * 24 * 60 * 60;
+
+///////////////////////////////////////////////////////////
+// Timers
+///////////////////////////////////////////////////////////
+
+#[dsl(plural_name = contribution_tally_timers, method(update = false))]
+#[spacetimedb::table(
+    accessor = contribution_tally_timer,
+    scheduled(contribution_tally_sweeper)
+)]
+pub struct ContributionTallyTimer {
+    #[primary_key]
+    #[auto_inc]
+    #[create_wrapper]
+    id: u64,
+    scheduled_at: spacetimedb::ScheduleAt,
+}
+
+pub fn create_contribution_tally_timer<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+) -> Result<(), String> {
+    dsl.create_contribution_tally_timer(CreateContributionTallyTimer {
+        scheduled_at: spacetimedb::ScheduleAt::Interval(Duration::from_hours(1).into()),
+    })?;
+    Ok(())
+}
+
+/// Deletes weekly tallies from before this week.
+#[spacetimedb::reducer]
+pub fn contribution_tally_sweeper(
+    ctx: &ReducerContext,
+    _timer: ContributionTallyTimer,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    try_server_only(&dsl)?;
+
+    let this_week = week_of(ctx.timestamp);
+    let stale: Vec<ContributionTally> = dsl
+        .get_contribution_tallies_by_board(&ContributionBoard::Weekly)
+        .filter(|tally| *tally.get_scope_id() < this_week)
+        .collect();
+    for tally in &stale {
+        dsl.delete_contribution_tally_by_id(tally)?;
+    }
+    if !stale.is_empty() {
+        info!(
+            "contribution_tally_sweeper: removed {} tallies from past weeks",
+            stale.len()
+        );
+    }
+    Ok(())
+}
+
+///////////////////////////////////////////////////////////
+// Utilities
+///////////////////////////////////////////////////////////
+
+/// Monday-based week number of `timestamp`, counted from the Unix epoch.
+pub fn week_of(timestamp: Timestamp) -> u64 {
+    let secs = timestamp.to_micros_since_unix_epoch().max(0) as u64 / 1_000_000;
+    (secs + WEEK_OFFSET_SECS) / WEEK_SECS
+}
+
+/// Adds one contribution to `player_id`'s tallies on every board.
+pub fn record_contribution_tally<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    station: &Station,
+    player_id: &PlayerId,
+    item_def: &ItemDefinition,
+    quantity: u32,
+    contributed_at: Timestamp,
+) -> Result<(), String> {
+    let value = quantity as u64 * *item_def.get_base_value() as u64;
+    let boards = [
+        (ContributionBoard::Station, station.get_id().value()),
+        (
+            ContributionBoard::Faction,
+            station.get_owner_faction_id().value() as u64,
+        ),
+        (ContributionBoard::Weekly, week_of(contributed_at)),
+        (ContributionBoard::AllTime, 0),
+    ];
+
+    for (board, scope_id) in boards {
+        let existing = dsl
+            .get_contribution_tallies_by_player_id(player_id)
+            .find(|t| *t.get_board() == board && *t.get_scope_id() == scope_id);
+        match existing {
+            Some(mut tally) => {
+                tally.quantity += quantity as u64;
+                tally.value += value;
+                dsl.update_contribution_tally_by_id(tally)?;
+            }
+            None => {
+                dsl.create_contribution_tally(CreateContributionTally {
+                    board,
+                    scope_id,
+                    player_id: player_id.clone(),
+                    quantity: quantity as u64,
+                    value,
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Throws every tally away and replays `ConstructionContributionLog`. Weekly
+/// tallies only get this week's contributions.
+pub fn rebuild_contribution_tallies<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+) -> Result<(), String> {
+    for tally in dsl.get_all_contribution_tallies() {
+        dsl.delete_contribution_tally_by_id(&tally)?;
+    }
+
+    let this_week = week_of(dsl.ctx().timestamp()?);
+    let mut replayed = 0;
+    for log in dsl.get_all_construction_contribution_logs() {
+        let Ok(station) = dsl.get_station_by_id(log.get_station_id()) else {
+            continue;
+        };
+        let item_def = dsl.get_item_definition_by_id(log.get_item_id())?;
+        record_contribution_tally(
+            dsl,
+            &station,
+            &log.get_player_id(),
+            &item_def,
+            *log.get_quantity(),
+            *log.get_contributed_at(),
+        )?;
+        replayed += 1;
+    }
+
+    // Replayed contributions from earlier weeks landed on their own week.
+    for tally in dsl
+        .get_contribution_tallies_by_board(&ContributionBoard::Weekly)
+        .filter(|t| *t.get_scope_id() < this_week)
+    {
+        dsl.delete_contribution_tally_by_id(&tally)?;
+    }
+
+    info!(
+        "rebuild_contribution_tallies: replayed {} contribution log rows",
+        replayed
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weeks_roll_over_on_monday() {
+        // 1970-01-05 was the first Monday after the epoch.
+        let monday = 4 * 24 * 60 * 60;
+        let at = |secs: i64| Timestamp::from_micros_since_unix_epoch(secs * 1_000_000);
+        assert_eq!(week_of(at(0)), 0);
+        assert_eq!(week_of(at(monday - 1)), 0);
+        assert_eq!(week_of(at(monday)), 1);
+        assert_eq!(week_of(at(monday + WEEK_SECS as i64)), 2);
+    }
+}
@@ -0,0 +1,285 @@
+//! # Station logistics
+//!
+//! Refineries, solar arrays and factories each work out of their own
+//! `StationModuleInventoryItem` slots. Every `LogisticsAndStorage` module
+//! (trading ports, storage depots) runs a logistics pass on its production
+//! tick that moves finished goods from producers' output slots into the input
+//! slots of the modules that consume them: ingots from a refinery into a
+//! metal-plate factory, energy cells into anything whose recipe burns them.
+//!
+//! Consumers are fed highest `ModuleLogisticsPriority` first (then by module
+//! id), so a station can be told which chain to keep running when supplies
+//! are short. Each logistics module moves at most
+//! `LOGISTICS_UNITS_PER_SLOT` units per storage slot of its blueprint per
+//! tick; more logistics modules mean more throughput.
+//!
+//! Trading port stock isn't routed: what a station sells stays on the market.
+
+use std::collections::HashMap;
+
+use log::info;
+use spacetimedsl::*;
+
+use crate::{
+    logic::stations::module_types::{manufacturing::*, refineries::*, solar_arrays::*},
+    tables::{economy::ResourceAmount, stations::*},
+};
+
+/// The priority of modules that were never given one.
+pub const DEFAULT_LOGISTICS_PRIORITY: u8 = 100;
+
+/// How many units a logistics module moves per tick for each of its
+/// blueprint's `max_internal_storage_slots`.
+pub const LOGISTICS_UNITS_PER_SLOT: u32 = 10;
+
+/// What a producing module takes in and puts out. `inputs` carry the amount
+/// one batch needs.
+#[derive(Clone, Debug)]
+pub struct ModuleFlows {
+    pub inputs: Vec<ResourceAmount>,
+    pub outputs: Vec<u32>,
+    /// `false` for a manufacturing module with no recipe set.
+    pub has_recipe: bool,
+}
+
+/// Items sitting in a producer's output slot.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Supply {
+    pub slot_id: u64,
+    pub module_id: u64,
+    pub item_id: u32,
+    pub quantity: u32,
+}
+
+/// Room in a consumer's input slot.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Demand {
+    pub slot_id: u64,
+    pub module_id: u64,
+    pub item_id: u32,
+    pub free: u32,
+    pub priority: u8,
+}
+
+/// One move between two slots.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlannedTransfer {
+    pub from_slot_id: u64,
+    pub to_slot_id: u64,
+    pub transfer: LogisticsTransfer,
+}
+
+/// The inputs and outputs of `module_id`, if it's a producing module.
+pub fn module_flows<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    module_id: &StationModuleId,
+) -> Option<ModuleFlows> {
+    if let Ok(refinery) = dsl.get_refinery_module_by_id(module_id) {
+        let mut outputs = vec![refinery.get_output_ingot_resource_id().value()];
+        if let Some(waste) = refinery.get_waste_resource_id() {
+            outputs.push(waste.value());
+        }
+        return Some(ModuleFlows {
+            inputs: vec![ResourceAmount::new(
+                refinery.get_input_ore_resource_id().value(),
+                *refinery.get_ore_to_ingot_ratio(),
+            )],
+            outputs,
+            has_recipe: true,
+        });
+    }
+    if let Ok(solar_array) = dsl.get_solar_array_module_by_id(module_id) {
+        return Some(ModuleFlows {
+            inputs: Vec::new(),
+            outputs: vec![solar_array.get_output_energy_cell_resource_id().value()],
+            has_recipe: true,
+        });
+    }
+    if let Ok(manufacturing) = dsl.get_manufacturing_module_by_id(module_id) {
+        let recipe = manufacturing
+            .get_current_recipe_id()
+            .and_then(|id| dsl.get_production_recipe_definition_by_id(id).ok());
+        return Some(match recipe {
+            Some(recipe) => ModuleFlows {
+                inputs: recipe.get_input_resources().clone(),
+                outputs: vec![recipe.get_output_resource_id().value()],
+                has_recipe: true,
+            },
+            None => ModuleFlows {
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+                has_recipe: false,
+            },
+        });
+    }
+    None
+}
+
+/// Moves up to `capacity` units from producers' outputs into consumers'
+/// inputs at `station`, and returns what was moved.
+pub fn run_logistics_pass<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    station: &Station,
+    capacity: u32,
+) -> Result<Vec<LogisticsTransfer>, String> {
+    let mut supplies = Vec::new();
+    let mut demands = Vec::new();
+    for module in dsl
+        .get_station_modules_by_station_id(station.get_id())
+        .filter(|m| *m.get_is_operational())
+    {
+        let Some(flows) = module_flows(dsl, &module.get_id()) else {
+            continue;
+        };
+        let priority = dsl
+            .get_module_logistics_priority_by_id(module.get_id())
+            .map(|p| *p.get_priority())
+            .unwrap_or(DEFAULT_LOGISTICS_PRIORITY);
+        for slot in dsl.get_station_module_inventory_items_by_module_id(module.get_id()) {
+            let item_id = slot.get_resource_item_id().value();
+            if flows.outputs.contains(&item_id) && *slot.get_quantity() > 0 {
+                supplies.push(Supply {
+                    slot_id: slot.get_id().value(),
+                    module_id: module.get_id().value(),
+                    item_id,
+                    quantity: *slot.get_quantity(),
+                });
+            } else if flows.inputs.iter().any(|i| i.resource_item_id == item_id)
+                && slot.get_quantity() < slot.get_max_quantity()
+            {
+                demands.push(Demand {
+                    slot_id: slot.get_id().value(),
+                    module_id: module.get_id().value(),
+                    item_id,
+                    free: slot.get_max_quantity() - slot.get_quantity(),
+                    priority,
+                });
+            }
+        }
+    }
+
+    let planned = plan_transfers(&supplies, &demands, capacity);
+    for planned_transfer in &planned {
+        let quantity = planned_transfer.transfer.quantity;
+        let mut from = dsl.get_station_module_inventory_item_by_id(
+            StationModuleInventoryItemId::new(planned_transfer.from_slot_id),
+        )?;
+        from.set_quantity(from.get_quantity() - quantity);
+        dsl.update_station_module_inventory_item_by_id(from)?;
+
+        let mut to = dsl.get_station_module_inventory_item_by_id(
+            StationModuleInventoryItemId::new(planned_transfer.to_slot_id),
+        )?;
+        to.set_quantity(to.get_quantity() + quantity);
+        dsl.update_station_module_inventory_item_by_id(to)?;
+    }
+
+    if !planned.is_empty() {
+        info!(
+            "run_logistics_pass: station={} moved {} units in {} transfers",
+            station.get_id().value(),
+            planned.iter().map(|p| p.transfer.quantity).sum::<u32>(),
+            planned.len()
+        );
+    }
+    Ok(planned.into_iter().map(|p| p.transfer).collect())
+}
+
+/// Fills `demands` from `supplies` of the same item in another module,
+/// highest priority first (then lowest module id), until `capacity` units
+/// have been moved.
+pub fn plan_transfers(
+    supplies: &[Supply],
+    demands: &[Demand],
+    capacity: u32,
+) -> Vec<PlannedTransfer> {
+    let mut remaining: HashMap<u64, u32> =
+        supplies.iter().map(|s| (s.slot_id, s.quantity)).collect();
+    let mut capacity = capacity;
+
+    let mut by_priority: Vec<&Demand> = demands.iter().collect();
+    by_priority.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then(a.module_id.cmp(&b.module_id))
+    });
+
+    let mut planned = Vec::new();
+    for demand in by_priority {
+        let mut free = demand.free;
+        for supply in supplies
+            .iter()
+            .filter(|s| s.item_id == demand.item_id && s.module_id != demand.module_id)
+        {
+            let available = remaining.get_mut(&supply.slot_id).unwrap();
+            let quantity = free.min(*available).min(capacity);
+            if quantity == 0 {
+                continue;
+            }
+            *available -= quantity;
+            free -= quantity;
+            capacity -= quantity;
+            planned.push(PlannedTransfer {
+                from_slot_id: supply.slot_id,
+                to_slot_id: demand.slot_id,
+                transfer: LogisticsTransfer {
+                    item_id: demand.item_id,
+                    from_module_id: supply.module_id,
+                    to_module_id: demand.module_id,
+                    quantity,
+                },
+            });
+        }
+        if capacity == 0 {
+            break;
+        }
+    }
+    planned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn higher_priority_consumers_are_fed_first() {
+        let supplies = [
+            Supply {
+                slot_id: 1,
+                module_id: 10,
+                item_id: 7,
+                quantity: 30,
+            },
+            // Never routed back into the module that made it.
+            Supply {
+                slot_id: 2,
+                module_id: 20,
+                item_id: 7,
+                quantity: 5,
+            },
+        ];
+        let demand = |slot_id, module_id, free, priority| Demand {
+            slot_id,
+            module_id,
+            item_id: 7,
+            free,
+            priority,
+        };
+        let demands = [demand(3, 20, 25, 100), demand(4, 30, 25, 200)];
+
+        let moved = |planned: Vec<PlannedTransfer>| -> Vec<(u64, u64, u32)> {
+            planned
+                .into_iter()
+                .map(|p| (p.from_slot_id, p.to_slot_id, p.transfer.quantity))
+                .collect()
+        };
+        assert_eq!(
+            moved(plan_transfers(&supplies, &demands, 100)),
+            vec![(1, 4, 25), (1, 3, 5)]
+        );
+        assert_eq!(
+            moved(plan_transfers(&supplies, &demands, 10)),
+            vec![(1, 4, 10)]
+        );
+    }
+}
@@ -0,0 +1,661 @@
+//! # Market orders
+//!
+//! Besides trading instantly against a trading port's stock
+//! (`buy_and_sell`), a player with a Docked Ship there can post a standing
+//! `MarketOrder`: buy up to N units at no more than a price, or sell N units
+//! at no less than one. Whatever the order could still cost or deliver is
+//! held in escrow — credits taken from the player for a buy order, cargo
+//! taken from the Docked Ship for a sell order.
+//!
+//! ## Matching
+//! - **Against other players:** a new order fills against the opposite side
+//!   of its item's book at that port as far as prices cross, best price first
+//!   and oldest first within a price. Trades happen at the resting order's
+//!   price, and a buyer who bid higher gets the difference back.
+//! - **Against the station:** a buy order also takes stock from the port
+//!   while the port's price (stock, pressure, spread and standing, see
+//!   `trading_port`) is at or under its limit, and a sell order sells into the
+//!   port while the port pays at least its limit. This is checked when the
+//!   order is posted and again on every production tick, so orders fill once
+//!   the station's price drifts across them. If the station's owner turns
+//!   hostile to the player's faction, the tick closes the order instead.
+//!
+//! Bought goods are delivered to the buyer's hangar at the port's station;
+//! the buyer doesn't need to be docked. An order that runs out its
+//! `expires_at`, or is cancelled, hands back what's left in escrow: credits to
+//! the player, unsold cargo to their hangar there.
+//!
+//! ## Hangar space
+//! Neither delivery overfills a hangar. A buy order only fills as far as the
+//! buyer's hangar has room, and the rest stays on the book. A sell order can
+//! only be posted if its cargo would fit back in the seller's hangar; if that
+//! room is gone by the time it closes, what doesn't fit stays in the order,
+//! off the book, until the production tick finds room for it.
+
+use std::time::Duration;
+
+use log::info;
+use spacetimedb::{ReducerContext, TimeDuration};
+use spacetimedsl::*;
+
+use crate::{
+    logic::{
+        moderation::refuse_banned,
+        ships::cargo::remove_cargo_from_ship,
+        stations::{
+            buy_and_sell::trading_standing,
+            hangar::{add_to_hangar, hangar_room},
+            module_types::trading_port::*,
+        },
+    },
+    tables::{items::*, messages::*, players::*, ships::*, stations::*},
+    utility::{is_server_or_ship_owner, ShipAccess},
+};
+use solarance_shared::TradeSide;
+
+/// How many orders one player may have open across every station.
+pub const MAX_OPEN_ORDERS_PER_PLAYER: usize = 10;
+
+/// The longest an order can stay open, in hours — one week.
+pub const MAX_ORDER_DURATION_HOURS: u32 = 7 * 24;
+
+///////////////////////////////////////////////////////////
+// Reducers
+///////////////////////////////////////////////////////////
+
+/// Posts a buy or sell order for `quantity` of `item_id` at `price_per_unit`
+/// on the trading port `station_module_id`, where `ship_id` is docked. The
+/// order's escrow is taken up front and the order is matched straight away;
+/// whatever doesn't fill stays open for `duration_hours`.
+#[spacetimedb::reducer]
+#[allow(clippy::too_many_arguments)]
+pub fn place_market_order(
+    ctx: &ReducerContext,
+    station_module_id: StationModuleId,
+    ship_id: ShipId,
+    item_id: ItemDefinitionId,
+    side: MarketOrderSide,
+    price_per_unit: u32,
+    quantity: u32,
+    duration_hours: u32,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    let station_module = dsl.get_station_module_by_id(&station_module_id)?;
+    let ship = is_server_or_ship_owner(
+        &dsl,
+        &ship_id,
+        ShipAccess::DockedAt(station_module.get_station_id()),
+    )?;
+    let player_id = ship.get_player_id();
+    let mut player = dsl.get_player_by_id(&player_id)?;
+    trading_standing(&dsl, &station_module, &player)?;
+    let item_def = dsl.get_item_definition_by_id(&item_id)?;
+
+    let is_trading_port = dsl
+        .get_trading_port_module_by_id(&station_module_id)
+        .is_ok();
+    let is_listed = dsl
+        .get_station_module_inventory_items_by_module_id(&station_module_id)
+        .any(|item| item.get_resource_item_id() == item_id);
+    // Orders kept past expiry for hangar space no longer trade, so they don't
+    // count against the limit.
+    let open_orders = dsl
+        .get_market_orders_by_player_id(&player_id)
+        .filter(|order| *order.get_expires_at() > ctx.timestamp)
+        .count();
+    let escrow = price_per_unit as u64 * quantity as u64;
+    let station = dsl.get_station_by_id(&station_module.get_station_id())?;
+    let room = hangar_room(&dsl, &station.get_id(), &player_id, &item_def);
+
+    let problem = if !is_trading_port {
+        Some("Orders can only be posted at a trading port.".to_string())
+    } else if !is_listed {
+        Some(format!(
+            "This trading port doesn't trade {}.",
+            item_def.get_name()
+        ))
+    } else if quantity == 0 || quantity > u16::MAX as u32 {
+        Some(format!(
+            "An order must be for 1 to {} units, not {}.",
+            u16::MAX,
+            quantity
+        ))
+    } else if price_per_unit == 0 {
+        Some("An order's price must be at least 1c.".to_string())
+    } else if duration_hours == 0 || duration_hours > MAX_ORDER_DURATION_HOURS {
+        Some(format!(
+            "An order can stay open for 1 to {} hours.",
+            MAX_ORDER_DURATION_HOURS
+        ))
+    } else if open_orders >= MAX_OPEN_ORDERS_PER_PLAYER {
+        Some(format!(
+            "You already have {} open orders, the most allowed.",
+            MAX_OPEN_ORDERS_PER_PLAYER
+        ))
+    } else if side == MarketOrderSide::Buy && escrow > *player.get_credits() {
+        Some(format!(
+            "Not enough credits: a buy order for {}x {} at {}c holds {}c, but you have {}c.",
+            quantity,
+            item_def.get_name(),
+            price_per_unit,
+            escrow,
+            player.get_credits()
+        ))
+    } else if side == MarketOrderSide::Sell && quantity > room {
+        Some(format!(
+            "Not enough hangar space at {}: unsold cargo comes back there, but only {} of {}x {} would fit.",
+            station.get_name(),
+            room,
+            quantity,
+            item_def.get_name()
+        ))
+    } else {
+        None
+    };
+    if let Some(msg) = problem {
+        let _ = send_direct_server_warning(&dsl, &player_id, msg.clone());
+        return Err(msg);
+    }
+
+    // Take the escrow
+    match side {
+        MarketOrderSide::Buy => {
+            player.set_credits(player.get_credits() - escrow);
+            dsl.update_player_by_id(player)?;
+        }
+        MarketOrderSide::Sell => {
+            if let Err(cargo_err) = remove_cargo_from_ship(
+                &dsl,
+                &mut dsl.get_ship_status_by_id(&ship_id)?,
+                &item_def,
+                quantity as u16,
+            ) {
+                let msg = format!(
+                    "Cannot post a sell order for {}x {}: {}",
+                    quantity,
+                    item_def.get_name(),
+                    cargo_err
+                );
+                let _ = send_direct_server_warning(&dsl, &player_id, msg.clone());
+                return Err(msg);
+            }
+        }
+    }
+
+    let order = dsl.create_market_order(CreateMarketOrder {
+        module_id: station_module_id.clone(),
+        player_id: player_id.clone(),
+        item_id: item_id.clone(),
+        side: side.clone(),
+        price_per_unit,
+        quantity_ordered: quantity,
+        quantity_remaining: quantity,
+        escrowed_credits: match side {
+            MarketOrderSide::Buy => escrow,
+            MarketOrderSide::Sell => 0,
+        },
+        expires_at: ctx
+            .timestamp
+            .checked_add(TimeDuration::from_duration(Duration::from_secs(
+                duration_hours as u64 * 60 * 60,
+            )))
+            .ok_or("place_market_order: expiry time overflowed")?,
+    })?;
+
+    info!(
+        "place_market_order: order={} module={} player={} item={} side={:?} price={} quantity={}",
+        order.get_id().value(),
+        station_module_id.value(),
+        player_id.value(),
+        item_id.value(),
+        side,
+        price_per_unit,
+        quantity
+    );
+    send_direct_server_info(
+        &dsl,
+        &player_id,
+        format!(
+            "Posted a {} order for {}x {} at {}c each, open for {} hours.",
+            side_name(&side),
+            quantity,
+            item_def.get_name(),
+            price_per_unit,
+            duration_hours
+        ),
+    )?;
+
+    if let Some(order) = match_against_orders(&dsl, order)? {
+        match_against_station(&dsl, order)?;
+    }
+
+    Ok(())
+}
+
+/// Cancels one of the sender's open orders and hands back its escrow.
+#[spacetimedb::reducer]
+pub fn cancel_market_order(ctx: &ReducerContext, order_id: MarketOrderId) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    refuse_banned(&dsl, ctx.sender())?;
+    let order = dsl.get_market_order_by_id(&order_id)?;
+    if order.get_player_id().value() != ctx.sender() {
+        return Err(format!(
+            "Market order #{} isn't yours to cancel.",
+            order_id.value()
+        ));
+    }
+
+    if *order.get_side() == MarketOrderSide::Sell {
+        let station = dsl.get_station_by_id(
+            &dsl.get_station_module_by_id(&order.get_module_id())?
+                .get_station_id(),
+        )?;
+        let item_def = dsl.get_item_definition_by_id(&order.get_item_id())?;
+        if hangar_room(&dsl, &station.get_id(), &order.get_player_id(), &item_def) == 0 {
+            return Err(format!(
+                "No hangar space at {} to take back the unsold {}.",
+                station.get_name(),
+                item_def.get_name()
+            ));
+        }
+    }
+
+    close_market_order(&dsl, order, "was cancelled")
+}
+
+///////////////////////////////////////////////////////////
+// Utilities
+///////////////////////////////////////////////////////////
+
+/// The production tick's pass over the market orders posted at `module`:
+/// expired orders are closed, and the rest are matched against the station's
+/// current prices.
+pub fn run_market_orders<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    module: &StationModule,
+) -> Result<(), String> {
+    let now = dsl.ctx().timestamp()?;
+    let mut orders: Vec<MarketOrder> = dsl
+        .get_market_orders_by_module_id(module.get_id())
+        .collect();
+    orders.sort_by_key(|order| *order.get_created_at());
+    for order in orders {
+        if *order.get_expires_at() <= now {
+            close_market_order(dsl, order, "expired")?;
+        } else {
+            match_against_station(dsl, order)?;
+        }
+    }
+    Ok(())
+}
+
+/// Fills `order` against the opposite side of its book as far as prices
+/// cross. Returns what's left of it, or `None` if it filled completely.
+fn match_against_orders<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    mut order: MarketOrder,
+) -> Result<Option<MarketOrder>, String> {
+    let now = dsl.ctx().timestamp()?;
+    let mut counters: Vec<MarketOrder> = dsl
+        .get_market_orders_by_module_id(&order.get_module_id())
+        .filter(|counter| {
+            *counter.get_expires_at() > now
+                && counter.get_item_id() == order.get_item_id()
+                && counter.get_side() != order.get_side()
+                && counter.get_player_id() != order.get_player_id()
+        })
+        .collect();
+    // Best price first, then oldest first
+    counters.sort_by_key(|counter| {
+        let price = *counter.get_price_per_unit() as i64;
+        let best_first = match counter.get_side() {
+            MarketOrderSide::Buy => -price,
+            MarketOrderSide::Sell => price,
+        };
+        (best_first, *counter.get_created_at())
+    });
+
+    let side = order.get_side().clone();
+    let station_id = dsl
+        .get_station_module_by_id(&order.get_module_id())?
+        .get_station_id();
+    let item_def = dsl.get_item_definition_by_id(&order.get_item_id())?;
+    for mut counter in counters {
+        let price = *counter.get_price_per_unit();
+        let crosses = match side {
+            MarketOrderSide::Buy => *order.get_price_per_unit() >= price,
+            MarketOrderSide::Sell => *order.get_price_per_unit() <= price,
+        };
+        if !crosses {
+            break;
+        }
+        // Only as much as the buyer's hangar takes; the rest stays ordered.
+        let buyer = match side {
+            MarketOrderSide::Buy => order.get_player_id(),
+            MarketOrderSide::Sell => counter.get_player_id(),
+        };
+        let quantity = order
+            .quantity_remaining
+            .min(counter.quantity_remaining)
+            .min(hangar_room(dsl, &station_id, &buyer, &item_def));
+        if quantity == 0 {
+            match side {
+                MarketOrderSide::Buy => break,
+                MarketOrderSide::Sell => continue,
+            }
+        }
+        match side {
+            MarketOrderSide::Buy => {
+                settle_between_players(dsl, &mut order, &mut counter, quantity, price)?
+            }
+            MarketOrderSide::Sell => {
+                settle_between_players(dsl, &mut counter, &mut order, quantity, price)?
+            }
+        }
+        save_or_finish(dsl, counter)?;
+        if order.quantity_remaining == 0 {
+            break;
+        }
+    }
+    save_or_finish(dsl, order)
+}
+
+/// Fills as much of `order` as the station's own stock and prices allow.
+fn match_against_station<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    mut order: MarketOrder,
+) -> Result<Option<MarketOrder>, String> {
+    let Some(mut item) = dsl
+        .get_station_module_inventory_items_by_module_id(&order.get_module_id())
+        .find(|item| item.get_resource_item_id() == order.get_item_id())
+    else {
+        return Ok(Some(order));
+    };
+    let station_module = dsl.get_station_module_by_id(&order.get_module_id())?;
+    let player = dsl.get_player_by_id(&order.get_player_id())?;
+    let station = dsl.get_station_by_id(&station_module.get_station_id())?;
+    // A station that turns hostile closes the order once, rather than
+    // warning its owner on every production tick until it expires.
+    let standing = match standing_discount(
+        dsl,
+        &station.get_owner_faction_id(),
+        player.get_faction_id(),
+    ) {
+        Ok(standing) => standing,
+        Err(refusal) => {
+            let reason = format!("was closed because {}", refusal.trim_end_matches('.'));
+            close_market_order(dsl, order, &reason)?;
+            return Ok(None);
+        }
+    };
+    let item_def = dsl.get_item_definition_by_id(&order.get_item_id())?;
+    let now = dsl.ctx().timestamp()?.to_micros_since_unix_epoch();
+    let quote = market_quote(dsl, &item, &item_def, standing, now);
+    let limit = *order.get_price_per_unit() as u64;
+    let room = hangar_room(
+        dsl,
+        &station_module.get_station_id(),
+        &order.get_player_id(),
+        &item_def,
+    );
+
+    let (trade_side, quantity) = match order.get_side() {
+        // The player buys from the station while it charges no more than the limit
+        MarketOrderSide::Buy => (
+            TradeSide::Buy,
+            largest_fill(
+                order.quantity_remaining.min(*item.get_quantity()).min(room),
+                |n| quote.total(TradeSide::Buy, n) as u64 <= limit * n as u64,
+            ),
+        ),
+        // The player sells to the station while it pays at least the limit
+        MarketOrderSide::Sell => (
+            TradeSide::Sell,
+            largest_fill(
+                order
+                    .quantity_remaining
+                    .min(item.get_max_quantity().saturating_sub(*item.get_quantity())),
+                |n| quote.total(TradeSide::Sell, n) as u64 >= limit * n as u64,
+            ),
+        ),
+    };
+    if quantity == 0 {
+        return Ok(Some(order));
+    }
+    let total = quote.total(trade_side, quantity) as u64;
+
+    match order.get_side() {
+        MarketOrderSide::Buy => {
+            let held = limit * quantity as u64;
+            order.escrowed_credits -= held;
+            pay_player(dsl, &order.get_player_id(), held - total)?;
+            add_to_hangar(
+                dsl,
+                &station_module.get_station_id(),
+                &order.get_player_id(),
+                &order.get_item_id(),
+                quantity,
+            )?;
+            item.set_quantity(item.get_quantity() - quantity);
+        }
+        MarketOrderSide::Sell => {
+            pay_player(dsl, &order.get_player_id(), total)?;
+            item.set_quantity(item.get_quantity() + quantity);
+        }
+    }
+    order.quantity_remaining -= quantity;
+
+    record_trade(dsl, &item.get_id(), &quote, trade_side, quantity, now)?;
+    let after = market_quote(dsl, &item, &item_def, 0.0, now);
+    item.set_cached_price(after.mid_price().round() as u32);
+    dsl.update_station_module_inventory_item_by_id(item)?;
+
+    send_direct_server_info(
+        dsl,
+        &order.get_player_id(),
+        format!(
+            "Your {} order filled {}x {} with the station for {}c.",
+            side_name(order.get_side()),
+            quantity,
+            item_def.get_name(),
+            total
+        ),
+    )?;
+
+    save_or_finish(dsl, order)
+}
+
+/// Trades `quantity` units at `price` each from `sell`'s escrow to `buy`'s
+/// owner, paying the seller and refunding the buyer what they held above
+/// `price`.
+fn settle_between_players<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    buy: &mut MarketOrder,
+    sell: &mut MarketOrder,
+    quantity: u32,
+    price: u32,
+) -> Result<(), String> {
+    let held = *buy.get_price_per_unit() as u64 * quantity as u64;
+    let paid = price as u64 * quantity as u64;
+    buy.escrowed_credits -= held;
+    buy.quantity_remaining -= quantity;
+    sell.quantity_remaining -= quantity;
+
+    pay_player(dsl, &buy.get_player_id(), held - paid)?;
+    pay_player(dsl, &sell.get_player_id(), paid)?;
+    let station_id = dsl
+        .get_station_module_by_id(&buy.get_module_id())?
+        .get_station_id();
+    add_to_hangar(
+        dsl,
+        &station_id,
+        &buy.get_player_id(),
+        &buy.get_item_id(),
+        quantity,
+    )?;
+
+    let item_name = dsl
+        .get_item_definition_by_id(&buy.get_item_id())?
+        .get_name()
+        .clone();
+    send_direct_server_info(
+        dsl,
+        &buy.get_player_id(),
+        format!(
+            "Your buy order bought {}x {} at {}c each. They're in your hangar.",
+            quantity, item_name, price
+        ),
+    )?;
+    send_direct_server_info(
+        dsl,
+        &sell.get_player_id(),
+        format!(
+            "Your sell order sold {}x {} at {}c each for {}c.",
+            quantity, item_name, price, paid
+        ),
+    )?;
+    Ok(())
+}
+
+/// Hands back what's left of `order`'s escrow, tells its owner why, and
+/// deletes it. Unsold cargo that doesn't fit in the owner's hangar stays in
+/// the order, which is closed again on a later tick once there's room.
+fn close_market_order<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    mut order: MarketOrder,
+    reason: &str,
+) -> Result<(), String> {
+    let item_def = dsl.get_item_definition_by_id(&order.get_item_id())?;
+    let left = order.quantity_remaining;
+    let returned = match order.get_side() {
+        MarketOrderSide::Buy => {
+            pay_player(dsl, &order.get_player_id(), order.escrowed_credits)?;
+            format!("{}c refunded", order.escrowed_credits)
+        }
+        MarketOrderSide::Sell => {
+            let station = dsl.get_station_by_id(
+                &dsl.get_station_module_by_id(&order.get_module_id())?
+                    .get_station_id(),
+            )?;
+            let fits = left.min(hangar_room(
+                dsl,
+                &station.get_id(),
+                &order.get_player_id(),
+                &item_def,
+            ));
+            if fits == 0 {
+                return Ok(());
+            }
+            add_to_hangar(
+                dsl,
+                &station.get_id(),
+                &order.get_player_id(),
+                &order.get_item_id(),
+                fits,
+            )?;
+            order.quantity_remaining -= fits;
+            // What's kept stops trading; the production tick retries it.
+            order.expires_at = order.expires_at.min(dsl.ctx().timestamp()?);
+            let kept = match order.quantity_remaining {
+                0 => String::new(),
+                kept => format!(", {}x stay in the order until it has room", kept),
+            };
+            format!(
+                "{}x {} returned to your hangar at {}{}",
+                fits,
+                item_def.get_name(),
+                station.get_name(),
+                kept
+            )
+        }
+    };
+
+    info!(
+        "close_market_order: order={} {} with {} of {} left",
+        order.get_id().value(),
+        reason,
+        left,
+        order.get_quantity_ordered()
+    );
+    send_direct_server_info(
+        dsl,
+        &order.get_player_id(),
+        format!(
+            "Your {} order for {}x {} at {}c {}: {}.",
+            side_name(order.get_side()),
+            order.get_quantity_ordered(),
+            item_def.get_name(),
+            order.get_price_per_unit(),
+            reason,
+            returned
+        ),
+    )?;
+    if *order.get_side() == MarketOrderSide::Sell && order.quantity_remaining > 0 {
+        dsl.update_market_order_by_id(order)?;
+    } else {
+        dsl.delete_market_order_by_id(&order)?;
+    }
+    Ok(())
+}
+
+/// Writes `order` back, or deletes it once nothing is left of it.
+fn save_or_finish<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    order: MarketOrder,
+) -> Result<Option<MarketOrder>, String> {
+    if order.quantity_remaining == 0 {
+        dsl.delete_market_order_by_id(&order)?;
+        Ok(None)
+    } else {
+        Ok(Some(dsl.update_market_order_by_id(order)?))
+    }
+}
+
+fn pay_player<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    player_id: &PlayerId,
+    credits: u64,
+) -> Result<(), String> {
+    if credits == 0 {
+        return Ok(());
+    }
+    let mut player = dsl.get_player_by_id(player_id)?;
+    player.set_credits(player.get_credits() + credits);
+    dsl.update_player_by_id(player)?;
+    Ok(())
+}
+
+/// The largest `n` in `0..=max` for which `fills(n)` holds, given it holds for
+/// every smaller `n` too.
+fn largest_fill(max: u32, fills: impl Fn(u32) -> bool) -> u32 {
+    let (mut low, mut high) = (0, max);
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        if fills(mid) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    low
+}
+
+fn side_name(side: &MarketOrderSide) -> &'static str {
+    match side {
+        MarketOrderSide::Buy => "buy",
+        MarketOrderSide::Sell => "sell",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn largest_fill_finds_the_edge() {
+        assert_eq!(largest_fill(100, |n| n <= 37), 37);
+        assert_eq!(largest_fill(100, |_| true), 100);
+        assert_eq!(largest_fill(100, |n| n == 0), 0);
+        assert_eq!(largest_fill(0, |_| true), 0);
+    }
+}
@@ -1,7 +1,9 @@
 use crate::{
-    definitions::item_types::*,
+    definitions::{item_types::*, station_module_types},
     logic::stations::{
-        module_types::{manufacturing::*, refineries::*, solar_arrays::*, trading_port},
+        module_types::{
+            manufacturing::*, refineries::*, solar_arrays::*, storage_depots::*, trading_port,
+        },
         production::*,
         status::*,
     },
@@ -15,10 +17,22 @@ use std::time::Duration;
 
 pub mod buy_and_sell;
 pub mod contribution;
+pub mod growth;
+pub mod hangar;
+pub mod leaderboards;
+pub mod logistics;
+pub mod market_orders;
+pub mod module_proposals;
 pub mod module_types;
+pub mod power;
 pub mod production;
+pub mod shipyard;
 pub mod status;
 
+/// How often every station runs a production tick — the galaxy's economy
+/// tick.
+pub const STATION_PRODUCTION_INTERVAL_SECS: u64 = 30;
+
 ///////////////////////////////////////////////////////////////////////////////////////////
 /// Utilties
 
@@ -75,6 +89,59 @@ pub fn create_silicon_refinery_module<T: spacetimedsl::WriteContext + 'static>(
     })
 }
 
+/// Map a well-known module key to its creator function. Shared by
+/// `admin_place_station` (fitting a new station), `admin_add_station_module`
+/// (fitting an existing one) and galaxy layouts so the paths can't drift. The error lists the
+/// valid keys so a typo is self-diagnosing in the logs.
+pub fn module_creator_from_key<T: spacetimedsl::WriteContext + 'static>(
+    key: &str,
+) -> Result<ModuleCreationFn<T>, String> {
+    Ok(match key {
+        "trading" => create_trading_module(),
+        "iron_refinery" => create_iron_refinery_module(),
+        "ice_refinery" => create_ice_refinery_module(),
+        "silicon_refinery" => create_silicon_refinery_module(),
+        "solar_array" => create_small_solar_array_module(),
+        "advanced_manufacturing" => create_advanced_manufacturing_module(),
+        "storage_depot" => create_minor_storage_depot_module(),
+        "shipyard" => create_shipyard_module(),
+        "metal_plate_factory" => create_metal_plate_module_fn(),
+        other => {
+            return Err(format!(
+                "unknown module key {:?} (known: trading, iron_refinery, ice_refinery, silicon_refinery, solar_array, advanced_manufacturing, storage_depot, shipyard, metal_plate_factory)",
+                other
+            ));
+        }
+    })
+}
+
+/// Map a `PROPOSABLE_MODULES` blueprint to its creator function, for modules
+/// built from a player proposal (`module_proposals.rs`). `None` for
+/// blueprints players can't propose.
+pub fn module_creator_from_blueprint<T: spacetimedsl::WriteContext + 'static>(
+    blueprint_id: u32,
+) -> Option<ModuleCreationFn<T>> {
+    Some(match blueprint_id {
+        station_module_types::MODULE_TRADING_BAZAAR => create_trading_module(),
+        station_module_types::MODULE_MINOR_DEPOT => create_minor_storage_depot_module(),
+        station_module_types::MODULE_MAJOR_DEPOT => Box::new(|dsl, station| {
+            create_storage_depot_module(dsl, station, false, StorageDepotSize::Major)
+        }),
+        station_module_types::MODULE_CAPITOL_DEPOT => Box::new(|dsl, station| {
+            create_storage_depot_module(dsl, station, false, StorageDepotSize::Capitol)
+        }),
+        station_module_types::MODULE_MANUFACTURING_FACTORY => create_basic_manufacturing_module_fn(),
+        station_module_types::MODULE_MANUFACTURING_FACTORY_ADVANCED => create_advanced_manufacturing_module(),
+        station_module_types::MODULE_MANUFACTURING_SHIPYARD => create_shipyard_module(),
+        station_module_types::MODULE_SOLAR_ARRAY_SMALL => create_small_solar_array_module(),
+        station_module_types::MODULE_SOLAR_ARRAY_MEDIUM => Box::new(|dsl, station| {
+            create_simple_solar_array_module(dsl, station, false, SolarArraySize::Medium)
+        }),
+        station_module_types::MODULE_SOLAR_ARRAY_LARGE => create_large_solar_array_module(),
+        _ => return None,
+    })
+}
+
 /// Helper function to create a station with modules and automatically set up schedules
 pub fn create_station_with_modules<T: spacetimedsl::WriteContext>(
     dsl: &DSL<T>,
@@ -107,7 +174,9 @@ pub fn create_station_with_modules<T: spacetimedsl::WriteContext>(
     // Set up station production schedule (every 30 seconds) TODO Tie this to GlobalConfig
     dsl.create_station_production_schedule(CreateStationProductionSchedule {
         id: station.get_id(),
-        scheduled_at: ScheduleAt::Interval(Duration::from_secs(30).into()),
+        scheduled_at: ScheduleAt::Interval(
+            Duration::from_secs(STATION_PRODUCTION_INTERVAL_SECS).into(),
+        ),
         last_processed_timestamp: dsl.ctx().timestamp()?,
     })?;
 
@@ -149,25 +218,20 @@ pub fn verify<T: spacetimedsl::WriteContext>(
 }
 
 /// LogisticsAndStorage,
+/// Refreshes the module's prices (and samples its price history) and works through its market orders, then
+/// runs a logistics pass sized by the blueprint's storage slots (see `logistics`) and returns what it moved.
 pub fn update_logistics_and_storage<T: spacetimedsl::WriteContext>(
     dsl: &DSL<T>,
-    _station: &Station,
+    station: &Station,
     module: &StationModule,
-    _blueprint: &StationModuleBlueprint,
-) -> Result<(), String> {
-    // Update cached prices for all inventory items in this module
-    for mut inventory_item in dsl.get_station_module_inventory_items_by_module_id(module.get_id()) {
-        if let Ok(item_def) =
-            dsl.get_item_definition_by_id(ItemDefinitionId::new(inventory_item.resource_item_id))
-        {
-            let current_price = inventory_item.calculate_current_price(&item_def);
-            //info!("    Old Value : {}c", inventory_item.cached_price);
-            inventory_item.set_cached_price(current_price);
-            dsl.update_station_module_inventory_item_by_id(inventory_item)?;
-        }
-    }
+    blueprint: &StationModuleBlueprint,
+) -> Result<Vec<LogisticsTransfer>, String> {
+    trading_port::refresh_module_prices(dsl, module)?;
+    market_orders::run_market_orders(dsl, module)?;
 
-    Ok(())
+    let capacity =
+        *blueprint.get_max_internal_storage_slots() as u32 * logistics::LOGISTICS_UNITS_PER_SLOT;
+    logistics::run_logistics_pass(dsl, station, capacity)
 }
 
 /// ResourceProductionAndRefining,
@@ -425,6 +489,18 @@ pub fn create_advanced_manufacturing_module<T: spacetimedsl::WriteContext + 'sta
     })
 }
 
+/// Helper function to create a shipyard fabrication module
+pub fn create_shipyard_module<T: spacetimedsl::WriteContext + 'static>() -> ModuleCreationFn<T> {
+    Box::new(|dsl, station| {
+        create_basic_manufacturing_module(
+            dsl,
+            station,
+            false,
+            ManufacturingType::ShipyardFabrication,
+        )
+    })
+}
+
 /// Helper function to create a small solar array module
 pub fn create_small_solar_array_module<T: spacetimedsl::WriteContext + 'static>(
 ) -> ModuleCreationFn<T> {
@@ -441,6 +517,14 @@ pub fn create_large_solar_array_module<T: spacetimedsl::WriteContext + 'static>(
     })
 }
 
+/// Helper function to create a minor storage depot module
+pub fn create_minor_storage_depot_module<T: spacetimedsl::WriteContext + 'static>(
+) -> ModuleCreationFn<T> {
+    Box::new(|dsl, station| {
+        create_storage_depot_module(dsl, station, false, StorageDepotSize::Minor)
+    })
+}
+
 /// Helper function to create a metal plate manufacturing module
 pub fn create_metal_plate_module_fn<T: spacetimedsl::WriteContext + 'static>() -> ModuleCreationFn<T>
 {
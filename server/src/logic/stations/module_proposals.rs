@@ -0,0 +1,363 @@
+//! # Module proposals
+//!
+//! Players fit out stations the same way they build them. A player with a
+//! Docked Ship at an operational station proposes one of the
+//! `PROPOSABLE_MODULES`, which opens a Contribution Pool sized by the
+//! blueprint's `build_cost_resources` (`StationModuleUnderConstruction`).
+//! Anyone docked there can contribute; when the pool fills the module is
+//! built, and the station's production schedule runs it from the next tick.
+//!
+//! Proposing needs a free module slot, no other module already being built
+//! at the station, and standing with the station's owner of at least
+//! `MODULE_PROPOSAL_MIN_REPUTATION`. Members of the owning faction always
+//! qualify.
+
+use log::info;
+use spacetimedb::ReducerContext;
+use spacetimedsl::*;
+
+use crate::{
+    definitions::{factions::REPUTATION_NEUTRAL, station_module_types::PROPOSABLE_MODULES},
+    logic::{
+        ships::cargo::remove_cargo_from_ship,
+        stations::{
+            contribution::compute_construction_progress, leaderboards::record_contribution_tally,
+            module_creator_from_blueprint, verify,
+        },
+    },
+    tables::{
+        factions::get_faction_reputation,
+        items::*,
+        messages::{
+            post_galaxy_channel, send_direct_server_info, send_direct_server_warning, MessageSender,
+        },
+        players::*,
+        ships::*,
+        stations::*,
+    },
+    utility::{is_server_or_ship_owner, ShipAccess},
+};
+
+/// The lowest standing between the player's faction and the station owner's
+/// that still lets them propose a module there.
+pub const MODULE_PROPOSAL_MIN_REPUTATION: i32 = REPUTATION_NEUTRAL;
+
+///////////////////////////////////////////////////////////
+// Reducers
+///////////////////////////////////////////////////////////
+
+/// Proposes building `blueprint_id` at the station the Docked Ship `ship_id`
+/// is docked at, opening its Contribution Pool.
+#[spacetimedb::reducer]
+pub fn propose_station_module(
+    ctx: &ReducerContext,
+    ship_id: ShipId,
+    blueprint_id: StationModuleBlueprintId,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    let ship = is_server_or_ship_owner(&dsl, &ship_id, ShipAccess::Docked)?;
+    let player_id = ship.get_player_id();
+    let player = dsl.get_player_by_id(&player_id)?;
+    let station = dsl.get_station_by_id(&ship.get_station_id())?;
+    let blueprint = dsl.get_station_module_blueprint_by_id(&blueprint_id)?;
+
+    if let Some(msg) = proposal_problem(&dsl, &player, &station, &blueprint) {
+        let _ = send_direct_server_warning(&dsl, &player_id, msg.clone());
+        return Err(msg);
+    }
+
+    dsl.create_station_module_under_construction(CreateStationModuleUnderConstruction {
+        station_id: station.get_id(),
+        blueprint: blueprint.get_id(),
+        proposed_by: player_id.value(),
+        construction_progress_percentage: 0.0,
+    })?;
+
+    info!(
+        "propose_station_module: player={} station={} blueprint={}",
+        player_id.value(),
+        station.get_id().value(),
+        blueprint_id.value()
+    );
+    send_direct_server_info(
+        &dsl,
+        &player_id,
+        format!(
+            "Proposed a {} at '{}'. Docked pilots can now contribute to it.",
+            blueprint.get_name(),
+            station.get_name()
+        ),
+    )?;
+    Ok(())
+}
+
+/// Deposits cargo from the Docked Ship `ship_id` into the module being built
+/// at its station. Capped at what the pool still needs of `item_id`.
+#[spacetimedb::reducer]
+pub fn contribute_to_station_module(
+    ctx: &ReducerContext,
+    ship_id: ShipId,
+    item_id: ItemDefinitionId,
+    quantity: u32,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    let ship = is_server_or_ship_owner(&dsl, &ship_id, ShipAccess::Docked)?;
+    let player_id = ship.get_player_id();
+    let station = dsl.get_station_by_id(&ship.get_station_id())?;
+    let item_def = dsl.get_item_definition_by_id(&item_id)?;
+
+    let warn = |msg: String| {
+        let _ = send_direct_server_warning(&dsl, &player_id, msg.clone());
+        Err(msg)
+    };
+
+    if quantity == 0 {
+        return warn("Contribute at least one unit.".to_string());
+    }
+    let Ok(proposal) = dsl.get_station_module_under_construction_by_station_id(&station.get_id())
+    else {
+        return warn(format!(
+            "No module is being built at '{}'.",
+            station.get_name()
+        ));
+    };
+    let blueprint = dsl.get_station_module_blueprint_by_id(&proposal.get_blueprint())?;
+
+    let required: u32 = blueprint
+        .build_cost_resources
+        .iter()
+        .filter(|cost| cost.resource_item_id == item_id.value())
+        .map(|cost| cost.quantity)
+        .sum();
+    if required == 0 {
+        return warn(format!(
+            "The {} doesn't need '{}'.",
+            blueprint.get_name(),
+            item_def.get_name()
+        ));
+    }
+    let already_contributed: u32 = aggregate_proposal_contributions(&dsl, &proposal)
+        .iter()
+        .filter(|(id, _)| *id == item_id.value())
+        .map(|(_, qty)| *qty)
+        .sum();
+    let remainder = required.saturating_sub(already_contributed);
+    if remainder == 0 {
+        return warn(format!(
+            "The {} already has all the '{}' it needs ({} / {}).",
+            blueprint.get_name(),
+            item_def.get_name(),
+            already_contributed,
+            required
+        ));
+    }
+
+    let effective_qty = quantity.min(remainder);
+    let effective_qty_u16: u16 = effective_qty.try_into().map_err(|_| {
+        format!(
+            "contribute_to_station_module: capped quantity {} exceeds u16 cargo limit",
+            effective_qty
+        )
+    })?;
+    let cargo_available: u32 = dsl
+        .get_ship_cargo_items_by_ship_id(&ship.get_id())
+        .filter(|c| c.get_item_id() == item_id)
+        .map(|c| *c.get_quantity() as u32)
+        .sum();
+    if cargo_available < effective_qty {
+        return warn(format!(
+            "Cannot contribute {}x {}: ship #{} only carries {}.",
+            effective_qty,
+            item_def.get_name(),
+            ship.get_id().value(),
+            cargo_available
+        ));
+    }
+
+    let mut ship_status = dsl.get_ship_status_by_id(&ship.get_id())?;
+    remove_cargo_from_ship(&dsl, &mut ship_status, &item_def, effective_qty_u16)?;
+
+    dsl.create_construction_contribution_log(CreateConstructionContributionLog {
+        station_id: station.get_id(),
+        player_id: player_id.clone(),
+        item_id,
+        quantity: effective_qty,
+        stage: 0,
+        module_proposal_id: Some(proposal.get_id().value()),
+        contributed_at: ctx.timestamp,
+    })?;
+    record_contribution_tally(
+        &dsl,
+        &station,
+        &player_id,
+        &item_def,
+        effective_qty,
+        ctx.timestamp,
+    )?;
+
+    let progress = refresh_proposal_progress(&dsl, &station, proposal, &blueprint)?;
+
+    send_direct_server_info(
+        &dsl,
+        &player_id,
+        format!(
+            "Contributed {}x {} to the {} at '{}'. Construction now {:.1}%.",
+            effective_qty,
+            item_def.get_name(),
+            blueprint.get_name(),
+            station.get_name(),
+            progress
+        ),
+    )?;
+    Ok(())
+}
+
+///////////////////////////////////////////////////////////
+// Utilities
+///////////////////////////////////////////////////////////
+
+/// Why `player` can't propose `blueprint` at `station`, if anything.
+fn proposal_problem<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    player: &Player,
+    station: &Station,
+    blueprint: &StationModuleBlueprint,
+) -> Option<String> {
+    let module_count = dsl
+        .get_station_modules_by_station_id(station.get_id())
+        .count();
+    let max_modules = station.get_size().max_module_amount() as usize;
+    let standing = get_faction_reputation(
+        dsl,
+        player.get_faction_id(),
+        &station.get_owner_faction_id(),
+    );
+
+    if !PROPOSABLE_MODULES.contains(&blueprint.get_id().value())
+        || blueprint.build_cost_resources.is_empty()
+    {
+        Some(format!("The {} can't be proposed.", blueprint.get_name()))
+    } else if dsl
+        .get_station_under_construction_by_id(station.get_id())
+        .is_ok_and(|uc| !*uc.get_is_operational())
+    {
+        Some(format!(
+            "'{}' is still under construction.",
+            station.get_name()
+        ))
+    } else if let Ok(open) =
+        dsl.get_station_module_under_construction_by_station_id(station.get_id())
+    {
+        let building = dsl
+            .get_station_module_blueprint_by_id(&open.get_blueprint())
+            .map(|b| b.get_name().clone())
+            .unwrap_or_default();
+        Some(format!(
+            "'{}' is already building a {}.",
+            station.get_name(),
+            building
+        ))
+    } else if module_count >= max_modules {
+        Some(format!(
+            "'{}' has no free module slot ({} / {}).",
+            station.get_name(),
+            module_count,
+            max_modules
+        ))
+    } else if player.get_faction_id().value() != station.get_owner_faction_id().value()
+        && standing < MODULE_PROPOSAL_MIN_REPUTATION
+    {
+        Some(format!(
+            "'{}' won't let your faction build there (standing {}).",
+            station.get_name(),
+            standing
+        ))
+    } else {
+        None
+    }
+}
+
+/// Sum every contribution to `proposal`, grouped by item id.
+fn aggregate_proposal_contributions<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    proposal: &StationModuleUnderConstruction,
+) -> Vec<(u32, u32)> {
+    let mut totals: Vec<(u32, u32)> = Vec::new();
+    for log in dsl
+        .get_construction_contribution_logs_by_station_id(proposal.get_station_id())
+        .filter(|log| *log.get_module_proposal_id() == Some(proposal.get_id().value()))
+    {
+        let item_id = log.get_item_id().value();
+        match totals.iter_mut().find(|(id, _)| *id == item_id) {
+            Some(entry) => entry.1 += *log.get_quantity(),
+            None => totals.push((item_id, *log.get_quantity())),
+        }
+    }
+    totals
+}
+
+/// Recompute `proposal`'s progress and persist it. A full pool builds the
+/// module, announces it and closes the proposal.
+fn refresh_proposal_progress<T: spacetimedsl::WriteContext + 'static>(
+    dsl: &DSL<T>,
+    station: &Station,
+    mut proposal: StationModuleUnderConstruction,
+    blueprint: &StationModuleBlueprint,
+) -> Result<f32, String> {
+    let requirements: Vec<(u32, u32)> = blueprint
+        .build_cost_resources
+        .iter()
+        .map(|cost| (cost.resource_item_id, cost.quantity))
+        .collect();
+    let contributions = aggregate_proposal_contributions(dsl, &proposal);
+    let progress = compute_construction_progress(&requirements, &contributions);
+
+    if progress < 100.0 {
+        proposal.set_construction_progress_percentage(progress);
+        dsl.update_station_module_under_construction_by_id(proposal)?;
+        return Ok(progress);
+    }
+
+    let creator = module_creator_from_blueprint(blueprint.get_id().value()).ok_or_else(|| {
+        format!(
+            "refresh_proposal_progress: no creator for blueprint {}",
+            blueprint.get_id().value()
+        )
+    })?;
+    creator(dsl, station)?;
+    verify(dsl, station)?;
+    dsl.delete_station_module_under_construction_by_id(&proposal)?;
+
+    post_galaxy_channel(
+        dsl,
+        MessageSender::System,
+        format!(
+            "'{}' (station #{}) has finished building a {}.",
+            station.get_name(),
+            station.get_id().value(),
+            blueprint.get_name()
+        ),
+    )?;
+    info!(
+        "refresh_proposal_progress: station={} built blueprint {}",
+        station.get_id().value(),
+        blueprint.get_id().value()
+    );
+    Ok(progress)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_proposable_module_has_a_creator() {
+        for blueprint_id in PROPOSABLE_MODULES {
+            assert!(
+                module_creator_from_blueprint::<ReducerContext>(blueprint_id).is_some(),
+                "{}",
+                blueprint_id
+            );
+        }
+    }
+}
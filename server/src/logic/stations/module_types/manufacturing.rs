@@ -3,33 +3,34 @@ use spacetimedb::*;
 use spacetimedsl::*;
 
 use crate::definitions::item_types::*;
+use crate::definitions::recipes::RECIPE_METAL_PLATES;
 use crate::tables::economy::ResourceAmount;
 use crate::tables::items::*;
 use crate::tables::stations::*;
 
-/// Defines a recipe that a manufacturing module can use.
-#[dsl(plural_name = production_recipe_definitions, method(update = false))]
+/// Defines a recipe that a manufacturing module can use. Loaded from
+/// `content/recipes.ron` by `definitions::content`.
+#[dsl(plural_name = production_recipe_definitions, method(update = true))]
 #[table(accessor = production_recipe_definition, public)]
 pub struct ProductionRecipeDefinition {
     #[primary_key]
-    #[auto_inc]
     #[create_wrapper]
     id: u32,
 
     #[unique]
-    name: String, // e.g., "Basic Hull Plating", "Mk1 Laser Cannon Assembly"
+    pub name: String, // e.g., "Basic Hull Plating", "Mk1 Laser Cannon Assembly"
 
-    input_resources: Vec<ResourceAmount>,
+    pub input_resources: Vec<ResourceAmount>,
 
     #[use_wrapper(crate::tables::items::ItemDefinitionId)]
     /// FK to ItemDefinition
-    output_resource_id: u32, // FK to ResourceDefinition
+    pub output_resource_id: u32, // FK to ResourceDefinition
 
-    output_quantity: u32,
-    base_production_time_seconds: u32,
+    pub output_quantity: u32,
+    pub base_production_time_seconds: u32,
     /// Which type of module can use this recipe (e.g., Factory, Assembler)
-    required_module_specific_type: StationModuleSpecificType,
-    required_tech_id_to_unlock: Option<u32>, // FK to TechnologyTreeNode
+    pub required_module_specific_type: StationModuleSpecificType,
+    pub required_tech_id_to_unlock: Option<u32>, // FK to TechnologyTreeNode
 }
 
 /// Data for a generic manufacturing module instance (Factory, Assembler, Fabricator).
@@ -213,22 +214,21 @@ pub fn create_basic_manufacturing_module<T: spacetimedsl::WriteContext>(
     Ok(())
 }
 
-/// Generic function to create a manufacturing module with a specific recipe
-pub fn create_manufacturing_module_and_recipe<T: spacetimedsl::WriteContext>(
+/// Generic function to create a manufacturing module running one of the
+/// recipes from `content/recipes.ron`
+pub fn create_manufacturing_module_with_recipe<T: spacetimedsl::WriteContext>(
     dsl: &DSL<T>,
     station: &Station,
     module_name: &str,
-    recipe_name: &str,
-    input_resources: Vec<ResourceAmount>,
-    output_resource_id: u32,
-    output_quantity: u32,
-    production_time_seconds: u32,
+    recipe_id: u32,
     under_construction: bool,
 ) -> Result<(), String> {
     if under_construction {
         return Err("Not yet implemented".to_string());
     }
 
+    let recipe =
+        dsl.get_production_recipe_definition_by_id(ProductionRecipeDefinitionId::new(recipe_id))?;
     let blueprint = dsl.get_station_module_blueprint_by_id(StationModuleBlueprintId::new(
         MODULE_MANUFACTURING_FACTORY,
     ))?;
@@ -242,17 +242,6 @@ pub fn create_manufacturing_module_and_recipe<T: spacetimedsl::WriteContext>(
         last_status_update_timestamp: dsl.ctx().timestamp()?,
     })?;
 
-    // Create the recipe
-    let recipe = dsl.create_production_recipe_definition(CreateProductionRecipeDefinition {
-        name: recipe_name.to_string(),
-        input_resources: input_resources.clone(),
-        output_resource_id: ItemDefinitionId::new(output_resource_id),
-        output_quantity,
-        base_production_time_seconds: production_time_seconds,
-        required_module_specific_type: StationModuleSpecificType::FactoryBasicComponents,
-        required_tech_id_to_unlock: None, // No tech requirement
-    })?;
-
     // Create manufacturing submodule with the recipe
     dsl.create_manufacturing_module(CreateManufacturingModule {
         id: module.get_id(),
@@ -264,9 +253,9 @@ pub fn create_manufacturing_module_and_recipe<T: spacetimedsl::WriteContext>(
     })?;
 
     // Create inventory slots for all input resources
-    for input_resource in &input_resources {
+    for input_resource in recipe.get_input_resources() {
         create_manufacturing_inventory_slot(
-            &dsl,
+            dsl,
             &module,
             &blueprint,
             input_resource.resource_item_id,
@@ -275,8 +264,9 @@ pub fn create_manufacturing_module_and_recipe<T: spacetimedsl::WriteContext>(
     }
 
     // Create inventory slot for output resource
+    let output_resource_id = recipe.get_output_resource_id().value();
     create_manufacturing_inventory_slot(
-        &dsl,
+        dsl,
         &module,
         &blueprint,
         output_resource_id,
@@ -292,20 +282,11 @@ pub fn create_metal_plate_module<T: spacetimedsl::WriteContext>(
     station: &Station,
     under_construction: bool,
 ) -> Result<(), String> {
-    let input_resources = vec![
-        ResourceAmount::new(ITEM_IRON_INGOT, 2),  // 2 iron ingots
-        ResourceAmount::new(ITEM_ENERGY_CELL, 5), // 5 energy cells
-    ];
-
-    create_manufacturing_module_and_recipe(
+    create_manufacturing_module_with_recipe(
         dsl,
         station,
         "metal_plate_factory",
-        "Basic Metal Plate Production",
-        input_resources,
-        ITEM_METAL_PLATES,
-        3,  // Produces 3 metal plates
-        60, // Takes 60 seconds
+        RECIPE_METAL_PLATES,
         under_construction,
     )
 }
@@ -1,4 +1,5 @@
 pub mod manufacturing;
 pub mod refineries;
 pub mod solar_arrays;
+pub mod storage_depots;
 pub mod trading_port;
@@ -0,0 +1,47 @@
+use spacetimedsl::*;
+
+use crate::definitions::station_module_types::*;
+use crate::tables::stations::*;
+
+#[derive(Clone, Debug)]
+pub enum StorageDepotSize {
+    Minor,
+    Major,
+    Capitol,
+}
+
+////////////////////////////////////
+// Create Module
+
+/// A storage depot has no table of its own: its blueprint's storage slots and
+/// volume are what the station's hangars are sized from (see
+/// `logic::stations::hangar`).
+pub fn create_storage_depot_module<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    station: &Station,
+    under_construction: bool,
+    depot_size: StorageDepotSize,
+) -> Result<(), String> {
+    if under_construction {
+        return Err("Not yet implemented".to_string());
+    }
+
+    let blueprint_id = match depot_size {
+        StorageDepotSize::Minor => MODULE_MINOR_DEPOT,
+        StorageDepotSize::Major => MODULE_MAJOR_DEPOT,
+        StorageDepotSize::Capitol => MODULE_CAPITOL_DEPOT,
+    };
+    let blueprint =
+        dsl.get_station_module_blueprint_by_id(StationModuleBlueprintId::new(blueprint_id))?;
+
+    dsl.create_station_module(CreateStationModule {
+        station_id: station.get_id(),
+        blueprint: blueprint.get_id(),
+        station_slot_identifier: format!("{:?} Storage Depot", depot_size),
+        is_operational: true,
+        built_at_timestamp: None,
+        last_status_update_timestamp: dsl.ctx().timestamp()?,
+    })?;
+
+    Ok(())
+}
@@ -1,11 +1,33 @@
+//! # Trading ports
+//!
+//! Station modules that buy and sell goods with players. Prices follow the
+//! shared model in `solarance_shared::pricing`: stock level, recent trading
+//! pressure, the spread and the player's faction standing with the station's
+//! owner. A port's listings carry the pressure, and every listing is
+//! sampled into `StationPriceSample` every `PRICE_HISTORY_INTERVAL` so clients
+//! can chart it.
+
+use std::time::Duration;
+
+use solarance_shared::{
+    decayed_pressure, pricing::TRADE_SPREAD, reputation_discount, MarketQuote, TradeSide,
+};
 use spacetimedb::*;
 use spacetimedsl::*;
 
+use crate::definitions::factions::REPUTATION_HOSTILE;
 use crate::definitions::item_types::*;
 use crate::definitions::station_module_types::*;
+use crate::tables::factions::*;
 use crate::tables::items::*;
 use crate::tables::stations::*;
 
+/// How often a listing's price is sampled into `StationPriceSample`.
+pub const PRICE_HISTORY_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// How many samples are kept per listing — two days' worth.
+pub const PRICE_HISTORY_SAMPLES: usize = 48;
+
 #[dsl(plural_name = trading_port_modules, method(update = true))]
 #[table(accessor = trading_port_module, public)]
 pub struct TradingPort {
@@ -32,6 +54,90 @@ pub struct TradingPortListing {
     pub buying_margin: Option<f32>,
     /// None if the port is not selling, Some percentage of how much margin the port want above base price.
     pub selling_margin: Option<f32>,
+
+    /// Recent trading's push on the price as of `pressure_updated_at`, as a
+    /// fraction of the stock price. Decays with `solarance_shared::decayed_pressure`.
+    pub trade_pressure: f32,
+    /// Microseconds since the Unix epoch.
+    pub pressure_updated_at: i64,
+}
+
+/// A listing's prices at one point in time, for charting. The oldest samples
+/// beyond `PRICE_HISTORY_SAMPLES` are dropped.
+#[dsl(plural_name = station_price_samples, method(update = false))]
+#[table(accessor = station_price_sample, public)]
+pub struct StationPriceSample {
+    #[primary_key]
+    #[auto_inc]
+    #[create_wrapper]
+    id: u64,
+
+    #[index(btree)]
+    #[use_wrapper(StationModuleInventoryItemId)]
+    #[foreign_key(path = crate::tables::stations, table = station_module_inventory_item, column = id, on_delete = Delete)]
+    /// FK to StationModuleInventoryItem
+    inventory_item_id: u64,
+
+    /// What one unit costs a player with no standing either way.
+    buy_price: u32,
+    /// What one unit pays a player with no standing either way.
+    sell_price: u32,
+    quantity: u32,
+
+    recorded_at: Timestamp,
+}
+
+/// Which side of the order book a `MarketOrder` is on, from its owner's side.
+#[derive(SpacetimeType, Clone, Debug, PartialEq, Eq)]
+pub enum MarketOrderSide {
+    Buy,
+    Sell,
+}
+
+/// A player's standing order to buy or sell an item at a trading port. What
+/// the order could still cost or deliver is held in escrow until it fills,
+/// expires or is cancelled: credits for a buy order, the items themselves
+/// for a sell order. See `logic::stations::market_orders`.
+#[dsl(plural_name = market_orders, method(update = true))]
+#[table(accessor = market_order, public)]
+pub struct MarketOrder {
+    #[primary_key]
+    #[auto_inc]
+    #[create_wrapper]
+    id: u64,
+
+    #[index(btree)]
+    #[use_wrapper(StationModuleId)]
+    #[foreign_key(path = crate::tables::stations, table = station_module, column = id, on_delete = Error)]
+    /// FK to StationModule — the trading port the order was posted at.
+    module_id: u64,
+
+    #[index(btree)]
+    #[use_wrapper(crate::tables::players::PlayerId)]
+    #[foreign_key(path = crate::tables::players, table = player, column = id, on_delete = Error)]
+    /// FK to Player
+    player_id: Identity,
+
+    #[index(btree)]
+    #[use_wrapper(crate::tables::items::ItemDefinitionId)]
+    #[foreign_key(path = crate::tables::items, table = item_definition, column = id, on_delete = Error)]
+    /// FK to ItemDefinition
+    item_id: u32,
+
+    side: MarketOrderSide,
+    /// The most a buy order pays, or the least a sell order takes, per unit.
+    price_per_unit: u32,
+    quantity_ordered: u32,
+    pub quantity_remaining: u32,
+    /// Credits held for the units a buy order hasn't bought yet. Always 0 for
+    /// sell orders, whose remaining units are the escrow.
+    pub escrowed_credits: u64,
+
+    /// Past it the order no longer trades and is closed on the next
+    /// production tick — again on later ticks while its unsold cargo doesn't
+    /// fit in the owner's hangar.
+    pub expires_at: Timestamp,
+    created_at: Timestamp,
 }
 
 /// Configuration for a trading port item listing
@@ -42,6 +148,130 @@ pub struct TradingPortItemConfig {
     pub selling_margin: Option<f32>,
 }
 
+///////////////////////////////////////////////////////////
+/// Pricing
+
+/// The discount (negative for a surcharge) a player of `player_faction` gets at
+/// a station owned by `owner_faction`. Members of the owning faction count as
+/// allies. Errors if the owner is hostile to them and won't trade at all.
+pub fn standing_discount<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    owner_faction: &FactionId,
+    player_faction: &FactionId,
+) -> Result<f32, String> {
+    let reputation = if owner_faction == player_faction {
+        100
+    } else {
+        dsl.get_faction_standings_by_faction_one_id(owner_faction)
+            .find(|standing| standing.get_faction_two_id() == *player_faction)
+            .map(|standing| *standing.get_reputation_score())
+            .unwrap_or(0)
+    };
+    if reputation <= REPUTATION_HOSTILE {
+        return Err(format!(
+            "{} refuses to trade with your faction.",
+            get_faction_name(dsl, owner_faction)
+        ));
+    }
+    Ok(reputation_discount(reputation))
+}
+
+/// Prices `item` as of `current_time` (microseconds since the Unix epoch).
+/// Only trading port listings carry pressure; other modules price on stock.
+pub fn market_quote<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    item: &StationModuleInventoryItem,
+    item_def: &ItemDefinition,
+    standing_discount: f32,
+    current_time: i64,
+) -> MarketQuote {
+    let pressure = dsl
+        .get_trading_port_listing_by_id(item.get_id())
+        .map(|listing| {
+            decayed_pressure(
+                *listing.get_trade_pressure(),
+                *listing.get_pressure_updated_at(),
+                current_time,
+            )
+        })
+        .unwrap_or(0.0);
+    MarketQuote {
+        pressure,
+        spread: TRADE_SPREAD,
+        standing_discount,
+        ..MarketQuote::for_stock(
+            *item_def.get_base_value(),
+            *item_def.get_margin_percentage(),
+            *item.get_quantity(),
+            *item.get_max_quantity(),
+        )
+    }
+}
+
+/// Applies the pressure of a trade priced with `quote` to the item's listing,
+/// if it has one.
+pub fn record_trade<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    item_id: &StationModuleInventoryItemId,
+    quote: &MarketQuote,
+    side: TradeSide,
+    count: u32,
+    current_time: i64,
+) -> Result<(), String> {
+    if let Ok(mut listing) = dsl.get_trading_port_listing_by_id(item_id) {
+        listing.set_trade_pressure(quote.pressure_after(side, count));
+        listing.set_pressure_updated_at(current_time);
+        dsl.update_trading_port_listing_by_id(listing)?;
+    }
+    Ok(())
+}
+
+/// Refreshes `cached_price` for every item in `module`, and samples trading
+/// port listings into `StationPriceSample` when `PRICE_HISTORY_INTERVAL` has
+/// passed since their last sample.
+pub fn refresh_module_prices<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    module: &StationModule,
+) -> Result<(), String> {
+    let now = dsl.ctx().timestamp()?;
+    let now_micros = now.to_micros_since_unix_epoch();
+    for mut item in dsl.get_station_module_inventory_items_by_module_id(module.get_id()) {
+        let Ok(item_def) = dsl.get_item_definition_by_id(item.get_resource_item_id()) else {
+            continue;
+        };
+        let quote = market_quote(dsl, &item, &item_def, 0.0, now_micros);
+        item.set_cached_price(quote.mid_price().round() as u32);
+        let item = dsl.update_station_module_inventory_item_by_id(item)?;
+
+        if dsl.get_trading_port_listing_by_id(item.get_id()).is_err() {
+            continue;
+        }
+        let mut samples: Vec<StationPriceSample> = dsl
+            .get_station_price_samples_by_inventory_item_id(item.get_id())
+            .collect();
+        samples.sort_by_key(|sample| *sample.get_recorded_at());
+        let due = samples.last().is_none_or(|last| {
+            now_micros - last.get_recorded_at().to_micros_since_unix_epoch()
+                >= PRICE_HISTORY_INTERVAL.as_micros() as i64
+        });
+        if !due {
+            continue;
+        }
+        dsl.create_station_price_sample(CreateStationPriceSample {
+            inventory_item_id: item.get_id(),
+            buy_price: quote.total(TradeSide::Buy, 1),
+            sell_price: quote.total(TradeSide::Sell, 1),
+            quantity: *item.get_quantity(),
+            recorded_at: now,
+        })?;
+        let excess = (samples.len() + 1).saturating_sub(PRICE_HISTORY_SAMPLES);
+        for sample in samples.iter().take(excess) {
+            dsl.delete_station_price_sample_by_id(sample)?;
+        }
+    }
+    Ok(())
+}
+
 ///////////////////////////////////////////////////////////
 /// Create Modules
 
@@ -105,6 +335,8 @@ pub fn create_trading_port_with_items<T: spacetimedsl::WriteContext>(
                 id: item.get_id(),
                 buying_margin: item_config.buying_margin,
                 selling_margin: item_config.selling_margin,
+                trade_pressure: 0.0,
+                pressure_updated_at: dsl.ctx().timestamp()?.to_micros_since_unix_epoch(),
             })?;
         }
     }
@@ -0,0 +1,245 @@
+//! # Station power grid
+//!
+//! Every production tick starts by balancing the station's power and CPU
+//! (`update_station_power_grid`). Generation is the station's core reactor
+//! (`StationSize::core_reactor_mw`) plus every generator module's
+//! `power_generation_mw`, scaled by sunlight and health for solar arrays.
+//! Every operational module draws at least its idle power; the rest of the
+//! budget goes to running modules at full draw, highest
+//! `StationModuleCategory::power_priority` first, as long as the station has
+//! the CPU for them. Whatever doesn't fit is idled for the tick and skipped by
+//! the production tick.
+//!
+//! `StationStatus.energy` is the station's battery: a surplus charges it up to
+//! `StationSize::battery_capacity_mwh`, and a deficit drains it before any
+//! module is idled.
+
+use log::info;
+use spacetimedsl::*;
+
+use crate::{
+    logic::stations::{module_types::solar_arrays::*, STATION_PRODUCTION_INTERVAL_SECS},
+    tables::stations::*,
+};
+
+/// One operational module's claim on the grid.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PowerConsumer {
+    pub module_id: u64,
+    pub priority: u8,
+    pub idle_mw: f32,
+    pub operational_mw: f32,
+    pub cpu_flops: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PowerAllocation {
+    pub idled_module_ids: Vec<u64>,
+    pub draw_mw: f32,
+    pub cpu_load_flops: f32,
+    /// Battery charge left after the tick, in MWh.
+    pub battery_mwh: f32,
+}
+
+/// Rebalances `station`'s grid for this tick, persists the ledger and the
+/// battery charge, and returns the ledger.
+pub fn update_station_power_grid<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    station: &Station,
+    modules: &[StationModule],
+) -> Result<StationPowerGrid, String> {
+    let size = station.get_size();
+    let mut status = match dsl.get_station_status_by_id(station.get_id()) {
+        Ok(status) => status,
+        Err(_) => dsl.create_station_status(CreateStationStatus {
+            id: station.get_id(),
+            health: 100.0,
+            shields: 100.0,
+            energy: size.battery_capacity_mwh(),
+        })?,
+    };
+
+    let mut generation_mw = size.core_reactor_mw();
+    let mut consumers = Vec::new();
+    for module in modules.iter().filter(|m| *m.get_is_operational()) {
+        let Ok(blueprint) = dsl.get_station_module_blueprint_by_id(&module.get_blueprint()) else {
+            continue;
+        };
+        let is_generator = *blueprint.get_power_generation_mw() > 0.0;
+        if is_generator {
+            generation_mw += blueprint.get_power_generation_mw()
+                * generator_efficiency(dsl, station, module, &blueprint)?;
+        }
+        consumers.push(PowerConsumer {
+            module_id: module.get_id().value(),
+            // Idling a generator to save its draw would only cost more.
+            priority: if is_generator {
+                u8::MAX
+            } else {
+                blueprint.get_category().power_priority()
+            },
+            idle_mw: *blueprint.get_power_consumption_mw_idle(),
+            operational_mw: *blueprint.get_power_consumption_mw_operational(),
+            cpu_flops: *blueprint.get_cpu_load_flops(),
+        });
+    }
+
+    let allocation = allocate_power(
+        &consumers,
+        generation_mw,
+        size.cpu_capacity_flops(),
+        *status.get_energy(),
+        size.battery_capacity_mwh(),
+        STATION_PRODUCTION_INTERVAL_SECS as f32 / 3600.0,
+    );
+
+    let previously_idled = dsl
+        .get_station_power_grid_by_id(station.get_id())
+        .map(|grid| grid.get_idled_module_ids().clone())
+        .unwrap_or_default();
+    if allocation.idled_module_ids != previously_idled {
+        info!(
+            "update_station_power_grid: station={} generation={:.0}MW draw={:.0}MW battery={:.0}MWh idled={:?}",
+            station.get_id().value(),
+            generation_mw,
+            allocation.draw_mw,
+            allocation.battery_mwh,
+            allocation.idled_module_ids
+        );
+    }
+
+    status.set_energy(allocation.battery_mwh);
+    dsl.update_station_status_by_id(status)?;
+
+    let demand_mw = consumers.iter().map(|c| c.operational_mw).sum();
+    match dsl.get_station_power_grid_by_id(station.get_id()) {
+        Ok(mut grid) => {
+            grid.set_generation_mw(generation_mw);
+            grid.set_demand_mw(demand_mw);
+            grid.set_draw_mw(allocation.draw_mw);
+            grid.set_cpu_capacity_flops(size.cpu_capacity_flops());
+            grid.set_cpu_load_flops(allocation.cpu_load_flops);
+            grid.set_battery_capacity_mwh(size.battery_capacity_mwh());
+            grid.set_idled_module_ids(allocation.idled_module_ids);
+            Ok(dsl.update_station_power_grid_by_id(grid)?)
+        }
+        Err(_) => Ok(dsl.create_station_power_grid(CreateStationPowerGrid {
+            id: station.get_id(),
+            generation_mw,
+            demand_mw,
+            draw_mw: allocation.draw_mw,
+            cpu_capacity_flops: size.cpu_capacity_flops(),
+            cpu_load_flops: allocation.cpu_load_flops,
+            battery_capacity_mwh: size.battery_capacity_mwh(),
+            idled_module_ids: allocation.idled_module_ids,
+        })?),
+    }
+}
+
+/// Splits one tick's energy between `consumers`. Every consumer draws its
+/// idle power; the rest of the generation plus the battery runs consumers at
+/// full draw by priority (then module id) while energy and CPU last. What's
+/// left over charges the battery, up to `battery_capacity_mwh`.
+pub fn allocate_power(
+    consumers: &[PowerConsumer],
+    generation_mw: f32,
+    cpu_capacity_flops: f32,
+    battery_mwh: f32,
+    battery_capacity_mwh: f32,
+    tick_hours: f32,
+) -> PowerAllocation {
+    let idle_mw: f32 = consumers.iter().map(|c| c.idle_mw).sum();
+    let mut budget_mwh = (generation_mw - idle_mw) * tick_hours + battery_mwh;
+    let mut draw_mw = idle_mw;
+    let mut cpu_load_flops = 0.0;
+    let mut idled_module_ids = Vec::new();
+
+    let mut by_priority: Vec<&PowerConsumer> = consumers.iter().collect();
+    by_priority.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then(a.module_id.cmp(&b.module_id))
+    });
+    for consumer in by_priority {
+        let extra_mw = (consumer.operational_mw - consumer.idle_mw).max(0.0);
+        let extra_mwh = extra_mw * tick_hours;
+        if extra_mwh <= budget_mwh && cpu_load_flops + consumer.cpu_flops <= cpu_capacity_flops {
+            budget_mwh -= extra_mwh;
+            draw_mw += extra_mw;
+            cpu_load_flops += consumer.cpu_flops;
+        } else {
+            idled_module_ids.push(consumer.module_id);
+        }
+    }
+    idled_module_ids.sort();
+
+    PowerAllocation {
+        idled_module_ids,
+        draw_mw,
+        cpu_load_flops,
+        battery_mwh: budget_mwh.clamp(0.0, battery_capacity_mwh),
+    }
+}
+
+/// How much of its nameplate output a generator module delivers right now.
+fn generator_efficiency<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    station: &Station,
+    module: &StationModule,
+    blueprint: &StationModuleBlueprint,
+) -> Result<f32, String> {
+    match blueprint.get_specific_type() {
+        StationModuleSpecificType::SolarArray => {
+            match dsl.get_solar_array_module_by_id(module.get_id()) {
+                Ok(solar_array) => calculate_solar_array_efficiency(dsl, &solar_array),
+                Err(_) => Ok(0.0),
+            }
+        }
+        _ => calculate_station_health_modifier(dsl, station),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn consumer(module_id: u64, priority: u8, operational_mw: f32) -> PowerConsumer {
+        PowerConsumer {
+            module_id,
+            priority,
+            idle_mw: 1_000.0,
+            operational_mw,
+            cpu_flops: 100.0,
+        }
+    }
+
+    #[test]
+    fn deficits_drain_the_battery_then_idle_low_priority_modules() {
+        let consumers = [
+            consumer(1, 1, 41_000.0),
+            consumer(2, 6, 11_000.0),
+            consumer(3, 2, 21_000.0),
+        ];
+
+        // Plenty of generation: everything runs and the surplus charges.
+        let flush = allocate_power(&consumers, 100_000.0, 1_000.0, 0.0, 500.0, 1.0);
+        assert!(flush.idled_module_ids.is_empty());
+        assert_eq!(flush.draw_mw, 73_000.0);
+        assert_eq!(flush.battery_mwh, 500.0);
+
+        // A 13 000 MWh shortfall is covered by a battery holding 20 000.
+        let covered = allocate_power(&consumers, 60_000.0, 1_000.0, 20_000.0, 50_000.0, 1.0);
+        assert!(covered.idled_module_ids.is_empty());
+        assert_eq!(covered.battery_mwh, 7_000.0);
+
+        // Without it, the lowest priority module is idled first.
+        let brownout = allocate_power(&consumers, 60_000.0, 1_000.0, 0.0, 50_000.0, 1.0);
+        assert_eq!(brownout.idled_module_ids, vec![1]);
+        assert_eq!(brownout.draw_mw, 33_000.0);
+        assert_eq!(brownout.battery_mwh, 27_000.0);
+
+        // CPU is a hard cap regardless of power.
+        let starved = allocate_power(&consumers, 100_000.0, 250.0, 0.0, 500.0, 1.0);
+        assert_eq!(starved.idled_module_ids, vec![1]);
+    }
+}
@@ -1,4 +1,6 @@
-use crate::*;
+use std::collections::HashMap;
+
+use crate::{tables::economy::ResourceAmount, *};
 use spacetimedb::{log::info, *};
 
 use super::*;
@@ -25,6 +27,7 @@ pub fn station_production_schedule_reducer(ctx: &ReducerContext, timer: StationP
 
 /// Processes production for all modules in a station.
 /// Handles resource production, manufacturing, logistics, and other station module operations.
+/// Balances the power grid first; modules it idles sit this tick out.
 pub fn process_station_production_tick<T: spacetimedsl::WriteContext>(
     dsl: &DSL<T>,
     station_id: StationId,
@@ -32,6 +35,9 @@ pub fn process_station_production_tick<T: spacetimedsl::WriteContext>(
     // Get the station
     let station = dsl.get_station_by_id(&station_id)?;
     let modules: Vec<_> = dsl.get_station_modules_by_station_id(&station_id).collect();
+    let power_grid = power::update_station_power_grid(dsl, &station, &modules)?;
+    let stock_before = station_stock(dsl, &modules);
+    let mut transfers = Vec::new();
 
     // info!(
     //     "Processing production tick for station #{}: {}",
@@ -44,7 +50,14 @@ pub fn process_station_production_tick<T: spacetimedsl::WriteContext>(
     // );
 
     // Iterate through each station's modules
-    for module in modules {
+    for module in &modules {
+        if power_grid
+            .get_idled_module_ids()
+            .contains(&module.get_id().value())
+        {
+            continue;
+        }
+
         let wrapped_blueprint = dsl.get_station_module_blueprint_by_id(&module.get_blueprint());
         if wrapped_blueprint.is_err() {
             info!(
@@ -64,25 +77,26 @@ pub fn process_station_production_tick<T: spacetimedsl::WriteContext>(
 
         let result = match blueprint.get_category() {
             StationModuleCategory::LogisticsAndStorage => {
-                update_logistics_and_storage(&dsl, &station, &module, &blueprint)
+                update_logistics_and_storage(&dsl, &station, module, &blueprint)
+                    .map(|moved| transfers.extend(moved))
             }
             StationModuleCategory::ResourceProductionAndRefining => {
-                update_resource_production_and_refining(&dsl, &station, &module, &blueprint)
+                update_resource_production_and_refining(&dsl, &station, module, &blueprint)
             }
             StationModuleCategory::ManufacturingAndAssembly => {
-                update_manufacturing_and_assembly(&dsl, &station, &module, &blueprint)
+                update_manufacturing_and_assembly(&dsl, &station, module, &blueprint)
             }
             StationModuleCategory::ResearchAndDevelopment => {
-                update_research_and_development(&dsl, &station, &module, &blueprint)
+                update_research_and_development(&dsl, &station, module, &blueprint)
             }
             StationModuleCategory::CivilianAndSupportServices => {
-                update_civilian_and_support_services(&dsl, &station, &module, &blueprint)
+                update_civilian_and_support_services(&dsl, &station, module, &blueprint)
             }
             StationModuleCategory::DiplomacyAndFaction => {
-                update_diplomacy_and_faction(&dsl, &station, &module, &blueprint)
+                update_diplomacy_and_faction(&dsl, &station, module, &blueprint)
             }
             StationModuleCategory::DefenseAndMilitary => {
-                update_defense_and_military(&dsl, &station, &module, &blueprint)
+                update_defense_and_military(&dsl, &station, module, &blueprint)
             }
         };
 
@@ -91,6 +105,15 @@ pub fn process_station_production_tick<T: spacetimedsl::WriteContext>(
         }
     }
 
+    write_production_report(
+        dsl,
+        &station,
+        &modules,
+        &power_grid,
+        stock_before,
+        transfers,
+    )?;
+
     info!(
         "Completed production tick for station #{}: {} (Sector ID#:{})",
         &station_id,
@@ -99,3 +122,131 @@ pub fn process_station_production_tick<T: spacetimedsl::WriteContext>(
     );
     Ok(())
 }
+
+/// Every module's stock at `modules`, summed per (module id, item id).
+fn station_stock<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    modules: &[StationModule],
+) -> HashMap<(u64, u32), i64> {
+    let mut stock = HashMap::new();
+    for module in modules {
+        for slot in dsl.get_station_module_inventory_items_by_module_id(module.get_id()) {
+            *stock
+                .entry((module.get_id().value(), slot.resource_item_id))
+                .or_insert(0) += *slot.get_quantity() as i64;
+        }
+    }
+    stock
+}
+
+/// Rewrites the station's `StationProductionReport`. What each producing
+/// module made or used is its change in stock, less what logistics moved.
+fn write_production_report<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    station: &Station,
+    modules: &[StationModule],
+    power_grid: &StationPowerGrid,
+    stock_before: HashMap<(u64, u32), i64>,
+    transfers: Vec<LogisticsTransfer>,
+) -> Result<(), String> {
+    let mut net = station_stock(dsl, modules);
+    for (key, before) in stock_before {
+        *net.entry(key).or_insert(0) -= before;
+    }
+    for transfer in &transfers {
+        *net.entry((transfer.from_module_id, transfer.item_id))
+            .or_insert(0) += transfer.quantity as i64;
+        *net.entry((transfer.to_module_id, transfer.item_id))
+            .or_insert(0) -= transfer.quantity as i64;
+    }
+
+    let mut throughput = Vec::new();
+    for module in modules {
+        let module_id = module.get_id().value();
+        let idled = power_grid.get_idled_module_ids().contains(&module_id);
+        let flows = logistics::module_flows(dsl, &module.get_id());
+        if flows.is_none() && !idled {
+            continue;
+        }
+
+        let mut produced = Vec::new();
+        let mut consumed = Vec::new();
+        let mut changes: Vec<(u32, i64)> = net
+            .iter()
+            .filter(|((id, _), delta)| *id == module_id && **delta != 0)
+            .map(|((_, item_id), delta)| (*item_id, *delta))
+            .collect();
+        changes.sort();
+        for (item_id, delta) in changes {
+            if delta > 0 {
+                produced.push(ResourceAmount::new(item_id, delta as u32));
+            } else {
+                consumed.push(ResourceAmount::new(item_id, delta.unsigned_abs() as u32));
+            }
+        }
+
+        throughput.push(ModuleThroughput {
+            module_id,
+            produced,
+            consumed,
+            bottleneck: module_bottleneck(dsl, module, flows.as_ref(), idled),
+        });
+    }
+
+    match dsl.get_station_production_report_by_id(station.get_id()) {
+        Ok(mut report) => {
+            report.set_modules(throughput);
+            report.set_transfers(transfers);
+            dsl.update_station_production_report_by_id(report)?;
+        }
+        Err(_) => {
+            dsl.create_station_production_report(CreateStationProductionReport {
+                id: station.get_id(),
+                modules: throughput,
+                transfers,
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// What's holding `module` back, judged from its slots after the tick.
+fn module_bottleneck<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    module: &StationModule,
+    flows: Option<&logistics::ModuleFlows>,
+    idled: bool,
+) -> Option<ProductionBottleneck> {
+    if idled {
+        return Some(ProductionBottleneck::Unpowered);
+    }
+    let flows = flows?;
+    if !flows.has_recipe {
+        return Some(ProductionBottleneck::NoRecipe);
+    }
+
+    let slots: Vec<StationModuleInventoryItem> = dsl
+        .get_station_module_inventory_items_by_module_id(module.get_id())
+        .collect();
+    let stock = |item_id: u32| {
+        slots
+            .iter()
+            .filter(|slot| slot.resource_item_id == item_id)
+            .map(|slot| *slot.get_quantity())
+            .sum::<u32>()
+    };
+    if flows
+        .inputs
+        .iter()
+        .any(|input| stock(input.resource_item_id) < input.quantity)
+    {
+        Some(ProductionBottleneck::StarvedOfInputs)
+    } else if slots.iter().any(|slot| {
+        flows.outputs.contains(&slot.resource_item_id)
+            && slot.get_quantity() >= slot.get_max_quantity()
+    }) {
+        Some(ProductionBottleneck::OutputFull)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,112 @@
+//! # Shipyards
+//!
+//! A station with an operational `Wharf` or `ShipyardFabrication` module sells
+//! new hulls. The buyer must have a Docked Ship at that station; the new ship
+//! is created docked right beside it, so buying never adds a second Piloted
+//! Ship (ADR-0002). The player switches hulls by docking one and undocking
+//! the other.
+
+use log::info;
+use spacetimedb::ReducerContext;
+use spacetimedsl::*;
+
+use crate::{
+    logic::ships::creation::create_ship_docked_at_station,
+    tables::{messages::*, players::*, ships::*, stations::*},
+    utility::{is_server_or_ship_owner, ShipAccess},
+};
+
+///////////////////////////////////////////////////////////
+// Reducers
+///////////////////////////////////////////////////////////
+
+/// Buys a new ship of `ship_type_id` from a shipyard module, paid for by the
+/// owner of the Docked Ship `ship_id`. The new ship joins the owner's fleet,
+/// docked at the same station.
+#[spacetimedb::reducer]
+pub fn buy_ship_from_station_module(
+    ctx: &ReducerContext,
+    station_module_id: StationModuleId,
+    ship_id: ShipId,
+    ship_type_id: ShipTypeDefinitionId,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    let station_module = dsl.get_station_module_by_id(&station_module_id)?;
+    let ship = is_server_or_ship_owner(
+        &dsl,
+        &ship_id,
+        ShipAccess::DockedAt(station_module.get_station_id()),
+    )?;
+    let player_id = ship.get_player_id();
+    let blueprint = dsl.get_station_module_blueprint_by_id(&station_module.get_blueprint())?;
+    let ship_type = dsl.get_ship_type_definition_by_id(&ship_type_id)?;
+    let mut player = dsl.get_player_by_id(&player_id)?;
+
+    let problem = if !is_shipyard(&blueprint.specific_type) || !*station_module.get_is_operational()
+    {
+        Some(format!("{} doesn't sell ships.", blueprint.get_name()))
+    } else if ship_type.price.is_none() {
+        Some(format!("The {} isn't for sale.", ship_type.get_name()))
+    } else if ship_type.price.unwrap_or(0) > player.credits {
+        Some(format!(
+            "Cannot buy a {}: Not enough credits. You have {}c but it costs {}c.",
+            ship_type.get_name(),
+            player.credits,
+            ship_type.price.unwrap_or(0)
+        ))
+    } else {
+        None
+    };
+    if let Some(msg) = problem {
+        let _ = send_direct_server_warning(&dsl, &player_id, msg.clone());
+        return Err(msg);
+    }
+
+    let price = ship_type.price.unwrap_or(0);
+    let station = dsl.get_station_by_id(&station_module.get_station_id())?;
+    player.credits -= price;
+    let faction_id = player.faction_id.clone();
+    dsl.update_player_by_id(player)?;
+
+    let (new_ship, _) = create_ship_docked_at_station(
+        &dsl,
+        ship_type.clone(),
+        &player_id,
+        &faction_id,
+        station.clone(),
+    )?;
+
+    info!(
+        "buy_ship_from_station_module: player={} station={} ship_type={} new_ship={} price={}",
+        player_id.value(),
+        station.get_id().value(),
+        ship_type_id.value(),
+        new_ship.get_id().value(),
+        price
+    );
+    send_direct_server_info(
+        &dsl,
+        &player_id,
+        format!(
+            "Bought a {} for {}c. It is docked at {} as ship #{}.",
+            ship_type.get_name(),
+            price,
+            station.get_name(),
+            new_ship.get_id().value()
+        ),
+    )?;
+
+    Ok(())
+}
+
+///////////////////////////////////////////////////////////
+// Utilities
+///////////////////////////////////////////////////////////
+
+/// Whether a module of this type sells ships.
+pub fn is_shipyard(specific_type: &StationModuleSpecificType) -> bool {
+    matches!(
+        specific_type,
+        StationModuleSpecificType::Wharf | StationModuleSpecificType::ShipyardFabrication
+    )
+}
@@ -10,13 +10,29 @@
 //!
 //! These are the only sanctioned writers of `Ship.movement` /
 //! `CargoCrate.movement`. The Phase 6 audit confirms no caller bypasses them.
+//!
+//! Collision resolution also lives here: while any Piloted Ship in a sector
+//! is moving, a 5 Hz sweep for that sector checks each moving ship's
+//! dead-reckoned path against the asteroids, stations and other ships there
+//! and writes a stopped/deflected snapshot on contact. Writing a moving
+//! snapshot starts the sector's sweep; the sweep removes itself once every
+//! ship there has come to rest.
+
+use std::time::Duration;
 
-use solarance_shared::{predict_movement, MovementState, Vec2};
+use log::info;
+use solarance_shared::{
+    first_impact, predict_movement, resolve_impact, Collider, MovementState, Vec2,
+};
+use spacetimedb::ReducerContext;
 use spacetimedsl::*;
 
-use crate::tables::{
-    asteroids::*, global_config::*, items::*, jumpgates::*, sectors::*, ships::*, stations::*,
-    stellarobjects::*,
+use crate::{
+    tables::{
+        asteroids::*, global_config::*, items::*, jumpgates::*, sectors::*, ships::*, stations::*,
+        stellarobjects::*,
+    },
+    utility::try_server_only,
 };
 
 // ── Ships ───────────────────────────────────────────────────────────────────
@@ -84,8 +100,14 @@ where
     next.max_speed = *ship_type.get_base_speed();
     next.max_turn_rate = *ship_type.get_base_max_turn_rate();
 
+    let in_sector = *ship.get_location() == ShipLocation::Sector;
+    let sector_id = ship.get_sector_id();
     ship.movement = next;
     dsl.update_ship_by_id(ship)?;
+
+    if in_sector && is_moving(&next, now) {
+        ensure_sector_collision_timer(dsl, &sector_id)?;
+    }
     Ok(next)
 }
 
@@ -285,3 +307,193 @@ pub fn get_sobj_pose<T: spacetimedsl::WriteContext>(
         }
     }
 }
+
+// ── Collisions ──────────────────────────────────────────────────────────────
+
+/// How often a sector's collision sweep runs. Matches the "Interactions" 5
+/// fps tier.
+const COLLISION_TICK_MS: u64 = 200;
+
+/// A sector's collision sweep. Exists only while a Piloted Ship there is
+/// moving — see `ensure_sector_collision_timer`.
+#[dsl(plural_name = sector_collision_timers, method(update = false))]
+#[spacetimedb::table(accessor = sector_collision_timer, scheduled(sector_collision_timer_reducer))]
+pub struct SectorCollisionTimer {
+    #[primary_key]
+    #[auto_inc]
+    #[create_wrapper]
+    id: u64,
+    scheduled_at: spacetimedb::ScheduleAt,
+
+    #[unique]
+    #[use_wrapper(SectorId)]
+    #[foreign_key(path = crate::tables::sectors, table = sector, column = id, on_delete = Delete)]
+    /// FK to Sector
+    sector_id: u64,
+}
+
+/// Starts the collision sweep for `sector_id` unless it is already running.
+pub fn ensure_sector_collision_timer<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    sector_id: &SectorId,
+) -> Result<(), String> {
+    if dsl
+        .get_sector_collision_timer_by_sector_id(sector_id)
+        .is_ok()
+    {
+        return Ok(());
+    }
+    dsl.create_sector_collision_timer(CreateSectorCollisionTimer {
+        scheduled_at: spacetimedb::ScheduleAt::Interval(
+            Duration::from_millis(COLLISION_TICK_MS).into(),
+        ),
+        sector_id: sector_id.clone(),
+    })?;
+    Ok(())
+}
+
+/// Whether a ship with snapshot `movement` is still moving at `now`: it has
+/// speed left, or is accelerating forward.
+fn is_moving(movement: &MovementState, now: i64) -> bool {
+    let (_, _, velocity, _) = predict_movement(movement, now);
+    velocity > 0.0 || movement.acceleration > 0.0
+}
+
+/// Scheduled reducer that sweeps every moving Piloted Ship in one sector over
+/// the last tick and resolves the first contact it finds. Once no ship there
+/// is moving, the sweep deletes its own timer.
+#[spacetimedb::reducer]
+pub fn sector_collision_timer_reducer(
+    ctx: &ReducerContext,
+    timer: SectorCollisionTimer,
+) -> Result<(), String> {
+    let dsl = dsl(ctx);
+    try_server_only(&dsl)?;
+
+    let sector_id = timer.get_sector_id();
+    let now = ctx.timestamp.to_micros_since_unix_epoch();
+    let moving: Vec<Ship> = dsl
+        .get_ships_by_sector_id(&sector_id)
+        .filter(|ship| *ship.get_location() == ShipLocation::Sector)
+        .filter(|ship| is_moving(&ship.movement, now))
+        .collect();
+    if moving.is_empty() {
+        dsl.delete_sector_collision_timer_by_id(&timer)?;
+        return Ok(());
+    }
+
+    let obstacles = collect_sector_obstacles(&dsl, &sector_id)?;
+    for ship in moving {
+        resolve_ship_collisions(&dsl, &ship, &obstacles)?;
+    }
+
+    Ok(())
+}
+
+/// Gathers every solid object in a sector as a `Collider`. Asteroids and
+/// stations are circles; ships are oriented boxes at their predicted pose and
+/// carry their id so a ship never collides with itself. Jumpgates and cargo
+/// crates are left out — ships must be able to fly into both.
+pub fn collect_sector_obstacles<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    sector_id: &SectorId,
+) -> Result<Vec<(Collider, Option<ShipId>)>, String> {
+    let mut obstacles = Vec::new();
+
+    for asteroid in dsl.get_asteroids_by_current_sector_id(sector_id) {
+        obstacles.push((
+            Collider::Circle {
+                center: *asteroid.get_position(),
+                radius: *asteroid.get_size_radius(),
+            },
+            None,
+        ));
+    }
+
+    for station in dsl.get_stations_by_sector_id(sector_id) {
+        obstacles.push((
+            Collider::Circle {
+                center: *station.get_position(),
+                radius: station.get_size().collision_radius(),
+            },
+            None,
+        ));
+    }
+
+    for ship in dsl.get_ships_by_sector_id(sector_id) {
+        if *ship.get_location() != ShipLocation::Sector {
+            continue;
+        }
+        let ship_type = dsl.get_ship_type_definition_by_id(ship.get_shiptype_id())?;
+        let snapshot = get_ship_movement_snapshot(dsl, &ship.get_id())?;
+        obstacles.push((
+            Collider::OrientedBox {
+                corners: ship_type.get_world_corners_at_position(&snapshot.pos, snapshot.rotation),
+            },
+            Some(ship.get_id()),
+        ));
+    }
+
+    Ok(obstacles)
+}
+
+/// Sweeps `ship` over the last collision tick (or since its snapshot, if that
+/// is more recent) and, on contact, writes the stopped/deflected snapshot
+/// from `resolve_impact`. Only the last tick is swept so an obstacle that
+/// appears in a ship's old path never yanks the ship backwards in time.
+///
+/// Returns whether a collision was resolved.
+pub fn resolve_ship_collisions<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    ship: &Ship,
+    obstacles: &[(Collider, Option<ShipId>)],
+) -> Result<bool, String> {
+    let ship_type = dsl.get_ship_type_definition_by_id(ship.get_shiptype_id())?;
+    let now = dsl.ctx().timestamp()?.to_micros_since_unix_epoch();
+    let window_start = ship
+        .movement
+        .last_update_time
+        .max(now - (COLLISION_TICK_MS as i64) * 1_000);
+    if window_start >= now {
+        return Ok(false);
+    }
+
+    let (pos, rotation, velocity, angular_velocity) =
+        predict_movement(&ship.movement, window_start);
+    let start = MovementState {
+        pos,
+        rotation,
+        velocity,
+        angular_velocity,
+        last_update_time: window_start,
+        ..ship.movement
+    };
+
+    let colliders: Vec<Collider> = obstacles
+        .iter()
+        .filter(|(_, owner)| owner.as_ref() != Some(&ship.get_id()))
+        .map(|(collider, _)| *collider)
+        .collect();
+    let horizon = (now - window_start) as f32 / 1_000_000.0;
+
+    let Some(impact) = first_impact(&start, ship_type.collision_radius(), &colliders, horizon)
+    else {
+        return Ok(false);
+    };
+
+    let resolved = resolve_impact(&start, &impact, now);
+    write_ship_movement_snapshot(dsl, &ship.get_id(), |state| {
+        *state = resolved;
+    })?;
+
+    info!(
+        "Ship #{} collided at ({:.0}, {:.0}); speed {:.0} -> {:.0}",
+        ship.get_id().value(),
+        impact.pos.x,
+        impact.pos.y,
+        impact.velocity,
+        resolved.velocity
+    );
+
+    Ok(true)
+}
@@ -0,0 +1,148 @@
+//! Admin roles and the admin audit log.
+//!
+//! `AdminRole` grants an identity a set of privileged reducers; see
+//! `AdminRoleKind::grants` for who may do what. Every privileged call that
+//! goes through `utility::require_admin_permission` leaves an
+//! `AdminAuditLog` row behind.
+//!
+//! ## View legality
+//! `AdminAuditLog` is private. Staff read it through `my_admin_audit_log`,
+//! which filters on indexed columns only: `caller` for a staff member's own
+//! rows, and a constant `galaxy_id` (always `0`) for Owners and Admins, who
+//! see everything — same trick as `GalaxyChannelMessage`.
+
+use spacetimedb::{table, view, Identity, SpacetimeType, Timestamp, ViewContext};
+use spacetimedsl::*;
+
+////////////////////////////////////////////////////////////////////////////////
+// Enums
+////////////////////////////////////////////////////////////////////////////////
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub enum AdminRoleKind {
+    /// Everything, including granting and revoking roles.
+    Owner,
+    /// Everything except role management.
+    Admin,
+    /// Player-facing communication and moderation: Direct Server Messages,
+    /// sanctions and the chat filter.
+    Moderator,
+    /// Galaxy layout, construction sites and content definitions.
+    Designer,
+}
+
+/// What a privileged reducer needs. Each admin reducer asks for exactly one.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AdminPermission {
+    SendServerMessages,
+    ModeratePlayers,
+    EditGalaxy,
+    EditDefinitions,
+    SpawnItems,
+    ManageRoles,
+}
+
+impl AdminRoleKind {
+    pub fn grants(&self, permission: &AdminPermission) -> bool {
+        match self {
+            AdminRoleKind::Owner => true,
+            AdminRoleKind::Admin => *permission != AdminPermission::ManageRoles,
+            AdminRoleKind::Moderator => matches!(
+                permission,
+                AdminPermission::SendServerMessages | AdminPermission::ModeratePlayers
+            ),
+            AdminRoleKind::Designer => matches!(
+                permission,
+                AdminPermission::EditGalaxy | AdminPermission::EditDefinitions
+            ),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tables
+////////////////////////////////////////////////////////////////////////////////
+
+/// One role per identity. Public, so the admin client can list the staff.
+#[dsl(plural_name = admin_roles, method(update = true))]
+#[table(accessor = admin_role, public)]
+pub struct AdminRole {
+    #[primary_key]
+    #[create_wrapper]
+    id: Identity,
+
+    pub role: AdminRoleKind,
+    pub granted_by: Identity,
+
+    created_at: Timestamp,
+    modified_at: Timestamp,
+}
+
+/// One row per privileged call that was let through. Calls by the module
+/// itself are logged with `role: None`.
+#[dsl(plural_name = admin_audit_logs, method(update = false))]
+#[table(accessor = admin_audit_log)]
+pub struct AdminAuditLog {
+    #[primary_key]
+    #[auto_inc]
+    #[create_wrapper]
+    id: u64,
+
+    #[index(btree)]
+    galaxy_id: u32,
+
+    #[index(btree)]
+    caller: Identity,
+    role: Option<AdminRoleKind>,
+
+    reducer: String,
+    details: String,
+
+    created_at: Timestamp,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Views
+////////////////////////////////////////////////////////////////////////////////
+
+/// Owners and Admins see the whole audit log; other staff see their own
+/// calls; everyone else sees nothing.
+#[view(accessor = my_admin_audit_log, public)]
+pub fn my_admin_audit_log(ctx: &ViewContext) -> Vec<AdminAuditLog> {
+    let dsl = spacetimedsl::read_only_dsl(ctx);
+    let Ok(role) = dsl.get_admin_role_by_id(AdminRoleId::new(ctx.sender())) else {
+        return Vec::new();
+    };
+    match role.role {
+        AdminRoleKind::Owner | AdminRoleKind::Admin => {
+            ctx.db.admin_audit_log().galaxy_id().filter(&0u32).collect()
+        }
+        _ => ctx
+            .db
+            .admin_audit_log()
+            .caller()
+            .filter(&ctx.sender())
+            .collect(),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Utility
+////////////////////////////////////////////////////////////////////////////////
+
+pub fn write_admin_audit_log<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    caller: Identity,
+    role: Option<AdminRoleKind>,
+    reducer: &str,
+    details: String,
+) -> Result<(), String> {
+    dsl.create_admin_audit_log(CreateAdminAuditLog {
+        galaxy_id: 0,
+        caller,
+        role,
+        reducer: reducer.to_string(),
+        details,
+    })?;
+    Ok(())
+}
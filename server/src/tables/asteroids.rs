@@ -1,8 +1,9 @@
 use log::info;
 use solarance_shared::Vec2;
-use spacetimedb::table;
+use spacetimedb::{table, Timestamp};
 use spacetimedsl::*;
 
+use crate::logic::sectors::asteroid_fields::asteroid_radius;
 use crate::logic::stellarobjects::stellar_object_creation::create_sobj;
 use crate::tables::{items::ItemDefinitionId, sectors::SectorId, stellarobjects::*};
 
@@ -21,7 +22,9 @@ pub struct Asteroid {
     /// FK to Sector.id // Because asteroid_sector.id exists, this can't be named sector_id.
     current_sector_id: u64,
 
-    size_radius: f32, // For collision
+    /// For collision and rendering. Follows `current_resources`, so a mined
+    /// asteroid shrinks; see `asteroid_fields::asteroid_radius`.
+    pub size_radius: f32,
 
     #[use_wrapper(crate::tables::items::ItemDefinitionId)]
     #[index(btree)]
@@ -39,6 +42,26 @@ pub struct Asteroid {
     pub position: Vec2,
 }
 
+/// An exhausted asteroid waiting to be replaced. `asteroid_sector_upkeep`
+/// spawns a new asteroid once `respawn_at` has passed, if the field is still
+/// short of its target population.
+#[dsl(plural_name = asteroid_respawns, method(update = false))]
+#[table(accessor = asteroid_respawn)]
+pub struct AsteroidRespawn {
+    #[primary_key]
+    #[auto_inc]
+    #[create_wrapper]
+    id: u64,
+
+    #[index(btree)]
+    #[use_wrapper(crate::tables::sectors::SectorId)]
+    #[foreign_key(path = crate::tables::sectors, table = sector, column = id, on_delete = Delete)]
+    /// FK to Sector
+    sector_id: u64,
+
+    respawn_at: Timestamp,
+}
+
 /////////////////////////////////////////
 /// Utility
 
@@ -62,7 +85,7 @@ pub fn create_asteroid<T: spacetimedsl::WriteContext>(
     match dsl.create_asteroid(CreateAsteroid {
         id: sobj.unwrap().get_id(),
         current_sector_id: sector.clone(),
-        size_radius: 16.0,
+        size_radius: asteroid_radius(resource_amount),
         resource_item_id: item.clone(),
         current_resources: resource_amount,
         initial_resources: resource_amount,
@@ -10,7 +10,7 @@ pub enum VisualEffectType {
     Explosion,
 }
 
-#[derive(SpacetimeType, Debug, Clone, PartialEq, Eq)]
+#[derive(SpacetimeType, Debug, Clone, PartialEq, Eq, serde::Deserialize)]
 pub enum WeaponType {
     /// Most autocannons/blasters
     Hitscan,
@@ -20,7 +20,7 @@ pub enum WeaponType {
     AreaOfEffect,
 }
 
-#[derive(SpacetimeType, Debug, Clone, PartialEq, Eq)]
+#[derive(SpacetimeType, Debug, Clone, PartialEq, Eq, serde::Deserialize)]
 pub enum MissileType {
     /// Set angle
     Dumbfire,
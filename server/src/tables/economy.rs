@@ -1,6 +1,6 @@
 use spacetimedb::SpacetimeType;
 
-#[derive(SpacetimeType, Debug, Clone)]
+#[derive(SpacetimeType, Debug, Clone, serde::Deserialize)]
 pub struct ResourceAmount {
     //#[use_wrapper(crate::tables::items::ItemDefinitionId)]
     /// FK to ItemDefinition
@@ -7,7 +7,7 @@ use crate::tables::{
     ships::*,
 };
 
-#[derive(SpacetimeType, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(SpacetimeType, Clone, Debug, PartialEq, Eq, Hash, serde::Deserialize)]
 pub enum ResourceCategory {
     RawOre,
     RefinedIngot,
@@ -34,7 +34,7 @@ pub enum OreType {
     Carbon,
 }
 
-#[derive(SpacetimeType, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(SpacetimeType, Clone, Debug, PartialEq, Eq, Hash, serde::Deserialize)]
 pub enum ShipModuleType {
     Engine,
     ShieldGenerator,
@@ -54,14 +54,14 @@ pub enum ShipModuleType {
 }
 
 // Enum for different categories of items
-#[derive(SpacetimeType, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(SpacetimeType, Clone, Debug, PartialEq, Eq, Hash, serde::Deserialize)]
 pub enum ItemCategory {
     ShipModule(ShipModuleType),
     Resource(ResourceCategory),
 }
 
 /// Enum for different effects for items/modules
-#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+#[derive(SpacetimeType, Clone, Debug, PartialEq, serde::Deserialize)]
 pub enum ItemMetadata {
     // Weapon Module Types
     /// This item is a type of weapon
@@ -93,10 +93,17 @@ pub enum ItemMetadata {
 
     /// A flat boost to the ship's shield output
     ShieldBoost(f32),
+    /// Added to the ship's shield regeneration, per second
+    ShieldRegenBoost(f32),
+    /// Added to the ship's energy regeneration, per second
+    EnergyRegenBoost(f32),
     /// Adds additional cargo capacity
     CargoCapacityBoost(u16),
     /// From 0.001 to 10.0
     MiningSpeedMultiplier(f32),
+    /// How hard a raw ore is to mine; mining lasers cut `1 / hardness` as much
+    /// of it per tick. 1.0 when absent.
+    Hardness(f32),
 
     /// How much energy this item consumes per second or usage.
     EnergyConsumption(f32),
@@ -128,6 +135,8 @@ pub struct ItemDefinition {
     #[referenced_by(path = crate::tables::stations, table = station_module_inventory_item)]
     #[referenced_by(path = crate::tables::stations, table = construction_requirement)]
     #[referenced_by(path = crate::tables::stations, table = construction_contribution_log)]
+    #[referenced_by(path = crate::tables::stations, table = station_hangar_item)]
+    #[referenced_by(path = crate::logic::stations::module_types::trading_port, table = market_order)]
     #[referenced_by(path = crate::tables::items, table = cargo_crate)]
     id: u32,
 
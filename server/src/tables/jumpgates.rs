@@ -1,7 +1,7 @@
 use spacetimedb::table;
 use spacetimedsl::*;
 
-use solarance_shared::Vec2;
+use solarance_shared::{GateEdge, Vec2};
 
 #[dsl(plural_name = jump_gates, method(update = true))]
 #[table(accessor = jump_gate, public)]
@@ -40,6 +40,21 @@ pub struct JumpGate {
                          //pub required_item_id: Option<u32>, // Optional: item needed to use the gate (e.g., jump drive, key)
 }
 
+impl JumpGate {
+    /// This gate as an edge of the jumpgate graph `solarance_shared::plan_route`
+    /// searches.
+    pub fn as_gate_edge(&self) -> GateEdge {
+        GateEdge {
+            gate_id: self.get_id().value(),
+            from_sector: self.get_current_sector_id().value(),
+            to_sector: self.get_target_sector_id().value(),
+            position: self.position,
+            arrival_pos: self.target_gate_arrival_pos,
+            is_active: self.is_active,
+        }
+    }
+}
+
 //////////////////////////////////////////////////////////////
 // Init
 //////////////////////////////////////////////////////////////
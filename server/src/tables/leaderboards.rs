@@ -0,0 +1,267 @@
+//! Contribution leaderboards.
+//!
+//! - `ContributionTally` — one row per (board, scope, player): how much that
+//!   player has contributed to construction, in units and in credits
+//!   (`quantity × ItemDefinition.base_value`). Kept up to date by
+//!   `contribute_to_station`; rebuildable from `ConstructionContributionLog`,
+//!   which stays the source of truth. Private.
+//!
+//! The leaderboard views rank tallies by credit value, so a unit of a
+//! valuable component counts for more than a unit of ore:
+//! - `construction_site_leaderboards` — every site, top contributors each.
+//! - `faction_contribution_leaderboards` — every faction, ranked by what was
+//!   contributed to the stations that faction owns.
+//! - `weekly_contribution_leaderboard` — this week (Monday 00:00 UTC on).
+//! - `all_time_contribution_leaderboard`
+//! - `my_contribution_history` — the caller's own contribution log.
+//!
+//! ## View legality
+//! Every board view filters `ContributionTally` on its indexed `board`
+//! column; the history view filters the log on `player_id`. Views can't see
+//! the clock, so the weekly board shows the newest week it holds and
+//! `contribution_tally_sweeper` deletes older weeks every hour.
+
+use spacetimedb::{table, view, Identity, SpacetimeType, Timestamp, ViewContext};
+use spacetimedsl::*;
+
+use crate::tables::{
+    factions::{FactionId, GetFactionRowOptionById},
+    items::{GetItemDefinitionRowOptionById, ItemDefinitionId},
+    players::{GetPlayerRowOptionById, PlayerId},
+    stations::{GetConstructionContributionLogRowsByPlayerId, GetStationRowOptionById, StationId},
+};
+
+/// How many contributors each leaderboard shows.
+pub const LEADERBOARD_SIZE: usize = 10;
+
+////////////////////////////////////////////////////////////////////////////////
+// Enums
+////////////////////////////////////////////////////////////////////////////////
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContributionBoard {
+    /// `scope_id` is the construction site's `StationId`.
+    Station,
+    /// `scope_id` is the `FactionId` that owns the station contributed to.
+    Faction,
+    /// `scope_id` is the week number (see `logic::stations::leaderboards::week_of`).
+    Weekly,
+    /// `scope_id` is always `0`.
+    AllTime,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// View rows
+////////////////////////////////////////////////////////////////////////////////
+
+/// One ranked line of a leaderboard.
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct ContributionLeaderboardEntry {
+    pub board: ContributionBoard,
+    pub scope_id: u64,
+    /// Station or faction name; empty for the weekly and all-time boards.
+    pub scope_name: String,
+    /// 1-based.
+    pub rank: u32,
+    pub player_id: Identity,
+    pub username: String,
+    pub quantity: u64,
+    pub value: u64,
+}
+
+/// One line of `my_contribution_history`, names resolved.
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct ContributionHistoryEntry {
+    pub log_id: u64,
+    pub station_id: u64,
+    pub station_name: String,
+    pub item_name: String,
+    pub quantity: u32,
+    pub value: u64,
+    pub contributed_at: Timestamp,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tables
+////////////////////////////////////////////////////////////////////////////////
+
+#[dsl(plural_name = contribution_tallies, method(update = true))]
+#[table(accessor = contribution_tally)]
+pub struct ContributionTally {
+    #[primary_key]
+    #[auto_inc]
+    #[create_wrapper]
+    id: u64,
+
+    #[index(btree)]
+    board: ContributionBoard,
+    scope_id: u64,
+
+    #[index(btree)]
+    #[use_wrapper(crate::tables::players::PlayerId)]
+    #[foreign_key(path = crate::tables::players, table = player, column = id, on_delete = Delete)]
+    player_id: Identity,
+
+    pub quantity: u64,
+    /// `quantity × base_value`, summed per contribution.
+    pub value: u64,
+
+    created_at: Timestamp,
+    modified_at: Timestamp,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Views
+////////////////////////////////////////////////////////////////////////////////
+
+#[view(accessor = construction_site_leaderboards, public)]
+pub fn construction_site_leaderboards(ctx: &ViewContext) -> Vec<ContributionLeaderboardEntry> {
+    let dsl = spacetimedsl::read_only_dsl(ctx);
+    let tallies: Vec<ContributionTally> = dsl
+        .get_contribution_tallies_by_board(&ContributionBoard::Station)
+        .collect();
+    rank_tallies(&tallies, LEADERBOARD_SIZE)
+        .into_iter()
+        .map(|(rank, tally)| {
+            let scope_name = dsl
+                .get_station_by_id(StationId::new(tally.scope_id))
+                .map(|s| s.get_name().clone())
+                .unwrap_or_default();
+            leaderboard_entry(&dsl, rank, tally, scope_name)
+        })
+        .collect()
+}
+
+#[view(accessor = faction_contribution_leaderboards, public)]
+pub fn faction_contribution_leaderboards(ctx: &ViewContext) -> Vec<ContributionLeaderboardEntry> {
+    let dsl = spacetimedsl::read_only_dsl(ctx);
+    let tallies: Vec<ContributionTally> = dsl
+        .get_contribution_tallies_by_board(&ContributionBoard::Faction)
+        .collect();
+    rank_tallies(&tallies, LEADERBOARD_SIZE)
+        .into_iter()
+        .map(|(rank, tally)| {
+            let scope_name = dsl
+                .get_faction_by_id(FactionId::new(tally.scope_id as u32))
+                .map(|f| f.get_name().clone())
+                .unwrap_or_default();
+            leaderboard_entry(&dsl, rank, tally, scope_name)
+        })
+        .collect()
+}
+
+#[view(accessor = weekly_contribution_leaderboard, public)]
+pub fn weekly_contribution_leaderboard(ctx: &ViewContext) -> Vec<ContributionLeaderboardEntry> {
+    let dsl = spacetimedsl::read_only_dsl(ctx);
+    let tallies: Vec<ContributionTally> = dsl
+        .get_contribution_tallies_by_board(&ContributionBoard::Weekly)
+        .collect();
+    // Between a week rolling over and the next sweep, last week's rows are
+    // still here; only show the newest week.
+    let Some(this_week) = tallies.iter().map(|t| t.scope_id).max() else {
+        return Vec::new();
+    };
+    let this_week: Vec<ContributionTally> = tallies
+        .into_iter()
+        .filter(|t| t.scope_id == this_week)
+        .collect();
+    rank_tallies(&this_week, LEADERBOARD_SIZE)
+        .into_iter()
+        .map(|(rank, tally)| leaderboard_entry(&dsl, rank, tally, String::new()))
+        .collect()
+}
+
+#[view(accessor = all_time_contribution_leaderboard, public)]
+pub fn all_time_contribution_leaderboard(ctx: &ViewContext) -> Vec<ContributionLeaderboardEntry> {
+    let dsl = spacetimedsl::read_only_dsl(ctx);
+    let tallies: Vec<ContributionTally> = dsl
+        .get_contribution_tallies_by_board(&ContributionBoard::AllTime)
+        .collect();
+    rank_tallies(&tallies, LEADERBOARD_SIZE)
+        .into_iter()
+        .map(|(rank, tally)| leaderboard_entry(&dsl, rank, tally, String::new()))
+        .collect()
+}
+
+/// Every contribution the caller has made, newest first.
+#[view(accessor = my_contribution_history, public)]
+pub fn my_contribution_history(ctx: &ViewContext) -> Vec<ContributionHistoryEntry> {
+    let dsl = spacetimedsl::read_only_dsl(ctx);
+    let mut history: Vec<ContributionHistoryEntry> = dsl
+        .get_construction_contribution_logs_by_player_id(PlayerId::new(ctx.sender()))
+        .map(|log| {
+            let station_name = dsl
+                .get_station_by_id(log.get_station_id())
+                .map(|s| s.get_name().clone())
+                .unwrap_or_default();
+            let (item_name, base_value) = dsl
+                .get_item_definition_by_id(ItemDefinitionId::new(log.get_item_id().value()))
+                .map(|i| (i.get_name().clone(), *i.get_base_value()))
+                .unwrap_or_default();
+            ContributionHistoryEntry {
+                log_id: log.get_id().value(),
+                station_id: log.get_station_id().value(),
+                station_name,
+                item_name,
+                quantity: *log.get_quantity(),
+                value: *log.get_quantity() as u64 * base_value as u64,
+                contributed_at: *log.get_contributed_at(),
+            }
+        })
+        .collect();
+    history.sort_by_key(|entry| std::cmp::Reverse(entry.log_id));
+    history
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Utility
+////////////////////////////////////////////////////////////////////////////////
+
+/// Groups `tallies` by scope and ranks each scope by value (then quantity),
+/// keeping the top `size`. Scopes come out in `scope_id` order.
+pub fn rank_tallies(tallies: &[ContributionTally], size: usize) -> Vec<(u32, &ContributionTally)> {
+    let mut sorted: Vec<&ContributionTally> = tallies.iter().collect();
+    sorted.sort_by(|a, b| {
+        a.scope_id
+            .cmp(&b.scope_id)
+            .then(b.value.cmp(&a.value))
+            .then(b.quantity.cmp(&a.quantity))
+    });
+
+    let mut ranked = Vec::new();
+    let mut scope = None;
+    let mut rank = 0;
+    for tally in sorted {
+        if scope != Some(tally.scope_id) {
+            scope = Some(tally.scope_id);
+            rank = 0;
+        }
+        rank += 1;
+        if rank as usize <= size {
+            ranked.push((rank, tally));
+        }
+    }
+    ranked
+}
+
+fn leaderboard_entry(
+    dsl: &ReadOnlyDSL<'_, ViewContext>,
+    rank: u32,
+    tally: &ContributionTally,
+    scope_name: String,
+) -> ContributionLeaderboardEntry {
+    let username = dsl
+        .get_player_by_id(tally.get_player_id())
+        .map(|p| p.username.clone())
+        .unwrap_or_default();
+    ContributionLeaderboardEntry {
+        board: tally.board,
+        scope_id: tally.scope_id,
+        scope_name,
+        rank,
+        player_id: tally.get_player_id().value(),
+        username,
+        quantity: tally.quantity,
+        value: tally.value,
+    }
+}
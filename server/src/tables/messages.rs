@@ -14,6 +14,20 @@
 //! ## Direct Server Messages — 1-to-1, server→player, async inbox
 //! - `DirectServerMessage`      — server is always the sender (implicit, no enum).
 //!
+//! ## Direct Messages — 1-to-1, player→player
+//! - `DirectMessage`            — the separate table CONTEXT.md reserves for
+//!   player DMs. Both ends are Players; read state is login-relative, exactly
+//!   like `DirectServerMessage`.
+//! - `PlayerChatBlock`          — per-player block/mute list. **Block** stops
+//!   the other player DMing you; **Mute** hides their messages from your views.
+//!
+//! ## Retention & history pages
+//! - `ArchivedChannelMessage`   — Galaxy/StarSystem/Sector/Faction rows older
+//!   than `CHAT_RETENTION_DAYS`, moved out of the live tables by
+//!   `channel_message_archive_timer` so the live views stay small.
+//! - `ChatHistoryMessage`       — archived rows a player asked to look back
+//!   at (`request_chat_history`), one page at a time. Cleared on disconnect.
+//!
 //! ## View legality (no `.iter()` full scans permitted)
 //! Every View filters on an *indexed* column. Star-system/sector/faction/DM
 //! pick the natural FK (`system_id`/`sector_id`/`faction_id`/`to`). Galaxy has
@@ -27,6 +41,7 @@ use spacetimedsl::*;
 // (`get_player_by_id`, `get_ships_by_player_id`, `get_sector_by_id`, …).
 use crate::tables::{
     factions::FactionId,
+    moderation::is_in_good_standing,
     players::{PlayerId, *},
     sectors::{SectorId, *},
     ships::*,
@@ -46,6 +61,26 @@ pub enum MessageSender {
     System,
 }
 
+/// How a `PlayerChatBlock` row treats the other player.
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub enum ChatBlockKind {
+    /// The blocked player can't send you Direct Messages at all.
+    Block,
+    /// The muted player can still post, but their channel messages and
+    /// Direct Messages are filtered out of your views.
+    Mute,
+}
+
+/// The player channels that are archived and can be paged back through. The
+/// Server channel is public and never archived.
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum ChatChannel {
+    Galaxy,
+    StarSystem,
+    Sector,
+    Faction,
+}
+
 /// Three-tier severity for `DirectServerMessage` only. Drives client filtering
 /// + visual treatment; intentionally short so each tier remains meaningful.
 ///
@@ -192,6 +227,111 @@ pub struct DirectServerMessage {
     created_at: Timestamp,
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// Direct Messages — 1-to-1, player→player
+////////////////////////////////////////////////////////////////////////////////
+
+/// 1-to-1 message FROM one Player TO another. Kept separate from
+/// `DirectServerMessage` (CONTEXT.md §5) so the server inbox keeps its
+/// implicit-sender shape. Same login-relative read state: unread means
+/// `created_at` is later than the recipient's `last_login`.
+#[dsl(plural_name = direct_messages, method(update = false))]
+#[table(accessor = direct_message)]
+pub struct DirectMessage {
+    #[primary_key]
+    #[auto_inc]
+    #[create_wrapper]
+    id: u64,
+
+    /// Sender Player identity. Indexed — the view pulls the caller's sent mail.
+    #[index(btree)]
+    #[use_wrapper(crate::tables::players::PlayerId)]
+    #[foreign_key(path = crate::tables::players, table = player, column = id, on_delete = Delete)]
+    from: Identity,
+
+    /// Recipient Player identity. Indexed — the view pulls the caller's inbox.
+    #[index(btree)]
+    #[use_wrapper(crate::tables::players::PlayerId)]
+    #[foreign_key(path = crate::tables::players, table = player, column = id, on_delete = Delete)]
+    to: Identity,
+
+    body: String,
+    created_at: Timestamp,
+}
+
+/// One entry on a player's block/mute list. Private — the owner sees their own
+/// list through `my_chat_blocks`; the other player is never told.
+#[dsl(plural_name = player_chat_blocks, method(update = false))]
+#[table(accessor = player_chat_block)]
+pub struct PlayerChatBlock {
+    #[primary_key]
+    #[auto_inc]
+    #[create_wrapper]
+    id: u64,
+
+    /// The player whose list this is. Indexed — views and reducers filter here.
+    #[index(btree)]
+    #[use_wrapper(crate::tables::players::PlayerId)]
+    #[foreign_key(path = crate::tables::players, table = player, column = id, on_delete = Delete)]
+    owner: Identity,
+
+    target: Identity,
+    kind: ChatBlockKind,
+    created_at: Timestamp,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Retention & history pages
+////////////////////////////////////////////////////////////////////////////////
+
+/// A channel message past retention. `scope_id` is the live table's scope key
+/// (`galaxy_id`, `system_id`, `sector_id` or `faction_id`), so
+/// `(channel, scope_id)` names the channel it was posted to. Private, no view
+/// — players read it a page at a time through `ChatHistoryMessage`.
+#[dsl(plural_name = archived_channel_messages, method(update = false))]
+#[table(accessor = archived_channel_message)]
+pub struct ArchivedChannelMessage {
+    #[primary_key]
+    #[auto_inc]
+    #[create_wrapper]
+    id: u64,
+
+    #[index(btree)]
+    scope_id: u64,
+    channel: ChatChannel,
+    /// The row's id in its live table. The paging cursor.
+    original_id: u64,
+
+    sender: MessageSender,
+    body: String,
+    /// When the message was posted; `created_at` is when it was archived.
+    sent_at: Timestamp,
+    created_at: Timestamp,
+}
+
+/// One archived message copied out for `owner` by `request_chat_history`.
+/// Private; the owner reads their pages through `my_chat_history`.
+#[dsl(plural_name = chat_history_messages, method(update = false))]
+#[table(accessor = chat_history_message)]
+pub struct ChatHistoryMessage {
+    #[primary_key]
+    #[auto_inc]
+    #[create_wrapper]
+    id: u64,
+
+    #[index(btree)]
+    owner: Identity,
+
+    channel: ChatChannel,
+    scope_id: u64,
+    original_id: u64,
+
+    sender: MessageSender,
+    body: String,
+    sent_at: Timestamp,
+    created_at: Timestamp,
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Views — every channel except Server (which is plain public) gets one.
 //
@@ -210,61 +350,90 @@ pub fn my_direct_server_messages(ctx: &ViewContext) -> Vec<DirectServerMessage>
         .collect()
 }
 
-/// Galaxy chat — gated to identities that resolve to a Player row.
-/// Non-players (not-logged-in / banned) get `vec![]`.
+/// Direct Messages the caller sent or received, minus anything from a player
+/// they've muted. Two indexed filters (`from`, `to`) rather than one scan.
+#[view(accessor = my_direct_messages, public)]
+pub fn my_direct_messages(ctx: &ViewContext) -> Vec<DirectMessage> {
+    let sender = ctx.sender();
+    let muted = muted_identities(ctx);
+    let received = ctx
+        .db
+        .direct_message()
+        .to()
+        .filter(&sender)
+        .filter(|m| !muted.contains(&m.from));
+    let sent = ctx
+        .db
+        .direct_message()
+        .from()
+        .filter(&sender)
+        .filter(|m| m.to != sender); // a note-to-self already came through `to`
+    received.chain(sent).collect()
+}
+
+/// The caller's own block/mute list.
+#[view(accessor = my_chat_blocks, public)]
+pub fn my_chat_blocks(ctx: &ViewContext) -> Vec<PlayerChatBlock> {
+    let sender = ctx.sender();
+    ctx.db
+        .player_chat_block()
+        .owner()
+        .filter(&sender)
+        .collect()
+}
+
+/// Galaxy chat — gated to players in good standing (a Player row and no
+/// Ban). Everyone else (not-logged-in / banned) gets `vec![]`.
 #[view(accessor = my_galaxy_chat, public)]
 pub fn my_galaxy_chat(ctx: &ViewContext) -> Vec<GalaxyChannelMessage> {
     let dsl = spacetimedsl::read_only_dsl(ctx);
-    if dsl.get_player_by_id(PlayerId::new(ctx.sender())).is_err() {
+    if dsl.get_player_by_id(PlayerId::new(ctx.sender())).is_err() || !is_in_good_standing(ctx) {
         return Vec::new();
     }
+    let muted = muted_identities(ctx);
     ctx.db
         .galaxy_channel_message()
         .galaxy_id()
         .filter(&0u32)
+        .filter(|m| !is_muted_sender(&muted, &m.sender))
         .collect()
 }
 
 /// StarSystem chat for the caller's *current* StarSystem (derived via their
-/// ship's sector). No ship → no view contents.
+/// Piloted Ship's sector). Docked → no view contents.
 #[view(accessor = my_star_system_chat, public)]
 pub fn my_star_system_chat(ctx: &ViewContext) -> Vec<StarSystemChannelMessage> {
     let dsl = spacetimedsl::read_only_dsl(ctx);
-    let ship = match dsl
-        .get_ships_by_player_id(&PlayerId::new(ctx.sender()))
-        .next()
-    {
-        Some(s) => s,
-        None => return Vec::new(),
+    let Some(sector_id) = piloted_sector_id(ctx) else {
+        return Vec::new();
     };
-    let sector = match dsl.get_sector_by_id(&ship.get_sector_id().clone()) {
+    let sector = match dsl.get_sector_by_id(SectorId::new(sector_id)) {
         Ok(s) => s,
         Err(_) => return Vec::new(),
     };
     let system_id = sector.get_system_id().value();
+    let muted = muted_identities(ctx);
     ctx.db
         .star_system_channel_message()
         .system_id()
         .filter(&system_id)
+        .filter(|m| !is_muted_sender(&muted, &m.sender))
         .collect()
 }
 
-/// Sector chat for the caller's *current* Sector (derived via their ship).
+/// Sector chat for the caller's *current* Sector (derived via their Piloted
+/// Ship). Docked → no view contents.
 #[view(accessor = my_sector_chat, public)]
 pub fn my_sector_chat(ctx: &ViewContext) -> Vec<SectorChannelMessage> {
-    let dsl = spacetimedsl::read_only_dsl(ctx);
-    let ship = match dsl
-        .get_ships_by_player_id(&PlayerId::new(ctx.sender()))
-        .next()
-    {
-        Some(s) => s,
-        None => return Vec::new(),
+    let Some(sector_id) = piloted_sector_id(ctx) else {
+        return Vec::new();
     };
-    let sector_id = ship.get_sector_id().value();
+    let muted = muted_identities(ctx);
     ctx.db
         .sector_channel_message()
         .sector_id()
         .filter(&sector_id)
+        .filter(|m| !is_muted_sender(&muted, &m.sender))
         .collect()
 }
 
@@ -277,13 +446,74 @@ pub fn my_faction_chat(ctx: &ViewContext) -> Vec<FactionChannelMessage> {
         Err(_) => return Vec::new(),
     };
     let faction_id = player.get_faction_id().value();
+    let muted = muted_identities(ctx);
     ctx.db
         .faction_channel_message()
         .faction_id()
         .filter(&faction_id)
+        .filter(|m| !is_muted_sender(&muted, &m.sender))
         .collect()
 }
 
+/// History pages the caller has loaded, limited to the channels they can
+/// read right now (a page from a Sector they've since left drops out) and
+/// minus anything from a player they've muted since.
+#[view(accessor = my_chat_history, public)]
+pub fn my_chat_history(ctx: &ViewContext) -> Vec<ChatHistoryMessage> {
+    let dsl = spacetimedsl::read_only_dsl(ctx);
+    let player_id = PlayerId::new(ctx.sender());
+    let Ok(player) = dsl.get_player_by_id(&player_id) else {
+        return Vec::new();
+    };
+    let sector_id = piloted_sector_id(ctx);
+    let system_id = sector_id
+        .and_then(|id| dsl.get_sector_by_id(SectorId::new(id)).ok())
+        .map(|sector| sector.get_system_id().value() as u64);
+    let readable = |channel: &ChatChannel, scope_id: u64| match channel {
+        ChatChannel::Galaxy => is_in_good_standing(ctx),
+        ChatChannel::StarSystem => system_id == Some(scope_id),
+        ChatChannel::Sector => sector_id == Some(scope_id),
+        ChatChannel::Faction => player.get_faction_id().value() as u64 == scope_id,
+    };
+
+    let muted = muted_identities(ctx);
+    ctx.db
+        .chat_history_message()
+        .owner()
+        .filter(&ctx.sender())
+        .filter(|m| readable(&m.channel, m.scope_id))
+        .filter(|m| !is_muted_sender(&muted, &m.sender))
+        .collect()
+}
+
+/// Sector of the caller's Piloted Ship. Their Docked Ships have left the
+/// sector simulation (ADR-0002), so none of them decides what they hear.
+fn piloted_sector_id(ctx: &ViewContext) -> Option<u64> {
+    let dsl = spacetimedsl::read_only_dsl(ctx);
+    let piloted = dsl
+        .get_ships_by_player_id(&PlayerId::new(ctx.sender()))
+        .find(|ship| *ship.get_location() == ShipLocation::Sector);
+    piloted.map(|ship| ship.get_sector_id().value())
+}
+
+/// Identities the caller has muted. Indexed lookup on `owner`.
+fn muted_identities(ctx: &ViewContext) -> Vec<Identity> {
+    ctx.db
+        .player_chat_block()
+        .owner()
+        .filter(&ctx.sender())
+        .filter(|b| b.kind == ChatBlockKind::Mute)
+        .map(|b| b.target)
+        .collect()
+}
+
+fn is_muted_sender(muted: &[Identity], sender: &MessageSender) -> bool {
+    match sender {
+        MessageSender::Player(id) => muted.contains(id),
+        MessageSender::System => false,
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Send helpers — keep callers stating intent, not steps. Reducers reach for
 // these instead of `dsl.create_*_channel_message(...)` directly.
@@ -331,6 +561,33 @@ pub fn send_direct_server_critical<T: spacetimedsl::WriteContext>(
     send_direct_server_message(dsl, to, MessageSeverity::Critical, body)
 }
 
+/// Send a Direct Message from one player to another. Callers validate the
+/// pair (existence, blocks) first — this only writes the row.
+pub fn send_direct_message_row<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    from: &PlayerId,
+    to: &PlayerId,
+    body: String,
+) -> Result<(), String> {
+    dsl.create_direct_message(CreateDirectMessage {
+        from: from.clone(),
+        to: to.clone(),
+        body,
+    })?;
+    Ok(())
+}
+
+/// Whether `owner` has a `kind` entry against `target`.
+pub fn has_chat_block<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    owner: &PlayerId,
+    target: &Identity,
+    kind: ChatBlockKind,
+) -> bool {
+    dsl.get_player_chat_blocks_by_owner(owner)
+        .any(|b| b.get_target() == target && *b.get_kind() == kind)
+}
+
 /// Post an official Server-channel message (MOTD / updates).
 pub fn post_server_channel<T: spacetimedsl::WriteContext>(
     dsl: &DSL<T>,
@@ -1,5 +1,6 @@
 //! Types used throughout the solarance-beginnings-server
 
+pub mod admin;
 pub mod asteroids;
 pub mod combat;
 pub mod economy;
@@ -7,10 +8,13 @@ pub mod factions;
 pub mod global_config;
 pub mod items;
 pub mod jumpgates;
+pub mod leaderboards;
 pub mod messages;
+pub mod moderation;
 pub mod players;
 pub mod sectors;
 pub mod ships;
 pub mod star_system;
 pub mod stations;
 pub mod stellarobjects;
+pub mod welcome_back;
@@ -0,0 +1,158 @@
+//! Moderation — player sanctions and the chat filter.
+//!
+//! - `PlayerSanction`   — a Mute or Ban on an identity, optionally timed.
+//!   Private; players read their own through `my_player_sanctions`, staff who
+//!   may moderate read all of them through `moderation_player_sanctions`.
+//! - `ChatFilterConfig` — the single (`id == 0`) row of chat filter tunables.
+//!   **Public**, so the admin client can show and edit it.
+//! - `ChatRateLimit`    — per-identity rate limit window. Private, no view.
+//!
+//! Views can't see the clock, so they treat every `PlayerSanction` row as
+//! active. `player_sanction_expiry_timer` deletes expired rows every minute;
+//! reducers compare `until` with `ctx.timestamp` themselves.
+
+use spacetimedb::{table, view, Identity, SpacetimeType, Timestamp, ViewContext};
+use spacetimedsl::*;
+
+use crate::tables::admin::{AdminPermission, AdminRoleId, GetAdminRoleRowOptionById};
+
+////////////////////////////////////////////////////////////////////////////////
+// Enums
+////////////////////////////////////////////////////////////////////////////////
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub enum SanctionKind {
+    /// Can't post Channel Messages or send Direct Messages.
+    Mute,
+    /// Can't connect, and isn't in good standing for the Galaxy channel. A
+    /// kick is a short Ban.
+    Ban,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tables
+////////////////////////////////////////////////////////////////////////////////
+
+/// Keyed by raw `Identity` rather than `PlayerId`: an identity can be banned
+/// before it ever registers a Player.
+#[dsl(plural_name = player_sanctions, method(update = false))]
+#[table(accessor = player_sanction)]
+pub struct PlayerSanction {
+    #[primary_key]
+    #[auto_inc]
+    #[create_wrapper]
+    id: u64,
+
+    /// Constant `0`, indexed so the staff view can list every row without a
+    /// full scan (see `GalaxyChannelMessage`).
+    #[index(btree)]
+    galaxy_id: u32,
+
+    #[index(btree)]
+    target: Identity,
+    kind: SanctionKind,
+    /// `None` lasts until lifted.
+    until: Option<Timestamp>,
+    reason: String,
+    issued_by: Identity,
+
+    created_at: Timestamp,
+}
+
+#[dsl(plural_name = chat_filter_configs, method(update = true))]
+#[table(accessor = chat_filter_config, public)]
+pub struct ChatFilterConfig {
+    #[primary_key]
+    #[create_wrapper]
+    id: u32,
+
+    /// Whole words (case-insensitive) masked with `*` in every player chat
+    /// message and Direct Message.
+    pub banned_words: Vec<String>,
+    /// How many messages one identity may send per `rate_limit_window_secs`.
+    pub max_messages_per_window: u32,
+    pub rate_limit_window_secs: u32,
+    pub max_message_length: u32,
+    /// Reject a message identical to the sender's previous one in the window.
+    pub reject_repeats: bool,
+
+    created_at: Timestamp,
+    modified_at: Timestamp,
+}
+
+#[dsl(plural_name = chat_rate_limits, method(update = true))]
+#[table(accessor = chat_rate_limit)]
+pub struct ChatRateLimit {
+    #[primary_key]
+    #[create_wrapper]
+    id: Identity,
+
+    pub window_start: Timestamp,
+    pub messages_in_window: u32,
+    pub last_message: String,
+
+    created_at: Timestamp,
+    modified_at: Timestamp,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Views
+////////////////////////////////////////////////////////////////////////////////
+
+/// The caller's own sanctions. The game client disconnects itself when a Ban
+/// shows up here, which is how a kick takes effect.
+#[view(accessor = my_player_sanctions, public)]
+pub fn my_player_sanctions(ctx: &ViewContext) -> Vec<PlayerSanction> {
+    ctx.db
+        .player_sanction()
+        .target()
+        .filter(&ctx.sender())
+        .collect()
+}
+
+/// Every sanction, for staff whose role may moderate players.
+#[view(accessor = moderation_player_sanctions, public)]
+pub fn moderation_player_sanctions(ctx: &ViewContext) -> Vec<PlayerSanction> {
+    let dsl = spacetimedsl::read_only_dsl(ctx);
+    match dsl.get_admin_role_by_id(AdminRoleId::new(ctx.sender())) {
+        Ok(role) if role.role.grants(&AdminPermission::ModeratePlayers) => {
+            ctx.db.player_sanction().galaxy_id().filter(&0u32).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Impls
+////////////////////////////////////////////////////////////////////////////////
+
+impl PlayerSanction {
+    pub fn is_active(&self, now: Timestamp) -> bool {
+        match self.until {
+            Some(until) => until > now,
+            None => true,
+        }
+    }
+
+    /// "until <time>" or "indefinitely", for player-facing messages.
+    pub fn describe_until(&self) -> String {
+        match self.until {
+            Some(until) => format!("until {}", until),
+            None => "indefinitely".to_string(),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Utility
+////////////////////////////////////////////////////////////////////////////////
+
+/// Whether a view's caller is in good standing: no Ban on record. Expired
+/// Bans count until the expiry timer removes them.
+pub fn is_in_good_standing(ctx: &ViewContext) -> bool {
+    !ctx.db
+        .player_sanction()
+        .target()
+        .filter(&ctx.sender())
+        .any(|sanction| sanction.kind == SanctionKind::Ban)
+}
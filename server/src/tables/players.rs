@@ -13,7 +13,14 @@ pub struct Player {
     #[referenced_by(path = crate::tables::ships, table = ship_movement_controller)]
     #[referenced_by(path = crate::tables::ships, table = ship)]
     #[referenced_by(path = crate::tables::messages, table = direct_server_message)]
+    #[referenced_by(path = crate::tables::messages, table = direct_message)]
+    #[referenced_by(path = crate::tables::messages, table = player_chat_block)]
     #[referenced_by(path = crate::tables::stations, table = construction_contribution_log)]
+    #[referenced_by(path = crate::tables::stations, table = station_hangar_item)]
+    #[referenced_by(path = crate::logic::stations::module_types::trading_port, table = market_order)]
+    #[referenced_by(path = crate::tables::welcome_back, table = welcome_back_snapshot)]
+    #[referenced_by(path = crate::tables::welcome_back, table = welcome_back_payload)]
+    #[referenced_by(path = crate::tables::leaderboards, table = contribution_tally)]
     id: Identity,
 
     #[unique]
@@ -34,11 +41,19 @@ pub struct Player {
 }
 
 impl Player {
-    /// Sobj id of the player's currently-controlled (in-sector) ship, if any.
-    pub fn get_ship_id<T: spacetimedsl::WriteContext>(&self, dsl: &DSL<T>) -> Option<u64> {
+    /// The player's Piloted Ship, if any. A player owns a fleet of ships but
+    /// at most one of them is ever in a sector (ADR-0002); `undock_ship`
+    /// enforces that, so the first match is the only one.
+    pub fn get_piloted_ship<T: spacetimedsl::WriteContext>(&self, dsl: &DSL<T>) -> Option<Ship> {
         dsl.get_ships_by_player_id(&self.get_id())
             .find(|s| *s.get_location() == crate::tables::ships::ShipLocation::Sector)
-            .map(|s| s.get_sobj_id().value())
+    }
+
+    /// Every ship the player owns, piloted or docked, in id order.
+    pub fn get_fleet<T: spacetimedsl::WriteContext>(&self, dsl: &DSL<T>) -> Vec<Ship> {
+        let mut fleet: Vec<Ship> = dsl.get_ships_by_player_id(&self.get_id()).collect();
+        fleet.sort_by_key(|s| s.get_id().value());
+        fleet
     }
 
     pub fn get_player_objects<T: spacetimedsl::WriteContext>(
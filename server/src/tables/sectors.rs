@@ -1,5 +1,5 @@
 use solarance_shared::Vec2;
-use spacetimedb::{table, SpacetimeType};
+use spacetimedb::{table, SpacetimeType, Timestamp};
 use spacetimedsl::*;
 
 use crate::{
@@ -13,9 +13,13 @@ pub struct Sector {
     #[primary_key] // NOT Auto-inc so it can be reloaded as-is
     #[create_wrapper]
     #[referenced_by(path = crate::tables::sectors, table = asteroid_sector)]
+    #[referenced_by(path = crate::tables::sectors, table = asteroid_field_report)]
     #[referenced_by(path = crate::tables::stellarobjects, table = stellar_object)]
     #[referenced_by(path = crate::tables::asteroids, table = asteroid)]
+    #[referenced_by(path = crate::tables::asteroids, table = asteroid_respawn)]
     #[referenced_by(path = crate::tables::ships, table = ship)]
+    #[referenced_by(path = crate::tables::ships, table = ship_autopilot)]
+    #[referenced_by(path = crate::logic::stellarobjects::movement, table = sector_collision_timer)]
     #[referenced_by(path = crate::tables::stations, table = station)]
     #[referenced_by(path = crate::tables::jumpgates, table = jump_gate)]
     #[referenced_by(path = crate::tables::combat, table = visual_effect)]
@@ -30,7 +34,7 @@ pub struct Sector {
     /// FK to StarSystem
     system_id: u32,
 
-    name: String,
+    pub name: String,
     pub description: Option<String>,
 
     #[index(btree)]
@@ -47,25 +51,25 @@ pub struct Sector {
     /// From 1.0 being in orbit around the sun, to 0.0 being outside a solar system.
     /// Most sectors will have 0.9 - 0.5 depending on how far from the center of the solar system it is.
     /// Solar power plants want to be in sectors of 0.5+
-    sunlight: f32,
+    pub sunlight: f32,
     /// How much weird stuff the current sector has going on.
     /// From 1.0 being inside the middle of eye of chaos, to 0.0 being a normal solar system.
     /// Most sectors will have 0.0 - 0.1, research stations want to be in sectors of 0.5+
-    anomalous: f32,
+    pub anomalous: f32,
     /// How much gas/dust the current sector has.
     /// From 1.0 being so thick you can't use your sensors, to 0.0 being a clear space.
     /// Most sectors will have 0.0 - 0.1, pirate stations want to be in sectors of 0.5+
-    nebula: f32,
+    pub nebula: f32,
     /// How likely rare ore is to appear in the current sector.
     /// From 1.0 being ONLY rare ore, to 0.0 being only iron.
     /// Most sectors will have 0.0 - 0.1, refinery stations want to be in sectors of 0.5+
-    rare_ore: f32,
+    pub rare_ore: f32,
 
     // Sector's star system position
-    x: f32,
-    y: f32,
+    pub x: f32,
+    pub y: f32,
 
-    background_gfx_key: Option<String>, // Key for client to look up background image
+    pub background_gfx_key: Option<String>, // Key for client to look up background image
 }
 
 /// One entry in a sector's asteroid ore composition: the relative `weight`
@@ -79,7 +83,7 @@ pub struct OreWeight {
 }
 
 #[dsl(plural_name = asteroid_sectors, method(update = false))]
-#[table(accessor = asteroid_sector)]
+#[table(accessor = asteroid_sector, public)]
 pub struct AsteroidSector {
     #[primary_key] // NOT Auto-inc so it can be reloaded as-is
     #[use_wrapper(SectorId)]
@@ -96,6 +100,38 @@ pub struct AsteroidSector {
     ore_weights: Vec<OreWeight>,
 }
 
+/// How much of one ore an asteroid field holds.
+#[derive(SpacetimeType, Debug, Clone, PartialEq)]
+pub struct FieldOreShare {
+    pub item_id: u32,
+    pub asteroids: u32,
+    pub resources: u64,
+}
+
+/// The state of a sector's asteroid field, for balancing. Rewritten by
+/// `asteroid_sector_upkeep` every hour.
+#[dsl(plural_name = asteroid_field_reports, method(update = true))]
+#[table(accessor = asteroid_field_report, public)]
+pub struct AsteroidFieldReport {
+    #[primary_key]
+    #[use_wrapper(SectorId)]
+    #[foreign_key(path = crate::tables::sectors, table = sector, column = id, on_delete = Delete)]
+    /// FK to Sector
+    id: u64,
+
+    pub asteroid_count: u32,
+    pub target_population: u32,
+    /// Exhausted asteroids still waiting out their respawn delay.
+    pub pending_respawns: u32,
+    pub current_resources: u64,
+    /// What the field's asteroids started with, fragments included.
+    pub initial_resources: u64,
+    /// One entry per ore, richest first.
+    pub ore_mix: Vec<FieldOreShare>,
+
+    modified_at: Timestamp,
+}
+
 /// Decorative in-sector nebula sprite (#107). Pure render flavor — no stellar
 /// object, no collision, no game mechanic. Ships fly straight through them.
 #[dsl(plural_name = sector_nebulae, method(update = false))]
@@ -145,6 +181,21 @@ pub fn connect_sectors_with_warpgates<T: spacetimedsl::WriteContext>(
     a: &Sector,
     b: &Sector,
 ) -> Result<(), String> {
+    let (a_wp_pos, b_wp_pos) = warpgate_positions(a, b);
+
+    create_jumpgate_internal(
+        dsl, a.id, a_wp_pos.x, a_wp_pos.y, b.id, b_wp_pos.x, b_wp_pos.y,
+    )?;
+    create_jumpgate_internal(
+        dsl, b.id, b_wp_pos.x, b_wp_pos.y, a.id, a_wp_pos.x, a_wp_pos.y,
+    )?;
+
+    Ok(())
+}
+
+/// Where `connect_sectors_with_warpgates` puts the gates in `a` and `b`: on
+/// the side of each sector facing the other one.
+pub fn warpgate_positions(a: &Sector, b: &Sector) -> (glam::Vec2, glam::Vec2) {
     let a_pos = glam::Vec2::new(a.x, a.y);
     let b_pos = glam::Vec2::new(b.x, b.y);
     //info!("Sector Positions: A{} B{}", a_pos, b_pos);
@@ -156,15 +207,7 @@ pub fn connect_sectors_with_warpgates<T: spacetimedsl::WriteContext>(
     let a_wp_pos = glam::Vec2::from_angle(a_angle) * 5000.0;
     let b_wp_pos = glam::Vec2::from_angle(b_angle) * 5000.0;
     //info!("Sector WP Pos: A{} B{}", a_wp_pos, b_wp_pos);
-
-    create_jumpgate_internal(
-        dsl, a.id, a_wp_pos.x, a_wp_pos.y, b.id, b_wp_pos.x, b_wp_pos.y,
-    )?;
-    create_jumpgate_internal(
-        dsl, b.id, b_wp_pos.x, b_wp_pos.y, a.id, a_wp_pos.x, a_wp_pos.y,
-    )?;
-
-    Ok(())
+    (a_wp_pos, b_wp_pos)
 }
 
 /// For jumpdrive-enabled ships, calculates the incoming vector the ship should be entering from.
@@ -1,14 +1,14 @@
 use log::info;
-use spacetimedb::{table, Identity, SpacetimeType};
+use spacetimedb::{table, Identity, SpacetimeType, Timestamp};
 use spacetimedsl::*;
 
-use solarance_shared::{MovementState, Vec2};
+use solarance_shared::{predict_regen, MovementState, RegenState, Vec2};
 
 use crate::tables::{
     items::*, players::PlayerId, sectors::*, stations::*, stellarobjects::*,
 };
 
-#[derive(SpacetimeType, Debug, Clone, PartialEq, Eq)]
+#[derive(SpacetimeType, Debug, Clone, PartialEq, Eq, serde::Deserialize)]
 pub enum ShipClass {
     Miner,
     Shuttle,
@@ -32,6 +32,15 @@ pub enum ShipLocation {
     Ship,
 }
 
+/// Where a `ShipAutopilot` is headed.
+#[derive(SpacetimeType, Debug, Clone, PartialEq)]
+pub enum AutopilotTarget {
+    /// A station, asteroid or jumpgate, possibly in another sector.
+    StellarObject(u64),
+    /// A point in the ship's sector at the time the autopilot was engaged.
+    Position(Vec2),
+}
+
 // Enum for different types of equipment slots on a ship
 #[derive(SpacetimeType, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum EquipmentSlotType {
@@ -61,6 +70,10 @@ pub struct ShipTypeDefinition {
     pub max_health: u16,
     pub max_shields: u16,
     pub max_energy: u16,
+    /// Shield points regenerated per second, before fitted modules.
+    pub shield_regen_per_sec: f32,
+    /// Energy regenerated per second, before fitted modules.
+    pub energy_regen_per_sec: f32,
 
     pub base_speed: f32,
     pub base_acceleration: f32,
@@ -86,6 +99,10 @@ pub struct ShipTypeDefinition {
     pub sprite_height: u16, // Height of the ship sprite in pixels
 
     pub gfx_key: Option<String>, // Key for client to look up 2D sprite/model
+
+    /// Credits a shipyard charges for a new hull of this type. `None` means
+    /// it isn't for sale.
+    pub price: Option<u64>,
 }
 
 impl ShipTypeDefinition {
@@ -111,6 +128,12 @@ impl ShipTypeDefinition {
             Vec2::new(position.x + rotated_x, position.y + rotated_y)
         })
     }
+
+    /// Radius of the circle a ship of this type sweeps when it moves. Averages
+    /// the sprite's half-extents so long, thin hulls don't snag on everything.
+    pub fn collision_radius(&self) -> f32 {
+        (self.sprite_width as f32 + self.sprite_height as f32) / 4.0
+    }
 }
 
 #[dsl(plural_name = ship_statuses, method(update = true))]
@@ -132,17 +155,40 @@ pub struct ShipStatus {
     pub player_id: Identity,
 
     pub health: f32,
-    pub shields: f32,
-    pub energy: f32,
+    /// Regenerating snapshot; read it with `current_shields`. Its cap is the
+    /// ship type's plus fitted shield generators, see `apply_loadout_stats`.
+    pub shields: RegenState,
+    /// Regenerating snapshot; read it with `current_energy`.
+    pub energy: RegenState,
 
     pub weapon_cooldown_ms: u32, // Milliseconds remaining until weapons can fire again
     pub missile_cooldown_ms: u32, // Milliseconds remaining until missiles can fire again
 
     pub used_cargo_capacity: u16, // Needs to be manually maintained via ShipCargoItem
-    pub max_cargo_capacity: u16,  // Needs to be manually maintained via ShipCargoItem
+    pub max_cargo_capacity: u16,  // Ship type + fitted cargo expanders, see `apply_loadout_stats`
 }
 
 impl ShipStatus {
+    /// Shields at `current_time` (microseconds since the Unix epoch).
+    pub fn current_shields(&self, current_time: i64) -> f32 {
+        predict_regen(&self.shields, current_time)
+    }
+
+    /// Energy at `current_time` (microseconds since the Unix epoch).
+    pub fn current_energy(&self, current_time: i64) -> f32 {
+        predict_regen(&self.energy, current_time)
+    }
+
+    /// Spends `amount` energy at `current_time` if the ship has it. Returns
+    /// whether it did.
+    pub fn try_spend_energy(&mut self, amount: f32, current_time: i64) -> bool {
+        if self.current_energy(current_time) < amount {
+            return false;
+        }
+        self.energy.adjust(-amount, current_time);
+        true
+    }
+
     pub fn get_remaining_cargo_space(&self) -> u16 {
         self.get_max_cargo_capacity() - self.get_used_cargo_capacity()
     }
@@ -195,6 +241,33 @@ pub struct ShipMovementController {
     pub right: bool,
 }
 
+#[dsl(plural_name = ship_autopilots, method(update = true))]
+#[table(accessor = ship_autopilot, public)]
+/// An engaged waypoint autopilot. While this row exists the autopilot tick
+/// owns the ship's accelerations; any manual input deletes it, as does
+/// arrival. See `logic::ships::autopilot`.
+pub struct ShipAutopilot {
+    #[primary_key]
+    #[use_wrapper(ShipId)]
+    #[foreign_key(path = crate::tables::ships, table = ship, column = id, on_delete = Delete)]
+    /// FK to Ship
+    id: u64,
+
+    pub target: AutopilotTarget,
+
+    #[index(btree)]
+    #[use_wrapper(SectorId)]
+    #[foreign_key(path = crate::tables::sectors, table = sector, column = id, on_delete = Delete)]
+    /// FK to Sector the target is in.
+    pub destination_sector_id: u64,
+
+    /// Jumpgate sobj ids still to be flown through, in order. Empty once the
+    /// ship is in `destination_sector_id`.
+    pub route: Vec<u64>,
+
+    created_at: Timestamp,
+}
+
 #[dsl(plural_name = ships, method(update = true))]
 #[table(accessor = ship, public)]
 pub struct Ship {
@@ -203,6 +276,7 @@ pub struct Ship {
     #[create_wrapper]
     #[referenced_by(path = crate::tables::ships, table = ship_cargo_item)]
     #[referenced_by(path = crate::tables::ships, table = ship_equipment_slot)]
+    #[referenced_by(path = crate::tables::ships, table = ship_autopilot)]
     id: u64,
 
     #[index(btree)]
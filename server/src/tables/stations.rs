@@ -1,7 +1,8 @@
-use solarance_shared::Vec2;
-use spacetimedb::{table, Identity, SpacetimeType, Timestamp};
+use solarance_shared::{MarketQuote, Vec2};
+use spacetimedb::{table, view, Identity, SpacetimeType, Timestamp, ViewContext};
 use spacetimedsl::*;
 
+use crate::definitions::station_module_types::PROPOSABLE_MODULES;
 use crate::tables::economy::ResourceAmount;
 use crate::tables::items::*;
 use crate::*;
@@ -19,7 +20,7 @@ pub enum StationSize {
     Satellite,
 }
 
-#[derive(SpacetimeType, Clone, Debug, PartialEq, Eq)]
+#[derive(SpacetimeType, Clone, Debug, PartialEq, Eq, serde::Deserialize)]
 pub enum StationModuleCategory {
     LogisticsAndStorage,
     ResourceProductionAndRefining,
@@ -32,7 +33,7 @@ pub enum StationModuleCategory {
 
 /// Enum for specific module types, more granular than category.
 /// This helps define what a blueprint *is*.
-#[derive(SpacetimeType, Clone, Debug, PartialEq, Eq)]
+#[derive(SpacetimeType, Clone, Debug, PartialEq, Eq, serde::Deserialize)]
 pub enum StationModuleSpecificType {
     // Logistics & Storage
     TradingPort,
@@ -72,6 +73,39 @@ pub enum StationModuleSpecificType {
     GarrisonRegionalDefense,
 }
 
+/// Why a producing module made less than it could this tick.
+#[derive(SpacetimeType, Debug, Clone, PartialEq, Eq)]
+pub enum ProductionBottleneck {
+    /// Idled by a brownout (see `StationPowerGrid`).
+    Unpowered,
+    /// A manufacturing module with no recipe set.
+    NoRecipe,
+    /// Not enough of an input for one more batch.
+    StarvedOfInputs,
+    /// An output slot is full.
+    OutputFull,
+}
+
+/// What one module made and used in a production tick. Items moved in or out
+/// by logistics aren't counted.
+#[derive(SpacetimeType, Debug, Clone, PartialEq)]
+pub struct ModuleThroughput {
+    pub module_id: u64,
+    pub produced: Vec<ResourceAmount>,
+    pub consumed: Vec<ResourceAmount>,
+    pub bottleneck: Option<ProductionBottleneck>,
+}
+
+/// Items the station's logistics moved from one module's output to another's
+/// input in a production tick.
+#[derive(SpacetimeType, Debug, Clone, PartialEq)]
+pub struct LogisticsTransfer {
+    pub item_id: u32,
+    pub from_module_id: u64,
+    pub to_module_id: u64,
+    pub quantity: u32,
+}
+
 /////////////////////////////////////
 // Tables
 
@@ -81,6 +115,7 @@ pub struct StationModuleBlueprint {
     #[primary_key]
     #[create_wrapper]
     #[referenced_by(path = crate::tables::stations, table = station_module)]
+    #[referenced_by(path = crate::tables::stations, table = station_module_under_construction)]
     id: u32,
 
     #[unique]
@@ -96,6 +131,9 @@ pub struct StationModuleBlueprint {
 
     pub power_consumption_mw_operational: f32, // Power needed when active
     pub power_consumption_mw_idle: f32,        // Power needed when idle
+    /// Nameplate output of generator modules (solar arrays, reactors) in full
+    /// sunlight and health; `0.0` for everything else.
+    pub power_generation_mw: f32,
     pub cpu_load_flops: f32,
 
     pub required_station_tech_level: u8,
@@ -117,6 +155,8 @@ pub struct StationModule {
     #[auto_inc]
     #[create_wrapper]
     #[referenced_by(path = crate::tables::stations, table = station_module_inventory_item)]
+    #[referenced_by(path = crate::tables::stations, table = module_logistics_priority)]
+    #[referenced_by(path = crate::logic::stations::module_types::trading_port, table = market_order)]
     id: u64,
 
     #[index(btree)]
@@ -147,8 +187,18 @@ pub struct StationUnderConstruction {
     /// FK to SpaceStation
     id: u64,
 
+    /// Flips once the initial build completes; the station can be docked at
+    /// from then on.
     pub is_operational: bool,
+    /// Progress of the Contribution Pool for the current `stage`.
     pub construction_progress_percentage: f32,
+
+    /// Which Contribution Pool is open: `0` is the initial build, each growth
+    /// stage after it counts up by one.
+    pub stage: u32,
+    /// The size the station grows into when the current growth pool fills.
+    /// `None` during the initial build and once the station is fully grown.
+    pub growing_to: Option<StationSize>,
 }
 
 /// One row per resource type required to complete a construction site.
@@ -177,6 +227,9 @@ pub struct ConstructionRequirement {
 
     /// Total quantity needed of this resource type for the site to complete.
     pub quantity_required: u32,
+
+    /// The `StationUnderConstruction::stage` whose pool this belongs to.
+    pub stage: u32,
 }
 
 /// Append-only log of every contribution event. Source of truth for both
@@ -209,21 +262,46 @@ pub struct ConstructionContributionLog {
 
     /// Quantity of `item_id` deposited in this single contribution event.
     quantity: u32,
+    /// The `StationUnderConstruction::stage` whose pool this went into; `0`
+    /// for module proposals.
+    stage: u32,
+    /// Set when this went into a module proposal's pool
+    /// (`StationModuleUnderConstruction`) rather than the station's own.
+    module_proposal_id: Option<u64>,
 
     contributed_at: Timestamp,
 }
 
+/// A player-proposed module being built at an operational station. Its
+/// Contribution Pool is the blueprint's `build_cost_resources`; contributions
+/// go into `ConstructionContributionLog` tagged with this row's id. At most
+/// one per station, deleted once the module is built.
 #[dsl(plural_name = station_modules_under_construction, method(update = true))]
 #[table(accessor = station_module_under_construction, public)]
 pub struct StationModuleUnderConstruction {
     #[primary_key]
+    #[auto_inc]
+    #[create_wrapper]
+    id: u64,
+
+    #[unique]
     #[use_wrapper(StationId)]
     #[foreign_key(path = crate::tables::stations, table = station, column = id, on_delete = Delete)]
     /// FK to SpaceStation
-    id: u64,
+    station_id: u64,
+
+    #[index(btree)]
+    #[use_wrapper(StationModuleBlueprintId)]
+    #[foreign_key(path = crate::tables::stations, table = station_module_blueprint, column = id, on_delete = Error)]
+    /// FK to StationModuleBlueprint — the module being built.
+    blueprint: u32,
+
+    /// Who proposed it.
+    proposed_by: Identity,
 
-    pub is_operational: bool,
     pub construction_progress_percentage: f32,
+
+    created_at: Timestamp,
 }
 
 /// Stores items used for a module's operation or as temporary input/output buffers.
@@ -233,6 +311,7 @@ pub struct StationModuleInventoryItem {
     #[primary_key]
     #[auto_inc]
     #[create_wrapper]
+    #[referenced_by(path = crate::logic::stations::module_types::trading_port, table = station_price_sample)]
     id: u64,
 
     #[index(btree)]
@@ -257,6 +336,38 @@ pub struct StationModuleInventoryItem {
     pub cached_price: u32,
 }
 
+/// A player's personal hangar stock at a station: one row per (station,
+/// player, item). Space comes from the station's `StorageDepot` modules — see
+/// `logic::stations::hangar`.
+#[dsl(plural_name = station_hangar_items, method(update = true))]
+#[table(accessor = station_hangar_item, public)]
+pub struct StationHangarItem {
+    #[primary_key]
+    #[auto_inc]
+    #[create_wrapper]
+    id: u64,
+
+    #[index(btree)]
+    #[use_wrapper(StationId)]
+    #[foreign_key(path = crate::tables::stations, table = station, column = id, on_delete = Delete)]
+    /// FK to Station
+    pub station_id: u64,
+
+    #[index(btree)]
+    #[use_wrapper(crate::tables::players::PlayerId)]
+    #[foreign_key(path = crate::tables::players, table = player, column = id, on_delete = Error)]
+    /// FK to Player — who owns the stored items.
+    pub player_id: Identity,
+
+    #[index(btree)]
+    #[use_wrapper(crate::tables::items::ItemDefinitionId)]
+    #[foreign_key(path = crate::tables::items, table = item_definition, column = id, on_delete = Error)]
+    /// FK to ItemDefinition
+    pub item_id: u32,
+
+    pub quantity: u32,
+}
+
 #[dsl(plural_name = stations, method(update = true))]
 #[table(accessor = station, public)]
 pub struct Station {
@@ -267,8 +378,11 @@ pub struct Station {
     #[referenced_by(path = crate::tables::stations, table = station_under_construction)]
     #[referenced_by(path = crate::tables::stations, table = station_module_under_construction)]
     #[referenced_by(path = crate::tables::stations, table = station_status)]
+    #[referenced_by(path = crate::tables::stations, table = station_power_grid)]
+    #[referenced_by(path = crate::tables::stations, table = station_production_report)]
     #[referenced_by(path = crate::tables::stations, table = construction_requirement)]
     #[referenced_by(path = crate::tables::stations, table = construction_contribution_log)]
+    #[referenced_by(path = crate::tables::stations, table = station_hangar_item)]
     #[referenced_by(path = crate::tables::ships, table = ship)]
     id: u64,
 
@@ -316,9 +430,74 @@ pub struct StationStatus {
 
     pub health: f32,
     pub shields: f32,
+    /// Charge of the station's battery in MWh, up to
+    /// `StationSize::battery_capacity_mwh`. See `logic::stations::power`.
     pub energy: f32,
 }
 
+/// How the station's logistics rank this module when handing out inputs:
+/// higher is fed first. Modules without a row rank at
+/// `DEFAULT_LOGISTICS_PRIORITY`.
+#[dsl(plural_name = module_logistics_priorities, method(update = true))]
+#[table(accessor = module_logistics_priority, public)]
+pub struct ModuleLogisticsPriority {
+    #[primary_key]
+    #[use_wrapper(StationModuleId)]
+    #[foreign_key(path = crate::tables::stations, table = station_module, column = id, on_delete = Delete)]
+    /// FK to StationModule
+    id: u64,
+
+    pub priority: u8,
+}
+
+/// The station's last production tick: what each producing module made and
+/// what held it back, and what logistics moved between modules. Rewritten
+/// every tick by `logic::stations::production`.
+#[dsl(plural_name = station_production_reports, method(update = true))]
+#[table(accessor = station_production_report, public)]
+pub struct StationProductionReport {
+    #[primary_key]
+    #[use_wrapper(StationId)]
+    #[foreign_key(path = crate::tables::stations, table = station, column = id, on_delete = Delete)]
+    /// FK to Station
+    id: u64,
+
+    pub modules: Vec<ModuleThroughput>,
+    pub transfers: Vec<LogisticsTransfer>,
+
+    modified_at: Timestamp,
+}
+
+/// The station's power and CPU ledger, rewritten every production tick by
+/// `logic::stations::power::update_station_power_grid`.
+#[dsl(plural_name = station_power_grids, method(update = true))]
+#[table(accessor = station_power_grid, public)]
+pub struct StationPowerGrid {
+    #[primary_key]
+    #[use_wrapper(StationId)]
+    #[foreign_key(path = crate::tables::stations, table = station, column = id, on_delete = Delete)]
+    /// FK to Station
+    id: u64,
+
+    /// Core reactor plus every generator module, after sunlight and health.
+    pub generation_mw: f32,
+    /// What every operational module would draw running flat out.
+    pub demand_mw: f32,
+    /// What was actually drawn: running modules at full draw, idled ones at
+    /// idle draw.
+    pub draw_mw: f32,
+
+    pub cpu_capacity_flops: f32,
+    pub cpu_load_flops: f32,
+
+    pub battery_capacity_mwh: f32,
+
+    /// Modules browned out this tick; the production tick skips them.
+    pub idled_module_ids: Vec<u64>,
+
+    modified_at: Timestamp,
+}
+
 ////////////////////////////////////////////////////
 /// Impls
 ///
@@ -336,6 +515,30 @@ impl StationSize {
         }
     }
 
+    /// Radius of the solid hull ships collide with. Kept well inside the
+    /// 500px docking range so every size can still be docked with.
+    pub fn collision_radius(&self) -> f32 {
+        match self {
+            StationSize::Capital => 320.0,
+            StationSize::Large => 260.0,
+            StationSize::Medium => 200.0,
+            StationSize::Small => 150.0,
+            StationSize::Outpost => 110.0,
+            StationSize::Satellite => 60.0,
+        }
+    }
+
+    /// The size a station of this size grows into once its growth pool
+    /// fills. Capitals are placed, never grown; Satellites stay small.
+    pub fn next_growth_size(&self) -> Option<StationSize> {
+        match self {
+            StationSize::Outpost => Some(StationSize::Small),
+            StationSize::Small => Some(StationSize::Medium),
+            StationSize::Medium => Some(StationSize::Large),
+            StationSize::Large | StationSize::Capital | StationSize::Satellite => None,
+        }
+    }
+
     pub fn calculate_base_cost(&self) -> u32 {
         (self.max_module_amount().pow(2) as u32) * 100_000 + 300_000
     }
@@ -353,36 +556,74 @@ impl StationSize {
     pub fn calculate_base_shields(&self) -> u32 {
         (self.max_module_amount().pow(2) as u32) * 50_000 + 200_000
     }
+
+    /// Output of the station's built-in reactor, before any generator
+    /// modules. Enough to run an average module in every slot.
+    pub fn core_reactor_mw(&self) -> f32 {
+        self.max_module_amount() as f32 * 50_000.0
+    }
+
+    pub fn cpu_capacity_flops(&self) -> f32 {
+        self.max_module_amount() as f32 * 600.0
+    }
+
+    pub fn battery_capacity_mwh(&self) -> f32 {
+        self.max_module_amount() as f32 * 2_000.0
+    }
+}
+
+impl StationModuleCategory {
+    /// Which modules keep running in a brownout: the highest priority is
+    /// powered first. Trading and storage are the last to be idled, so a
+    /// starved station can still be resupplied.
+    pub fn power_priority(&self) -> u8 {
+        match self {
+            StationModuleCategory::LogisticsAndStorage => 6,
+            StationModuleCategory::DefenseAndMilitary => 5,
+            StationModuleCategory::CivilianAndSupportServices => 4,
+            StationModuleCategory::DiplomacyAndFaction => 3,
+            StationModuleCategory::ResourceProductionAndRefining => 2,
+            StationModuleCategory::ManufacturingAndAssembly => 1,
+            StationModuleCategory::ResearchAndDevelopment => 0,
+        }
+    }
+}
+
+impl StationUnderConstruction {
+    /// Whether a Contribution Pool is accepting contributions: the initial
+    /// build, or a growth stage.
+    pub fn has_open_pool(&self) -> bool {
+        !self.is_operational || self.growing_to.is_some()
+    }
 }
 
 impl StationModuleInventoryItem {
-    /// Calculates the current price of an item based on its quantity and item definition.
+    /// The item's price on stock alone (see `solarance_shared::pricing`):
+    /// `base_value` plus its margin when empty, minus it when full.
     pub fn calculate_current_price(&self, item_def: &ItemDefinition) -> u32 {
-        // Convert to floats
-        let max_quantity = self.max_quantity as f32;
-        let current_quantity = self.quantity as f32;
-        let value = *item_def.get_base_value() as f32;
-
-        //info!("Calculating price for {}", item_def.name);
-        // Calc the percent and resultant multiplier
-        let percent_full = current_quantity / max_quantity;
-        let multiplier = percent_full * -2.0 + 1.0; // 1.0 .. -1.0
-                                                    // info!("    Curr/Max : {}/{}", current_quantity, max_quantity);
-                                                    // info!("    Multipler : {}", multiplier);
-
-        // Find the value of the given margin
-        let curr_margin_perc = (*item_def.get_margin_percentage() as f32) * 0.01;
-        let margin_value = value * curr_margin_perc;
-        // info!("    Curr Margin Perc : {}", curr_margin_perc);
-        // info!("    Base Margin Value : {}c", margin_value);
-        // info!("    Adjusted Value : {}c", margin_value * multiplier);
-        // info!(
-        //     "    New Value : {}c",
-        //     (value + margin_value * multiplier) as u32
-        // );
-
-        // If current_quantity == max_quantity then the current price should be base_value + (base_value * default_margin * -1.0)
-        // If current_quantity == 0 then the current price should be base_value + (base_value * default_margin * 1.0)
-        (value + margin_value * multiplier) as u32
+        MarketQuote::for_stock(
+            *item_def.get_base_value(),
+            *item_def.get_margin_percentage(),
+            self.quantity,
+            self.max_quantity,
+        )
+        .mid_price() as u32
     }
 }
+
+//////////////////////////////////
+// Views
+
+/// The blueprints players can propose at a station, with their build costs
+/// (see `logic::stations::module_proposals`).
+#[view(accessor = proposable_station_modules, public)]
+pub fn proposable_station_modules(ctx: &ViewContext) -> Vec<StationModuleBlueprint> {
+    let dsl = spacetimedsl::read_only_dsl(ctx);
+    PROPOSABLE_MODULES
+        .iter()
+        .filter_map(|id| {
+            dsl.get_station_module_blueprint_by_id(StationModuleBlueprintId::new(*id))
+                .ok()
+        })
+        .collect()
+}
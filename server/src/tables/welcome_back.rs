@@ -0,0 +1,150 @@
+//! Welcome-back snapshots and payloads (#92 follow-up).
+//!
+//! - `WelcomeBackSnapshot` — what a player last saw of the world: every
+//!   construction site's progress, every station's module counts and every
+//!   sector's controlling faction. Written when they disconnect. Private.
+//! - `WelcomeBackPayload`  — the structured summary composed at connect by
+//!   diffing that snapshot against the world now (`OfflineTickCalculator`).
+//!   One row per player, replaced every connect; read through
+//!   `my_welcome_back`.
+//!
+//! The same summary still goes out as a text `DirectServerMessage` so it
+//! stays in the Inbox; the payload only drives the welcome-back panel.
+
+use spacetimedb::{table, view, Identity, SpacetimeType, Timestamp, ViewContext};
+use spacetimedsl::*;
+
+use crate::tables::players::PlayerId;
+
+////////////////////////////////////////////////////////////////////////////////
+// Snapshot entries
+////////////////////////////////////////////////////////////////////////////////
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct SiteSnapshot {
+    pub station_id: u64,
+    pub progress_percentage: f32,
+    pub is_operational: bool,
+}
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct StationModulesSnapshot {
+    pub station_id: u64,
+    pub module_count: u32,
+    pub operational_modules: u32,
+}
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct SectorControlSnapshot {
+    pub sector_id: u64,
+    pub controlling_faction_id: u32,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Payload entries — names resolved server-side so the client just renders.
+////////////////////////////////////////////////////////////////////////////////
+
+/// A construction site that moved while the player was away.
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct SiteProgressChange {
+    pub station_id: u64,
+    pub station_name: String,
+    pub sector_name: String,
+    /// `None` if the site didn't exist when the player left.
+    pub from_percentage: Option<f32>,
+    pub to_percentage: f32,
+    pub came_online: bool,
+    /// The player's own contributions as a share of everything the site
+    /// needed, all-time.
+    pub your_share_percentage: f32,
+    pub is_your_faction: bool,
+}
+
+/// A station whose modules were added, removed or brought online.
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct StationModuleChange {
+    pub station_id: u64,
+    pub station_name: String,
+    pub modules_before: u32,
+    pub modules_now: u32,
+    pub operational_before: u32,
+    pub operational_now: u32,
+}
+
+/// A sector that changed hands.
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct SectorControlChange {
+    pub sector_id: u64,
+    pub sector_name: String,
+    pub from_faction: String,
+    pub to_faction: String,
+}
+
+/// Units contributed to construction sites since the player left.
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct ContributorTotal {
+    pub username: String,
+    pub quantity: u32,
+    pub is_you: bool,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tables
+////////////////////////////////////////////////////////////////////////////////
+
+#[dsl(plural_name = welcome_back_snapshots, method(update = true))]
+#[table(accessor = welcome_back_snapshot)]
+pub struct WelcomeBackSnapshot {
+    #[primary_key]
+    #[use_wrapper(crate::tables::players::PlayerId)]
+    #[foreign_key(path = crate::tables::players, table = player, column = id, on_delete = Delete)]
+    id: Identity,
+
+    pub sites: Vec<SiteSnapshot>,
+    pub stations: Vec<StationModulesSnapshot>,
+    pub sectors: Vec<SectorControlSnapshot>,
+
+    created_at: Timestamp,
+    modified_at: Timestamp,
+}
+
+#[dsl(plural_name = welcome_back_payloads, method(update = true))]
+#[table(accessor = welcome_back_payload)]
+pub struct WelcomeBackPayload {
+    #[primary_key]
+    #[use_wrapper(crate::tables::players::PlayerId)]
+    #[foreign_key(path = crate::tables::players, table = player, column = id, on_delete = Delete)]
+    id: Identity,
+
+    /// The `last_login` this payload was composed against; `None` on a first
+    /// visit, when there is nothing to diff.
+    pub since: Option<Timestamp>,
+    pub away_secs: u64,
+    /// Economy ticks the galaxy ran while the player was away. Their own
+    /// assets sat still for all of them (Offline Pause).
+    pub offline_ticks: u64,
+
+    pub site_changes: Vec<SiteProgressChange>,
+    pub module_changes: Vec<StationModuleChange>,
+    pub control_changes: Vec<SectorControlChange>,
+    pub top_contributors: Vec<ContributorTotal>,
+
+    /// Cargo and hangar lines, as in the text summary.
+    pub asset_lines: Vec<String>,
+
+    created_at: Timestamp,
+    modified_at: Timestamp,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Views
+////////////////////////////////////////////////////////////////////////////////
+
+/// The caller's latest welcome-back payload.
+#[view(accessor = my_welcome_back, public)]
+pub fn my_welcome_back(ctx: &ViewContext) -> Vec<WelcomeBackPayload> {
+    let dsl = spacetimedsl::read_only_dsl(ctx);
+    dsl.get_welcome_back_payload_by_id(PlayerId::new(ctx.sender()))
+        .into_iter()
+        .collect()
+}
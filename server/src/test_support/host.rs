@@ -0,0 +1,748 @@
+//! In-memory stand-in for the SpacetimeDB host.
+//!
+//! `spacetimedb-bindings-sys` only *declares* the host ABI (`table_id_from_name`,
+//! `datastore_insert_bsatn`, ...); on wasm the SpacetimeDB host provides the
+//! definitions. In native test builds this module provides them instead, so
+//! the generated table handles — and everything spacetimedsl builds on top of
+//! them — run against plain `Vec`s and `BTreeMap`s.
+//!
+//! Rows are kept decoded as `ProductValue`s and re-encoded on read. Index
+//! scans are linear filters sorted by key, which is plenty for test-sized
+//! worlds. Unique constraints, auto-inc sequences and set semantics (inserting
+//! an exact duplicate is a no-op) behave like the real datastore.
+//!
+//! State is thread-local: each test thread owns one `Datastore`, installed by
+//! `TestWorld::new`.
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap, VecDeque},
+    ops::{Bound, RangeBounds},
+    slice,
+};
+
+use spacetimedb::{
+    sats::{bsatn, AlgebraicType, AlgebraicValue, ProductValue},
+    table::{ColId, IndexId},
+    Errno, Identity, ScheduleAt, TableId, Timestamp,
+};
+
+use super::schema::{schema, IndexSchema, Schema, TableSchema};
+
+//////////////////////////////////////////////////////////////
+// Datastore
+//////////////////////////////////////////////////////////////
+
+#[derive(Clone)]
+pub struct Row {
+    pub value: ProductValue,
+    /// When a scheduled row next fires. `None` for ordinary tables.
+    pub due: Option<Timestamp>,
+}
+
+#[derive(Clone, Default)]
+pub struct TableData {
+    /// Keyed by insertion order, which is also the order of a table scan.
+    pub rows: BTreeMap<u64, Row>,
+    next_row: u64,
+    /// Next value per auto-inc column, in `TableSchema::sequences` order.
+    next_sequence: Vec<u128>,
+}
+
+pub struct Datastore {
+    pub tables: Vec<TableData>,
+    pub now: Timestamp,
+    pub module_identity: Identity,
+    iters: HashMap<u32, VecDeque<Vec<u8>>>,
+    next_iter: u32,
+}
+
+impl Datastore {
+    pub fn new(now: Timestamp, module_identity: Identity) -> Self {
+        let tables = schema()
+            .tables
+            .iter()
+            .map(|t| TableData {
+                next_sequence: vec![1; t.sequences.len()],
+                ..Default::default()
+            })
+            .collect();
+        Self {
+            tables,
+            now,
+            module_identity,
+            iters: HashMap::new(),
+            next_iter: 1,
+        }
+    }
+
+    /// The earliest scheduled row due at or before `until`, as
+    /// `(table, row, due)`.
+    pub fn next_due(&self, until: Timestamp) -> Option<(usize, u64, Timestamp)> {
+        self.tables
+            .iter()
+            .enumerate()
+            .flat_map(|(t, data)| {
+                data.rows
+                    .iter()
+                    .filter_map(move |(&r, row)| row.due.map(|due| (t, r, due)))
+            })
+            .filter(|&(_, _, due)| due <= until)
+            .min_by_key(|&(t, r, due)| (due, t, r))
+    }
+
+    pub fn encode_row(&self, table: usize, row: u64) -> Option<Vec<u8>> {
+        let row = self.tables[table].rows.get(&row)?;
+        Some(bsatn::to_vec(&row.value).expect("row re-encodes"))
+    }
+
+    fn open_iter(&mut self, rows: VecDeque<Vec<u8>>) -> u32 {
+        let id = self.next_iter;
+        self.next_iter += 1;
+        self.iters.insert(id, rows);
+        id
+    }
+
+    fn iter_rows(&mut self, table: usize, row_ids: &[u64]) -> u32 {
+        let rows = row_ids
+            .iter()
+            .filter_map(|&r| self.encode_row(table, r))
+            .collect();
+        self.open_iter(rows)
+    }
+
+    fn delete_rows(&mut self, table: usize, row_ids: &[u64]) -> u32 {
+        let rows = &mut self.tables[table].rows;
+        row_ids.iter().filter(|r| rows.remove(r).is_some()).count() as u32
+    }
+
+    fn insert(&mut self, table: usize, bytes: &[u8]) -> Result<Vec<u8>, Errno> {
+        let schema = &schema().tables[table];
+        let mut value = decode_row(schema, bytes)?;
+
+        let data = &mut self.tables[table];
+        for (seq, &col) in schema.sequences.iter().enumerate() {
+            if is_zero(&value.elements[col]) {
+                value.elements[col] = int_value(schema.column_type(col), data.next_sequence[seq]);
+                data.next_sequence[seq] += 1;
+            }
+        }
+
+        if !data.rows.values().any(|row| row.value == value) {
+            check_unique(schema, data, &value, None)?;
+            let due = due_at(schema, &value, self.now);
+            let data = &mut self.tables[table];
+            data.rows.insert(
+                data.next_row,
+                Row {
+                    value: value.clone(),
+                    due,
+                },
+            );
+            data.next_row += 1;
+        }
+
+        Ok(generated_columns(schema, &value))
+    }
+
+    fn update(&mut self, table: usize, index: usize, bytes: &[u8]) -> Result<Vec<u8>, Errno> {
+        let schema_all = schema();
+        let schema = &schema_all.tables[table];
+        let index = &schema_all.indexes[index];
+        if !schema_all.index_is_unique(index) {
+            return Err(Errno::INDEX_NOT_UNIQUE);
+        }
+        let value = decode_row(schema, bytes)?;
+        let key = key_of(&value, &index.columns);
+
+        let data = &self.tables[table];
+        let (&row_id, _) = data
+            .rows
+            .iter()
+            .find(|(_, row)| key_of(&row.value, &index.columns) == key)
+            .ok_or(Errno::NO_SUCH_ROW)?;
+        check_unique(schema, data, &value, Some(row_id))?;
+
+        let due = due_at(schema, &value, self.now);
+        let generated = generated_columns(schema, &value);
+        self.tables[table].rows.insert(row_id, Row { value, due });
+        Ok(generated)
+    }
+
+    fn delete_by_value(&mut self, table: usize, bytes: &[u8]) -> Result<u32, Errno> {
+        let schema = &schema().tables[table];
+        let mut reader = bytes;
+        let count = u32::from_le_bytes(take(&mut reader, 4)?.try_into().unwrap());
+        let mut values = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            values.push(
+                ProductValue::decode(&schema.row_type, &mut reader)
+                    .map_err(|_| Errno::BSATN_DECODE_ERROR)?,
+            );
+        }
+
+        let data = &mut self.tables[table];
+        let before = data.rows.len();
+        data.rows.retain(|_, row| !values.contains(&row.value));
+        Ok((before - data.rows.len()) as u32)
+    }
+}
+
+thread_local! {
+    static DATASTORE: RefCell<Option<Datastore>> = const { RefCell::new(None) };
+    static SINKS: RefCell<HashMap<u32, Vec<u8>>> = RefCell::new(HashMap::new());
+}
+
+pub fn install(datastore: Option<Datastore>) {
+    DATASTORE.with_borrow_mut(|ds| *ds = datastore);
+}
+
+pub fn with_datastore<R>(f: impl FnOnce(&mut Datastore) -> R) -> R {
+    DATASTORE.with_borrow_mut(|ds| {
+        f(ds.as_mut()
+            .expect("no TestWorld on this thread: table access needs `TestWorld::new()` first"))
+    })
+}
+
+/// Drains everything written to `sink` so far.
+pub fn take_sink(sink: u32) -> Vec<u8> {
+    SINKS.with_borrow_mut(|sinks| sinks.remove(&sink).unwrap_or_default())
+}
+
+//////////////////////////////////////////////////////////////
+// Row helpers
+//////////////////////////////////////////////////////////////
+
+fn decode_row(schema: &TableSchema, bytes: &[u8]) -> Result<ProductValue, Errno> {
+    ProductValue::decode(&schema.row_type, &mut &*bytes).map_err(|_| Errno::BSATN_DECODE_ERROR)
+}
+
+fn key_of(value: &ProductValue, columns: &[usize]) -> Vec<AlgebraicValue> {
+    columns.iter().map(|&c| value.elements[c].clone()).collect()
+}
+
+fn check_unique(
+    schema: &TableSchema,
+    data: &TableData,
+    value: &ProductValue,
+    except: Option<u64>,
+) -> Result<(), Errno> {
+    let clash = schema.unique_columns.iter().any(|&col| {
+        data.rows
+            .iter()
+            .any(|(&r, row)| Some(r) != except && row.value.elements[col] == value.elements[col])
+    });
+    if clash {
+        Err(Errno::UNIQUE_ALREADY_EXISTS)
+    } else {
+        Ok(())
+    }
+}
+
+/// BSATN of the auto-inc columns, which the host writes back after an
+/// insert or update.
+fn generated_columns(schema: &TableSchema, value: &ProductValue) -> Vec<u8> {
+    let generated: ProductValue = schema
+        .sequences
+        .iter()
+        .map(|&col| value.elements[col].clone())
+        .collect();
+    bsatn::to_vec(&generated).expect("generated columns encode")
+}
+
+/// The `ScheduleAt` of a row in a scheduled table.
+pub fn schedule_at(schema: &TableSchema, value: &ProductValue) -> Option<ScheduleAt> {
+    let col = schema.scheduled_at?;
+    let bytes = bsatn::to_vec(&value.elements[col]).ok()?;
+    bsatn::from_slice(&bytes).ok()
+}
+
+/// When a scheduled row fires: at its `Time`, or one `Interval` from now.
+fn due_at(schema: &TableSchema, value: &ProductValue, now: Timestamp) -> Option<Timestamp> {
+    match schedule_at(schema, value)? {
+        ScheduleAt::Time(at) => Some(at),
+        ScheduleAt::Interval(every) => Some(now + every),
+    }
+}
+
+fn is_zero(value: &AlgebraicValue) -> bool {
+    matches!(
+        value,
+        AlgebraicValue::U8(0)
+            | AlgebraicValue::U16(0)
+            | AlgebraicValue::U32(0)
+            | AlgebraicValue::U64(0)
+            | AlgebraicValue::I8(0)
+            | AlgebraicValue::I16(0)
+            | AlgebraicValue::I32(0)
+            | AlgebraicValue::I64(0)
+    )
+}
+
+fn int_value(ty: &AlgebraicType, n: u128) -> AlgebraicValue {
+    match ty {
+        AlgebraicType::U8 => AlgebraicValue::U8(n as u8),
+        AlgebraicType::U16 => AlgebraicValue::U16(n as u16),
+        AlgebraicType::U32 => AlgebraicValue::U32(n as u32),
+        AlgebraicType::U64 => AlgebraicValue::U64(n as u64),
+        AlgebraicType::I8 => AlgebraicValue::I8(n as i8),
+        AlgebraicType::I16 => AlgebraicValue::I16(n as i16),
+        AlgebraicType::I32 => AlgebraicValue::I32(n as i32),
+        AlgebraicType::I64 => AlgebraicValue::I64(n as i64),
+        other => panic!("auto-inc column of unsupported type {other:?}"),
+    }
+}
+
+fn take<'a>(reader: &mut &'a [u8], n: usize) -> Result<&'a [u8], Errno> {
+    if reader.len() < n {
+        return Err(Errno::BSATN_DECODE_ERROR);
+    }
+    let (head, rest) = reader.split_at(n);
+    *reader = rest;
+    Ok(head)
+}
+
+//////////////////////////////////////////////////////////////
+// Index scans
+//////////////////////////////////////////////////////////////
+
+fn lookup_index(index_id: IndexId) -> Result<(&'static Schema, &'static IndexSchema), Errno> {
+    let schema = schema();
+    let index = schema
+        .indexes
+        .get(index_id.0 as usize)
+        .ok_or(Errno::NO_SUCH_INDEX)?;
+    Ok((schema, index))
+}
+
+/// Row ids whose key equals `point`, sorted by key like a btree scan.
+fn scan_point(ds: &Datastore, index_id: IndexId, point: &[u8]) -> Result<(usize, Vec<u64>), Errno> {
+    let (schema, index) = lookup_index(index_id)?;
+    let table = &schema.tables[index.table];
+    let mut reader = point;
+    let key = match index.columns.as_slice() {
+        [col] => vec![AlgebraicValue::decode(table.column_type(*col), &mut reader)
+            .map_err(|_| Errno::BSATN_DECODE_ERROR)?],
+        cols => {
+            let mut key = Vec::with_capacity(cols.len());
+            for &col in cols {
+                key.push(
+                    AlgebraicValue::decode(table.column_type(col), &mut reader)
+                        .map_err(|_| Errno::BSATN_DECODE_ERROR)?,
+                );
+            }
+            key
+        }
+    };
+    Ok((index.table, matching(ds, index, |k| k == key.as_slice())))
+}
+
+/// Row ids matching `prefix` on the leading columns and the bounds on the
+/// next one, sorted by key.
+fn scan_range(
+    ds: &Datastore,
+    index_id: IndexId,
+    prefix: &[u8],
+    prefix_elems: ColId,
+    rstart: &[u8],
+    rend: &[u8],
+) -> Result<(usize, Vec<u64>), Errno> {
+    let (schema, index) = lookup_index(index_id)?;
+    let table = &schema.tables[index.table];
+    let prefix_elems = prefix_elems.0 as usize;
+
+    let mut reader = prefix;
+    let mut prefix_key = Vec::with_capacity(prefix_elems);
+    for &col in &index.columns[..prefix_elems] {
+        prefix_key.push(
+            AlgebraicValue::decode(table.column_type(col), &mut reader)
+                .map_err(|_| Errno::BSATN_DECODE_ERROR)?,
+        );
+    }
+
+    let bounds = match index.columns.get(prefix_elems) {
+        Some(&col) => {
+            let ty = table.column_type(col);
+            Some((decode_bound(ty, rstart)?, decode_bound(ty, rend)?))
+        }
+        None => None,
+    };
+
+    Ok((
+        index.table,
+        matching(ds, index, |key| {
+            key[..prefix_elems] == prefix_key[..]
+                && bounds
+                    .as_ref()
+                    .is_none_or(|range| range.contains(&key[prefix_elems]))
+        }),
+    ))
+}
+
+fn decode_bound(ty: &AlgebraicType, mut bytes: &[u8]) -> Result<Bound<AlgebraicValue>, Errno> {
+    let tag = take(&mut bytes, 1)?[0];
+    let mut value =
+        || AlgebraicValue::decode(ty, &mut bytes).map_err(|_| Errno::BSATN_DECODE_ERROR);
+    match tag {
+        0 => Ok(Bound::Included(value()?)),
+        1 => Ok(Bound::Excluded(value()?)),
+        2 => Ok(Bound::Unbounded),
+        _ => Err(Errno::BSATN_DECODE_ERROR),
+    }
+}
+
+fn matching(
+    ds: &Datastore,
+    index: &IndexSchema,
+    keep: impl Fn(&[AlgebraicValue]) -> bool,
+) -> Vec<u64> {
+    let mut hits: Vec<(Vec<AlgebraicValue>, u64)> = ds.tables[index.table]
+        .rows
+        .iter()
+        .map(|(&r, row)| (key_of(&row.value, &index.columns), r))
+        .filter(|(key, _)| keep(key))
+        .collect();
+    hits.sort();
+    hits.into_iter().map(|(_, r)| r).collect()
+}
+
+//////////////////////////////////////////////////////////////
+// ABI
+//////////////////////////////////////////////////////////////
+
+fn status(result: Result<(), Errno>) -> u16 {
+    match result {
+        Ok(()) => 0,
+        Err(e) => e.code(),
+    }
+}
+
+unsafe fn bytes<'a>(ptr: *const u8, len: usize) -> &'a [u8] {
+    if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(ptr, len)
+    }
+}
+
+fn lookup_table(table_id: TableId) -> Result<usize, Errno> {
+    let table = table_id.0 as usize;
+    if table < schema().tables.len() {
+        Ok(table)
+    } else {
+        Err(Errno::NO_SUCH_TABLE)
+    }
+}
+
+#[no_mangle]
+extern "C" fn table_id_from_name(name: *const u8, name_len: usize, out: *mut TableId) -> u16 {
+    let name = String::from_utf8_lossy(unsafe { bytes(name, name_len) });
+    match schema().table_by_name(&name) {
+        Some(table) => {
+            unsafe { *out = TableId(table as u32) };
+            0
+        }
+        None => {
+            eprintln!("test_support: table `{name}` is not listed in test_support/schema.rs");
+            Errno::NO_SUCH_TABLE.code()
+        }
+    }
+}
+
+#[no_mangle]
+extern "C" fn index_id_from_name(name: *const u8, name_len: usize, out: *mut IndexId) -> u16 {
+    let name = String::from_utf8_lossy(unsafe { bytes(name, name_len) });
+    match schema().index_by_name(&name) {
+        Some(index) => {
+            unsafe { *out = IndexId(index as u32) };
+            0
+        }
+        None => {
+            eprintln!("test_support: index `{name}` belongs to a table missing from test_support/schema.rs");
+            Errno::NO_SUCH_INDEX.code()
+        }
+    }
+}
+
+#[no_mangle]
+extern "C" fn datastore_table_row_count(table_id: TableId, out: *mut u64) -> u16 {
+    status(lookup_table(table_id).map(|table| {
+        let count = with_datastore(|ds| ds.tables[table].rows.len() as u64);
+        unsafe { *out = count };
+    }))
+}
+
+#[no_mangle]
+extern "C" fn datastore_table_scan_bsatn(table_id: TableId, out: *mut u32) -> u16 {
+    status(lookup_table(table_id).map(|table| {
+        let iter = with_datastore(|ds| {
+            let rows: Vec<u64> = ds.tables[table].rows.keys().copied().collect();
+            ds.iter_rows(table, &rows)
+        });
+        unsafe { *out = iter };
+    }))
+}
+
+#[no_mangle]
+extern "C" fn datastore_index_scan_point_bsatn(
+    index_id: IndexId,
+    point_ptr: *const u8,
+    point_len: usize,
+    out: *mut u32,
+) -> u16 {
+    let point = unsafe { bytes(point_ptr, point_len) };
+    status(with_datastore(|ds| {
+        let (table, rows) = scan_point(ds, index_id, point)?;
+        let iter = ds.iter_rows(table, &rows);
+        unsafe { *out = iter };
+        Ok(())
+    }))
+}
+
+#[no_mangle]
+extern "C" fn datastore_delete_by_index_scan_point_bsatn(
+    index_id: IndexId,
+    point_ptr: *const u8,
+    point_len: usize,
+    out: *mut u32,
+) -> u16 {
+    let point = unsafe { bytes(point_ptr, point_len) };
+    status(with_datastore(|ds| {
+        let (table, rows) = scan_point(ds, index_id, point)?;
+        let deleted = ds.delete_rows(table, &rows);
+        unsafe { *out = deleted };
+        Ok(())
+    }))
+}
+
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+extern "C" fn datastore_index_scan_range_bsatn(
+    index_id: IndexId,
+    prefix_ptr: *const u8,
+    prefix_len: usize,
+    prefix_elems: ColId,
+    rstart_ptr: *const u8,
+    rstart_len: usize,
+    rend_ptr: *const u8,
+    rend_len: usize,
+    out: *mut u32,
+) -> u16 {
+    let (prefix, rstart, rend) = unsafe {
+        (
+            bytes(prefix_ptr, prefix_len),
+            bytes(rstart_ptr, rstart_len),
+            bytes(rend_ptr, rend_len),
+        )
+    };
+    status(with_datastore(|ds| {
+        let (table, rows) = scan_range(ds, index_id, prefix, prefix_elems, rstart, rend)?;
+        let iter = ds.iter_rows(table, &rows);
+        unsafe { *out = iter };
+        Ok(())
+    }))
+}
+
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+extern "C" fn datastore_delete_by_index_scan_range_bsatn(
+    index_id: IndexId,
+    prefix_ptr: *const u8,
+    prefix_len: usize,
+    prefix_elems: ColId,
+    rstart_ptr: *const u8,
+    rstart_len: usize,
+    rend_ptr: *const u8,
+    rend_len: usize,
+    out: *mut u32,
+) -> u16 {
+    let (prefix, rstart, rend) = unsafe {
+        (
+            bytes(prefix_ptr, prefix_len),
+            bytes(rstart_ptr, rstart_len),
+            bytes(rend_ptr, rend_len),
+        )
+    };
+    status(with_datastore(|ds| {
+        let (table, rows) = scan_range(ds, index_id, prefix, prefix_elems, rstart, rend)?;
+        let deleted = ds.delete_rows(table, &rows);
+        unsafe { *out = deleted };
+        Ok(())
+    }))
+}
+
+#[no_mangle]
+extern "C" fn datastore_delete_all_by_eq_bsatn(
+    table_id: TableId,
+    rel_ptr: *const u8,
+    rel_len: usize,
+    out: *mut u32,
+) -> u16 {
+    let rel = unsafe { bytes(rel_ptr, rel_len) };
+    status(lookup_table(table_id).and_then(|table| {
+        let deleted = with_datastore(|ds| ds.delete_by_value(table, rel))?;
+        unsafe { *out = deleted };
+        Ok(())
+    }))
+}
+
+#[no_mangle]
+extern "C" fn datastore_clear(table_id: TableId, out: *mut u64) -> u16 {
+    status(lookup_table(table_id).map(|table| {
+        let cleared = with_datastore(|ds| std::mem::take(&mut ds.tables[table].rows).len() as u64);
+        unsafe { *out = cleared };
+    }))
+}
+
+/// Writes `generated` back over the caller's row buffer, as the real host
+/// does for auto-inc columns.
+unsafe fn write_back(generated: &[u8], row_ptr: *mut u8, row_len_ptr: *mut usize) {
+    debug_assert!(generated.len() <= *row_len_ptr);
+    std::ptr::copy_nonoverlapping(generated.as_ptr(), row_ptr, generated.len());
+    *row_len_ptr = generated.len();
+}
+
+#[no_mangle]
+extern "C" fn datastore_insert_bsatn(
+    table_id: TableId,
+    row_ptr: *mut u8,
+    row_len_ptr: *mut usize,
+) -> u16 {
+    let row = unsafe { bytes(row_ptr, *row_len_ptr) }.to_vec();
+    status(lookup_table(table_id).and_then(|table| {
+        let generated = with_datastore(|ds| ds.insert(table, &row))?;
+        unsafe { write_back(&generated, row_ptr, row_len_ptr) };
+        Ok(())
+    }))
+}
+
+#[no_mangle]
+extern "C" fn datastore_update_bsatn(
+    table_id: TableId,
+    index_id: IndexId,
+    row_ptr: *mut u8,
+    row_len_ptr: *mut usize,
+) -> u16 {
+    let row = unsafe { bytes(row_ptr, *row_len_ptr) }.to_vec();
+    status(lookup_table(table_id).and_then(|table| {
+        let generated = with_datastore(|ds| ds.update(table, index_id.0 as usize, &row))?;
+        unsafe { write_back(&generated, row_ptr, row_len_ptr) };
+        Ok(())
+    }))
+}
+
+#[no_mangle]
+extern "C" fn row_iter_bsatn_advance(
+    iter: u32,
+    buffer_ptr: *mut u8,
+    buffer_len_ptr: *mut usize,
+) -> i16 {
+    with_datastore(|ds| {
+        let Some(rows) = ds.iters.get_mut(&iter) else {
+            return Errno::NO_SUCH_ITER.code() as i16;
+        };
+
+        let capacity = unsafe { *buffer_len_ptr };
+        let mut written = 0;
+        while let Some(row) = rows.front() {
+            if written + row.len() > capacity {
+                break;
+            }
+            unsafe {
+                std::ptr::copy_nonoverlapping(row.as_ptr(), buffer_ptr.add(written), row.len())
+            };
+            written += row.len();
+            rows.pop_front();
+        }
+
+        if let (0, Some(next)) = (written, rows.front()) {
+            unsafe { *buffer_len_ptr = next.len() };
+            return Errno::BUFFER_TOO_SMALL.code() as i16;
+        }
+
+        unsafe { *buffer_len_ptr = written };
+        if rows.is_empty() {
+            ds.iters.remove(&iter);
+            -1
+        } else {
+            0
+        }
+    })
+}
+
+#[no_mangle]
+extern "C" fn row_iter_bsatn_close(iter: u32) -> u16 {
+    with_datastore(|ds| match ds.iters.remove(&iter) {
+        Some(_) => 0,
+        None => Errno::NO_SUCH_ITER.code(),
+    })
+}
+
+#[no_mangle]
+extern "C" fn identity(out_ptr: *mut u8) {
+    let identity = with_datastore(|ds| ds.module_identity).to_byte_array();
+    unsafe { std::ptr::copy_nonoverlapping(identity.as_ptr(), out_ptr, identity.len()) };
+}
+
+#[no_mangle]
+extern "C" fn bytes_sink_write(
+    sink: u32,
+    buffer_ptr: *const u8,
+    buffer_len_ptr: *mut usize,
+) -> u16 {
+    let buffer = unsafe { bytes(buffer_ptr, *buffer_len_ptr) };
+    SINKS.with_borrow_mut(|sinks| sinks.entry(sink).or_default().extend_from_slice(buffer));
+    0
+}
+
+// Reducer arguments are never passed through a `BytesSource`: `TestWorld`
+// calls reducers as plain Rust functions.
+#[no_mangle]
+extern "C" fn bytes_source_read(
+    _source: u32,
+    _buffer_ptr: *mut u8,
+    _buffer_len_ptr: *mut usize,
+) -> i16 {
+    Errno::NO_SUCH_BYTES.code() as i16
+}
+
+#[no_mangle]
+extern "C" fn bytes_source_remaining_length(_source: u32, _out: *mut u32) -> i16 {
+    Errno::NO_SUCH_BYTES.code() as i16
+}
+
+#[no_mangle]
+extern "C" fn get_jwt(_connection_id_ptr: *const u8, bytes_source_id: *mut u32) -> u16 {
+    unsafe { *bytes_source_id = 0 };
+    0
+}
+
+/// Only errors and warnings are printed; `cargo test` shows them for failing
+/// tests.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+extern "C" fn console_log(
+    level: u8,
+    _target_ptr: *const u8,
+    _target_len: usize,
+    _filename_ptr: *const u8,
+    _filename_len: usize,
+    _line_number: u32,
+    message_ptr: *const u8,
+    message_len: usize,
+) {
+    if level <= 1 || level == 101 {
+        let message = String::from_utf8_lossy(unsafe { bytes(message_ptr, message_len) });
+        eprintln!("[module] {message}");
+    }
+}
+
+#[no_mangle]
+extern "C" fn console_timer_start(_name_ptr: *const u8, _name_len: usize) -> u32 {
+    1
+}
+
+#[no_mangle]
+extern "C" fn console_timer_end(_timer_id: u32) -> u16 {
+    0
+}
@@ -0,0 +1,285 @@
+//! # Reducer Test Harness
+//!
+//! Runs the module's reducers natively, against in-memory tables, with a
+//! clock the test controls — no SpacetimeDB instance needed.
+//!
+//! ```ignore
+//! let world = TestWorld::with_init();
+//! let pilot = world.player(1);
+//! world.call(pilot, |ctx| register_playername(ctx, pilot, "Ace".into(), faction_id))?;
+//! world.advance(Duration::from_secs(9)); // three mining ticks
+//! ```
+//!
+//! ## How it works
+//! - `host` implements the SpacetimeDB host ABI over a thread-local datastore,
+//!   so generated table handles and the spacetimedsl layer run unmodified.
+//! - `schema` lists the module's tables. **A new table must be added there**
+//!   before reducers touching it can run under test.
+//! - `TestWorld::call` enters through the real `__call_reducer__` entry point,
+//!   so the closure gets a genuine `ReducerContext` with the chosen sender and
+//!   the world's current timestamp. A reducer that returns `Err` (or panics)
+//!   is rolled back, as on the real host.
+//! - `TestWorld::advance` moves the clock forward, firing every scheduled
+//!   timer row that comes due on the way, in order.
+//!
+//! Each test thread gets its own world; `cargo test` runs them in parallel.
+
+mod host;
+mod scenarios;
+mod schema;
+
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+    time::Duration,
+};
+
+use spacetimedb::{Identity, ReducerContext, ScheduleAt, TimeDuration, Timestamp};
+use spacetimedsl::{dsl, DSL};
+
+use host::{with_datastore, Datastore};
+use schema::schema;
+
+/// The module identity the test datastore runs as, so `try_server_only`
+/// accepts calls made with it.
+const SERVER_IDENTITY: &str = "c2001b668b8b961618fb1271998d5be0789eff815e5e82b69cd146ef0370be66";
+
+/// 2025-06-15 UTC. Any fixed date works; a fixed one keeps `ctx.rng()`
+/// deterministic.
+const START_MICROS: i64 = 1_750_000_000_000_000;
+
+pub struct TestWorld {
+    // Tables live in a thread-local; keep the world on the thread that made it.
+    _not_send: std::marker::PhantomData<*const ()>,
+}
+
+impl TestWorld {
+    /// An empty world: every table exists, none has rows.
+    pub fn new() -> Self {
+        static DESCRIBE: Once = Once::new();
+        DESCRIBE.call_once(|| unsafe {
+            spacetimedb::rt::register_reducer::<_, test_world_call>(test_world_call);
+            let sink = next_sink();
+            __describe_module__(sink);
+            host::take_sink(sink);
+        });
+
+        host::install(Some(Datastore::new(
+            Timestamp::from_micros_since_unix_epoch(START_MICROS),
+            Self::server(),
+        )));
+        Self {
+            _not_send: std::marker::PhantomData,
+        }
+    }
+
+    /// A world after the module's `init` reducer: definitions, the galaxy
+    /// and the global timers are all in place.
+    pub fn with_init() -> Self {
+        let world = Self::new();
+        world
+            .call_as_server(crate::lifecycle::init::init)
+            .expect("init reducer failed");
+        world
+    }
+
+    /// The identity the module schedules timers as.
+    pub fn server() -> Identity {
+        Identity::from_hex(SERVER_IDENTITY).unwrap()
+    }
+
+    /// A distinct client identity per `n`.
+    pub fn player(&self, n: u8) -> Identity {
+        let mut bytes = [0u8; 32];
+        bytes[0] = n;
+        bytes[31] = 0xaa;
+        Identity::from_byte_array(bytes)
+    }
+
+    pub fn now(&self) -> Timestamp {
+        with_datastore(|ds| ds.now)
+    }
+
+    /// Runs `f` as a reducer called by `sender`, at the current time. Table
+    /// changes are kept on `Ok` and rolled back on `Err` or panic.
+    pub fn call<R>(
+        &self,
+        sender: Identity,
+        f: impl FnOnce(&ReducerContext) -> Result<R, String>,
+    ) -> Result<R, String> {
+        let snapshot = with_datastore(|ds| ds.tables.clone());
+
+        let mut f = Some(f);
+        let mut output = None;
+        let mut body = |ctx: &ReducerContext| {
+            let f = f.take().expect("reducer body runs once");
+            f(ctx).map(|value| output = Some(value))
+        };
+        let result = invoke(sender, self.now(), &mut body);
+
+        if result.is_err() || PANIC.with_borrow(Option::is_some) {
+            with_datastore(|ds| ds.tables = snapshot);
+        }
+        if let Some(payload) = PANIC.take() {
+            panic::resume_unwind(payload);
+        }
+        result.map(|()| output.expect("reducer body ran"))
+    }
+
+    pub fn call_as_server<R>(
+        &self,
+        f: impl FnOnce(&ReducerContext) -> Result<R, String>,
+    ) -> Result<R, String> {
+        self.call(Self::server(), f)
+    }
+
+    /// Read-only access to the tables between calls.
+    pub fn with_dsl<R>(&self, f: impl FnOnce(&DSL<'_, ReducerContext>) -> R) -> R {
+        self.call_as_server(|ctx| Ok(f(&dsl(ctx)))).unwrap()
+    }
+
+    /// Moves the clock forward by `duration`, firing every scheduled row
+    /// that comes due along the way, earliest first. Interval timers re-arm
+    /// and may fire several times. Returns the errors of timers that failed.
+    pub fn advance(&self, duration: Duration) -> Vec<String> {
+        let until = self.now() + TimeDuration::from(duration);
+        let mut errors = Vec::new();
+
+        while let Some((table, row_id, due)) = with_datastore(|ds| ds.next_due(until)) {
+            let row = with_datastore(|ds| {
+                ds.now = due;
+                let row = ds.encode_row(table, row_id).unwrap();
+                rearm(ds, table, row_id, due);
+                row
+            });
+
+            let timer = schema().tables[table].timer.as_ref().unwrap_or_else(|| {
+                panic!(
+                    "scheduled table `{}` has no reducer in test_support/schema.rs",
+                    schema().tables[table].name
+                )
+            });
+            if let Err(e) = self.call_as_server(|ctx| timer(ctx, &row)) {
+                errors.push(format!("{}: {e}", schema().tables[table].name));
+            }
+        }
+
+        with_datastore(|ds| ds.now = until);
+        errors
+    }
+}
+
+impl Default for TestWorld {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for TestWorld {
+    fn drop(&mut self) {
+        host::install(None);
+    }
+}
+
+/// One-shot rows are consumed before they fire; interval rows are pushed to
+/// their next due time (never the same instant, so a zero interval can't
+/// spin forever).
+fn rearm(ds: &mut Datastore, table: usize, row_id: u64, due: Timestamp) {
+    let rows = &mut ds.tables[table].rows;
+    match host::schedule_at(&schema().tables[table], &rows[&row_id].value) {
+        Some(ScheduleAt::Interval(every)) => {
+            let next = (due + every).max(due + TimeDuration::from_micros(1));
+            rows.get_mut(&row_id).unwrap().due = Some(next);
+        }
+        _ => {
+            rows.remove(&row_id);
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////
+// Reducer trampoline
+//////////////////////////////////////////////////////////////
+
+type Body<'a> = dyn FnMut(&ReducerContext) -> Result<(), String> + 'a;
+
+thread_local! {
+    static BODY: Cell<Option<*mut Body<'static>>> = const { Cell::new(None) };
+    static PANIC: RefCell<Option<Box<dyn Any + Send>>> = const { RefCell::new(None) };
+}
+
+unsafe extern "C" {
+    fn __describe_module__(description: u32);
+    #[allow(clippy::too_many_arguments)]
+    fn __call_reducer__(
+        id: usize,
+        sender_0: u64,
+        sender_1: u64,
+        sender_2: u64,
+        sender_3: u64,
+        conn_id_0: u64,
+        conn_id_1: u64,
+        timestamp: u64,
+        args: u32,
+        error: u32,
+    ) -> i16;
+}
+
+/// The only reducer the harness registers, so it's always id 0. It runs
+/// whatever body `invoke` left for it.
+#[spacetimedb::reducer]
+fn test_world_call(ctx: &ReducerContext) -> Result<(), String> {
+    let body = BODY.get().expect("test_world_call outside TestWorld::call");
+    // Panics must not unwind through `__call_reducer__` (an `extern "C"` fn).
+    match panic::catch_unwind(AssertUnwindSafe(|| unsafe { (*body)(ctx) })) {
+        Ok(result) => result,
+        Err(payload) => {
+            PANIC.set(Some(payload));
+            Err("reducer panicked".into())
+        }
+    }
+}
+
+fn invoke(sender: Identity, now: Timestamp, body: &mut Body<'_>) -> Result<(), String> {
+    // SAFETY: the pointer is only dereferenced inside `__call_reducer__`
+    // below, while `body` is still borrowed.
+    let body: *mut Body<'static> = unsafe { std::mem::transmute(body as *mut Body<'_>) };
+    BODY.set(Some(body));
+
+    let words: Vec<u64> = sender
+        .to_byte_array()
+        .chunks(8)
+        .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+        .collect();
+    let sink = next_sink();
+    let code = unsafe {
+        __call_reducer__(
+            0,
+            words[0],
+            words[1],
+            words[2],
+            words[3],
+            0,
+            0,
+            now.to_micros_since_unix_epoch() as u64,
+            0,
+            sink,
+        )
+    };
+    BODY.set(None);
+
+    let message = host::take_sink(sink);
+    match code {
+        0 => Ok(()),
+        _ => Err(String::from_utf8_lossy(&message).into_owned()),
+    }
+}
+
+fn next_sink() -> u32 {
+    thread_local! {
+        static NEXT: Cell<u32> = const { Cell::new(1) };
+    }
+    NEXT.replace(NEXT.get() + 1)
+}
@@ -0,0 +1,2232 @@
+//! End-to-end reducer scenarios on a freshly initialised galaxy.
+
+use std::{f32::consts::PI, time::Duration};
+
+use solarance_shared::{
+    galaxy::{GalaxyLayout, GateEndLayout, GateLink, StationLayout},
+    pricing::MAX_STANDING_DISCOUNT,
+    TradeSide, Vec2,
+};
+use spacetimedb::Identity;
+use spacetimedsl::*;
+
+use super::TestWorld;
+use crate::{
+    admin::{
+        construction::{
+            admin_add_station_module, admin_create_construction_site,
+            admin_reset_construction_site, admin_set_module_logistics_priority,
+        },
+        creation::admin_create_sector,
+        definitions::admin_reload_definitions,
+        galaxy::admin_import_galaxy,
+        messages::admin_send_direct_server_message,
+        moderation::{admin_kick_player, admin_sanction_player, admin_set_chat_filter},
+        roles::{admin_grant_role, admin_revoke_role},
+    },
+    definitions::{
+        content::ITEMS_RON,
+        factions::{FACTION_FREE_TRADE_UNION, FACTION_LRAK_COMBINE},
+        galaxy::GALAXY_RON,
+        item_types::{
+            ITEM_ENERGY_CELL, ITEM_IRON_ORE, ITEM_METAL_PLATES, SMOD_ADVANCED_MINING_LASER,
+            SMOD_BASIC_MINING_LASER, SMOD_BASIC_SHIELD_GENERATOR, SMOD_CARGO_POD,
+        },
+        ship_types::SHIP_TYPE_COLUMN,
+        station_module_types::{MODULE_REFINERY_MINOR, MODULE_SOLAR_ARRAY_LARGE},
+    },
+    lifecycle::{client_connected::identity_connected, client_disconnected::identity_disconnected},
+    logic::{
+        chat_history::{archive_channel_messages_before, clear_chat_history, request_chat_history},
+        chat_messages::{send_direct_message, send_galaxy_chat, send_sector_chat},
+        combat::visual_effects::has_sufficient_energy_for_action,
+        factions::update_faction_standing,
+        players::registration::register_playername,
+        sectors::asteroid_fields::{
+            asteroid_radius, asteroid_sector_upkeep, extract_from_asteroid,
+            remove_exhausted_asteroid, target_population, ASTEROID_RESPAWN_DELAY,
+        },
+        ships::{
+            autopilot::{clear_autopilot, set_autopilot_target, GetAllShipAutopilotTimerRows},
+            cargo::{attempt_to_load_cargo_into_ship, jettison_cargo_from_ship},
+            creation::create_player_controlled_ship,
+            equipment::{equip_module, unequip_module},
+            mining::{
+                ore_hardness, try_mining_asteroid, GetShipMiningTimerRowsByShipSobjId,
+                MINING_TICK_SECS, MINING_VOLUME_PER_TICK,
+            },
+            station_interactions::{dock_ship, undock_ship},
+        },
+        stations::{
+            buy_and_sell::{buy_item_from_station_module, sell_item_to_station_module},
+            contribution::contribute_to_station,
+            hangar::{deposit_cargo_to_hangar, hangar_room, withdraw_cargo_from_hangar},
+            leaderboards::week_of,
+            market_orders::{cancel_market_order, place_market_order, run_market_orders},
+            module_proposals::{contribute_to_station_module, propose_station_module},
+            module_types::{
+                solar_arrays::*,
+                trading_port::{
+                    market_quote, refresh_module_prices, CreateMarketOrder, CreateMarketOrderRow,
+                    DeleteMarketOrderRowById, GetMarketOrderRowsByPlayerId,
+                    GetStationPriceSampleRowsByInventoryItemId, GetTradingPortListingRowOptionById,
+                    MarketOrderSide,
+                },
+            },
+            shipyard::{buy_ship_from_station_module, is_shipyard},
+        },
+        stellarobjects::movement::{
+            get_ship_movement_snapshot, transit_ship_to_sector, write_ship_movement_snapshot,
+            GetAllSectorCollisionTimerRows,
+        },
+    },
+    tables::{
+        admin::*, asteroids::*, economy::ResourceAmount, factions::FactionId, items::*,
+        jumpgates::*, leaderboards::*, messages::*, moderation::*, players::*, sectors::*,
+        ships::*, stations::*, welcome_back::*,
+    },
+};
+
+/// Registers player `n` with the Lrak Combine and spawns their ship.
+fn spawn_pilot(world: &TestWorld, n: u8) -> (Identity, Ship) {
+    let pilot = world.player(n);
+    let name = format!("Pilot{n}");
+    world
+        .call(pilot, |ctx| {
+            register_playername(ctx, pilot, name.clone(), FACTION_LRAK_COMBINE)?;
+            create_player_controlled_ship(ctx, pilot, name.clone())
+        })
+        .expect("pilot spawns");
+    let ship = world.with_dsl(|dsl| {
+        dsl.get_ships_by_player_id(&PlayerId::new(pilot))
+            .next()
+            .unwrap()
+    });
+    (pilot, ship)
+}
+
+/// Moves a ship to `pos` in `sector` and brings it to a stop.
+fn teleport(world: &TestWorld, ship: &Ship, sector: &SectorId, pos: Vec2) {
+    world
+        .call_as_server(|ctx| transit_ship_to_sector(&dsl(ctx), &ship.get_id(), sector, pos, 0.0))
+        .unwrap();
+}
+
+/// The ship's stacks of `item`, summed.
+fn cargo_quantity(world: &TestWorld, ship: &Ship, item: &ItemDefinitionId) -> u32 {
+    world.with_dsl(|dsl| {
+        dsl.get_ship_cargo_items_by_ship_id(&ship.get_id())
+            .filter(|stack| stack.get_item_id() == *item)
+            .map(|stack| *stack.get_quantity() as u32)
+            .sum()
+    })
+}
+
+/// Parks the ship just outside `asteroid`, facing it, and starts mining it.
+fn start_mining(world: &TestWorld, pilot: Identity, ship: &Ship, asteroid: &Asteroid) {
+    let offset = asteroid.get_size_radius() + 60.0;
+    let pos = Vec2::new(asteroid.position.x + offset, asteroid.position.y);
+    let sector = asteroid.get_current_sector_id();
+    world
+        .call_as_server(|ctx| transit_ship_to_sector(&dsl(ctx), &ship.get_id(), &sector, pos, PI))
+        .unwrap();
+    world
+        .call(pilot, |ctx| try_mining_asteroid(ctx, asteroid.get_id()))
+        .unwrap();
+}
+
+fn first_asteroid(world: &TestWorld) -> Asteroid {
+    world.with_dsl(|dsl| {
+        dsl.get_all_asteroids()
+            .next()
+            .expect("galaxy seeds asteroids")
+    })
+}
+
+#[test]
+fn mine_dock_contribute_completes_site() {
+    let world = TestWorld::with_init();
+    let (pilot, ship) = spawn_pilot(&world, 1);
+    let asteroid = first_asteroid(&world);
+    let ore = asteroid.get_resource_item_id();
+
+    // The Basic Mining Laser cuts `1 / hardness` volume per 3s tick and a unit
+    // of ore completes at its volume; the cargo transfer lands 1s later.
+    let (volume, hardness) = world.with_dsl(|dsl| {
+        let item_def = dsl.get_item_definition_by_id(&ore).unwrap();
+        (*item_def.get_volume_per_unit(), ore_hardness(&item_def))
+    });
+    start_mining(&world, pilot, &ship, &asteroid);
+    let ticks = (volume as f32 * hardness / MINING_VOLUME_PER_TICK).ceil() as u64;
+    assert_eq!(
+        world.advance(Duration::from_secs(ticks * 3 + 1)),
+        Vec::<String>::new()
+    );
+    let mined = cargo_quantity(&world, &ship, &ore);
+    assert!(mined > 0, "mining produced no ore");
+
+    // A site next to the asteroid that needs exactly what was mined.
+    let site_pos = Vec2::new(asteroid.position.x, asteroid.position.y + 2000.0);
+    world
+        .call_as_server(|ctx| {
+            admin_create_construction_site(
+                ctx,
+                asteroid.get_current_sector_id().value(),
+                "Test Site".to_string(),
+                StationSize::Outpost,
+                FACTION_LRAK_COMBINE,
+                site_pos.x,
+                site_pos.y,
+                vec![ResourceAmount {
+                    resource_item_id: ore.value(),
+                    quantity: mined,
+                }],
+            )
+        })
+        .unwrap();
+    let site = world.with_dsl(|dsl| {
+        dsl.get_all_stations()
+            .find(|s| s.get_name() == "Test Site")
+            .unwrap()
+    });
+    teleport(
+        &world,
+        &ship,
+        &site.get_sector_id(),
+        Vec2::new(site_pos.x + 150.0, site_pos.y),
+    );
+
+    let refused = world.call(pilot, |ctx| dock_ship(ctx, site.get_sobj_id().value()));
+    assert!(refused.unwrap_err().contains("under construction"));
+
+    world
+        .call(pilot, |ctx| {
+            contribute_to_station(ctx, site.get_id(), ore.clone(), mined)
+        })
+        .unwrap();
+    assert_eq!(cargo_quantity(&world, &ship, &ore), 0);
+    let finished = world.with_dsl(|dsl| {
+        dsl.get_station_under_construction_by_id(&site.get_id())
+            .unwrap()
+    });
+    assert!(*finished.get_is_operational());
+
+    world
+        .call(pilot, |ctx| dock_ship(ctx, site.get_sobj_id().value()))
+        .unwrap();
+    let docked = world.with_dsl(|dsl| dsl.get_ship_by_id(&ship.get_id()).unwrap());
+    assert_eq!(*docked.get_location(), ShipLocation::Station);
+}
+
+#[test]
+fn mining_stops_once_ship_drifts_out_of_range() {
+    let world = TestWorld::with_init();
+    let (pilot, ship) = spawn_pilot(&world, 1);
+    let asteroid = first_asteroid(&world);
+
+    start_mining(&world, pilot, &ship, &asteroid);
+    let far = Vec2::new(asteroid.position.x + 5000.0, asteroid.position.y);
+    teleport(&world, &ship, &asteroid.get_current_sector_id(), far);
+    world.advance(Duration::from_secs(4));
+
+    world.with_dsl(|dsl| {
+        assert_eq!(
+            dsl.get_ship_mining_timers_by_ship_sobj_id(&ship.get_sobj_id())
+                .count(),
+            0
+        );
+    });
+    assert_eq!(
+        cargo_quantity(&world, &ship, &asteroid.get_resource_item_id()),
+        0
+    );
+}
+
+#[test]
+fn mining_yield_follows_aim_lasers_and_energy() {
+    let world = TestWorld::with_init();
+    let (pilot, ship) = spawn_pilot(&world, 1);
+    let asteroid = first_asteroid(&world);
+    let hardness = world.with_dsl(|dsl| {
+        ore_hardness(
+            &dsl.get_item_definition_by_id(&asteroid.get_resource_item_id())
+                .unwrap(),
+        )
+    });
+    let rate = || {
+        world.with_dsl(|dsl| {
+            *dsl.get_ship_mining_timers_by_ship_sobj_id(&ship.get_sobj_id())
+                .next()
+                .unwrap()
+                .get_extraction_volume_per_sec()
+        })
+    };
+    let per_sec =
+        |multiplier: f32| MINING_VOLUME_PER_TICK * multiplier / hardness / MINING_TICK_SECS as f32;
+
+    start_mining(&world, pilot, &ship, &asteroid);
+    world.advance(Duration::from_secs(MINING_TICK_SECS));
+    assert!((rate() - per_sec(1.0)).abs() < 1e-6);
+
+    // Turned away from the asteroid, the Basic Mining Laser's cone misses.
+    let pos = world.with_dsl(|dsl| dsl.get_ship_by_id(&ship.get_id()).unwrap().movement.pos);
+    teleport(&world, &ship, &asteroid.get_current_sector_id(), pos);
+    world.advance(Duration::from_secs(MINING_TICK_SECS));
+    assert_eq!(rate(), 0.0);
+
+    // A second laser adds its own cut on top of the first.
+    let sector = asteroid.get_current_sector_id();
+    world
+        .call_as_server(|ctx| transit_ship_to_sector(&dsl(ctx), &ship.get_id(), &sector, pos, PI))
+        .unwrap();
+    world.with_dsl(|dsl| {
+        dsl.create_ship_equipment_slot(CreateShipEquipmentSlot {
+            ship_id: ship.get_id(),
+            slot_type: EquipmentSlotType::MiningLaser,
+            slot_index: 1,
+            item_id: ItemDefinitionId::new(SMOD_ADVANCED_MINING_LASER),
+        })
+        .unwrap();
+    });
+    world.advance(Duration::from_secs(MINING_TICK_SECS));
+    assert!((rate() - per_sec(1.0 + 2.5)).abs() < 1e-6);
+
+    // Short on energy, only the lasers it can still power fire.
+    world.with_dsl(|dsl| {
+        let mut status = dsl.get_ship_status_by_id(&ship.get_id()).unwrap();
+        status.energy.value = 2.0;
+        status.energy.rate = 0.0;
+        status.energy.last_update_time = dsl.ctx().timestamp.to_micros_since_unix_epoch();
+        dsl.update_ship_status_by_id(status).unwrap();
+    });
+    world.advance(Duration::from_secs(MINING_TICK_SECS));
+    assert!((rate() - per_sec(1.0)).abs() < 1e-6);
+}
+
+#[test]
+fn a_cold_mining_beam_is_reported_once() {
+    let world = TestWorld::with_init();
+    let (pilot, ship) = spawn_pilot(&world, 1);
+    let asteroid = first_asteroid(&world);
+    let notices = |text: &str| {
+        world.with_dsl(|dsl| {
+            dsl.get_direct_server_messages_by_to(&PlayerId::new(pilot))
+                .filter(|m| m.get_body().contains(text))
+                .count()
+        })
+    };
+    let timers = || {
+        world.with_dsl(|dsl| {
+            dsl.get_ship_mining_timers_by_ship_sobj_id(&ship.get_sobj_id())
+                .count()
+        })
+    };
+
+    start_mining(&world, pilot, &ship, &asteroid);
+    world.advance(Duration::from_secs(MINING_TICK_SECS));
+    assert_eq!(notices("aren't pointed"), 0);
+
+    // Turned away for several ticks: one notice, not one per tick.
+    let pos = world.with_dsl(|dsl| dsl.get_ship_by_id(&ship.get_id()).unwrap().movement.pos);
+    teleport(&world, &ship, &asteroid.get_current_sector_id(), pos);
+    world.advance(Duration::from_secs(3 * MINING_TICK_SECS));
+    assert_eq!(notices("aren't pointed"), 1);
+    assert_eq!(timers(), 1);
+
+    // With the laser pulled, mining stops instead of ticking on.
+    world.with_dsl(|dsl| {
+        for slot in dsl.get_ship_equipment_slots_by_ship_id(&ship.get_id()) {
+            if *slot.get_slot_type() == EquipmentSlotType::MiningLaser {
+                dsl.delete_ship_equipment_slot_by_id(&slot).unwrap();
+            }
+        }
+    });
+    world.advance(Duration::from_secs(MINING_TICK_SECS));
+    assert_eq!(timers(), 0);
+    assert_eq!(notices("no mining laser"), 1);
+}
+
+#[test]
+fn asteroids_shrink_fracture_and_respawn() {
+    let world = TestWorld::with_init();
+    let asteroid = first_asteroid(&world);
+    let sector = asteroid.get_current_sector_id();
+    let count = || world.with_dsl(|dsl| dsl.get_asteroids_by_current_sector_id(&sector).count());
+    let target =
+        world.with_dsl(|dsl| target_population(&dsl.get_asteroid_sector_by_id(&sector).unwrap()));
+    assert_eq!(count(), target);
+
+    // Mining it past half knocks a fragment off the side away from the miner.
+    let initial = *asteroid.get_initial_resources();
+    let miner = Vec2::new(asteroid.position.x + 100.0, asteroid.position.y);
+    let taken = initial - initial / 2 + 1;
+    world.with_dsl(|dsl| {
+        assert_eq!(
+            extract_from_asteroid(dsl, asteroid.clone(), taken, &miner).unwrap(),
+            taken
+        );
+    });
+    assert_eq!(count(), target + 1);
+    let (parent, fragment) = world.with_dsl(|dsl| {
+        let parent = dsl.get_asteroid_by_id(&asteroid.get_id()).unwrap();
+        let fragment = dsl
+            .get_asteroids_by_current_sector_id(&sector)
+            .max_by_key(|a| a.get_id().value())
+            .unwrap();
+        (parent, fragment)
+    });
+    assert_eq!(
+        parent.get_current_resources() + fragment.get_initial_resources(),
+        initial - taken
+    );
+    assert_eq!(
+        *parent.get_size_radius(),
+        asteroid_radius(*parent.get_current_resources())
+    );
+    assert!(parent.get_size_radius() < asteroid.get_size_radius());
+    assert!(fragment.position.x < parent.position.x);
+
+    // Mined out, both wait out the respawn delay; only one replacement fits.
+    world.with_dsl(|dsl| {
+        remove_exhausted_asteroid(dsl, &parent).unwrap();
+        remove_exhausted_asteroid(dsl, &fragment).unwrap();
+    });
+    assert_eq!(count(), target - 1);
+    let upkeep = || {
+        world
+            .call_as_server(|ctx| asteroid_sector_upkeep(&dsl(ctx), &sector))
+            .unwrap()
+    };
+    upkeep();
+    assert_eq!(count(), target - 1);
+
+    // Rather than sit through the delay, bring the respawns due.
+    world.with_dsl(|dsl| {
+        let respawns: Vec<_> = dsl.get_asteroid_respawns_by_sector_id(&sector).collect();
+        assert_eq!(respawns.len(), 2);
+        for respawn in respawns {
+            assert_eq!(
+                respawn.get_respawn_at().to_micros_since_unix_epoch(),
+                world.now().to_micros_since_unix_epoch()
+                    + ASTEROID_RESPAWN_DELAY.as_micros() as i64
+            );
+            dsl.delete_asteroid_respawn_by_id(&respawn.get_id())
+                .unwrap();
+            dsl.create_asteroid_respawn(CreateAsteroidRespawn {
+                sector_id: sector.clone(),
+                respawn_at: world.now(),
+            })
+            .unwrap();
+        }
+    });
+    upkeep();
+    assert_eq!(count(), target);
+
+    let report = world.with_dsl(|dsl| dsl.get_asteroid_field_report_by_id(&sector).unwrap());
+    assert_eq!(*report.get_asteroid_count() as usize, target);
+    assert_eq!(*report.get_pending_respawns(), 0);
+    assert_eq!(
+        report
+            .get_ore_mix()
+            .iter()
+            .map(|o| o.asteroids)
+            .sum::<u32>() as usize,
+        target
+    );
+}
+
+#[test]
+fn rejected_reducer_leaves_tables_untouched() {
+    let world = TestWorld::with_init();
+    let (pilot, ship) = spawn_pilot(&world, 1);
+    let stack = world.with_dsl(|dsl| {
+        dsl.get_ship_cargo_items_by_ship_id(&ship.get_id())
+            .next()
+            .unwrap()
+    });
+
+    let too_many = *stack.get_quantity() + 1;
+    let result = world.call(pilot, |ctx| {
+        jettison_cargo_from_ship(ctx, ship.get_id().value(), stack.get_id().value(), too_many)
+    });
+    assert!(result.is_err());
+
+    let after = world.with_dsl(|dsl| dsl.get_ship_cargo_item_by_id(&stack.get_id()).unwrap());
+    assert_eq!(after.get_quantity(), stack.get_quantity());
+    world.with_dsl(|dsl| assert_eq!(dsl.get_all_cargo_crates().count(), 0));
+}
+
+#[test]
+fn other_players_cannot_jettison_your_cargo() {
+    let world = TestWorld::with_init();
+    let (pilot, ship) = spawn_pilot(&world, 1);
+    let (thief, _) = spawn_pilot(&world, 2);
+    let stack = world.with_dsl(|dsl| {
+        dsl.get_ship_cargo_items_by_ship_id(&ship.get_id())
+            .next()
+            .unwrap()
+    });
+    let jettison =
+        |ctx: &_| jettison_cargo_from_ship(ctx, ship.get_id().value(), stack.get_id().value(), 1);
+
+    assert!(world.call(thief, jettison).is_err());
+    world.call(pilot, jettison).unwrap();
+    world.with_dsl(|dsl| assert_eq!(dsl.get_all_cargo_crates().count(), 1));
+}
+
+#[test]
+fn collision_sweep_runs_only_while_ships_move() {
+    let world = TestWorld::with_init();
+    let (_, mover) = spawn_pilot(&world, 1);
+    let (_, parked) = spawn_pilot(&world, 2);
+    let sector = mover.get_sector_id();
+    // Far out in the sector, clear of its asteroids and station.
+    let start = Vec2::new(-20_000.0, -20_000.0);
+    teleport(&world, &mover, &sector, start);
+    teleport(
+        &world,
+        &parked,
+        &sector,
+        Vec2::new(start.x + 400.0, start.y),
+    );
+    let sweeps = || world.with_dsl(|dsl| dsl.get_all_sector_collision_timers().count());
+    assert_eq!(sweeps(), 0);
+
+    // Coasting straight at the parked ship starts the sector's sweep...
+    world.with_dsl(|dsl| {
+        write_ship_movement_snapshot(dsl, &mover.get_id(), |state| {
+            state.rotation = 0.0;
+            state.velocity = 100.0;
+        })
+        .unwrap();
+    });
+    assert_eq!(sweeps(), 1);
+
+    // ...which stops the mover at the parked ship, then removes itself.
+    assert!(world.advance(Duration::from_secs(10)).is_empty());
+    let snapshot = world.with_dsl(|dsl| get_ship_movement_snapshot(dsl, &mover.get_id()).unwrap());
+    assert_eq!(snapshot.velocity, 0.0);
+    assert!(snapshot.pos.x > start.x && snapshot.pos.x < start.x + 400.0);
+    assert_eq!(sweeps(), 0);
+}
+
+#[test]
+fn autopilot_tick_runs_only_while_a_ship_is_engaged() {
+    let world = TestWorld::with_init();
+    let (pilot, ship) = spawn_pilot(&world, 1);
+    let start = Vec2::new(-20_000.0, -20_000.0);
+    teleport(&world, &ship, &ship.get_sector_id(), start);
+    let ticks = || world.with_dsl(|dsl| dsl.get_all_ship_autopilot_timers().count());
+    assert_eq!(ticks(), 0);
+
+    let goal = Vec2::new(start.x + 600.0, start.y);
+    world
+        .call(pilot, |ctx| {
+            set_autopilot_target(ctx, AutopilotTarget::Position(goal))
+        })
+        .unwrap();
+    assert_eq!(ticks(), 1);
+
+    assert!(world.advance(Duration::from_secs(60)).is_empty());
+    world.with_dsl(|dsl| {
+        assert_eq!(dsl.get_all_ship_autopilots().count(), 0);
+        let snapshot = get_ship_movement_snapshot(dsl, &ship.get_id()).unwrap();
+        assert!(snapshot.pos.distance_to(&goal) < 100.0);
+    });
+    assert_eq!(ticks(), 0);
+}
+
+/// Parks the ship next to the named station and docks it.
+fn dock_at(world: &TestWorld, pilot: Identity, ship: &Ship, station_name: &str) -> Station {
+    let station = world.with_dsl(|dsl| {
+        dsl.get_all_stations()
+            .find(|s| s.get_name() == station_name)
+            .unwrap()
+    });
+    let pos = Vec2::new(station.position.x + 150.0, station.position.y);
+    teleport(world, ship, &station.get_sector_id(), pos);
+    world
+        .call(pilot, |ctx| dock_ship(ctx, station.get_sobj_id().value()))
+        .unwrap();
+    station
+}
+
+#[test]
+fn hangar_round_trip_needs_a_storage_depot() {
+    let world = TestWorld::with_init();
+    let (pilot, ship) = spawn_pilot(&world, 1);
+    let stack = world.with_dsl(|dsl| {
+        dsl.get_ship_cargo_items_by_ship_id(&ship.get_id())
+            .next()
+            .unwrap()
+    });
+    let item = stack.get_item_id();
+    let carried = cargo_quantity(&world, &ship, &item);
+
+    let capital = dock_at(&world, pilot, &ship, "Lrakhold City");
+    world
+        .call(pilot, |ctx| {
+            deposit_cargo_to_hangar(ctx, ship.get_id(), item.clone(), 2)
+        })
+        .unwrap();
+    assert_eq!(cargo_quantity(&world, &ship, &item), carried - 2);
+    let stored = world.with_dsl(|dsl| {
+        dsl.get_station_hangar_items_by_player_id(&PlayerId::new(pilot))
+            .next()
+            .unwrap()
+    });
+    assert_eq!(stored.get_station_id(), capital.get_id());
+    assert_eq!(stored.quantity, 2);
+
+    let greedy = world.call(pilot, |ctx| {
+        withdraw_cargo_from_hangar(ctx, ship.get_id(), item.clone(), 3)
+    });
+    assert!(greedy.is_err());
+    world
+        .call(pilot, |ctx| {
+            withdraw_cargo_from_hangar(ctx, ship.get_id(), item.clone(), 2)
+        })
+        .unwrap();
+    assert_eq!(cargo_quantity(&world, &ship, &item), carried);
+    world.with_dsl(|dsl| assert_eq!(dsl.get_all_station_hangar_items().count(), 0));
+
+    // No depot, no hangar.
+    let docked = world.with_dsl(|dsl| dsl.get_ship_by_id(&ship.get_id()).unwrap());
+    world
+        .call(pilot, |ctx| undock_ship(ctx, docked.clone()))
+        .unwrap();
+    dock_at(&world, pilot, &ship, "Ore Trench Exchange");
+    let refused = world.call(pilot, |ctx| {
+        deposit_cargo_to_hangar(ctx, ship.get_id(), item.clone(), 1)
+    });
+    assert!(refused.unwrap_err().contains("no storage depot"));
+}
+
+#[test]
+fn a_fleet_has_at_most_one_piloted_ship() {
+    let world = TestWorld::with_init();
+    let (pilot, first) = spawn_pilot(&world, 1);
+    let capital = dock_at(&world, pilot, &first, "Lrakhold City");
+    let shipyard = world.with_dsl(|dsl| {
+        dsl.get_station_modules_by_station_id(&capital.get_id())
+            .find(|m| {
+                is_shipyard(
+                    &dsl.get_station_module_blueprint_by_id(&m.get_blueprint())
+                        .unwrap()
+                        .specific_type,
+                )
+            })
+            .unwrap()
+    });
+    let column = ShipTypeDefinitionId::new(SHIP_TYPE_COLUMN);
+
+    let broke = world.call(pilot, |ctx| {
+        buy_ship_from_station_module(ctx, shipyard.get_id(), first.get_id(), column.clone())
+    });
+    assert!(broke.unwrap_err().contains("Not enough credits"));
+
+    let price = world.with_dsl(|dsl| dsl.get_ship_type_definition_by_id(&column).unwrap().price);
+    world.with_dsl(|dsl| {
+        let mut player = dsl.get_player_by_id(&PlayerId::new(pilot)).unwrap();
+        player.credits = price.unwrap() + 100;
+        dsl.update_player_by_id(player).unwrap();
+    });
+    world
+        .call(pilot, |ctx| {
+            buy_ship_from_station_module(ctx, shipyard.get_id(), first.get_id(), column.clone())
+        })
+        .unwrap();
+    let (credits, fleet) = world.with_dsl(|dsl| {
+        let player = dsl.get_player_by_id(&PlayerId::new(pilot)).unwrap();
+        (player.credits, player.get_fleet(dsl))
+    });
+    assert_eq!(credits, 100);
+    assert_eq!(fleet.len(), 2);
+    let second = fleet[1].clone();
+    assert_eq!(*second.get_location(), ShipLocation::Station);
+    assert_eq!(second.get_station_id(), capital.get_id());
+
+    // Swap hulls: the first ship has to dock before the second can leave.
+    let first_docked = world.with_dsl(|dsl| dsl.get_ship_by_id(&first.get_id()).unwrap());
+    world
+        .call(pilot, |ctx| undock_ship(ctx, first_docked.clone()))
+        .unwrap();
+    let blocked = world.call(pilot, |ctx| undock_ship(ctx, second.clone()));
+    assert!(blocked.is_err());
+    dock_at(&world, pilot, &first, "Lrakhold City");
+    world
+        .call(pilot, |ctx| undock_ship(ctx, second.clone()))
+        .unwrap();
+
+    let piloted = world.with_dsl(|dsl| {
+        dsl.get_player_by_id(&PlayerId::new(pilot))
+            .unwrap()
+            .get_piloted_ship(dsl)
+            .map(|s| s.get_id())
+    });
+    assert_eq!(piloted, Some(second.get_id()));
+}
+
+#[test]
+fn sector_chat_follows_the_piloted_ship() {
+    let world = TestWorld::with_init();
+    let (pilot, first) = spawn_pilot(&world, 1);
+    let capital = dock_at(&world, pilot, &first, "Lrakhold City");
+    let shipyard = world.with_dsl(|dsl| {
+        dsl.get_station_modules_by_station_id(&capital.get_id())
+            .find(|m| {
+                is_shipyard(
+                    &dsl.get_station_module_blueprint_by_id(&m.get_blueprint())
+                        .unwrap()
+                        .specific_type,
+                )
+            })
+            .unwrap()
+    });
+    let column = ShipTypeDefinitionId::new(SHIP_TYPE_COLUMN);
+    let price = world.with_dsl(|dsl| dsl.get_ship_type_definition_by_id(&column).unwrap().price);
+    world.with_dsl(|dsl| {
+        let mut player = dsl.get_player_by_id(&PlayerId::new(pilot)).unwrap();
+        player.credits = price.unwrap();
+        dsl.update_player_by_id(player).unwrap();
+    });
+    world
+        .call(pilot, |ctx| {
+            buy_ship_from_station_module(ctx, shipyard.get_id(), first.get_id(), column.clone())
+        })
+        .unwrap();
+    let second = world.with_dsl(|dsl| {
+        dsl.get_player_by_id(&PlayerId::new(pilot))
+            .unwrap()
+            .get_fleet(dsl)[1]
+            .clone()
+    });
+    assert!(first.get_id().value() < second.get_id().value());
+
+    // The older, lower-id hull stays docked; the new one flies elsewhere.
+    world
+        .call(pilot, |ctx| undock_ship(ctx, second.clone()))
+        .unwrap();
+    let elsewhere = world.with_dsl(|dsl| {
+        dsl.get_all_sectors()
+            .map(|sector| sector.get_id())
+            .find(|id| *id != capital.get_sector_id())
+            .unwrap()
+    });
+    teleport(&world, &second, &elsewhere, Vec2::ZERO);
+
+    world
+        .call(pilot, |ctx| {
+            send_sector_chat(ctx, "Anyone out here?".into())
+        })
+        .unwrap();
+    let (here, docked_sector) = world.with_dsl(|dsl| {
+        (
+            dsl.get_sector_channel_messages_by_sector_id(&elsewhere)
+                .count(),
+            dsl.get_sector_channel_messages_by_sector_id(&capital.get_sector_id())
+                .count(),
+        )
+    });
+    assert_eq!((here, docked_sector), (1, 0));
+}
+
+#[test]
+fn only_the_owner_can_act_on_a_ship() {
+    let world = TestWorld::with_init();
+    let (pilot, ship) = spawn_pilot(&world, 1);
+    let (thief, _) = spawn_pilot(&world, 2);
+    let stack = world.with_dsl(|dsl| {
+        dsl.get_ship_cargo_items_by_ship_id(&ship.get_id())
+            .next()
+            .unwrap()
+    });
+    let item = stack.get_item_id();
+    let capital = dock_at(&world, pilot, &ship, "Lrakhold City");
+    let module = world.with_dsl(|dsl| {
+        dsl.get_station_modules_by_station_id(&capital.get_id())
+            .next()
+            .unwrap()
+    });
+    world
+        .call(pilot, |ctx| {
+            deposit_cargo_to_hangar(ctx, ship.get_id(), item.clone(), 1)
+        })
+        .unwrap();
+    let slot = world.with_dsl(|dsl| {
+        dsl.get_ship_equipment_slots_by_ship_id(&ship.get_id())
+            .next()
+            .unwrap()
+    });
+    let docked = world.with_dsl(|dsl| dsl.get_ship_by_id(&ship.get_id()).unwrap());
+    let column = ShipTypeDefinitionId::new(SHIP_TYPE_COLUMN);
+
+    let attempts: Vec<(&str, Result<(), String>)> = vec![
+        (
+            "buy_item_from_station_module",
+            world.call(thief, |ctx| {
+                buy_item_from_station_module(ctx, module.get_id(), ship.get_id(), item.clone(), 1)
+            }),
+        ),
+        (
+            "sell_item_to_station_module",
+            world.call(thief, |ctx| {
+                sell_item_to_station_module(ctx, module.get_id(), ship.get_id(), item.clone(), 1)
+            }),
+        ),
+        (
+            "buy_ship_from_station_module",
+            world.call(thief, |ctx| {
+                buy_ship_from_station_module(ctx, module.get_id(), ship.get_id(), column.clone())
+            }),
+        ),
+        (
+            "deposit_cargo_to_hangar",
+            world.call(thief, |ctx| {
+                deposit_cargo_to_hangar(ctx, ship.get_id(), item.clone(), 1)
+            }),
+        ),
+        (
+            "withdraw_cargo_from_hangar",
+            world.call(thief, |ctx| {
+                withdraw_cargo_from_hangar(ctx, ship.get_id(), item.clone(), 1)
+            }),
+        ),
+        (
+            "equip_module",
+            world.call(thief, |ctx| equip_module(ctx, ship.get_id(), item.clone())),
+        ),
+        (
+            "unequip_module",
+            world.call(thief, |ctx| {
+                unequip_module(ctx, ship.get_id(), slot.get_id())
+            }),
+        ),
+        (
+            "undock_ship",
+            world.call(thief, |ctx| undock_ship(ctx, docked.clone())),
+        ),
+        (
+            "jettison_cargo_from_ship",
+            world.call(thief, |ctx| {
+                jettison_cargo_from_ship(ctx, ship.get_id().value(), stack.get_id().value(), 1)
+            }),
+        ),
+    ];
+    for (reducer, result) in attempts {
+        let err = result.expect_err(reducer);
+        assert!(err.contains("owner"), "{reducer}: {err}");
+    }
+    let still_docked = world.with_dsl(|dsl| dsl.get_ship_by_id(&ship.get_id()).unwrap());
+    assert_eq!(*still_docked.get_location(), ShipLocation::Station);
+
+    // The owner still has to be in the right state: a Piloted Ship can't
+    // trade, and a Docked Ship can't jettison.
+    let jettison = world.call(pilot, |ctx| {
+        jettison_cargo_from_ship(ctx, ship.get_id().value(), stack.get_id().value(), 1)
+    });
+    assert!(jettison.unwrap_err().contains("must be undocked"));
+    world
+        .call(pilot, |ctx| undock_ship(ctx, docked.clone()))
+        .unwrap();
+    let remote = world.call(pilot, |ctx| {
+        sell_item_to_station_module(ctx, module.get_id(), ship.get_id(), item.clone(), 1)
+    });
+    assert!(remote
+        .unwrap_err()
+        .contains("must be docked at Lrakhold City"));
+}
+
+#[test]
+fn trade_prices_follow_stock_pressure_and_standing() {
+    let world = TestWorld::with_init();
+    let (pilot, ship) = spawn_pilot(&world, 1);
+    let capital = dock_at(&world, pilot, &ship, "Lrakhold City");
+    let listing = world.with_dsl(|dsl| {
+        dsl.get_station_modules_by_station_id(&capital.get_id())
+            .flat_map(|module| dsl.get_station_module_inventory_items_by_module_id(module.get_id()))
+            .find(|item| {
+                dsl.get_trading_port_listing_by_id(item.get_id()).is_ok()
+                    && item.get_quantity() + 100 <= *item.get_max_quantity()
+            })
+            .unwrap()
+    });
+    let item = listing.get_resource_item_id();
+    give_cargo(&world, &ship, item.value(), 20);
+    let credits = || {
+        world.with_dsl(|dsl| {
+            *dsl.get_player_by_id(&PlayerId::new(pilot))
+                .unwrap()
+                .get_credits()
+        })
+    };
+    let quote = || {
+        world.with_dsl(|dsl| {
+            let item = dsl
+                .get_station_module_inventory_item_by_id(&listing.get_id())
+                .unwrap();
+            let item_def = dsl
+                .get_item_definition_by_id(&item.get_resource_item_id())
+                .unwrap();
+            let now = dsl.ctx().timestamp.to_micros_since_unix_epoch();
+            market_quote(dsl, &item, &item_def, MAX_STANDING_DISCOUNT, now)
+        })
+    };
+    let sell = |quantity| {
+        world.call(pilot, |ctx| {
+            sell_item_to_station_module(
+                ctx,
+                listing.get_module_id(),
+                ship.get_id(),
+                item.clone(),
+                quantity,
+            )
+        })
+    };
+
+    // At a station of their own faction, players get the allied discount.
+    let first_quote = quote();
+    let before = credits();
+    sell(1).unwrap();
+    let first_unit = credits() - before;
+    assert_eq!(first_unit, first_quote.total(TradeSide::Sell, 1) as u64);
+    assert!(first_quote.standing_discount > 0.0);
+
+    // Selling in bulk walks the price down, and leaves pressure behind.
+    let before = credits();
+    sell(19).unwrap();
+    assert!(credits() - before < 19 * first_unit);
+    let after = quote();
+    assert!(after.pressure < 0.0);
+    assert!(after.mid_price() < first_quote.mid_price());
+
+    // Prices are sampled once per interval.
+    let module = world.with_dsl(|dsl| {
+        dsl.get_station_module_by_id(&listing.get_module_id())
+            .unwrap()
+    });
+    let samples = || {
+        world.with_dsl(|dsl| {
+            refresh_module_prices(dsl, &module).unwrap();
+            dsl.get_station_price_samples_by_inventory_item_id(&listing.get_id())
+                .count()
+        })
+    };
+    assert_eq!(samples(), 1);
+    assert_eq!(samples(), 1);
+
+    // A station whose owner is hostile to the player's faction won't trade.
+    world.with_dsl(|dsl| {
+        let mut player = dsl.get_player_by_id(&PlayerId::new(pilot)).unwrap();
+        player.faction_id = FactionId::new(FACTION_FREE_TRADE_UNION);
+        dsl.update_player_by_id(player).unwrap();
+        update_faction_standing(
+            dsl,
+            &capital.get_owner_faction_id(),
+            &FactionId::new(FACTION_FREE_TRADE_UNION),
+            -100,
+        )
+        .unwrap();
+    });
+    assert!(sell(1).unwrap_err().contains("refuses to trade"));
+}
+
+#[test]
+fn market_orders_escrow_match_and_expire() {
+    let world = TestWorld::with_init();
+    let (seller, seller_ship) = spawn_pilot(&world, 1);
+    let (buyer, buyer_ship) = spawn_pilot(&world, 2);
+    let capital = dock_at(&world, seller, &seller_ship, "Lrakhold City");
+    dock_at(&world, buyer, &buyer_ship, "Lrakhold City");
+    let (listing, base_value) = world.with_dsl(|dsl| {
+        dsl.get_station_modules_by_station_id(&capital.get_id())
+            .flat_map(|module| dsl.get_station_module_inventory_items_by_module_id(module.get_id()))
+            .filter(|item| dsl.get_trading_port_listing_by_id(item.get_id()).is_ok())
+            .map(|item| {
+                let item_def = dsl
+                    .get_item_definition_by_id(&item.get_resource_item_id())
+                    .unwrap();
+                (item, *item_def.get_base_value())
+            })
+            .find(|(item, base_value)| {
+                (1..=20).contains(base_value)
+                    && *item.get_quantity() >= 100
+                    && item.get_quantity() + 100 <= *item.get_max_quantity()
+            })
+            .unwrap()
+    });
+    let module_id = listing.get_module_id();
+    let item = listing.get_resource_item_id();
+    give_cargo(&world, &seller_ship, item.value(), 10);
+    let credits = |pilot| {
+        world.with_dsl(|dsl| {
+            *dsl.get_player_by_id(&PlayerId::new(pilot))
+                .unwrap()
+                .get_credits()
+        })
+    };
+    let in_hangar = |pilot| {
+        world.with_dsl(|dsl| {
+            dsl.get_station_hangar_items_by_player_id(&PlayerId::new(pilot))
+                .filter(|stored| stored.get_item_id() == item)
+                .map(|stored| stored.quantity)
+                .sum::<u32>()
+        })
+    };
+    let open_orders = |pilot| {
+        world.with_dsl(|dsl| {
+            dsl.get_market_orders_by_player_id(&PlayerId::new(pilot))
+                .collect::<Vec<_>>()
+        })
+    };
+    let place = |pilot, ship: &Ship, side, price, quantity| {
+        world.call(pilot, |ctx| {
+            place_market_order(
+                ctx,
+                module_id.clone(),
+                ship.get_id(),
+                item.clone(),
+                side,
+                price,
+                quantity,
+                24,
+            )
+        })
+    };
+
+    // A sell order far above the station's price rests on the book, holding
+    // the seller's cargo.
+    let ask = base_value * 10;
+    let carried = cargo_quantity(&world, &seller_ship, &item);
+    place(seller, &seller_ship, MarketOrderSide::Sell, ask, 10).unwrap();
+    assert_eq!(cargo_quantity(&world, &seller_ship, &item), carried - 10);
+    assert_eq!(open_orders(seller)[0].quantity_remaining, 10);
+
+    // A higher bid fills at the ask; the buyer gets the difference back and
+    // the goods in their hangar.
+    let (seller_before, buyer_before) = (credits(seller), credits(buyer));
+    place(buyer, &buyer_ship, MarketOrderSide::Buy, ask + 5, 4).unwrap();
+    assert_eq!(credits(buyer), buyer_before - 4 * ask as u64);
+    assert_eq!(credits(seller), seller_before + 4 * ask as u64);
+    assert_eq!(in_hangar(buyer), 4);
+    assert_eq!(open_orders(seller)[0].quantity_remaining, 6);
+    assert!(open_orders(buyer).is_empty());
+
+    // A bid under the ask but over the station's price fills from its stock.
+    let limit = base_value * 2;
+    let buyer_before = credits(buyer);
+    place(buyer, &buyer_ship, MarketOrderSide::Buy, limit, 5).unwrap();
+    assert_eq!(in_hangar(buyer), 9);
+    assert!(open_orders(buyer).is_empty());
+    let paid = buyer_before - credits(buyer);
+    assert!(paid > 0 && paid <= 5 * limit as u64);
+
+    // Only the owner can cancel, and cancelling returns the unsold cargo.
+    let order_id = open_orders(seller)[0].get_id();
+    let cancel = |pilot| world.call(pilot, |ctx| cancel_market_order(ctx, order_id.clone()));
+    assert!(cancel(buyer).is_err());
+    cancel(seller).unwrap();
+    assert_eq!(in_hangar(seller), 6);
+
+    // An unfilled bid expires on the production tick, refunding its escrow.
+    let buyer_before = credits(buyer);
+    place(buyer, &buyer_ship, MarketOrderSide::Buy, 1, 10).unwrap();
+    assert_eq!(credits(buyer), buyer_before - 10);
+    world.with_dsl(|dsl| {
+        let order = dsl
+            .get_market_orders_by_player_id(&PlayerId::new(buyer))
+            .next()
+            .unwrap();
+        dsl.delete_market_order_by_id(&order).unwrap();
+        dsl.create_market_order(CreateMarketOrder {
+            module_id: order.get_module_id(),
+            player_id: order.get_player_id(),
+            item_id: order.get_item_id(),
+            side: order.get_side().clone(),
+            price_per_unit: *order.get_price_per_unit(),
+            quantity_ordered: *order.get_quantity_ordered(),
+            quantity_remaining: order.quantity_remaining,
+            escrowed_credits: order.escrowed_credits,
+            expires_at: dsl.ctx().timestamp,
+        })
+        .unwrap();
+        let module = dsl.get_station_module_by_id(&module_id).unwrap();
+        run_market_orders(dsl, &module).unwrap();
+    });
+    assert!(open_orders(buyer).is_empty());
+    assert_eq!(credits(buyer), buyer_before);
+}
+
+#[test]
+fn a_hostile_station_closes_orders_once() {
+    let world = TestWorld::with_init();
+    let (pilot, ship) = spawn_pilot(&world, 1);
+    let capital = dock_at(&world, pilot, &ship, "Lrakhold City");
+    let listing = world.with_dsl(|dsl| {
+        dsl.get_station_modules_by_station_id(&capital.get_id())
+            .flat_map(|module| dsl.get_station_module_inventory_items_by_module_id(module.get_id()))
+            .find(|item| dsl.get_trading_port_listing_by_id(item.get_id()).is_ok())
+            .unwrap()
+    });
+    let module_id = listing.get_module_id();
+    let credits = || {
+        world.with_dsl(|dsl| {
+            *dsl.get_player_by_id(&PlayerId::new(pilot))
+                .unwrap()
+                .get_credits()
+        })
+    };
+    let before = credits();
+    world
+        .call(pilot, |ctx| {
+            place_market_order(
+                ctx,
+                module_id.clone(),
+                ship.get_id(),
+                listing.get_resource_item_id(),
+                MarketOrderSide::Buy,
+                1,
+                10,
+                24,
+            )
+        })
+        .unwrap();
+
+    world.with_dsl(|dsl| {
+        let mut player = dsl.get_player_by_id(&PlayerId::new(pilot)).unwrap();
+        player.faction_id = FactionId::new(FACTION_FREE_TRADE_UNION);
+        dsl.update_player_by_id(player).unwrap();
+        update_faction_standing(
+            dsl,
+            &capital.get_owner_faction_id(),
+            &FactionId::new(FACTION_FREE_TRADE_UNION),
+            -100,
+        )
+        .unwrap();
+        let module = dsl.get_station_module_by_id(&module_id).unwrap();
+        run_market_orders(dsl, &module).unwrap();
+        run_market_orders(dsl, &module).unwrap();
+    });
+
+    let refusals = world.with_dsl(|dsl| {
+        dsl.get_direct_server_messages_by_to(&PlayerId::new(pilot))
+            .filter(|m| m.get_body().contains("refuses to trade"))
+            .count()
+    });
+    assert_eq!(refusals, 1);
+    assert_eq!(credits(), before);
+    world.with_dsl(|dsl| {
+        assert_eq!(
+            dsl.get_market_orders_by_player_id(&PlayerId::new(pilot))
+                .count(),
+            0
+        );
+    });
+}
+
+#[test]
+fn market_orders_only_deliver_what_the_hangar_holds() {
+    let world = TestWorld::with_init();
+    let (seller, seller_ship) = spawn_pilot(&world, 1);
+    let (buyer, buyer_ship) = spawn_pilot(&world, 2);
+    let capital = dock_at(&world, seller, &seller_ship, "Lrakhold City");
+    dock_at(&world, buyer, &buyer_ship, "Lrakhold City");
+    let (listing, base_value) = world.with_dsl(|dsl| {
+        dsl.get_station_modules_by_station_id(&capital.get_id())
+            .flat_map(|module| dsl.get_station_module_inventory_items_by_module_id(module.get_id()))
+            .filter(|item| dsl.get_trading_port_listing_by_id(item.get_id()).is_ok())
+            .map(|item| {
+                let item_def = dsl
+                    .get_item_definition_by_id(&item.get_resource_item_id())
+                    .unwrap();
+                (item, *item_def.get_base_value())
+            })
+            .find(|(_, base_value)| (1..=20).contains(base_value))
+            .unwrap()
+    });
+    let module_id = listing.get_module_id();
+    let item = listing.get_resource_item_id();
+    give_cargo(&world, &seller_ship, item.value(), 10);
+    let stored = |pilot| {
+        world.with_dsl(|dsl| {
+            dsl.get_station_hangar_items_by_player_id(&PlayerId::new(pilot))
+                .find(|stored| stored.get_item_id() == item)
+        })
+    };
+    // Tops the pilot's hangar stack up (or down) until `n` more units fit.
+    let leave_room = |pilot, n: u32| {
+        world.with_dsl(|dsl| {
+            let player_id = PlayerId::new(pilot);
+            let item_def = dsl.get_item_definition_by_id(&item).unwrap();
+            let room = hangar_room(dsl, &capital.get_id(), &player_id, &item_def);
+            match stored(pilot) {
+                Some(mut stack) => {
+                    stack.quantity = stack.quantity + room - n;
+                    dsl.update_station_hangar_item_by_id(stack).unwrap();
+                }
+                None => {
+                    dsl.create_station_hangar_item(CreateStationHangarItem {
+                        station_id: capital.get_id(),
+                        player_id,
+                        item_id: item.clone(),
+                        quantity: room - n,
+                    })
+                    .unwrap();
+                }
+            }
+        })
+    };
+    let held = |pilot| stored(pilot).map_or(0, |stack| stack.quantity);
+    let open_orders = |pilot| {
+        world.with_dsl(|dsl| {
+            dsl.get_market_orders_by_player_id(&PlayerId::new(pilot))
+                .collect::<Vec<_>>()
+        })
+    };
+    let place = |pilot, ship: &Ship, side, quantity| {
+        world.call(pilot, |ctx| {
+            place_market_order(
+                ctx,
+                module_id.clone(),
+                ship.get_id(),
+                item.clone(),
+                side,
+                base_value * 10,
+                quantity,
+                24,
+            )
+        })
+    };
+
+    // A sell order must be able to come back unsold.
+    leave_room(seller, 6);
+    let refused = place(seller, &seller_ship, MarketOrderSide::Sell, 10);
+    assert!(refused.unwrap_err().contains("Not enough hangar space"));
+    place(seller, &seller_ship, MarketOrderSide::Sell, 6).unwrap();
+
+    // A buyer with room for 2 of the 3 they bid on gets 2; the rest stays bid.
+    leave_room(buyer, 2);
+    let before = held(buyer);
+    place(buyer, &buyer_ship, MarketOrderSide::Buy, 3).unwrap();
+    assert_eq!(held(buyer), before + 2);
+    assert_eq!(open_orders(buyer)[0].quantity_remaining, 1);
+    assert_eq!(open_orders(seller)[0].quantity_remaining, 4);
+
+    // Cancelling hands back what fits and keeps the rest off the book.
+    leave_room(seller, 0);
+    let order_id = open_orders(seller)[0].get_id();
+    let cancel = || world.call(seller, |ctx| cancel_market_order(ctx, order_id.clone()));
+    assert!(cancel().unwrap_err().contains("No hangar space"));
+    leave_room(seller, 1);
+    let before = held(seller);
+    cancel().unwrap();
+    assert_eq!(held(seller), before + 1);
+    assert_eq!(open_orders(seller)[0].quantity_remaining, 3);
+
+    // Once there's room, the production tick returns the rest.
+    leave_room(seller, 3);
+    let before = held(seller);
+    world.with_dsl(|dsl| {
+        let module = dsl.get_station_module_by_id(&module_id).unwrap();
+        run_market_orders(dsl, &module).unwrap();
+    });
+    assert!(open_orders(seller).is_empty());
+    assert_eq!(held(seller), before + 3);
+}
+
+/// Puts `quantity` of `item` straight into the ship's hold.
+fn give_cargo(world: &TestWorld, ship: &Ship, item: u32, quantity: u16) {
+    world
+        .call_as_server(|ctx| {
+            let dsl = dsl(ctx);
+            attempt_to_load_cargo_into_ship(
+                ctx,
+                &dsl,
+                &mut dsl.get_ship_status_by_id(&ship.get_id())?,
+                &ship.get_id(),
+                &dsl.get_item_definition_by_id(ItemDefinitionId::new(item))?,
+                quantity,
+                false,
+            )
+        })
+        .unwrap();
+}
+
+#[test]
+fn special_and_cargo_modules_share_slot_indices() {
+    let world = TestWorld::with_init();
+    let (pilot, ship) = spawn_pilot(&world, 1);
+    dock_at(&world, pilot, &ship, "Lrakhold City");
+    give_cargo(&world, &ship, SMOD_CARGO_POD, 2);
+    // Two special hardpoints, the first already taken by a Special module.
+    world.with_dsl(|dsl| {
+        let mut ship_type = dsl
+            .get_ship_type_definition_by_id(&ship.get_shiptype_id())
+            .unwrap();
+        ship_type.num_special_slots = 2;
+        dsl.update_ship_type_definition_by_id(ship_type).unwrap();
+        dsl.create_ship_equipment_slot(CreateShipEquipmentSlot {
+            ship_id: ship.get_id(),
+            slot_type: EquipmentSlotType::Special,
+            slot_index: 0,
+            item_id: ItemDefinitionId::new(SMOD_CARGO_POD),
+        })
+        .unwrap();
+    });
+    let pod = ItemDefinitionId::new(SMOD_CARGO_POD);
+    let equip = || world.call(pilot, |ctx| equip_module(ctx, ship.get_id(), pod.clone()));
+
+    equip().unwrap();
+    let index = world.with_dsl(|dsl| {
+        dsl.get_ship_equipment_slots_by_ship_id(&ship.get_id())
+            .find(|slot| *slot.get_slot_type() == EquipmentSlotType::CargoExpansion)
+            .unwrap()
+            .slot_index
+    });
+    assert_eq!(index, 1);
+    assert!(equip().unwrap_err().contains("no free"));
+}
+
+#[test]
+fn fitting_modules_updates_derived_stats() {
+    let world = TestWorld::with_init();
+    let (pilot, ship) = spawn_pilot(&world, 1);
+    let status = || world.with_dsl(|dsl| dsl.get_ship_status_by_id(&ship.get_id()).unwrap());
+    let base = status();
+    give_cargo(&world, &ship, SMOD_CARGO_POD, 1);
+    give_cargo(&world, &ship, SMOD_BASIC_SHIELD_GENERATOR, 1);
+    give_cargo(&world, &ship, SMOD_BASIC_MINING_LASER, 1);
+
+    let pod = ItemDefinitionId::new(SMOD_CARGO_POD);
+    let undocked = world.call(pilot, |ctx| equip_module(ctx, ship.get_id(), pod.clone()));
+    assert!(undocked.unwrap_err().contains("must be docked"));
+
+    dock_at(&world, pilot, &ship, "Lrakhold City");
+    world
+        .call(pilot, |ctx| equip_module(ctx, ship.get_id(), pod.clone()))
+        .unwrap();
+    world
+        .call(pilot, |ctx| {
+            equip_module(
+                ctx,
+                ship.get_id(),
+                ItemDefinitionId::new(SMOD_BASIC_SHIELD_GENERATOR),
+            )
+        })
+        .unwrap();
+    let fitted = status();
+    assert_eq!(fitted.max_cargo_capacity, base.max_cargo_capacity + 8);
+    assert_eq!(fitted.shields.cap, base.shields.cap + 50.0);
+    assert_eq!(fitted.shields.rate, base.shields.rate + 0.5);
+    assert_eq!(cargo_quantity(&world, &ship, &pod), 0);
+
+    // The Column's only mining slot already holds its starter laser.
+    let full = world.call(pilot, |ctx| {
+        equip_module(
+            ctx,
+            ship.get_id(),
+            ItemDefinitionId::new(SMOD_BASIC_MINING_LASER),
+        )
+    });
+    assert!(full.unwrap_err().contains("no free"));
+
+    let pod_slot = world.with_dsl(|dsl| {
+        dsl.get_ship_equipment_slots_by_ship_id(&ship.get_id())
+            .find(|slot| slot.get_item_id() == pod)
+            .unwrap()
+    });
+    world
+        .call(pilot, |ctx| {
+            unequip_module(ctx, ship.get_id(), pod_slot.get_id())
+        })
+        .unwrap();
+    assert_eq!(status().max_cargo_capacity, base.max_cargo_capacity);
+    assert_eq!(cargo_quantity(&world, &ship, &pod), 1);
+}
+
+#[test]
+fn shields_and_energy_regenerate_from_snapshots() {
+    let world = TestWorld::with_init();
+    let (_, ship) = spawn_pilot(&world, 1);
+    let now = || world.now().to_micros_since_unix_epoch();
+    let status = || world.with_dsl(|dsl| dsl.get_ship_status_by_id(&ship.get_id()).unwrap());
+
+    let drained_at = now();
+    world.with_dsl(|dsl| {
+        let mut drained = dsl.get_ship_status_by_id(&ship.get_id()).unwrap();
+        drained.energy.adjust(-1_000.0, drained_at);
+        drained.shields.adjust(-1_000.0, drained_at);
+        dsl.update_ship_status_by_id(drained).unwrap();
+    });
+    assert!(!status().try_spend_energy(1.0, now()));
+
+    // Nothing ticks the row; the value is worked out when it's read.
+    world.advance(Duration::from_secs(10));
+    let regenerated = status();
+    assert_eq!(regenerated.energy.value, 0.0);
+    assert_eq!(regenerated.energy.last_update_time, drained_at);
+    let energy = regenerated.current_energy(now());
+    assert!((energy - regenerated.energy.rate * 10.0).abs() < 0.001);
+    assert!((regenerated.current_shields(now()) - regenerated.shields.rate * 10.0).abs() < 0.001);
+    assert!(has_sufficient_energy_for_action(&regenerated, energy - 0.1, false, now()).is_ok());
+    assert!(has_sufficient_energy_for_action(&regenerated, energy + 0.1, false, now()).is_err());
+
+    // A long wait tops out at the cap.
+    world.advance(Duration::from_secs(3_600));
+    let full = status();
+    assert_eq!(full.current_energy(now()), full.energy.cap);
+}
+
+#[test]
+fn reloading_definitions_edits_in_place() {
+    let world = TestWorld::with_init();
+    let (_, ship) = spawn_pilot(&world, 1);
+    let cell = ItemDefinitionId::new(ITEM_ENERGY_CELL);
+    let carried = cargo_quantity(&world, &ship, &cell);
+    assert!(carried > 0, "new ships start with energy cells");
+
+    let repriced = ITEMS_RON.replacen("base_value: 20,", "base_value: 24,", 1);
+    assert_ne!(repriced, ITEMS_RON);
+    world
+        .call_as_server(|ctx| {
+            admin_reload_definitions(ctx, Some(repriced.clone()), None, None, None)
+        })
+        .unwrap();
+
+    world.with_dsl(|dsl| {
+        assert_eq!(dsl.get_item_definition_by_id(&cell).unwrap().base_value, 24);
+        assert_eq!(dsl.count_of_all_item_definitions(), 63);
+    });
+    assert_eq!(cargo_quantity(&world, &ship, &cell), carried);
+
+    let broken = ITEMS_RON.replacen("volume_per_unit: 1,", "volume_per_unit: 0,", 1);
+    let refused = world.call_as_server(|ctx| {
+        admin_reload_definitions(ctx, Some(broken.clone()), None, None, None)
+    });
+    assert!(refused.unwrap_err().contains("volume_per_unit"));
+    assert!(world
+        .call(world.player(1), |ctx| admin_reload_definitions(
+            ctx, None, None, None, None
+        ))
+        .is_err());
+}
+
+/// Sectors, stations and gates, in that order.
+fn galaxy_counts(world: &TestWorld) -> (usize, usize, usize) {
+    world.with_dsl(|dsl| {
+        (
+            dsl.get_all_sectors().count(),
+            dsl.get_all_stations().count(),
+            dsl.get_all_jump_gates().count(),
+        )
+    })
+}
+
+#[test]
+fn reimporting_the_seed_layout_changes_nothing() {
+    let world = TestWorld::with_init();
+    let before = galaxy_counts(&world);
+
+    world
+        .call_as_server(|ctx| admin_import_galaxy(ctx, GALAXY_RON.to_string()))
+        .unwrap();
+
+    assert_eq!(galaxy_counts(&world), before);
+}
+
+#[test]
+fn imported_layout_extends_the_galaxy() {
+    let world = TestWorld::with_init();
+    let (sectors, stations, gates) = galaxy_counts(&world);
+
+    let mut layout = GalaxyLayout::from_ron(GALAXY_RON).unwrap();
+    let omicron = &mut layout.star_systems[1];
+    let mut cinder = omicron.sectors[1].clone();
+    cinder.id = 12;
+    cinder.name = "Cinder Gap".to_string();
+    cinder.stations = vec![StationLayout {
+        name: "Cinder Depot".to_string(),
+        size: solarance_shared::galaxy::StationSize::Outpost,
+        owner_faction_id: FACTION_LRAK_COMBINE,
+        position: Vec2::new(100.0, 0.0),
+        rotation: 0.0,
+        capital: false,
+        modules: vec!["trading".to_string()],
+        construction_requirements: Vec::new(),
+    }];
+    omicron.sectors[1].security_level = 3;
+    omicron.sectors.push(cinder);
+    layout.jumpgates.push(GateLink::new(11, 12));
+
+    world
+        .call_as_server(|ctx| admin_import_galaxy(ctx, layout.to_ron()?))
+        .unwrap();
+
+    assert_eq!(
+        galaxy_counts(&world),
+        (sectors + 1, stations + 1, gates + 2)
+    );
+    world.with_dsl(|dsl| {
+        let ashfall = dsl.get_sector_by_id(&SectorId::new(11)).unwrap();
+        assert_eq!(ashfall.security_level, 3);
+        let depot = dsl
+            .get_stations_by_sector_id(&SectorId::new(12))
+            .next()
+            .unwrap();
+        assert_eq!(
+            dsl.get_station_modules_by_station_id(&depot.get_id())
+                .count(),
+            1
+        );
+    });
+
+    // Re-importing places and switches off a gate that already exists.
+    let placed = Vec2::new(-4_000.0, 1_000.0);
+    layout.jumpgates.last_mut().unwrap().a_gate = Some(GateEndLayout {
+        position: placed,
+        is_active: false,
+    });
+    world
+        .call_as_server(|ctx| admin_import_galaxy(ctx, layout.to_ron()?))
+        .unwrap();
+    world.with_dsl(|dsl| {
+        let leading_to = |from: u64, to: u64| {
+            dsl.get_jump_gates_by_current_sector_id(&SectorId::new(from))
+                .find(|gate| gate.get_target_sector_id().value() == to)
+                .unwrap()
+        };
+        let gate = leading_to(11, 12);
+        assert_eq!(*gate.get_position(), placed);
+        assert!(!*gate.get_is_active());
+        assert_eq!(*leading_to(12, 11).get_target_gate_arrival_pos(), placed);
+    });
+
+    layout.jumpgates.push(GateLink::new(12, 99));
+    let refused = world.call_as_server(|ctx| admin_import_galaxy(ctx, layout.to_ron()?));
+    assert!(refused.unwrap_err().contains("unknown sector 99"));
+}
+
+#[test]
+fn admin_roles_gate_privileged_reducers() {
+    let world = TestWorld::with_init();
+    let (pilot, _) = spawn_pilot(&world, 1);
+    let moderator = world.player(2);
+    let owner = TestWorld::server();
+    let send_notice = |sender: Identity| {
+        world.call(sender, |ctx| {
+            admin_send_direct_server_message(ctx, pilot, MessageSeverity::Info, "Hi".into())
+        })
+    };
+    let create_sector = |sender: Identity| {
+        world.call(sender, |ctx| {
+            admin_create_sector(ctx, 0, "Nowhere".into(), 0, 1, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0)
+        })
+    };
+
+    world.with_dsl(|dsl| {
+        let seeded = dsl.get_admin_role_by_id(AdminRoleId::new(owner)).unwrap();
+        assert_eq!(seeded.role, AdminRoleKind::Owner);
+    });
+    assert!(send_notice(moderator).is_err());
+
+    world
+        .call(owner, |ctx| {
+            admin_grant_role(ctx, moderator, AdminRoleKind::Moderator)
+        })
+        .unwrap();
+    send_notice(moderator).unwrap();
+    assert!(create_sector(moderator).is_err());
+    assert!(world
+        .call(moderator, |ctx| admin_grant_role(
+            ctx,
+            moderator,
+            AdminRoleKind::Admin
+        ))
+        .is_err());
+    assert!(world
+        .call(owner, |ctx| admin_revoke_role(ctx, owner))
+        .is_err());
+
+    world.with_dsl(|dsl| {
+        let calls: Vec<AdminAuditLog> = dsl.get_admin_audit_logs_by_caller(&moderator).collect();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].get_reducer(), "admin_send_direct_server_message");
+        assert_eq!(*calls[0].get_role(), Some(AdminRoleKind::Moderator));
+    });
+
+    world
+        .call(owner, |ctx| admin_revoke_role(ctx, moderator))
+        .unwrap();
+    assert!(send_notice(moderator).is_err());
+}
+
+#[test]
+fn moderators_mute_kick_and_filter_chat() {
+    let world = TestWorld::with_init();
+    let (pilot, _) = spawn_pilot(&world, 1);
+    let moderator = world.player(2);
+    world
+        .call_as_server(|ctx| admin_grant_role(ctx, moderator, AdminRoleKind::Moderator))
+        .unwrap();
+    let chat = |body: &str| world.call(pilot, |ctx| send_galaxy_chat(ctx, body.to_string()));
+    let last_body = || {
+        world.with_dsl(|dsl| {
+            dsl.get_all_galaxy_channel_messages()
+                .max_by_key(|m| m.get_id().value())
+                .unwrap()
+                .get_body()
+                .clone()
+        })
+    };
+
+    assert!(world
+        .call(pilot, |ctx| admin_kick_player(ctx, moderator, "no".into()))
+        .is_err());
+    world
+        .call(moderator, |ctx| {
+            admin_set_chat_filter(ctx, vec!["frak".into()], 3, 10, 100, true)
+        })
+        .unwrap();
+
+    chat("well FRAK me").unwrap();
+    assert_eq!(last_body(), "well **** me");
+    assert!(chat("well FRAK me").unwrap_err().contains("just sent"));
+    chat("two").unwrap();
+    chat("three").unwrap();
+    assert!(chat("four").unwrap_err().contains("too quickly"));
+    world.advance(Duration::from_secs(10));
+    chat("four").unwrap();
+
+    world
+        .call(moderator, |ctx| {
+            admin_sanction_player(ctx, pilot, SanctionKind::Mute, Some(60), "spam".into())
+        })
+        .unwrap();
+    assert!(chat("five").unwrap_err().contains("Mute"));
+    world.advance(Duration::from_secs(61));
+    chat("five").unwrap();
+    world.advance(Duration::from_secs(60));
+    world.with_dsl(|dsl| assert_eq!(dsl.get_all_player_sanctions().count(), 0));
+
+    world
+        .call(moderator, |ctx| {
+            admin_kick_player(ctx, pilot, "cool off".into())
+        })
+        .unwrap();
+    assert!(world
+        .call(pilot, identity_connected)
+        .unwrap_err()
+        .contains("cool off"));
+    world.advance(Duration::from_secs(5 * 60));
+    world.call(pilot, identity_connected).unwrap();
+}
+
+#[test]
+fn a_banned_session_cannot_keep_playing() {
+    let world = TestWorld::with_init();
+    let (pilot, ship) = spawn_pilot(&world, 1);
+    let stack = world.with_dsl(|dsl| {
+        dsl.get_ship_cargo_items_by_ship_id(&ship.get_id())
+            .next()
+            .unwrap()
+    });
+    let jettison =
+        |ctx: &_| jettison_cargo_from_ship(ctx, ship.get_id().value(), stack.get_id().value(), 1);
+
+    world
+        .call_as_server(|ctx| admin_kick_player(ctx, pilot, "cool off".into()))
+        .unwrap();
+    // Still connected: both ship-id and sender-derived reducers refuse.
+    assert!(world
+        .call(pilot, jettison)
+        .unwrap_err()
+        .contains("cool off"));
+    assert!(world
+        .call(pilot, clear_autopilot)
+        .unwrap_err()
+        .contains("cool off"));
+    assert!(world
+        .call(pilot, clear_chat_history)
+        .unwrap_err()
+        .contains("cool off"));
+
+    world.advance(Duration::from_secs(5 * 60));
+    world.call(pilot, jettison).unwrap();
+}
+
+#[test]
+fn direct_messages_share_the_channel_length_cap() {
+    let world = TestWorld::with_init();
+    let (pilot, _) = spawn_pilot(&world, 1);
+    spawn_pilot(&world, 2);
+    world
+        .call_as_server(|ctx| admin_set_chat_filter(ctx, Vec::new(), 5, 10, 10, true))
+        .unwrap();
+    let dm = |body: &str| {
+        world.call(pilot, |ctx| {
+            send_direct_message(ctx, "Pilot2".into(), body.to_string())
+        })
+    };
+
+    assert!(dm("far too long for the cap")
+        .unwrap_err()
+        .contains("at most 10 characters"));
+    dm("short one").unwrap();
+    world.with_dsl(|dsl| assert_eq!(dsl.get_all_direct_messages().count(), 1));
+}
+
+#[test]
+fn old_chat_is_archived_and_paged_back() {
+    let world = TestWorld::with_init();
+    let (pilot, _) = spawn_pilot(&world, 1);
+    for body in ["one", "two", "three"] {
+        world
+            .call(pilot, |ctx| send_galaxy_chat(ctx, body.to_string()))
+            .unwrap();
+    }
+    // The ship announcement plus three messages.
+    let newest = world.with_dsl(|dsl| {
+        assert_eq!(dsl.get_all_galaxy_channel_messages().count(), 4);
+        dsl.get_all_galaxy_channel_messages()
+            .map(|m| m.get_id().value())
+            .max()
+            .unwrap()
+    });
+
+    let archived = world
+        .call_as_server(|ctx| {
+            archive_channel_messages_before(&dsl(ctx), world.now() + Duration::from_secs(1))
+        })
+        .unwrap();
+    assert!(archived >= 4);
+    world.with_dsl(|dsl| assert_eq!(dsl.get_all_galaxy_channel_messages().count(), 0));
+
+    // Paging back from the newest message skips it.
+    world
+        .call(pilot, |ctx| {
+            request_chat_history(ctx, ChatChannel::Galaxy, Some(newest))
+        })
+        .unwrap();
+    let bodies = |world: &TestWorld| {
+        world.with_dsl(|dsl| {
+            dsl.get_chat_history_messages_by_owner(&pilot)
+                .map(|m| m.get_body().clone())
+                .collect::<Vec<_>>()
+        })
+    };
+    let page = bodies(&world);
+    assert_eq!(page.len(), 3);
+    assert!(page.contains(&"two".to_string()));
+    assert!(!page.contains(&"three".to_string()));
+
+    // Asking again only adds what the caller doesn't hold yet.
+    for before_id in [Some(newest), None] {
+        world
+            .call(pilot, |ctx| {
+                request_chat_history(ctx, ChatChannel::Galaxy, before_id)
+            })
+            .unwrap();
+    }
+    let page = bodies(&world);
+    assert_eq!(page.len(), 4);
+    assert!(page.contains(&"three".to_string()));
+
+    // Strangers can't read the archive, and pages end with the session.
+    assert!(world
+        .call(world.player(9), |ctx| {
+            request_chat_history(ctx, ChatChannel::Galaxy, None)
+        })
+        .is_err());
+    world.call(pilot, identity_disconnected).unwrap();
+    assert!(bodies(&world).is_empty());
+}
+
+#[test]
+fn welcome_back_diffs_what_changed_while_away() {
+    let world = TestWorld::with_init();
+    let (pilot, ship) = spawn_pilot(&world, 1);
+    world.call(pilot, identity_connected).unwrap();
+    world.call(pilot, identity_disconnected).unwrap();
+
+    // While the pilot is away: a site is founded and finished, half of it
+    // from the pilot's own (earlier) contributions, and a sector changes hands.
+    world
+        .call_as_server(|ctx| {
+            admin_create_construction_site(
+                ctx,
+                ship.get_sector_id().value(),
+                "Away Site".to_string(),
+                StationSize::Outpost,
+                FACTION_LRAK_COMBINE,
+                0.0,
+                0.0,
+                vec![ResourceAmount {
+                    resource_item_id: ITEM_ENERGY_CELL,
+                    quantity: 10,
+                }],
+            )
+        })
+        .unwrap();
+    world.advance(Duration::from_secs(2 * 60));
+    let sector_id = ship.get_sector_id();
+    world.with_dsl(|dsl| {
+        let site = dsl
+            .get_all_stations()
+            .find(|s| s.get_name() == "Away Site")
+            .unwrap();
+        dsl.create_construction_contribution_log(CreateConstructionContributionLog {
+            station_id: site.get_id(),
+            player_id: PlayerId::new(pilot),
+            item_id: ItemDefinitionId::new(ITEM_ENERGY_CELL),
+            quantity: 5,
+            stage: 0,
+            module_proposal_id: None,
+            contributed_at: dsl.ctx().timestamp,
+        })
+        .unwrap();
+        let mut uc = dsl
+            .get_station_under_construction_by_id(&site.get_id())
+            .unwrap();
+        uc.construction_progress_percentage = 100.0;
+        uc.is_operational = true;
+        dsl.update_station_under_construction_by_id(uc).unwrap();
+        let mut sector = dsl.get_sector_by_id(&sector_id).unwrap();
+        sector.controlling_faction_id = FACTION_FREE_TRADE_UNION;
+        dsl.update_sector_by_id(sector).unwrap();
+    });
+
+    world.call(pilot, identity_connected).unwrap();
+    let payload = world.with_dsl(|dsl| {
+        dsl.get_welcome_back_payload_by_id(&PlayerId::new(pilot))
+            .unwrap()
+    });
+    assert_eq!(payload.away_secs, 2 * 60);
+    assert_eq!(payload.offline_ticks, 4);
+
+    let site = payload
+        .site_changes
+        .iter()
+        .find(|c| c.station_name == "Away Site")
+        .unwrap();
+    assert_eq!(site.from_percentage, None);
+    assert!(site.came_online);
+    assert_eq!(site.your_share_percentage, 50.0);
+
+    assert_eq!(payload.control_changes.len(), 1);
+    assert_eq!(payload.control_changes[0].sector_id, sector_id.value());
+    assert_eq!(payload.top_contributors.len(), 1);
+    assert!(payload.top_contributors[0].is_you);
+}
+
+#[test]
+fn contributions_rank_on_every_leaderboard() {
+    let world = TestWorld::with_init();
+    let (first, first_ship) = spawn_pilot(&world, 1);
+    let (second, second_ship) = spawn_pilot(&world, 2);
+    let site_pos = Vec2::new(5000.0, 5000.0);
+    world
+        .call_as_server(|ctx| {
+            admin_create_construction_site(
+                ctx,
+                first_ship.get_sector_id().value(),
+                "Ranked Site".to_string(),
+                StationSize::Outpost,
+                FACTION_FREE_TRADE_UNION,
+                site_pos.x,
+                site_pos.y,
+                vec![ResourceAmount {
+                    resource_item_id: ITEM_ENERGY_CELL,
+                    quantity: 20,
+                }],
+            )
+        })
+        .unwrap();
+    let site = world.with_dsl(|dsl| {
+        dsl.get_all_stations()
+            .find(|s| s.get_name() == "Ranked Site")
+            .unwrap()
+    });
+
+    let cells = ItemDefinitionId::new(ITEM_ENERGY_CELL);
+    for (pilot, ship, quantity) in [(first, &first_ship, 3), (second, &second_ship, 6)] {
+        give_cargo(&world, ship, ITEM_ENERGY_CELL, quantity);
+        teleport(
+            &world,
+            ship,
+            &site.get_sector_id(),
+            Vec2::new(site_pos.x + 150.0, site_pos.y),
+        );
+        world
+            .call(pilot, |ctx| {
+                contribute_to_station(ctx, site.get_id(), cells.clone(), quantity as u32)
+            })
+            .unwrap();
+    }
+
+    let base_value = world.with_dsl(|dsl| {
+        *dsl.get_item_definition_by_id(&cells)
+            .unwrap()
+            .get_base_value() as u64
+    });
+    let this_week = week_of(world.now());
+    for (board, scope_id) in [
+        (ContributionBoard::Station, site.get_id().value()),
+        (ContributionBoard::Faction, FACTION_FREE_TRADE_UNION as u64),
+        (ContributionBoard::Weekly, this_week),
+        (ContributionBoard::AllTime, 0),
+    ] {
+        let tallies: Vec<ContributionTally> = world.with_dsl(|dsl| {
+            dsl.get_contribution_tallies_by_board(&board)
+                .filter(|t| *t.get_scope_id() == scope_id)
+                .collect()
+        });
+        let ranked = rank_tallies(&tallies, LEADERBOARD_SIZE);
+        assert_eq!(ranked.len(), 2, "{:?}", board);
+        assert_eq!(ranked[0].0, 1);
+        assert_eq!(ranked[0].1.get_player_id().value(), second);
+        assert_eq!(ranked[0].1.value, 6 * base_value);
+        assert_eq!(ranked[1].1.get_player_id().value(), first);
+        assert_eq!(ranked[1].1.quantity, 3);
+    }
+
+    // Resetting the site wipes its log, and the tallies follow.
+    world
+        .call_as_server(|ctx| admin_reset_construction_site(ctx, site.get_id().value()))
+        .unwrap();
+    let remaining = world.with_dsl(|dsl| dsl.get_all_contribution_tallies().count());
+    assert_eq!(remaining, 0);
+}
+
+#[test]
+fn finished_sites_grow_through_station_sizes() {
+    let world = TestWorld::with_init();
+    let (pilot, ship) = spawn_pilot(&world, 1);
+    let site_pos = Vec2::new(5000.0, -5000.0);
+    world
+        .call_as_server(|ctx| {
+            admin_create_construction_site(
+                ctx,
+                ship.get_sector_id().value(),
+                "Growing Site".to_string(),
+                StationSize::Outpost,
+                FACTION_LRAK_COMBINE,
+                site_pos.x,
+                site_pos.y,
+                vec![ResourceAmount {
+                    resource_item_id: ITEM_ENERGY_CELL,
+                    quantity: 5,
+                }],
+            )
+        })
+        .unwrap();
+    let site = world.with_dsl(|dsl| {
+        dsl.get_all_stations()
+            .find(|s| s.get_name() == "Growing Site")
+            .unwrap()
+    });
+    teleport(
+        &world,
+        &ship,
+        &site.get_sector_id(),
+        Vec2::new(site_pos.x + 150.0, site_pos.y),
+    );
+
+    // Fills every requirement of the open pool, a cargo hold at a time.
+    let fill_open_pool = || {
+        let stage = world.with_dsl(|dsl| {
+            *dsl.get_station_under_construction_by_id(&site.get_id())
+                .unwrap()
+                .get_stage()
+        });
+        let requirements: Vec<ConstructionRequirement> = world.with_dsl(|dsl| {
+            dsl.get_construction_requirements_by_station_id(&site.get_id())
+                .filter(|r| r.stage == stage)
+                .collect()
+        });
+        for req in requirements {
+            let item = req.get_resource_item_id();
+            let mut remaining = *req.get_quantity_required();
+            while remaining > 0 {
+                let batch = remaining.min(10);
+                give_cargo(&world, &ship, item.value(), batch as u16);
+                world
+                    .call(pilot, |ctx| {
+                        contribute_to_station(ctx, site.get_id(), item.clone(), batch)
+                    })
+                    .unwrap();
+                remaining -= batch;
+            }
+        }
+    };
+    let site_state = || {
+        world.with_dsl(|dsl| {
+            let station = dsl.get_station_by_id(&site.get_id()).unwrap();
+            let uc = dsl
+                .get_station_under_construction_by_id(&site.get_id())
+                .unwrap();
+            (
+                station.get_size().clone(),
+                *uc.get_stage(),
+                uc.get_growing_to().clone(),
+            )
+        })
+    };
+
+    // The initial build opens the first growth pool.
+    fill_open_pool();
+    assert_eq!(
+        site_state(),
+        (StationSize::Outpost, 1, Some(StationSize::Small))
+    );
+
+    // Filling it grows the station and opens the next one, from scratch.
+    fill_open_pool();
+    assert_eq!(
+        site_state(),
+        (StationSize::Small, 2, Some(StationSize::Medium))
+    );
+    let progress = world.with_dsl(|dsl| {
+        *dsl.get_station_under_construction_by_id(&site.get_id())
+            .unwrap()
+            .get_construction_progress_percentage()
+    });
+    assert_eq!(progress, 0.0);
+
+    fill_open_pool();
+    fill_open_pool();
+    assert_eq!(site_state(), (StationSize::Large, 3, None));
+    let cells = ItemDefinitionId::new(ITEM_ENERGY_CELL);
+    give_cargo(&world, &ship, ITEM_ENERGY_CELL, 1);
+    let refused = world.call(pilot, |ctx| {
+        contribute_to_station(ctx, site.get_id(), cells.clone(), 1)
+    });
+    assert!(refused.unwrap_err().contains("fully grown"));
+}
+
+#[test]
+fn proposed_modules_are_built_from_their_pool() {
+    let world = TestWorld::with_init();
+    let (pilot, ship) = spawn_pilot(&world, 1);
+    // A cheap bill, so one hold fills the pool.
+    world.with_dsl(|dsl| {
+        let mut blueprint = dsl
+            .get_station_module_blueprint_by_id(StationModuleBlueprintId::new(
+                MODULE_SOLAR_ARRAY_LARGE,
+            ))
+            .unwrap();
+        blueprint.build_cost_resources = vec![ResourceAmount::new(ITEM_ENERGY_CELL, 6)];
+        dsl.update_station_module_blueprint_by_id(blueprint)
+            .unwrap();
+    });
+    let station = dock_at(&world, pilot, &ship, "Ore Trench Exchange");
+    let propose = |blueprint: u32| {
+        world.call(pilot, |ctx| {
+            propose_station_module(ctx, ship.get_id(), StationModuleBlueprintId::new(blueprint))
+        })
+    };
+    let module_count = || {
+        world.with_dsl(|dsl| {
+            dsl.get_station_modules_by_station_id(&station.get_id())
+                .count()
+        })
+    };
+    let modules_before = module_count();
+
+    assert!(propose(MODULE_REFINERY_MINOR)
+        .unwrap_err()
+        .contains("can't be proposed"));
+    propose(MODULE_SOLAR_ARRAY_LARGE).unwrap();
+    assert!(propose(MODULE_SOLAR_ARRAY_LARGE)
+        .unwrap_err()
+        .contains("already building"));
+
+    let cells = ItemDefinitionId::new(ITEM_ENERGY_CELL);
+    let carried = cargo_quantity(&world, &ship, &cells);
+    give_cargo(&world, &ship, ITEM_ENERGY_CELL, 10);
+    world
+        .call(pilot, |ctx| {
+            contribute_to_station_module(ctx, ship.get_id(), cells.clone(), 4)
+        })
+        .unwrap();
+    assert_eq!(module_count(), modules_before);
+
+    // Over-contributing is capped at what the pool still needs.
+    world
+        .call(pilot, |ctx| {
+            contribute_to_station_module(ctx, ship.get_id(), cells.clone(), 10)
+        })
+        .unwrap();
+    assert_eq!(cargo_quantity(&world, &ship, &cells), carried + 4);
+    assert_eq!(module_count(), modules_before + 1);
+    let open = world.with_dsl(|dsl| dsl.get_all_station_modules_under_construction().count());
+    assert_eq!(open, 0);
+
+    // The station's production schedule picks the new module up.
+    world.advance(Duration::from_secs(10 * 60));
+    let produced: u32 = world.with_dsl(|dsl| {
+        let array = dsl
+            .get_station_modules_by_station_id(&station.get_id())
+            .find(|m| m.get_blueprint().value() == MODULE_SOLAR_ARRAY_LARGE)
+            .unwrap();
+        dsl.get_station_module_inventory_items_by_module_id(&array.get_id())
+            .map(|item| item.quantity)
+            .sum()
+    });
+    assert!(produced > 0);
+
+    // A faction the owner dislikes can't propose there.
+    let (outsider, outsider_ship) = spawn_pilot(&world, 2);
+    dock_at(&world, outsider, &outsider_ship, "Ore Trench Exchange");
+    world.with_dsl(|dsl| {
+        let mut player = dsl.get_player_by_id(&PlayerId::new(outsider)).unwrap();
+        player.faction_id = FactionId::new(FACTION_FREE_TRADE_UNION);
+        dsl.update_player_by_id(player).unwrap();
+    });
+    let refused = world.call(outsider, |ctx| {
+        propose_station_module(
+            ctx,
+            outsider_ship.get_id(),
+            StationModuleBlueprintId::new(MODULE_SOLAR_ARRAY_LARGE),
+        )
+    });
+    assert!(refused.unwrap_err().contains("won't let your faction"));
+}
+
+#[test]
+fn brownouts_idle_modules_the_grid_cant_carry() {
+    let world = TestWorld::with_init();
+    // An Outpost whose core reactor exactly carries its three refineries.
+    let station = world.with_dsl(|dsl| {
+        dsl.get_all_stations()
+            .find(|s| s.get_name() == "Tarol's Rest & Refinery Stop")
+            .unwrap()
+    });
+    let grid =
+        || world.with_dsl(|dsl| dsl.get_station_power_grid_by_id(&station.get_id()).unwrap());
+    let battery = || {
+        world.with_dsl(|dsl| {
+            *dsl.get_station_status_by_id(&station.get_id())
+                .unwrap()
+                .get_energy()
+        })
+    };
+    let set_battery = |energy: f32| {
+        world.with_dsl(|dsl| {
+            let mut status = dsl.get_station_status_by_id(&station.get_id()).unwrap();
+            status.set_energy(energy);
+            dsl.update_station_status_by_id(status).unwrap();
+        })
+    };
+
+    world.advance(Duration::from_secs(30));
+    assert!(grid().get_idled_module_ids().is_empty());
+    assert_eq!(grid().generation_mw, grid().demand_mw);
+    assert!((battery() - StationSize::Outpost.battery_capacity_mwh()).abs() < 0.01);
+
+    // Shrunk to a Satellite with a flat battery, nothing can run at full draw
+    // and the idle surplus trickles into the battery.
+    world.with_dsl(|dsl| {
+        let mut shrunk = dsl.get_station_by_id(&station.get_id()).unwrap();
+        shrunk.size = StationSize::Satellite;
+        dsl.update_station_by_id(shrunk).unwrap();
+    });
+    set_battery(0.0);
+    world.advance(Duration::from_secs(30));
+    let mut refineries: Vec<u64> = world.with_dsl(|dsl| {
+        dsl.get_station_modules_by_station_id(&station.get_id())
+            .map(|m| m.get_id().value())
+            .collect()
+    });
+    refineries.sort();
+    assert_eq!(grid().get_idled_module_ids(), &refineries);
+    assert!(battery() > 0.0);
+
+    // A full battery covers the deficit, but the Satellite only has the CPU
+    // for one refinery.
+    set_battery(StationSize::Satellite.battery_capacity_mwh());
+    world.advance(Duration::from_secs(30));
+    assert_eq!(grid().get_idled_module_ids(), &refineries[1..]);
+    assert!(battery() < StationSize::Satellite.battery_capacity_mwh());
+}
+
+#[test]
+fn logistics_chain_refined_ore_into_metal_plates() {
+    let world = TestWorld::with_init();
+    // Ore Trench Exchange's bazaar is the logistics module.
+    let station = world.with_dsl(|dsl| {
+        dsl.get_all_stations()
+            .find(|s| s.get_name() == "Ore Trench Exchange")
+            .unwrap()
+    });
+    for key in ["iron_refinery", "metal_plate_factory", "solar_array"] {
+        world
+            .call_as_server(|ctx| {
+                admin_add_station_module(ctx, station.get_id().value(), key.to_string())
+            })
+            .unwrap();
+    }
+    let slots = |item_id: u32| {
+        world.with_dsl(|dsl| {
+            dsl.get_station_modules_by_station_id(&station.get_id())
+                .flat_map(|m| {
+                    dsl.get_station_module_inventory_items_by_module_id(m.get_id())
+                        .collect::<Vec<_>>()
+                })
+                .filter(|slot| slot.resource_item_id == item_id)
+                .filter(|slot| !slot.get_storage_purpose_tag().ends_with(";trading"))
+                .collect::<Vec<_>>()
+        })
+    };
+    let mut ore = slots(ITEM_IRON_ORE).pop().unwrap();
+    let refinery_id = ore.get_module_id().value();
+    ore.set_quantity(50);
+    world.with_dsl(|dsl| {
+        dsl.update_station_module_inventory_item_by_id(ore).unwrap();
+        // A small array makes under one whole cell a tick; boost it.
+        let array = dsl
+            .get_station_modules_by_station_id(&station.get_id())
+            .find(|m| dsl.get_solar_array_module_by_id(&m.get_id()).is_ok())
+            .unwrap();
+        let mut solar = dsl.get_solar_array_module_by_id(&array.get_id()).unwrap();
+        solar.set_current_efficiency_modifier(8.0);
+        dsl.update_solar_array_module_by_id(solar).unwrap();
+    });
+    let factory = world.with_dsl(|dsl| {
+        dsl.get_station_modules_by_station_id(&station.get_id())
+            .find(|m| m.station_slot_identifier == "metal_plate_factory")
+            .unwrap()
+    });
+
+    world.advance(Duration::from_secs(5 * 60));
+    // The factory only ever sees ingots and cells through logistics.
+    let plates: u32 = slots(ITEM_METAL_PLATES).iter().map(|s| s.quantity).sum();
+    assert!(plates > 0, "no metal plates were made");
+
+    let report = world.with_dsl(|dsl| {
+        dsl.get_station_production_report_by_id(&station.get_id())
+            .unwrap()
+    });
+    let refinery = report
+        .modules
+        .iter()
+        .find(|m| m.module_id == refinery_id)
+        .unwrap();
+    // Ten ingots' worth of ore went in, and nothing is left for an eleventh.
+    assert_eq!(
+        refinery.bottleneck,
+        Some(ProductionBottleneck::StarvedOfInputs)
+    );
+
+    // Logistics priorities are configurable per module.
+    world
+        .call_as_server(|ctx| {
+            admin_set_module_logistics_priority(ctx, factory.get_id().value(), 200)
+        })
+        .unwrap();
+    let priority = world.with_dsl(|dsl| {
+        *dsl.get_module_logistics_priority_by_id(&factory.get_id())
+            .unwrap()
+            .get_priority()
+    });
+    assert_eq!(priority, 200);
+}
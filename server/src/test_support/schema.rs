@@ -0,0 +1,314 @@
+//! The table list the fake host serves.
+//!
+//! Natively there is no `__describe_module__` pass, so the host can't discover
+//! tables on its own: every table the module declares must be listed in
+//! `build` below. A reducer touching an unlisted table fails its test with a
+//! message naming the table. Scheduled tables are listed with `timer` so
+//! `TestWorld::advance` knows which reducer to fire.
+
+use std::sync::OnceLock;
+
+use spacetimedb::{
+    sats::{bsatn, AlgebraicType, ProductType},
+    table::{IndexAlgo, TableInternal},
+    ReducerContext, Table,
+};
+
+use crate::{
+    logic::{
+        cargo_crates::{cargo_crate_despawn_sweeper, cargo_crate_despawn_sweeper_timer},
+        chat_history::{channel_message_archive_sweeper, channel_message_archive_timer},
+        combat::visual_effects::{cleanup_visual_effect, visual_effect_timer},
+        factions::{
+            faction_management_timer, faction_management_timer_reducer,
+            faction_station_check_timer, faction_station_check_timer_reducer,
+        },
+        moderation::{player_sanction_expiry_sweeper, player_sanction_expiry_timer},
+        sectors::{sector_upkeep, sector_upkeep_timer},
+        ships::{
+            add_cargo_timer::{ship_add_cargo_timer, ship_add_cargo_timer_reducer},
+            autopilot::{ship_autopilot_timer, ship_autopilot_timer_reducer},
+            mining::{ship_mining_timer, ship_mining_timer_reducer},
+        },
+        stations::{
+            leaderboards::{contribution_tally_sweeper, contribution_tally_timer},
+            module_types::{
+                manufacturing::{manufacturing_module, production_recipe_definition},
+                refineries::refinery_module,
+                solar_arrays::solar_array_module,
+                trading_port::{
+                    market_order, station_price_sample, trading_port_listing, trading_port_module,
+                },
+            },
+            production::{station_production_schedule, station_production_schedule_reducer},
+            status::{station_status_schedule, station_status_schedule_reducer},
+        },
+        stellarobjects::movement::{sector_collision_timer, sector_collision_timer_reducer},
+    },
+    tables::{
+        admin::{admin_audit_log, admin_role},
+        asteroids::{asteroid, asteroid_respawn},
+        combat::visual_effect,
+        factions::{faction, faction_standing},
+        global_config::global_config,
+        items::{cargo_crate, item_definition},
+        jumpgates::jump_gate,
+        leaderboards::contribution_tally,
+        messages::{
+            archived_channel_message, chat_history_message, direct_message, direct_server_message,
+            faction_channel_message, galaxy_channel_message, player_chat_block,
+            sector_channel_message, server_channel_message, star_system_channel_message,
+        },
+        moderation::{chat_filter_config, chat_rate_limit, player_sanction},
+        players::player,
+        sectors::{asteroid_field_report, asteroid_sector, sector, sector_nebula},
+        ships::{
+            ship, ship_autopilot, ship_cargo_item, ship_equipment_slot, ship_movement_controller,
+            ship_status, ship_type_definition,
+        },
+        star_system::{star_system, star_system_object},
+        stations::{
+            construction_contribution_log, construction_requirement, module_logistics_priority,
+            station, station_hangar_item, station_module, station_module_blueprint,
+            station_module_inventory_item, station_module_under_construction, station_power_grid,
+            station_production_report, station_status, station_under_construction,
+        },
+        stellarobjects::stellar_object,
+        welcome_back::{welcome_back_payload, welcome_back_snapshot},
+    },
+};
+
+/// Runs a scheduled reducer against a BSATN-encoded timer row.
+pub type TimerFn = Box<dyn Fn(&ReducerContext, &[u8]) -> Result<(), String> + Send + Sync>;
+
+pub struct TableSchema {
+    pub name: &'static str,
+    pub row_type: ProductType,
+    pub unique_columns: Vec<usize>,
+    pub sequences: Vec<usize>,
+    /// Column holding the `ScheduleAt`, for scheduled tables.
+    pub scheduled_at: Option<usize>,
+    pub timer: Option<TimerFn>,
+}
+
+impl TableSchema {
+    pub fn column_type(&self, col: usize) -> &AlgebraicType {
+        &self.row_type.elements[col].algebraic_type
+    }
+}
+
+pub struct IndexSchema {
+    pub name: &'static str,
+    pub table: usize,
+    pub columns: Vec<usize>,
+}
+
+pub struct Schema {
+    pub tables: Vec<TableSchema>,
+    pub indexes: Vec<IndexSchema>,
+}
+
+impl Schema {
+    pub fn table_by_name(&self, name: &str) -> Option<usize> {
+        self.tables.iter().position(|t| t.name == name)
+    }
+
+    pub fn index_by_name(&self, name: &str) -> Option<usize> {
+        self.indexes.iter().position(|i| i.name == name)
+    }
+
+    /// An index is unique when it covers exactly one unique column.
+    pub fn index_is_unique(&self, index: &IndexSchema) -> bool {
+        match index.columns.as_slice() {
+            [col] => self.tables[index.table].unique_columns.contains(col),
+            _ => false,
+        }
+    }
+}
+
+pub fn schema() -> &'static Schema {
+    static SCHEMA: OnceLock<Schema> = OnceLock::new();
+    SCHEMA.get_or_init(build)
+}
+
+//////////////////////////////////////////////////////////////
+// Table list
+//////////////////////////////////////////////////////////////
+
+fn build() -> Schema {
+    let ctx = ReducerContext::__dummy();
+    let db = &ctx.db;
+
+    let tables = vec![
+        // tables/
+        table(db.admin_role()),
+        table(db.admin_audit_log()),
+        table(db.asteroid()),
+        table(db.asteroid_respawn()),
+        table(db.visual_effect()),
+        table(db.faction()),
+        table(db.faction_standing()),
+        table(db.global_config()),
+        table(db.item_definition()),
+        table(db.cargo_crate()),
+        table(db.jump_gate()),
+        table(db.contribution_tally()),
+        table(db.server_channel_message()),
+        table(db.galaxy_channel_message()),
+        table(db.star_system_channel_message()),
+        table(db.sector_channel_message()),
+        table(db.faction_channel_message()),
+        table(db.direct_server_message()),
+        table(db.direct_message()),
+        table(db.player_chat_block()),
+        table(db.archived_channel_message()),
+        table(db.chat_history_message()),
+        table(db.player_sanction()),
+        table(db.chat_filter_config()),
+        table(db.chat_rate_limit()),
+        table(db.player()),
+        table(db.sector()),
+        table(db.asteroid_sector()),
+        table(db.asteroid_field_report()),
+        table(db.sector_nebula()),
+        table(db.ship_type_definition()),
+        table(db.ship_status()),
+        table(db.ship_movement_controller()),
+        table(db.ship_autopilot()),
+        table(db.ship()),
+        table(db.ship_cargo_item()),
+        table(db.ship_equipment_slot()),
+        table(db.star_system()),
+        table(db.star_system_object()),
+        table(db.station_module_blueprint()),
+        table(db.station_module()),
+        table(db.station_under_construction()),
+        table(db.construction_requirement()),
+        table(db.construction_contribution_log()),
+        table(db.station_module_under_construction()),
+        table(db.station_module_inventory_item()),
+        table(db.station_hangar_item()),
+        table(db.station()),
+        table(db.station_status()),
+        table(db.station_power_grid()),
+        table(db.station_production_report()),
+        table(db.module_logistics_priority()),
+        table(db.stellar_object()),
+        table(db.welcome_back_snapshot()),
+        table(db.welcome_back_payload()),
+        // logic/stations/module_types/
+        table(db.trading_port_module()),
+        table(db.trading_port_listing()),
+        table(db.station_price_sample()),
+        table(db.market_order()),
+        table(db.solar_array_module()),
+        table(db.production_recipe_definition()),
+        table(db.manufacturing_module()),
+        table(db.refinery_module()),
+        // Scheduled tables
+        timer(
+            db.cargo_crate_despawn_sweeper_timer(),
+            cargo_crate_despawn_sweeper,
+        ),
+        timer(
+            db.channel_message_archive_timer(),
+            channel_message_archive_sweeper,
+        ),
+        timer(db.visual_effect_timer(), cleanup_visual_effect),
+        timer(db.contribution_tally_timer(), contribution_tally_sweeper),
+        timer(
+            db.faction_station_check_timer(),
+            faction_station_check_timer_reducer,
+        ),
+        timer(
+            db.faction_management_timer(),
+            faction_management_timer_reducer,
+        ),
+        timer(
+            db.player_sanction_expiry_timer(),
+            player_sanction_expiry_sweeper,
+        ),
+        timer(db.sector_upkeep_timer(), sector_upkeep),
+        timer(db.ship_add_cargo_timer(), ship_add_cargo_timer_reducer),
+        timer(db.ship_autopilot_timer(), ship_autopilot_timer_reducer),
+        timer(db.ship_mining_timer(), ship_mining_timer_reducer),
+        timer(db.station_production_schedule(), |ctx, row| {
+            station_production_schedule_reducer(ctx, row);
+            Ok(())
+        }),
+        timer(db.station_status_schedule(), |ctx, row| {
+            station_status_schedule_reducer(ctx, row);
+            Ok(())
+        }),
+        timer(db.sector_collision_timer(), sector_collision_timer_reducer),
+    ];
+
+    let mut schema = Schema {
+        tables: Vec::new(),
+        indexes: Vec::new(),
+    };
+    for (table, indexes) in tables {
+        let id = schema.tables.len();
+        schema.tables.push(table);
+        schema
+            .indexes
+            .extend(indexes.into_iter().map(|(name, columns)| IndexSchema {
+                name,
+                table: id,
+                columns,
+            }));
+    }
+    schema
+}
+
+type Entry = (TableSchema, Vec<(&'static str, Vec<usize>)>);
+
+fn table<H: Table>(_handle: &H) -> Entry {
+    let AlgebraicType::Product(row_type) =
+        spacetimedb::spacetimedb_lib::resolved_type_via_v9::<H::Row>()
+    else {
+        panic!("table `{}` has a non-product row type", H::TABLE_NAME);
+    };
+
+    let mut unique_columns: Vec<usize> = H::UNIQUE_COLUMNS.iter().map(|&c| c as usize).collect();
+    if let Some(pk) = H::PRIMARY_KEY {
+        if !unique_columns.contains(&(pk as usize)) {
+            unique_columns.push(pk as usize);
+        }
+    }
+
+    let indexes = H::INDEXES
+        .iter()
+        .map(|index| {
+            let columns = match index.algo {
+                IndexAlgo::BTree { columns } | IndexAlgo::Hash { columns } => {
+                    columns.iter().map(|&c| c as usize).collect()
+                }
+                IndexAlgo::Direct { column } => vec![column as usize],
+            };
+            (index.source_name, columns)
+        })
+        .collect();
+
+    let schema = TableSchema {
+        name: H::TABLE_NAME,
+        row_type,
+        unique_columns,
+        sequences: H::SEQUENCES.iter().map(|&c| c as usize).collect(),
+        scheduled_at: <H as TableInternal>::SCHEDULE.map(|s| s.scheduled_at_column as usize),
+        timer: None,
+    };
+    (schema, indexes)
+}
+
+fn timer<H: Table>(
+    handle: &H,
+    reducer: fn(&ReducerContext, H::Row) -> Result<(), String>,
+) -> Entry {
+    let (mut schema, indexes) = table(handle);
+    schema.timer = Some(Box::new(move |ctx, row| {
+        let row = bsatn::from_slice(row).map_err(|e| e.to_string())?;
+        reducer(ctx, row)
+    }));
+    (schema, indexes)
+}
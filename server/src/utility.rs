@@ -1,60 +1,164 @@
 use log::{warn, info};
 use spacetimedsl::*;
 
-use crate::{ships::*, stellarobjects::*};
+use crate::{
+    logic::moderation::refuse_banned,
+    ships::*,
+    stellarobjects::*,
+    tables::{
+        admin::{write_admin_audit_log, AdminPermission, AdminRoleId, *},
+        messages::send_direct_server_warning,
+        stations::*,
+    },
+};
 
 const IS_SERVER_ERROR: &str = "This reducer can only be called by SpacetimeDB!";
+const IS_NOT_PERMITTED_ERROR: &str = "Your admin role doesn't allow this!";
 const IS_SERVER_OR_OWNER_ERROR: &str =
     "This reducer can only be called by SpacetimeDB or the owner!";
 
+/// Which of the two Ship states (ADR-0002) a reducer needs the ship to be in.
+pub enum ShipAccess {
+    /// Either state.
+    Any,
+    /// In a Sector, with a `StellarObject`.
+    Piloted,
+    /// Docked at any Station.
+    Docked,
+    /// Docked at this Station, e.g. the one a station module belongs to.
+    DockedAt(StationId),
+}
+
 // For helper reducers that utilize several different tables
 //
 
 /// Checks if the context sender is the server. ONLY for spacetimedb reducer functions!
+///
+/// The module calls its own scheduled reducers as its own identity, so the
+/// server is whoever sends as `ctx.identity()`.
 pub fn try_server_only<T: spacetimedsl::WriteContext>(dsl: &DSL<T>) -> Result<(), String> {
-    let sender = dsl.ctx().sender()?.to_string();
+    let sender = dsl.ctx().sender()?;
     info!("Sender: {}", sender);
-    if sender.contains("c2009ba0980240569a0be51")
-        || sender.contains("c20029638c4f24cb63494c49b28b533e")
-        || sender.contains("c200bd933b6c70cefa975a42ae0b")
-        || sender.contains("c2001b668b8b961618fb1271998d5be0789eff815e5e82b69cd146ef0370be66")
-    {
+    if sender == dsl.ctx().module_identity()? {
         return Ok(());
     }
 
-    warn!(
-        "Deined server request from: {}",
-        dsl.ctx().sender()?.to_string()
-    );
+    warn!("Deined server request from: {}", sender);
 
     Err(IS_SERVER_ERROR.to_string())
 }
 
-/// Checks if the context sender is the server or the owner of the given stellar object. ONLY for spacetimedb reducer functions!
-pub fn is_server_or_sobj_owner<T: spacetimedsl::WriteContext>(
+/// Checks if the context sender is the server, or holds an `AdminRole` that
+/// grants `permission`, and writes an `AdminAuditLog` row for the call.
+/// Denied calls are only logged: the reducer's `Err` rolls back any row.
+pub fn require_admin_permission<T: spacetimedsl::WriteContext>(
     dsl: &DSL<T>,
-    stellar_object_id: Option<StellarObjectId>,
+    permission: AdminPermission,
+    reducer: &str,
+    details: String,
 ) -> Result<(), String> {
-    let sobj_id = stellar_object_id.ok_or_else(|| "Given a missing SOBJ ID".to_string())?;
-
-    // Post-Phase 9: the `sobj_player_window` table is gone — find the owning
-    // ship by `sobj_id` and check its player_id against the sender.
     let sender = dsl.ctx().sender()?;
-    if let Some(ship) = dsl.get_ships_by_sobj_id(&sobj_id).next() {
-        if ship.get_player_id().value() == sender {
-            return Ok(());
-        }
+    let role = match dsl.get_admin_role_by_id(AdminRoleId::new(sender)) {
+        Ok(admin_role) => Some(admin_role.role),
+        Err(_) => None,
+    };
+
+    let allowed = match &role {
+        Some(role) => role.grants(&permission),
+        None => sender == dsl.ctx().module_identity()?,
+    };
+    if !allowed {
+        warn!(
+            "Denied {} ({:?}) for {} with role {:?}",
+            reducer, permission, sender, role
+        );
+        return Err(match role {
+            Some(_) => IS_NOT_PERMITTED_ERROR.to_string(),
+            None => IS_SERVER_ERROR.to_string(),
+        });
     }
 
-    warn!("Denied server/sobj-owner request from: {}", sender.to_string());
-    Err(IS_SERVER_OR_OWNER_ERROR.to_string())
+    write_admin_audit_log(dsl, sender, role, reducer, details)
 }
 
-/// Checks if the context sender is the server or the owner of the given Ship.
+/// Checks that the context sender is the server or the owner of the given
+/// Ship, and that the ship is in the state `access` asks for. Returns the
+/// ship. Every reducer that acts on a ship by id goes through this, directly
+/// or via `is_server_or_sobj_owner`.
+///
+/// A banned or foreign sender gets a bare error. The owner, having passed
+/// those checks, also gets a Direct Server Message when their ship is in the
+/// wrong state.
 pub fn is_server_or_ship_owner<T: spacetimedsl::WriteContext>(
-    _dsl: &DSL<T>,
-    _ship_id: Option<ShipId>,
-) -> Result<(), String> {
-    // For now, always allow - this needs proper server identity check
-    return Ok(());
+    dsl: &DSL<T>,
+    ship_id: &ShipId,
+    access: ShipAccess,
+) -> Result<Ship, String> {
+    let sender = dsl.ctx().sender()?;
+    refuse_banned(dsl, sender)?;
+    let ship = dsl
+        .get_ship_by_id(ship_id)
+        .map_err(|_| format!("Ship #{} doesn't exist.", ship_id.value()))?;
+    if ship.get_player_id().value() != sender && sender != dsl.ctx().module_identity()? {
+        warn!(
+            "Denied request on ship #{} from non-owner {}",
+            ship_id.value(),
+            sender
+        );
+        return Err(IS_SERVER_OR_OWNER_ERROR.to_string());
+    }
+
+    let location = ship.get_location();
+    let problem = match access {
+        ShipAccess::Any => None,
+        ShipAccess::Piloted if *location != ShipLocation::Sector => Some(format!(
+            "Ship #{} must be undocked to do that.",
+            ship_id.value()
+        )),
+        ShipAccess::Docked if *location != ShipLocation::Station => Some(format!(
+            "Ship #{} must be docked to do that.",
+            ship_id.value()
+        )),
+        ShipAccess::DockedAt(station_id)
+            if *location != ShipLocation::Station || ship.get_station_id() != station_id =>
+        {
+            let station_name = dsl
+                .get_station_by_id(&station_id)
+                .map(|station| station.get_name().clone())
+                .unwrap_or_else(|_| format!("station #{}", station_id.value()));
+            Some(format!(
+                "Ship #{} must be docked at {} to do that.",
+                ship_id.value(),
+                station_name
+            ))
+        }
+        _ => None,
+    };
+    if let Some(msg) = problem {
+        let _ = send_direct_server_warning(dsl, &ship.get_player_id(), msg.clone());
+        return Err(msg);
+    }
+    Ok(ship)
+}
+
+/// `is_server_or_ship_owner` for the Piloted Ship behind a stellar object.
+/// Docked Ships share the `0` sobj sentinel, so it never matches one.
+pub fn is_server_or_sobj_owner<T: spacetimedsl::WriteContext>(
+    dsl: &DSL<T>,
+    stellar_object_id: &StellarObjectId,
+) -> Result<Ship, String> {
+    let ship = match stellar_object_id.value() {
+        0 => None,
+        _ => dsl.get_ships_by_sobj_id(stellar_object_id).next(),
+    };
+    match ship {
+        Some(ship) => is_server_or_ship_owner(dsl, &ship.get_id(), ShipAccess::Piloted),
+        None => {
+            warn!(
+                "Denied request on sobj #{}: not a Piloted Ship",
+                stellar_object_id.value()
+            );
+            Err(IS_SERVER_OR_OWNER_ERROR.to_string())
+        }
+    }
 }
@@ -0,0 +1,380 @@
+//! # Galaxy Layout Files
+//!
+//! A galaxy layout describes the hand-authored parts of the galaxy — star
+//! systems, their sectors, asteroid fields, nebulae, seeded stations and the
+//! jumpgate network — as one RON document. The server seeds the galaxy from
+//! `server/content/galaxy.ron` and `admin_import_galaxy` applies a layout to
+//! a live database; the Galaxy Creator exports the current galaxy in the same
+//! format so a designer can iterate on it and check it in.
+//!
+//! ## Identity
+//! Sectors carry their database id, since ships, cargo and messages refer to
+//! them by id. Everything else is matched by name: star systems globally,
+//! stations within their sector.
+//!
+//! ## Versioning
+//! Every file starts with `version`. `from_ron` rejects any version other than
+//! `GALAXY_LAYOUT_VERSION`; bump it whenever a change would make an older file
+//! mean something different, and convert the checked-in layouts.
+//!
+//! Item, faction and module ids are not checked here — only the server knows
+//! which exist, and it reports bad references when the layout is applied.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::physics::Vec2;
+
+/// The format version this build reads and writes.
+pub const GALAXY_LAYOUT_VERSION: u32 = 1;
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct GalaxyLayout {
+    pub version: u32,
+    pub star_systems: Vec<StarSystemLayout>,
+    /// Two-way gate links between sectors, by sector id.
+    #[serde(default)]
+    pub jumpgates: Vec<GateLink>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct StarSystemLayout {
+    pub name: String,
+    pub map_coordinates: Vec2,
+    pub spectral: SpectralKind,
+    /// 0, Hypergiants ... 5, Main sequence (Sol) ... 7, White dwarfs
+    pub luminosity: u8,
+    pub controlling_faction_id: u32,
+    /// Stars, planets and belts drawn on the system map.
+    #[serde(default)]
+    pub objects: Vec<CelestialLayout>,
+    #[serde(default)]
+    pub sectors: Vec<SectorLayout>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpectralKind {
+    O,
+    B,
+    A,
+    F,
+    G,
+    K,
+    M,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CelestialLayout {
+    pub kind: CelestialKind,
+    pub orbit_au: f32,
+    /// Either the rotation in the orbit in radians, or the kilometers wide
+    /// for asteroid/nebula belts.
+    pub rotation_or_width_km: f32,
+    #[serde(default)]
+    pub gfx_key: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CelestialKind {
+    Star,
+    Planet,
+    Moon,
+    AsteroidBelt,
+    NebulaBelt,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SectorLayout {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub controlling_faction_id: u32,
+    /// 0 (lawless) to 10 (heavily policed)
+    pub security_level: u8,
+    /// Sector potentials, each 0.0 to 1.0.
+    pub sunlight: f32,
+    pub anomalous: f32,
+    pub nebula: f32,
+    pub rare_ore: f32,
+    /// Position on the star system map.
+    pub position: Vec2,
+    #[serde(default)]
+    pub background_gfx_key: Option<String>,
+    #[serde(default)]
+    pub asteroid_field: Option<AsteroidFieldLayout>,
+    #[serde(default)]
+    pub nebulae: Vec<NebulaLayout>,
+    #[serde(default)]
+    pub stations: Vec<StationLayout>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AsteroidFieldLayout {
+    /// Relative amount of asteroids to keep in the field.
+    pub sparseness: u8,
+    /// Skews the global ore distribution toward rare ores. Only used when
+    /// `ore_weights` is empty.
+    pub rarity: u8,
+    /// How far from 0,0 asteroids can spawn.
+    pub cluster_extent: f32,
+    /// How far from 0,0 asteroids can NOT spawn.
+    #[serde(default)]
+    pub cluster_inner: Option<f32>,
+    #[serde(default)]
+    pub ore_weights: Vec<OreWeightLayout>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct OreWeightLayout {
+    pub item_id: u32,
+    pub weight: u16,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NebulaLayout {
+    pub position: Vec2,
+    pub gfx_key: String,
+    pub scale: f32,
+    pub rotation_radians: f32,
+    /// Packed 0xRRGGBBAA tint.
+    pub tint: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct StationLayout {
+    pub name: String,
+    pub size: StationSize,
+    pub owner_faction_id: u32,
+    pub position: Vec2,
+    #[serde(default)]
+    pub rotation: f32,
+    /// Makes this station its owner faction's capital — the spawn anchor for
+    /// the faction's new players.
+    #[serde(default)]
+    pub capital: bool,
+    /// Module keys, as understood by `admin_place_station`.
+    #[serde(default)]
+    pub modules: Vec<String>,
+    /// Non-empty for a station that starts as a construction site. Such a
+    /// station has no modules.
+    #[serde(default)]
+    pub construction_requirements: Vec<RequirementLayout>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StationSize {
+    Capital,
+    Large,
+    Medium,
+    Small,
+    Outpost,
+    Satellite,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RequirementLayout {
+    pub item_id: u32,
+    pub quantity: u32,
+}
+
+/// A pair of gates, one in each sector, each leading to the other. An end
+/// without a `GateEndLayout` gets an active gate placed by where the sectors
+/// sit on the system map.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct GateLink {
+    pub a: u64,
+    pub b: u64,
+    /// The gate in sector `a`, leading to `b`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub a_gate: Option<GateEndLayout>,
+    /// The gate in sector `b`, leading to `a`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub b_gate: Option<GateEndLayout>,
+}
+
+/// Where one end of a `GateLink` sits and whether ships may use it.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct GateEndLayout {
+    pub position: Vec2,
+    #[serde(default = "gate_is_active_by_default")]
+    pub is_active: bool,
+}
+
+fn gate_is_active_by_default() -> bool {
+    true
+}
+
+impl GateLink {
+    /// A link with both gates placed from the sector positions.
+    pub fn new(a: u64, b: u64) -> Self {
+        Self {
+            a,
+            b,
+            a_gate: None,
+            b_gate: None,
+        }
+    }
+
+    /// The same link regardless of which end is `a`.
+    pub fn normalized(&self) -> (u64, u64) {
+        (self.a.min(self.b), self.a.max(self.b))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+impl GalaxyLayout {
+    /// Parses and validates a layout file.
+    pub fn from_ron(text: &str) -> Result<Self, String> {
+        let layout: GalaxyLayout =
+            ron::from_str(text).map_err(|e| format!("galaxy layout: {e}"))?;
+        layout.validate()?;
+        Ok(layout)
+    }
+
+    /// Serializes the layout in the checked-in file style.
+    pub fn to_ron(&self) -> Result<String, String> {
+        let config = ron::ser::PrettyConfig::new()
+            .struct_names(false)
+            .indentor("    ".to_string());
+        ron::ser::to_string_pretty(self, config).map_err(|e| format!("galaxy layout: {e}"))
+    }
+
+    pub fn sectors(&self) -> impl Iterator<Item = &SectorLayout> {
+        self.star_systems.iter().flat_map(|system| &system.sectors)
+    }
+
+    /// Checks the layout's internal consistency. The error lists every
+    /// problem found, one per line.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut problems = Vec::new();
+
+        if self.version != GALAXY_LAYOUT_VERSION {
+            problems.push(format!(
+                "version {} is not supported (expected {})",
+                self.version, GALAXY_LAYOUT_VERSION
+            ));
+        }
+
+        let mut system_names = HashSet::new();
+        for system in &self.star_systems {
+            if system.name.trim().is_empty() || !system_names.insert(system.name.as_str()) {
+                problems.push(format!(
+                    "star system {:?}: name must be non-empty and unique",
+                    system.name
+                ));
+            }
+        }
+
+        let mut sector_ids = HashSet::new();
+        for sector in self.sectors() {
+            let at = format!("sector {} ({:?})", sector.id, sector.name);
+            if !sector_ids.insert(sector.id) {
+                problems.push(format!("{at}: duplicate id"));
+            }
+            validate_sector(sector, &at, &mut problems);
+        }
+
+        let mut links = HashSet::new();
+        for link in &self.jumpgates {
+            let at = format!("jumpgate {} <-> {}", link.a, link.b);
+            if link.a == link.b {
+                problems.push(format!("{at}: links a sector to itself"));
+            }
+            for end in [link.a, link.b] {
+                if !sector_ids.contains(&end) {
+                    problems.push(format!("{at}: unknown sector {end}"));
+                }
+            }
+            if !links.insert(link.normalized()) {
+                problems.push(format!("{at}: duplicate link"));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} problem(s) in galaxy layout:\n{}",
+                problems.len(),
+                problems.join("\n")
+            ))
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Internals
+// ---------------------------------------------------------------------------
+
+fn validate_sector(sector: &SectorLayout, at: &str, problems: &mut Vec<String>) {
+    if sector.name.trim().is_empty() {
+        problems.push(format!("{at}: name must not be empty"));
+    }
+    if sector.security_level > 10 {
+        problems.push(format!("{at}: security_level over 10"));
+    }
+    let potentials = [
+        sector.sunlight,
+        sector.anomalous,
+        sector.nebula,
+        sector.rare_ore,
+    ];
+    if potentials.iter().any(|p| !(0.0..=1.0).contains(p)) {
+        problems.push(format!("{at}: potentials must be within 0.0..=1.0"));
+    }
+
+    if let Some(field) = &sector.asteroid_field {
+        let inner = field.cluster_inner.unwrap_or(0.0);
+        if !(field.cluster_extent > 0.0 && (0.0..field.cluster_extent).contains(&inner)) {
+            problems.push(format!(
+                "{at}: asteroid field needs 0 <= cluster_inner < cluster_extent"
+            ));
+        }
+        if !field.ore_weights.is_empty() && field.ore_weights.iter().all(|w| w.weight == 0) {
+            problems.push(format!("{at}: asteroid field ore weights are all zero"));
+        }
+    }
+
+    let mut station_names = HashSet::new();
+    for station in &sector.stations {
+        let at = format!("{at}, station {:?}", station.name);
+        if station.name.trim().is_empty() || !station_names.insert(station.name.as_str()) {
+            problems.push(format!(
+                "{at}: name must be non-empty and unique in the sector"
+            ));
+        }
+        if !station.construction_requirements.is_empty() && !station.modules.is_empty() {
+            problems.push(format!("{at}: construction sites start without modules"));
+        }
+        if station
+            .construction_requirements
+            .iter()
+            .any(|r| r.quantity == 0)
+        {
+            problems.push(format!("{at}: construction requirement with zero quantity"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
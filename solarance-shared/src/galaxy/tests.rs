@@ -0,0 +1,119 @@
+use super::*;
+
+const TWO_SECTORS: &str = r#"(
+    version: 1,
+    star_systems: [(
+        name: "Procyon",
+        map_coordinates: (x: 13.0, y: 37.0),
+        spectral: G,
+        luminosity: 5,
+        controlling_faction_id: 1,
+        sectors: [
+            (
+                id: 0,
+                name: "Tarol's Belt",
+                controlling_faction_id: 0,
+                security_level: 5,
+                sunlight: 0.9, anomalous: 0.1, nebula: 0.1, rare_ore: 0.2,
+                position: (x: 4.0, y: -30.0),
+                asteroid_field: Some((sparseness: 2, rarity: 25, cluster_extent: 3000.0)),
+                stations: [(
+                    name: "Rest Stop",
+                    size: Outpost,
+                    owner_faction_id: 0,
+                    position: (x: 0.0, y: 0.0),
+                    modules: ["iron_refinery"],
+                )],
+            ),
+            (
+                id: 1,
+                name: "Ore Trench",
+                controlling_faction_id: 1,
+                security_level: 6,
+                sunlight: 0.8, anomalous: 0.0, nebula: 0.1, rare_ore: 0.6,
+                position: (x: 75.0, y: -20.0),
+            ),
+        ],
+    )],
+    jumpgates: [(a: 0, b: 1)],
+)"#;
+
+#[test]
+fn parses_a_minimal_layout() {
+    let layout = GalaxyLayout::from_ron(TWO_SECTORS).unwrap();
+    assert_eq!(layout.sectors().count(), 2);
+    assert_eq!(layout.jumpgates, vec![GateLink::new(0, 1)]);
+    let station = &layout.star_systems[0].sectors[0].stations[0];
+    assert_eq!(station.size, StationSize::Outpost);
+    assert!(!station.capital);
+}
+
+#[test]
+fn round_trips_through_ron() {
+    let layout = GalaxyLayout::from_ron(TWO_SECTORS).unwrap();
+    let again = GalaxyLayout::from_ron(&layout.to_ron().unwrap()).unwrap();
+    assert_eq!(again.to_ron().unwrap(), layout.to_ron().unwrap());
+}
+
+#[test]
+fn gate_ends_round_trip() {
+    let placed = TWO_SECTORS.replacen(
+        "(a: 0, b: 1)",
+        "(a: 0, b: 1, a_gate: Some((position: (x: 10.0, y: 20.0), is_active: false)), \
+         b_gate: Some((position: (x: -5.0, y: 0.0))))",
+        1,
+    );
+    let layout = GalaxyLayout::from_ron(&placed).unwrap();
+    let link = layout.jumpgates[0];
+    assert_eq!(
+        link.a_gate,
+        Some(GateEndLayout {
+            position: Vec2::new(10.0, 20.0),
+            is_active: false,
+        })
+    );
+    assert!(link.b_gate.unwrap().is_active);
+
+    let again = GalaxyLayout::from_ron(&layout.to_ron().unwrap()).unwrap();
+    assert_eq!(again.jumpgates, layout.jumpgates);
+}
+
+#[test]
+fn rejects_other_versions() {
+    let future = TWO_SECTORS.replacen("version: 1", "version: 2", 1);
+    let err = GalaxyLayout::from_ron(&future).unwrap_err();
+    assert!(err.contains("version 2 is not supported"), "{err}");
+}
+
+#[test]
+fn reports_every_problem_at_once() {
+    let broken = TWO_SECTORS
+        .replacen(
+            "(a: 0, b: 1)",
+            "(a: 0, b: 1), (a: 1, b: 0), (a: 1, b: 7)",
+            1,
+        )
+        .replacen("    id: 1,", "    id: 0,", 1);
+    let err = GalaxyLayout::from_ron(&broken).unwrap_err();
+    assert!(
+        err.contains("sector 0 (\"Ore Trench\"): duplicate id"),
+        "{err}"
+    );
+    assert!(err.contains("unknown sector 7"), "{err}");
+    assert!(err.contains("duplicate link"), "{err}");
+}
+
+#[test]
+fn construction_sites_have_no_modules() {
+    let site = TWO_SECTORS.replacen(
+        r#"modules: ["iron_refinery"],"#,
+        r#"modules: ["iron_refinery"],
+                    construction_requirements: [(item_id: 2001, quantity: 10)],"#,
+        1,
+    );
+    let err = GalaxyLayout::from_ron(&site).unwrap_err();
+    assert!(
+        err.contains("construction sites start without modules"),
+        "{err}"
+    );
+}
@@ -1,3 +1,14 @@
+pub mod galaxy;
 pub mod physics;
+pub mod pricing;
+pub mod regen;
+pub mod routing;
 
-pub use physics::{predict_movement, MovementState, Vec2};
+pub use galaxy::GalaxyLayout;
+pub use physics::{
+    collision::{first_impact, resolve_impact, time_of_impact, Collider, Impact},
+    predict_movement, MovementState, Vec2,
+};
+pub use pricing::{decayed_pressure, reputation_discount, MarketQuote, TradeSide};
+pub use regen::{predict_regen, RegenState};
+pub use routing::{plan_route, GateEdge, Route};
@@ -0,0 +1,304 @@
+//! # Swept-Circle Collision
+//!
+//! Time-of-impact queries between a moving entity (described by the same
+//! `MovementState` snapshot that `predict_movement` extrapolates) and static
+//! obstacles. The mover is approximated by a circle; obstacles are either
+//! circles (asteroids, stations) or oriented boxes (ships, via
+//! `ShipTypeDefinition::get_world_corners_at_position`).
+//!
+//! ## How the sweep works
+//! The mover's path over `horizon` seconds is sampled with `simulate` — the
+//! exact integrator `predict_movement` uses — so the swept path is the one
+//! both server and clients render. Each sample interval is treated as a
+//! straight segment and solved analytically against the obstacle grown by the
+//! mover's radius (Minkowski sum). Arcs are short at `SWEEP_STEPS` samples, so
+//! the chord error is well below a sprite's width.
+//!
+//! Resolution is deliberately simple: a glancing hit slides the mover along
+//! the contact surface keeping the tangential share of its speed; a head-on
+//! hit stops it dead. See `resolve_impact`.
+
+use super::{MovementState, Vec2, simulate};
+
+// ---------------------------------------------------------------------------
+// Tunables
+// ---------------------------------------------------------------------------
+
+/// Number of straight segments the swept path is split into.
+const SWEEP_STEPS: usize = 16;
+
+/// Below this tangential share of the pre-impact speed (|cos| of the angle
+/// between heading and surface) the hit counts as head-on and the mover stops.
+const HEAD_ON_THRESHOLD: f32 = 0.25;
+
+/// Distance the mover is pushed out along the contact normal after resolution
+/// so the next sweep doesn't immediately re-report the same contact.
+const CONTACT_SKIN: f32 = 0.5;
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// A static obstacle in world space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Collider {
+    Circle {
+        center: Vec2,
+        radius: f32,
+    },
+    /// Convex quad. Corner winding doesn't matter — outward normals are
+    /// derived from the centroid.
+    OrientedBox {
+        corners: [Vec2; 4],
+    },
+}
+
+/// The first contact found by `time_of_impact`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Impact {
+    /// Seconds after `state.last_update_time` at which contact occurs.
+    pub time: f32,
+    /// Mover's centre at the moment of contact.
+    pub pos: Vec2,
+    /// Mover's heading at the moment of contact. Units: radians.
+    pub rotation: f32,
+    /// Mover's forward speed at the moment of contact. Units: px/s.
+    pub velocity: f32,
+    /// Unit contact normal, pointing from the obstacle toward the mover.
+    pub normal: Vec2,
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/// Sweeps a circle of `radius` along the path `state` predicts over the next
+/// `horizon` seconds and returns the first contact with `collider`, if any.
+///
+/// A mover that starts already overlapping the obstacle only reports a
+/// contact (at `time == 0`) while it is heading *into* it, so a ship that was
+/// pushed out and is now flying away is left alone.
+pub fn time_of_impact(
+    state: &MovementState,
+    radius: f32,
+    collider: &Collider,
+    horizon: f32,
+) -> Option<Impact> {
+    if horizon <= 0.0 {
+        return None;
+    }
+
+    let step_dt = horizon / SWEEP_STEPS as f32;
+    let mut t0 = 0.0;
+    let (mut p0, mut rot0, mut v0, _) = simulate(state, 0.0);
+
+    for step in 1..=SWEEP_STEPS {
+        let t1 = step_dt * step as f32;
+        let (p1, rot1, v1, _) = simulate(state, t1);
+
+        if let Some((fraction, normal)) = sweep_segment(p0, p1, radius, collider) {
+            // Overlapping at the start of a segment while moving away isn't a
+            // collision — that's a mover backing out of a contact.
+            let moving_in = heading(rot0).dot(normal.to_glam()) < 0.0 && v0 > f32::EPSILON;
+            let overlapping = fraction <= f32::EPSILON;
+            if !overlapping || moving_in {
+                let pos = p0.to_glam().lerp(p1.to_glam(), fraction);
+                return Some(Impact {
+                    time: t0 + (t1 - t0) * fraction,
+                    pos: Vec2::from_glam(pos),
+                    rotation: rot0 + (rot1 - rot0) * fraction,
+                    velocity: v0 + (v1 - v0) * fraction,
+                    normal,
+                });
+            }
+        }
+
+        t0 = t1;
+        p0 = p1;
+        rot0 = rot1;
+        v0 = v1;
+    }
+
+    None
+}
+
+/// Returns the earliest `Impact` against any of `colliders`.
+pub fn first_impact(
+    state: &MovementState,
+    radius: f32,
+    colliders: &[Collider],
+    horizon: f32,
+) -> Option<Impact> {
+    colliders
+        .iter()
+        .filter_map(|c| time_of_impact(state, radius, c, horizon))
+        .min_by(|a, b| a.time.total_cmp(&b.time))
+}
+
+/// Builds the post-contact snapshot for `impact`, stamped at `now`.
+///
+/// * **Glancing** (heading mostly along the surface): heading snaps to the
+///   surface tangent and speed keeps only the tangential share. Linear
+///   acceleration is preserved so a held thrust key keeps sliding.
+/// * **Head-on**: speed and linear acceleration are zeroed.
+///
+/// Either way the mover is nudged `CONTACT_SKIN` px out along the normal and
+/// angular state is carried over untouched.
+pub fn resolve_impact(state: &MovementState, impact: &Impact, now: i64) -> MovementState {
+    let n = impact.normal.to_glam();
+    let h = heading(impact.rotation);
+    let tangent = h - n * h.dot(n).min(0.0);
+    let share = tangent.length();
+
+    let mut next = MovementState {
+        pos: Vec2::from_glam(impact.pos.to_glam() + n * CONTACT_SKIN),
+        rotation: impact.rotation,
+        velocity: impact.velocity,
+        last_update_time: now,
+        ..*state
+    };
+
+    if share < HEAD_ON_THRESHOLD {
+        next.velocity = 0.0;
+        next.acceleration = 0.0;
+    } else {
+        next.rotation = tangent.y.atan2(tangent.x);
+        next.velocity = impact.velocity * share;
+    }
+
+    next
+}
+
+// ---------------------------------------------------------------------------
+// Segment sweeps
+// ---------------------------------------------------------------------------
+
+/// Forward unit vector for heading `theta` (see module-level notes in
+/// `physics`).
+fn heading(theta: f32) -> glam::Vec2 {
+    glam::Vec2::new(theta.cos(), theta.sin())
+}
+
+/// Sweeps a circle of `radius` from `a` to `b`. Returns the fraction of the
+/// segment travelled at first contact and the contact normal.
+fn sweep_segment(a: Vec2, b: Vec2, radius: f32, collider: &Collider) -> Option<(f32, Vec2)> {
+    let a = a.to_glam();
+    let b = b.to_glam();
+    match collider {
+        Collider::Circle { center, radius: r } => {
+            sweep_point_circle(a, b, center.to_glam(), radius + r)
+        }
+        Collider::OrientedBox { corners } => sweep_point_box(a, b, corners, radius),
+    }
+    .map(|(fraction, normal)| (fraction, Vec2::from_glam(normal)))
+}
+
+/// Moving point vs. circle of radius `r`: solves `|a + t·d − c|² = r²` for the
+/// smallest `t ∈ [0, 1]`.
+fn sweep_point_circle(
+    a: glam::Vec2,
+    b: glam::Vec2,
+    c: glam::Vec2,
+    r: f32,
+) -> Option<(f32, glam::Vec2)> {
+    let m = a - c;
+    let normal_at = |p: glam::Vec2| (p - c).try_normalize().unwrap_or(glam::Vec2::X);
+
+    let cc = m.length_squared() - r * r;
+    if cc <= 0.0 {
+        return Some((0.0, normal_at(a)));
+    }
+
+    let d = b - a;
+    let aa = d.length_squared();
+    if aa < f32::EPSILON {
+        return None;
+    }
+    let bb = m.dot(d);
+    if bb >= 0.0 {
+        return None; // moving away (or tangent)
+    }
+    let disc = bb * bb - aa * cc;
+    if disc < 0.0 {
+        return None;
+    }
+
+    let t = (-bb - disc.sqrt()) / aa;
+    if t > 1.0 {
+        return None;
+    }
+    Some((t, normal_at(a + d * t)))
+}
+
+/// Moving circle vs. convex quad. Tests the four edges pushed out by `radius`
+/// and the four rounded corners, keeping the earliest hit.
+fn sweep_point_box(
+    a: glam::Vec2,
+    b: glam::Vec2,
+    corners: &[Vec2; 4],
+    radius: f32,
+) -> Option<(f32, glam::Vec2)> {
+    let pts = corners.map(|c| c.to_glam());
+    let centroid = (pts[0] + pts[1] + pts[2] + pts[3]) / 4.0;
+    let d = b - a;
+
+    let mut best: Option<(f32, glam::Vec2)> = None;
+    let mut keep = |hit: Option<(f32, glam::Vec2)>| {
+        if let Some(hit) = hit
+            && best.is_none_or(|(t, _)| hit.0 < t)
+        {
+            best = Some(hit);
+        }
+    };
+
+    // Already touching? Inside the quad, or within `radius` of an edge.
+    let mut inside_quad = true;
+    let mut nearest: Option<(f32, glam::Vec2)> = None;
+
+    for i in 0..4 {
+        let e0 = pts[i];
+        let e1 = pts[(i + 1) % 4];
+        let edge = e1 - e0;
+        let Some(edge_dir) = edge.try_normalize() else {
+            continue;
+        };
+        let mut n = edge_dir.perp();
+        if n.dot(e0 - centroid) < 0.0 {
+            n = -n;
+        }
+
+        let plane0 = (a - e0).dot(n);
+        if plane0 > 0.0 {
+            inside_quad = false;
+        }
+        let closest = e0 + edge_dir * (a - e0).dot(edge_dir).clamp(0.0, edge.length());
+        let gap = a.distance(closest);
+        if nearest.is_none_or(|(g, _)| gap < g) {
+            nearest = Some((gap, (a - closest).try_normalize().unwrap_or(n)));
+        }
+
+        // Signed distance of the start point from the grown edge line.
+        let dist0 = plane0 - radius;
+
+        // Edge face: plane crossing, then check the hit lies within the edge.
+        let closing = d.dot(n);
+        if dist0 > 0.0 && closing < 0.0 {
+            let t = dist0 / -closing;
+            if t <= 1.0 {
+                let along = (a + d * t - e0).dot(edge_dir);
+                if (0.0..=edge.length()).contains(&along) {
+                    keep(Some((t, n)));
+                }
+            }
+        }
+
+        // Rounded corner.
+        keep(sweep_point_circle(a, b, e0, radius));
+    }
+
+    match nearest {
+        Some((_, n)) if inside_quad => Some((0.0, (a - centroid).try_normalize().unwrap_or(n))),
+        Some((gap, n)) if gap <= radius => Some((0.0, n)),
+        _ => best,
+    }
+}
@@ -33,6 +33,8 @@
 
 use std::hash::Hasher;
 
+pub mod collision;
+
 // ---------------------------------------------------------------------------
 // Data types
 // ---------------------------------------------------------------------------
@@ -40,7 +42,7 @@ use std::hash::Hasher;
 /// World-space 2D vector. Shared between server tables (via `SpacetimeType`)
 /// and client renderers — the single canonical position/heading type.
 #[cfg_attr(feature = "server", derive(spacetimedb::SpacetimeType))]
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct Vec2 {
     pub x: f32,
     pub y: f32,
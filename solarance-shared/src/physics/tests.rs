@@ -253,3 +253,162 @@ fn dampening_arc_position_stops_curving() {
         moved
     );
 }
+
+// ---------------------------------------------------------------------------
+// Collision
+// ---------------------------------------------------------------------------
+
+use super::collision::*;
+
+/// Axis-aligned box corners in the same order `ShipTypeDefinition::
+/// get_world_corners_at_position` emits them, rotated by `angle` about `center`.
+fn make_box(center: Vec2, half_width: f32, half_height: f32, angle: f32) -> Collider {
+    let (sin, cos) = angle.sin_cos();
+    let corners = [
+        (half_width, half_height),
+        (-half_width, half_height),
+        (-half_width, -half_height),
+        (half_width, -half_height),
+    ]
+    .map(|(x, y)| Vec2::new(center.x + x * cos - y * sin, center.y + x * sin + y * cos));
+    Collider::OrientedBox { corners }
+}
+
+#[test]
+fn circle_head_on_time_of_impact() {
+    // Mover radius 10 heading +x at 100 px/s; obstacle radius 50 at x=200.
+    // Contact when the centres are 60 apart → x = 140 → t = 1.4 s.
+    let state = make_state(0.0, 0.0, 100.0, 0.0, 0.0, BASE_TIME);
+    let obstacle = Collider::Circle { center: Vec2::new(200.0, 0.0), radius: 50.0 };
+
+    let impact = time_of_impact(&state, 10.0, &obstacle, 2.0).expect("should hit");
+
+    assert!((impact.time - 1.4).abs() < 0.01, "toi should be ~1.4, got {}", impact.time);
+    assert!((impact.pos.x - 140.0).abs() < 0.5, "x should be ~140, got {}", impact.pos.x);
+    assert!(
+        (impact.normal.x + 1.0).abs() < 0.01 && impact.normal.y.abs() < 0.01,
+        "normal should point back along -x, got {:?}",
+        impact.normal
+    );
+}
+
+#[test]
+fn circle_miss_and_out_of_horizon() {
+    let state = make_state(0.0, 0.0, 100.0, 0.0, 0.0, BASE_TIME);
+
+    // Passes 100 px above a 50 px obstacle with a 10 px mover — clear by 40.
+    let beside = Collider::Circle { center: Vec2::new(200.0, 100.0), radius: 50.0 };
+    assert!(time_of_impact(&state, 10.0, &beside, 5.0).is_none());
+
+    // Dead ahead, but contact (t = 1.4 s) is past a 1 s horizon.
+    let ahead = Collider::Circle { center: Vec2::new(200.0, 0.0), radius: 50.0 };
+    assert!(time_of_impact(&state, 10.0, &ahead, 1.0).is_none());
+}
+
+#[test]
+fn overlapping_mover_leaving_is_not_a_collision() {
+    // Starts inside the obstacle but heading away from its centre.
+    let state = make_state(0.0, 0.0, 100.0, 0.0, 0.0, BASE_TIME);
+    let behind = Collider::Circle { center: Vec2::new(-5.0, 0.0), radius: 20.0 };
+    assert!(time_of_impact(&state, 10.0, &behind, 1.0).is_none());
+
+    // Same overlap, heading into it: immediate contact.
+    let inward = make_state(0.0, 0.0, 100.0, PI, 0.0, BASE_TIME);
+    let impact = time_of_impact(&inward, 10.0, &behind, 1.0).expect("should hit");
+    assert!(impact.time.abs() < f32::EPSILON, "toi should be 0, got {}", impact.time);
+}
+
+#[test]
+fn box_face_and_corner_time_of_impact() {
+    let state = make_state(0.0, 0.0, 100.0, 0.0, 0.0, BASE_TIME);
+
+    // Axis-aligned box whose near face sits at x = 280 → contact at x = 270.
+    let face = make_box(Vec2::new(300.0, 0.0), 20.0, 20.0, 0.0);
+    let impact = time_of_impact(&state, 10.0, &face, 3.0).expect("should hit face");
+    assert!((impact.time - 2.7).abs() < 0.01, "face toi should be ~2.7, got {}", impact.time);
+    assert!(
+        (impact.normal.x + 1.0).abs() < 0.01,
+        "face normal should be -x, got {:?}",
+        impact.normal
+    );
+
+    // Same box rotated 45°: a corner now points at the mover from
+    // x = 300 − 20√2 ≈ 271.7, so contact is ≈ 10 px earlier.
+    let corner = make_box(Vec2::new(300.0, 0.0), 20.0, 20.0, FRAC_PI_4);
+    let impact = time_of_impact(&state, 10.0, &corner, 3.0).expect("should hit corner");
+    let expected = (300.0 - 20.0 * 2.0_f32.sqrt() - 10.0) / 100.0;
+    assert!(
+        (impact.time - expected).abs() < 0.01,
+        "corner toi should be ~{}, got {}",
+        expected,
+        impact.time
+    );
+}
+
+#[test]
+fn curved_path_impact_matches_prediction() {
+    // A turning ship must hit exactly where `predict_movement` says it is at
+    // the impact time, so the client's rendered path and the server's
+    // collision agree.
+    let state = make_state(0.0, 0.0, 200.0, 0.0, FRAC_PI_4, BASE_TIME);
+    let obstacle = Collider::Circle { center: Vec2::new(300.0, 120.0), radius: 60.0 };
+
+    let impact = time_of_impact(&state, 10.0, &obstacle, 3.0).expect("should hit");
+    let at = BASE_TIME + (impact.time * ONE_SECOND as f32) as i64;
+    let (pos, ..) = predict_movement(&state, at);
+
+    assert!(
+        pos.distance_to(&impact.pos) < 2.0,
+        "impact pos {:?} should match predicted pos {:?}",
+        impact.pos,
+        pos
+    );
+}
+
+#[test]
+fn first_impact_picks_earliest() {
+    let state = make_state(0.0, 0.0, 100.0, 0.0, 0.0, BASE_TIME);
+    let colliders = [
+        Collider::Circle { center: Vec2::new(400.0, 0.0), radius: 20.0 },
+        make_box(Vec2::new(200.0, 0.0), 20.0, 20.0, 0.0),
+        Collider::Circle { center: Vec2::new(0.0, 300.0), radius: 20.0 },
+    ];
+
+    let impact = first_impact(&state, 10.0, &colliders, 5.0).expect("should hit");
+    assert!((impact.time - 1.7).abs() < 0.01, "should hit the box first, got {}", impact.time);
+}
+
+#[test]
+fn resolve_head_on_stops_and_glancing_slides() {
+    let mut state = make_state(0.0, 0.0, 100.0, 0.0, 0.0, BASE_TIME);
+    state.acceleration = 50.0;
+
+    // Head-on: heading +x into a -x normal.
+    let head_on = Impact {
+        time: 1.0,
+        pos: Vec2::new(100.0, 0.0),
+        rotation: 0.0,
+        velocity: 100.0,
+        normal: Vec2::new(-1.0, 0.0),
+    };
+    let stopped = resolve_impact(&state, &head_on, BASE_TIME + ONE_SECOND);
+    assert_eq!(stopped.velocity, 0.0, "head-on hit should stop the mover");
+    assert_eq!(stopped.acceleration, 0.0, "head-on hit should cut thrust");
+    assert!(stopped.pos.x < 100.0, "mover should be pushed back out, got {}", stopped.pos.x);
+    assert_eq!(stopped.last_update_time, BASE_TIME + ONE_SECOND);
+
+    // Glancing: heading 0.2 rad into a floor whose normal is -y.
+    let glancing = Impact {
+        rotation: 0.2,
+        normal: Vec2::new(0.0, -1.0),
+        ..head_on
+    };
+    let slid = resolve_impact(&state, &glancing, BASE_TIME + ONE_SECOND);
+    assert!(slid.rotation.abs() < 0.01, "heading should follow the surface, got {}", slid.rotation);
+    assert!(
+        (slid.velocity - 100.0 * 0.2_f32.cos()).abs() < 0.1,
+        "speed should keep the tangential share, got {}",
+        slid.velocity
+    );
+    assert_eq!(slid.acceleration, 50.0, "glancing hit keeps thrust");
+}
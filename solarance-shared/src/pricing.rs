@@ -0,0 +1,175 @@
+//! # Market Pricing
+//!
+//! A station's price for an item is built from:
+//! - **Stock** — how full its slot is. An empty slot pays `base_value` plus the
+//!   item's margin; a full one sells at `base_value` minus it.
+//! - **Pressure** — recent trading. Selling pushes the price down and buying
+//!   pushes it up on top of what the stock change does, and that push halves
+//!   every `PRICE_PRESSURE_HALF_LIFE_SECS`, so prices drift back toward base
+//!   once the traders leave.
+//! - **Who's asking** — players buy above the mid price and sell below it by
+//!   the spread, and faction standing with the station's owner tilts both.
+//!
+//! A trade is priced unit by unit as the stock moves, so selling 500 ore gets
+//! a worse average than selling 5. The server charges with
+//! `MarketQuote::total` and the client quotes the same numbers before a trade.
+
+/// How long it takes trading pressure to halve.
+pub const PRICE_PRESSURE_HALF_LIFE_SECS: f32 = 3600.0;
+
+/// How much pressure trading a whole slot's worth (`max_quantity` units) adds.
+pub const PRICE_IMPACT_PER_FULL_SLOT: f32 = 0.5;
+
+/// Pressure never takes a price below half or above triple its stock price.
+pub const MIN_PRICE_PRESSURE: f32 = -0.5;
+pub const MAX_PRICE_PRESSURE: f32 = 2.0;
+
+/// How far above the mid price players buy, and below it they sell.
+pub const TRADE_SPREAD: f32 = 0.05;
+
+/// The discount allies of a station's owner get, and the surcharge its enemies
+/// pay, at a reputation of ±100.
+pub const MAX_STANDING_DISCOUNT: f32 = 0.1;
+
+/// The `standing_discount` for a faction `reputation` of -100 to 100.
+pub fn reputation_discount(reputation: i32) -> f32 {
+    reputation.clamp(-100, 100) as f32 / 100.0 * MAX_STANDING_DISCOUNT
+}
+
+/// Which way a trade goes, from the player's side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+/// Everything needed to price a trade against one station inventory slot.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MarketQuote {
+    pub base_value: u32,
+    /// The item's `margin_percentage` as a fraction.
+    pub margin: f32,
+    pub quantity: u32,
+    pub max_quantity: u32,
+    /// Trading pressure right now, already decayed (see `decayed_pressure`).
+    pub pressure: f32,
+    /// Fraction above the mid price players buy at, and below it they sell at.
+    pub spread: f32,
+    /// Fraction off what players pay and on top of what they're paid. Negative
+    /// for factions the station's owner dislikes.
+    pub standing_discount: f32,
+}
+
+impl MarketQuote {
+    /// A quote on stock alone: no pressure, spread or standing.
+    pub fn for_stock(
+        base_value: u32,
+        margin_percentage: u8,
+        quantity: u32,
+        max_quantity: u32,
+    ) -> Self {
+        MarketQuote {
+            base_value,
+            margin: margin_percentage as f32 * 0.01,
+            quantity,
+            max_quantity,
+            pressure: 0.0,
+            spread: 0.0,
+            standing_discount: 0.0,
+        }
+    }
+
+    /// The mid price of one unit with the slot at `fill` (0.0 empty, 1.0 full).
+    pub fn mid_price_at(&self, fill: f32) -> f32 {
+        let stock_price = self.base_value as f32 * (1.0 + self.margin * (1.0 - 2.0 * fill));
+        (stock_price * (1.0 + self.pressure)).max(0.0)
+    }
+
+    /// The mid price of one unit at the current stock.
+    pub fn mid_price(&self) -> f32 {
+        self.mid_price_at(self.fill(self.quantity as f32))
+    }
+
+    /// What `count` units cost the player (`Buy`) or pay them (`Sell`), in
+    /// whole credits. Rounded in the station's favour.
+    pub fn total(&self, side: TradeSide, count: u32) -> u32 {
+        let half = count as f32 / 2.0;
+        // The price is linear in fill, so the average over the trade is the
+        // price halfway through it.
+        let (fill, factor) = match side {
+            TradeSide::Buy => (
+                self.fill(self.quantity as f32 - half),
+                (1.0 + self.spread) * (1.0 - self.standing_discount),
+            ),
+            TradeSide::Sell => (
+                self.fill(self.quantity as f32 + half),
+                (1.0 - self.spread) * (1.0 + self.standing_discount),
+            ),
+        };
+        let total = self.mid_price_at(fill) * count as f32 * factor;
+        match side {
+            TradeSide::Buy => total.ceil() as u32,
+            TradeSide::Sell => total.floor() as u32,
+        }
+    }
+
+    /// The pressure left behind by trading `count` units.
+    pub fn pressure_after(&self, side: TradeSide, count: u32) -> f32 {
+        let impact = PRICE_IMPACT_PER_FULL_SLOT * count as f32 / self.max_quantity.max(1) as f32;
+        let pressure = match side {
+            TradeSide::Buy => self.pressure + impact,
+            TradeSide::Sell => self.pressure - impact,
+        };
+        pressure.clamp(MIN_PRICE_PRESSURE, MAX_PRICE_PRESSURE)
+    }
+
+    fn fill(&self, quantity: f32) -> f32 {
+        (quantity / self.max_quantity.max(1) as f32).clamp(0.0, 1.0)
+    }
+}
+
+/// `pressure` recorded at `last_update_time`, decayed to `current_time`. Both
+/// are microseconds since the Unix epoch.
+pub fn decayed_pressure(pressure: f32, last_update_time: i64, current_time: i64) -> f32 {
+    let dt = (current_time - last_update_time).max(0) as f32 / 1_000_000.0;
+    pressure * 0.5f32.powf(dt / PRICE_PRESSURE_HALF_LIFE_SECS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bigger_trades_get_worse_average_prices() {
+        let quote = MarketQuote::for_stock(100, 20, 500, 1000);
+        assert_eq!(quote.mid_price(), 100.0);
+        assert_eq!(quote.total(TradeSide::Sell, 10), 998);
+        // 500 units fill the slot from half to full: the average is 90c.
+        assert_eq!(quote.total(TradeSide::Sell, 500), 45_000);
+        assert_eq!(quote.total(TradeSide::Buy, 500), 55_000);
+
+        let spread = MarketQuote {
+            spread: 0.1,
+            standing_discount: 0.05,
+            ..quote
+        };
+        assert_eq!(spread.total(TradeSide::Buy, 1), 105);
+        assert_eq!(spread.total(TradeSide::Sell, 1), 94);
+    }
+
+    #[test]
+    fn pressure_pushes_prices_and_fades() {
+        let quote = MarketQuote::for_stock(100, 0, 50, 100);
+        let pressure = quote.pressure_after(TradeSide::Sell, 50);
+        assert_eq!(pressure, -0.25);
+        assert_eq!(MarketQuote { pressure, ..quote }.mid_price(), 75.0);
+
+        let hour = 3_600_000_000;
+        assert_eq!(decayed_pressure(pressure, 0, hour), -0.125);
+        assert_eq!(decayed_pressure(pressure, hour, 0), pressure);
+        assert_eq!(
+            quote.pressure_after(TradeSide::Buy, 1_000),
+            MAX_PRICE_PRESSURE
+        );
+    }
+}
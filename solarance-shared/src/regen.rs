@@ -0,0 +1,100 @@
+//! # Lazy Regeneration
+//!
+//! Shields and energy refill at a steady rate up to a cap. Rather than ticking
+//! every ship's values on a timer, the server stores a `RegenState` snapshot
+//! whenever something *changes* the value or the rate (firing, taking damage,
+//! refitting) and everyone — server checks and client gauges alike — works
+//! out the current value with `predict_regen`, the same way `MovementState`
+//! is extrapolated with `predict_movement`.
+
+/// A regenerating value as of `last_update_time`.
+#[cfg_attr(feature = "server", derive(spacetimedb::SpacetimeType))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RegenState {
+    /// The value at `last_update_time`.
+    pub value: f32,
+    /// How fast `value` refills. Units: per second.
+    pub rate: f32,
+    /// The most `value` regenerates to.
+    pub cap: f32,
+    /// When this snapshot was recorded, as microseconds since the Unix epoch.
+    pub last_update_time: i64,
+}
+
+impl RegenState {
+    /// A full snapshot taken at `current_time`.
+    pub fn full(cap: f32, rate: f32, current_time: i64) -> Self {
+        RegenState {
+            value: cap,
+            rate,
+            cap,
+            last_update_time: current_time,
+        }
+    }
+
+    /// Re-snapshots at `current_time`, then adds `delta` (negative to spend),
+    /// keeping the result within `0..=cap`.
+    pub fn adjust(&mut self, delta: f32, current_time: i64) {
+        let value = predict_regen(self, current_time);
+        self.value = (value + delta).min(self.cap).max(0.0);
+        self.last_update_time = current_time;
+    }
+
+    /// Re-snapshots at `current_time` with a new `rate` and `cap`. The value
+    /// regenerated so far is kept, clamped to the new cap.
+    pub fn retune(&mut self, rate: f32, cap: f32, current_time: i64) {
+        let value = predict_regen(self, current_time);
+        self.value = value.min(cap).max(0.0);
+        self.rate = rate;
+        self.cap = cap;
+        self.last_update_time = current_time;
+    }
+}
+
+/// The value of `state` at `current_time`, regenerated since the snapshot and
+/// clamped to `0..=cap`.
+///
+/// If `current_time` is at or before the snapshot time (clock skew, same
+/// frame) the stored value is returned, still clamped.
+pub fn predict_regen(state: &RegenState, current_time: i64) -> f32 {
+    let dt = (current_time - state.last_update_time).max(0) as f32 / 1_000_000.0;
+    (state.value + state.rate * dt).min(state.cap).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE_SECOND: i64 = 1_000_000;
+
+    #[test]
+    fn regenerates_linearly_up_to_the_cap() {
+        let state = RegenState {
+            value: 10.0,
+            rate: 2.5,
+            cap: 20.0,
+            last_update_time: ONE_SECOND,
+        };
+        assert_eq!(predict_regen(&state, 0), 10.0);
+        assert_eq!(predict_regen(&state, ONE_SECOND), 10.0);
+        assert_eq!(predict_regen(&state, 3 * ONE_SECOND), 15.0);
+        assert_eq!(predict_regen(&state, 60 * ONE_SECOND), 20.0);
+    }
+
+    #[test]
+    fn adjusting_rebases_the_snapshot() {
+        let mut state = RegenState::full(100.0, 1.0, 0);
+        state.adjust(-40.0, 0);
+        assert_eq!(predict_regen(&state, 10 * ONE_SECOND), 70.0);
+
+        // Spending more than is there bottoms out at zero.
+        state.adjust(-500.0, 10 * ONE_SECOND);
+        assert_eq!(state.value, 0.0);
+        assert_eq!(predict_regen(&state, 15 * ONE_SECOND), 5.0);
+
+        // A lower cap clips what has regenerated so far.
+        state.retune(4.0, 3.0, 15 * ONE_SECOND);
+        assert_eq!(state.value, 3.0);
+        assert_eq!(predict_regen(&state, 20 * ONE_SECOND), 3.0);
+    }
+}
@@ -0,0 +1,190 @@
+//! # Jumpgate Route Planning
+//!
+//! Shortest routes over the jumpgate network, shared by the server (autopilot,
+//! NPC traffic) and the client map window so both agree on "the" route.
+//!
+//! ## The graph
+//! Every `JumpGate` row is a directed edge `from_sector → to_sector`. A ship
+//! flying a route crosses each intermediate sector from where it arrived (the
+//! previous gate's `arrival_pos`) to the next gate's `position`, so that
+//! in-sector distance is the edge weight. Jumping itself is instant and free.
+//! Inactive gates are left out of the graph entirely.
+//!
+//! Gates that lead into another star system (the warpgate out of The Hinge)
+//! are ordinary edges here — the planner has no notion of systems.
+//!
+//! ## How the search works
+//! Dijkstra over *gates* rather than sectors: the cost of leaving a sector
+//! depends on which gate the ship came in by, so a sector isn't a single
+//! node. The start is a virtual node at `start_pos`. Once every gate is
+//! settled, the cheapest gate that lands in the destination sector — plus the
+//! final leg to `end_pos`, if given — wins.
+
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use crate::physics::Vec2;
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// One directed jumpgate, flattened out of whatever table it came from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GateEdge {
+    /// The gate's stellar object id.
+    pub gate_id: u64,
+    /// Sector the gate sits in.
+    pub from_sector: u64,
+    /// Sector the gate leads to.
+    pub to_sector: u64,
+    /// Gate position in `from_sector`.
+    pub position: Vec2,
+    /// Where ships appear in `to_sector`.
+    pub arrival_pos: Vec2,
+    pub is_active: bool,
+}
+
+/// A planned route from `plan_route`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Route {
+    /// Gate ids to fly through, in order. Empty for a same-sector route.
+    pub gates: Vec<u64>,
+    /// Every sector visited, starting sector first and destination last.
+    pub sectors: Vec<u64>,
+    /// Total in-sector flight distance. Units: px.
+    pub distance: f32,
+}
+
+impl Route {
+    pub fn jump_count(&self) -> usize {
+        self.gates.len()
+    }
+
+    /// Seconds to fly `distance` at a constant `speed` (px/s). Ignores
+    /// acceleration and turning, so treat it as a lower bound. Infinite for a
+    /// ship that can't move.
+    pub fn estimated_travel_secs(&self, speed: f32) -> f32 {
+        if speed <= 0.0 {
+            f32::INFINITY
+        } else {
+            self.distance / speed
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/// Plans the shortest route from `start_pos` in `from_sector` to
+/// `to_sector`. With `end_pos` the final leg to that point counts toward the
+/// distance; without it the route ends on arrival in `to_sector`.
+///
+/// Returns `None` when no chain of active gates reaches `to_sector`.
+pub fn plan_route(
+    gates: &[GateEdge],
+    from_sector: u64,
+    start_pos: Vec2,
+    to_sector: u64,
+    end_pos: Option<Vec2>,
+) -> Option<Route> {
+    let final_leg = |pos: &Vec2| end_pos.map_or(0.0, |end| pos.distance_to(&end));
+
+    if from_sector == to_sector {
+        return Some(Route {
+            gates: Vec::new(),
+            sectors: vec![from_sector],
+            distance: final_leg(&start_pos),
+        });
+    }
+
+    // Node `i` is "just came out of gates[i]". `previous[i]` is the gate
+    // flown through before it, `None` for the first jump.
+    let mut cost = vec![f32::INFINITY; gates.len()];
+    let mut previous: Vec<Option<usize>> = vec![None; gates.len()];
+    let mut queue = BinaryHeap::new();
+
+    for (i, gate) in gates.iter().enumerate() {
+        if gate.is_active && gate.from_sector == from_sector {
+            cost[i] = start_pos.distance_to(&gate.position);
+            queue.push(Candidate { cost: cost[i], node: i });
+        }
+    }
+
+    while let Some(Candidate { cost: settled, node }) = queue.pop() {
+        if settled > cost[node] {
+            continue; // stale entry
+        }
+        let here = &gates[node];
+        for (next, gate) in gates.iter().enumerate() {
+            if !gate.is_active || gate.from_sector != here.to_sector {
+                continue;
+            }
+            let through = settled + here.arrival_pos.distance_to(&gate.position);
+            if through < cost[next] {
+                cost[next] = through;
+                previous[next] = Some(node);
+                queue.push(Candidate { cost: through, node: next });
+            }
+        }
+    }
+
+    let (last, distance) = gates
+        .iter()
+        .enumerate()
+        .filter(|(i, gate)| gate.to_sector == to_sector && cost[*i].is_finite())
+        .map(|(i, gate)| (i, cost[i] + final_leg(&gate.arrival_pos)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))?;
+
+    let mut chain = vec![last];
+    while let Some(before) = previous[*chain.last().unwrap()] {
+        chain.push(before);
+    }
+    chain.reverse();
+
+    let mut sectors = vec![from_sector];
+    sectors.extend(chain.iter().map(|&i| gates[i].to_sector));
+
+    Some(Route {
+        gates: chain.iter().map(|&i| gates[i].gate_id).collect(),
+        sectors,
+        distance,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Priority queue entry
+// ---------------------------------------------------------------------------
+
+/// Min-heap entry: `BinaryHeap` is a max-heap, so the ordering is reversed.
+#[derive(Clone, Copy, Debug)]
+struct Candidate {
+    cost: f32,
+    node: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+#[cfg(test)]
+mod tests;
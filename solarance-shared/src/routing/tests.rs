@@ -0,0 +1,85 @@
+use super::*;
+
+/// A two-way link between sectors `a` and `b`: gate `a*10 + b` sits at
+/// `a_pos` in `a`, gate `b*10 + a` at `b_pos` in `b`, and each one drops
+/// ships at the other's position — the layout `connect_sectors_with_warpgates`
+/// produces.
+fn link(a: u64, a_pos: Vec2, b: u64, b_pos: Vec2) -> [GateEdge; 2] {
+    [
+        GateEdge {
+            gate_id: a * 10 + b,
+            from_sector: a,
+            to_sector: b,
+            position: a_pos,
+            arrival_pos: b_pos,
+            is_active: true,
+        },
+        GateEdge {
+            gate_id: b * 10 + a,
+            from_sector: b,
+            to_sector: a,
+            position: b_pos,
+            arrival_pos: a_pos,
+            is_active: true,
+        },
+    ]
+}
+
+/// Sectors 1-2-3 in a line, plus a long way round 1-4-3.
+fn network() -> Vec<GateEdge> {
+    let mut gates = Vec::new();
+    gates.extend(link(1, Vec2::new(1000.0, 0.0), 2, Vec2::new(-1000.0, 0.0)));
+    gates.extend(link(2, Vec2::new(1000.0, 0.0), 3, Vec2::new(-1000.0, 0.0)));
+    gates.extend(link(1, Vec2::new(0.0, 5000.0), 4, Vec2::new(0.0, -5000.0)));
+    gates.extend(link(4, Vec2::new(5000.0, 0.0), 3, Vec2::new(0.0, 5000.0)));
+    gates
+}
+
+#[test]
+fn same_sector_route_has_no_jumps() {
+    let route = plan_route(&network(), 1, Vec2::ZERO, 1, Some(Vec2::new(30.0, 40.0))).unwrap();
+    assert_eq!(route.jump_count(), 0);
+    assert_eq!(route.sectors, vec![1]);
+    assert!((route.distance - 50.0).abs() < 1e-3);
+}
+
+#[test]
+fn picks_the_shorter_chain() {
+    let route = plan_route(&network(), 1, Vec2::ZERO, 3, None).unwrap();
+    assert_eq!(route.gates, vec![12, 23]);
+    assert_eq!(route.sectors, vec![1, 2, 3]);
+    // 1000 to the first gate, then 2000 across sector 2.
+    assert!((route.distance - 3000.0).abs() < 1e-3);
+}
+
+#[test]
+fn fewer_jumps_can_lose_to_shorter_flight() {
+    // A direct 1→3 gate placed far away loses to the two-jump route.
+    let mut gates = network();
+    gates.extend(link(1, Vec2::new(-9000.0, 0.0), 3, Vec2::new(9000.0, 0.0)));
+    let route = plan_route(&gates, 1, Vec2::ZERO, 3, None).unwrap();
+    assert_eq!(route.gates, vec![12, 23]);
+}
+
+#[test]
+fn inactive_gates_are_skipped() {
+    let mut gates = network();
+    for gate in gates.iter_mut().filter(|g| g.gate_id == 23) {
+        gate.is_active = false;
+    }
+    let route = plan_route(&gates, 1, Vec2::ZERO, 3, None).unwrap();
+    assert_eq!(route.gates, vec![14, 43]);
+}
+
+#[test]
+fn unreachable_sector_returns_none() {
+    assert!(plan_route(&network(), 1, Vec2::ZERO, 99, None).is_none());
+}
+
+#[test]
+fn end_pos_counts_toward_distance() {
+    let route = plan_route(&network(), 1, Vec2::ZERO, 2, Some(Vec2::ZERO)).unwrap();
+    assert!((route.distance - 2000.0).abs() < 1e-3);
+    assert!((route.estimated_travel_secs(100.0) - 20.0).abs() < 1e-3);
+    assert!(route.estimated_travel_secs(0.0).is_infinite());
+}